@@ -3,10 +3,12 @@
 //! This module defines constants, loads configuration (preferring environment variables),
 //! and provides utility functions like test key generation.
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
+use age::secrecy::Secret;
 use sui_sdk::types::base_types::SuiAddress;
 use sui_sdk::types::crypto::{SuiKeyPair, EncodeDecodeBase64};
 use base64;
+use std::io::{Read, Write};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use sui_types::crypto::{SignatureScheme};
 
@@ -14,7 +16,8 @@ use sui_types::crypto::{SignatureScheme};
 // The constants below are **DEMO-ONLY PLACEHOLDERS**.
 //   • They use a zeroed Sui address and an invalid Base64 string.
 //   • In production you **MUST** provide real values via environment variables
-//     SUBMITTER_ADDRESS and SUBMITTER_KEYPAIR_BASE64  (see README).
+//     SUBMITTER_ADDRESS and SUBMITTER_KEYFILE (an encrypted keyfile, see README).
+//     SUBMITTER_KEYPAIR_BASE64 still works but keeps the key in plaintext.
 //   • Optionally copy this file to `src/config.rs.local`, fill in your
 //     secrets, and add `src/config.rs.local` to `.gitignore`.
 // -------------------------------------------------------------------------------
@@ -25,6 +28,10 @@ pub const SUBMITTER_ADDRESS: &str = "0x00000000000000000000000000000000000000000
 /// Submitter keypair encoded as a Base64 string.
 pub const SUBMITTER_KEYPAIR_BASE64: &str = "PLACEHOLDER_BASE64_KEYPAIR_REPLACE_BEFORE_RUNNING";
 
+/// Signature scheme of the submitter key: "auto" (detect from the key's flag byte),
+/// "ed25519", "secp256k1" or "secp256r1". Overridden by the SUBMITTER_KEY_SCHEME env var.
+pub const SUBMITTER_KEY_SCHEME: &str = "auto";
+
 /// Gas object ID owned by the submitter.
 pub const SUBMITTER_GAS_OBJECT_ID: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
 
@@ -53,36 +60,180 @@ pub const SUI_RPC_URL: &str = SUI_TESTNET_RPC; // Defaulting to Testnet
 
 // --- Keypair Loading Functions ---
 
+/// Parses a scheme name from config. Returns `None` for "auto".
+pub fn parse_signature_scheme(name: &str) -> Result<Option<SignatureScheme>> {
+    match name.trim().to_lowercase().as_str() {
+        "auto" | "" => Ok(None),
+        "ed25519" => Ok(Some(SignatureScheme::ED25519)),
+        "secp256k1" => Ok(Some(SignatureScheme::Secp256k1)),
+        "secp256r1" => Ok(Some(SignatureScheme::Secp256r1)),
+        other => Err(anyhow!("Unsupported signature scheme '{}' (expected auto, ed25519, secp256k1 or secp256r1)", other)),
+    }
+}
+
+/// Returns the signature scheme of a keypair, as encoded in its flag byte.
+pub fn detect_signature_scheme(keypair: &SuiKeyPair) -> SignatureScheme {
+    keypair.public().scheme()
+}
+
+/// Decodes a flag-prefixed Base64 keypair and checks it matches `expected_scheme`, if given.
+///
+/// All three Sui account schemes go through `SuiKeyPair`, so the same
+/// `Signature::new_secure` call signs for any of them.
+pub fn decode_submitter_keypair(base64_keypair: &str, expected_scheme: Option<SignatureScheme>) -> Result<SuiKeyPair> {
+    let keypair = SuiKeyPair::decode_base64(base64_keypair.trim())
+        .map_err(|e| anyhow!("Failed to decode base64 keypair: {}", e))?;
+    let scheme = detect_signature_scheme(&keypair);
+    if let Some(expected) = expected_scheme {
+        if scheme != expected {
+            return Err(anyhow!("Keypair scheme mismatch: key is {:?} but {:?} was configured", scheme, expected));
+        }
+    }
+    Ok(keypair)
+}
+
+/// Scheme requested via the SUBMITTER_KEY_SCHEME env var, falling back to the constant.
+fn configured_submitter_scheme() -> Result<Option<SignatureScheme>> {
+    let name = std::env::var("SUBMITTER_KEY_SCHEME").unwrap_or_else(|_| SUBMITTER_KEY_SCHEME.to_string());
+    parse_signature_scheme(&name)
+}
+
+// --- Encrypted Keyfiles ---
+
+/// Header of a binary age file.
+const AGE_BINARY_HEADER: &[u8] = b"age-encryption.org/";
+/// Header of an ASCII-armored age file.
+const AGE_ARMOR_HEADER: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+
+/// Whether `contents` is an age-encrypted file (binary or armored) rather than a plaintext key.
+pub fn is_encrypted_keyfile(contents: &[u8]) -> bool {
+    let trimmed = match contents.iter().position(|b| !b.is_ascii_whitespace()) {
+        Some(start) => &contents[start..],
+        None => return false,
+    };
+    trimmed.starts_with(AGE_BINARY_HEADER) || trimmed.starts_with(AGE_ARMOR_HEADER)
+}
+
+/// Encrypts a Base64 keypair with a passphrase (age, scrypt-derived key).
+///
+/// The output is a standard age file, so `age -d` can decrypt it too.
+pub fn encrypt_keypair(base64_keypair: &str, passphrase: &str) -> Result<Vec<u8>> {
+    let encryptor = age::Encryptor::with_user_passphrase(Secret::new(passphrase.to_string()));
+    let mut encrypted = Vec::new();
+    let mut writer = encryptor.wrap_output(&mut encrypted).context("Failed to start keyfile encryption")?;
+    writer.write_all(base64_keypair.trim().as_bytes())?;
+    writer.finish().context("Failed to finish keyfile encryption")?;
+    Ok(encrypted)
+}
+
+/// Writes a keyfile readable only by its owner (mode 0600 on Unix).
+///
+/// An existing file is truncated and has its permissions tightened too.
+pub fn write_keyfile(path: &str, contents: &[u8]) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path).with_context(|| format!("Failed to create keyfile {}", path))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // `mode` only applies to newly created files
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to restrict permissions of keyfile {}", path))?;
+    }
+    file.write_all(contents).with_context(|| format!("Failed to write keyfile {}", path))?;
+    file.sync_all().with_context(|| format!("Failed to flush keyfile {}", path))
+}
+
+/// Decrypts an age keyfile produced by `encrypt_keypair` (or `age -p`), returning the Base64 keypair.
+pub fn decrypt_keypair(encrypted: &[u8], passphrase: &str) -> Result<String> {
+    let reader = age::armor::ArmoredReader::new(encrypted);
+    let decryptor = match age::Decryptor::new(reader).map_err(|e| anyhow!("Invalid encrypted keyfile: {}", e))? {
+        age::Decryptor::Passphrase(decryptor) => decryptor,
+        _ => return Err(anyhow!("Keyfile is encrypted to recipients, not a passphrase")),
+    };
+    let mut plaintext = String::new();
+    decryptor.decrypt(&Secret::new(passphrase.to_string()), None)
+        .map_err(|e| anyhow!("Failed to decrypt keyfile (wrong passphrase?): {}", e))?
+        .read_to_string(&mut plaintext)
+        .context("Decrypted keyfile is not valid UTF-8")?;
+    Ok(plaintext)
+}
+
+/// Passphrase from the KEYPAIR_PASSPHRASE env var, or prompted on the terminal.
+pub fn keyfile_passphrase(prompt: &str) -> Result<String> {
+    if let Ok(passphrase) = std::env::var("KEYPAIR_PASSPHRASE") {
+        return Ok(passphrase);
+    }
+    rpassword::prompt_password(prompt).context("Failed to read passphrase")
+}
+
+/// Loads a keypair from `path`: age-encrypted files are decrypted with
+/// `keyfile_passphrase`, anything else is read as a plaintext Base64 key.
+pub fn load_keypair_file(path: &str, expected_scheme: Option<SignatureScheme>) -> Result<SuiKeyPair> {
+    let contents = std::fs::read(path).with_context(|| format!("Failed to read keyfile {}", path))?;
+    let base64_keypair = if is_encrypted_keyfile(&contents) {
+        let passphrase = keyfile_passphrase(&format!("Passphrase for {}: ", path))?;
+        decrypt_keypair(&contents, &passphrase)?
+    } else {
+        println!("[WARN] Keyfile {} is not encrypted. Encrypt it with --encrypt-keypair.", path);
+        String::from_utf8(contents).with_context(|| format!("Keyfile {} is neither encrypted nor Base64 text", path))?
+    };
+    decode_submitter_keypair(&base64_keypair, expected_scheme)
+        .map_err(|e| anyhow!("Invalid keypair in {}: {}", path, e))
+}
+
+/// Checks the keypair against the SUBMITTER_ADDRESS env var, if set.
+fn check_submitter_address(sui_keypair: &SuiKeyPair, source: &str) -> Result<()> {
+    let derived_address = SuiAddress::from(&sui_keypair.public()).to_string();
+    if let Ok(expected_address) = std::env::var("SUBMITTER_ADDRESS") {
+        if derived_address != expected_address {
+            return Err(anyhow!("Keypair mismatch: Derived address ({}) doesn't match SUBMITTER_ADDRESS env var ({})",
+                     derived_address, expected_address));
+        }
+        println!("Address validation successful against env var: {}", derived_address);
+    } else {
+        println!("Using keypair from {}. Derived address: {}. (SUBMITTER_ADDRESS env var not set for verification)", source, derived_address);
+    }
+    Ok(())
+}
+
 /// Loads the submitter keypair, prioritizing environment variables.
 ///
-/// Attempts to read SUBMITTER_KEYPAIR_BASE64 and SUBMITTER_ADDRESS from environment variables.
-/// If found, validates the keypair against the address (if provided).
-/// If environment variables are not set or invalid, falls back to the hardcoded constants above.
+/// Sources, in order:
+/// 1. SUBMITTER_KEYFILE: path to a keyfile, decrypted if it's age-encrypted (see `load_keypair_file`).
+/// 2. SUBMITTER_KEYPAIR_BASE64: plaintext Base64 key. Deprecated; migrate with `--encrypt-keypair`.
+/// 3. The hardcoded constants above.
+///
+/// Keys from the environment are validated against SUBMITTER_ADDRESS if it is set.
+/// Ed25519, secp256k1 and secp256r1 keys are accepted; see `SUBMITTER_KEY_SCHEME`.
 pub fn load_submitter_keypair() -> Result<SuiKeyPair> {
-    // First try from environment variable
+    let expected_scheme = configured_submitter_scheme()?;
+
+    if let Ok(path) = std::env::var("SUBMITTER_KEYFILE") {
+        println!("Loading keypair from SUBMITTER_KEYFILE ({}).", path);
+        let sui_keypair = load_keypair_file(&path, expected_scheme)?;
+        println!("Submitter key scheme: {:?}", detect_signature_scheme(&sui_keypair));
+        check_submitter_address(&sui_keypair, "keyfile")?;
+        return Ok(sui_keypair);
+    }
+
+    // Then the plaintext environment variable
     if let Ok(base64_keypair) = std::env::var("SUBMITTER_KEYPAIR_BASE64") {
         println!("Attempting to load keypair from SUBMITTER_KEYPAIR_BASE64 env var.");
-        let sui_keypair = SuiKeyPair::decode_base64(&base64_keypair)
-            .map_err(|e| anyhow!("Failed to decode base64 keypair from env: {}", e))?;
-
-        if let Ok(expected_address) = std::env::var("SUBMITTER_ADDRESS") {
-            let derived_address = SuiAddress::from(&sui_keypair.public()).to_string();
-            if derived_address != expected_address {
-                return Err(anyhow!("Keypair mismatch: Derived address ({}) doesn't match SUBMITTER_ADDRESS env var ({})",
-                         derived_address, expected_address));
-            }
-            println!("Address validation successful against env var: {}", derived_address);
-        } else {
-            let derived_address = SuiAddress::from(&sui_keypair.public()).to_string();
-            println!("Using keypair from env var. Derived address: {}. (SUBMITTER_ADDRESS env var not set for verification)", derived_address);
-        }
+        println!("[WARN] SUBMITTER_KEYPAIR_BASE64 holds the private key in plaintext. Run --encrypt-keypair <FILE> and set SUBMITTER_KEYFILE instead.");
+        let sui_keypair = decode_submitter_keypair(&base64_keypair, expected_scheme)
+            .map_err(|e| anyhow!("Invalid keypair in SUBMITTER_KEYPAIR_BASE64 env var: {}", e))?;
+        println!("Submitter key scheme: {:?}", detect_signature_scheme(&sui_keypair));
+        check_submitter_address(&sui_keypair, "env var")?;
         return Ok(sui_keypair);
     }
 
     // Fallback to hardcoded constants (Now using actual values for testing)
-    println!("[INFO] SUBMITTER_KEYPAIR_BASE64 env var not set. Falling back to hardcoded constants for testing.");
-    let sui_keypair = SuiKeyPair::decode_base64(SUBMITTER_KEYPAIR_BASE64)
-        .map_err(|e| anyhow!("Failed to decode hardcoded keypair constant: {}", e))?;
+    println!("[INFO] Neither SUBMITTER_KEYFILE nor SUBMITTER_KEYPAIR_BASE64 is set. Falling back to hardcoded constants for testing.");
+    let sui_keypair = decode_submitter_keypair(SUBMITTER_KEYPAIR_BASE64, expected_scheme)
+        .map_err(|e| anyhow!("Invalid hardcoded keypair constant: {}", e))?;
 
     let derived_address = SuiAddress::from(&sui_keypair.public()).to_string();
     if derived_address != SUBMITTER_ADDRESS {
@@ -1 +1,4 @@
+pub mod scenarios;
 pub mod weather;
+
+pub use scenarios::load_scenarios;
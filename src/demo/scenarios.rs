@@ -0,0 +1,139 @@
+//! Data-driven demo scenarios loaded from a JSON file.
+//!
+//! A scenario file is a JSON array of `Transaction` objects. Empty `sender`,
+//! `receiver` and `gas_payment` fields are filled in with the demo submitter's
+//! address and gas object at run time, so the same file works for any key.
+//!
+//! A custom scenario named `<demo>_demo` belongs to `--demo <demo>`; the
+//! shipped file holds the JS, Python and weather demos. When the file is
+//! missing, the copy compiled into the binary is used instead. The weather
+//! scenario runs through `weather::run_weather_based_transaction_demo`, which
+//! adds the API key and falls back to demo data when offline.
+
+use crate::demo::weather::run_weather_based_transaction_demo;
+use crate::execution::manager::ExecutionManager;
+use crate::metrics::storage::MetricsStorage;
+use crate::security::audit::SecurityAuditLog;
+use crate::transaction::handler::TransactionHandler;
+use crate::transaction::types::{Transaction, TransactionType};
+use crate::transaction::utils::process_and_submit_verification;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::crypto::SuiKeyPair;
+
+/// Scenario file shipped with the repository, containing the built-in demos.
+pub const DEFAULT_SCENARIOS_PATH: &str = "scenarios.json";
+
+const BUILTIN_SCENARIOS: &str = include_str!("../../scenarios.json");
+
+/// Read the scenario transactions from `path`.
+pub fn load_scenarios(path: impl AsRef<Path>) -> Result<Vec<Transaction>> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read scenario file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Scenario file {} is not a JSON array of transactions", path.display()))
+}
+
+/// The scenarios shipped in `scenarios.json`, as compiled into the binary.
+pub fn builtin_scenarios() -> Result<Vec<Transaction>> {
+    serde_json::from_str(BUILTIN_SCENARIOS).context("Built-in scenarios are not a JSON array of transactions")
+}
+
+/// Read the scenarios from `path`, falling back to the built-in ones with a
+/// warning when the file doesn't exist. A file that exists but doesn't parse
+/// is still an error.
+pub fn load_scenarios_or_builtin(path: impl AsRef<Path>) -> Result<Vec<Transaction>> {
+    let path = path.as_ref();
+    if !path.exists() {
+        eprintln!("WARNING: Scenario file {} not found, using the built-in scenarios", path.display());
+        return builtin_scenarios();
+    }
+    load_scenarios(path)
+}
+
+/// Display name for a scenario, used in logs and metrics.
+pub fn scenario_name(tx: &Transaction) -> String {
+    match &tx.tx_type {
+        TransactionType::Custom(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The `--demo` name a scenario belongs to: the prefix of a `<demo>_demo`
+/// custom name, otherwise based on its script language.
+pub fn scenario_demo_name(tx: &Transaction) -> String {
+    if let TransactionType::Custom(name) = &tx.tx_type {
+        if let Some(demo) = name.strip_suffix("_demo") {
+            return demo.to_string();
+        }
+    }
+    match tx.language.as_deref() {
+        Some("javascript") => "js",
+        Some("python") => "python",
+        _ => "native",
+    }
+    .to_string()
+}
+
+fn fill_submitter_defaults(tx: &mut Transaction, submitter_address: &SuiAddress, gas_object_id: &ObjectID) {
+    if tx.sender.is_empty() {
+        tx.sender = submitter_address.to_string();
+    }
+    if tx.receiver.is_empty() {
+        tx.receiver = submitter_address.to_string();
+    }
+    if tx.gas_payment.is_empty() {
+        tx.gas_payment = gas_object_id.to_string();
+    }
+}
+
+/// Run each scenario through `process_and_submit_verification`.
+///
+/// A failing scenario is reported and the rest still run, matching how the
+/// built-in demos behave.
+pub async fn run_scenarios(
+    scenarios: &[Transaction],
+    transaction_handler: &Arc<TransactionHandler>,
+    execution_manager: &Arc<ExecutionManager>,
+    metrics_storage: Option<&Arc<MetricsStorage>>,
+    security_audit_log: &Arc<SecurityAuditLog>,
+    submitter_keypair: &SuiKeyPair,
+    gas_object_id: &ObjectID,
+) {
+    let submitter_address = SuiAddress::from(&submitter_keypair.public());
+    for scenario in scenarios {
+        let mut tx = scenario.clone();
+        fill_submitter_defaults(&mut tx, &submitter_address, gas_object_id);
+        let name = scenario_name(&tx);
+
+        let result = if scenario_demo_name(&tx) == "weather" {
+            run_weather_based_transaction_demo(
+                &tx,
+                transaction_handler,
+                execution_manager,
+                metrics_storage,
+                security_audit_log,
+                submitter_keypair,
+                gas_object_id,
+            ).await
+        } else {
+            process_and_submit_verification(
+                &tx,
+                &name,
+                transaction_handler,
+                execution_manager,
+                metrics_storage,
+                security_audit_log,
+                submitter_keypair,
+                gas_object_id,
+            ).await.map(|_| ())
+        };
+        if let Err(e) = result {
+            eprintln!("ERROR in scenario {}: {:#}", name, e);
+        }
+    }
+}
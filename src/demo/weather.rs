@@ -1,20 +1,79 @@
-use crate::config;
+//! The weather demo: submits the `weather_demo` scenario when its temperature
+//! condition holds.
+//!
+//! The scenario's `external_query` names the endpoint and condition, but the
+//! demo evaluates it itself so it can fall back to canned data when offline.
+//! The OpenWeatherMap key comes from `OPENWEATHERMAP_API_KEY` and is only
+//! added to the URL used for the fetch, never to the submitted transaction.
+
 use crate::metrics::performance::PerformanceMetrics;
 use crate::metrics::storage::MetricsStorage;
-use crate::transaction::{handler::TransactionHandler, types::{Transaction, TransactionType}};
+use crate::transaction::{handler::TransactionHandler, types::Transaction};
 use crate::external::api::cached_api_call;
+use crate::external::oracle::extract_path_value;
 use crate::security::audit::SecurityAuditLog;
 use crate::transaction::utils::process_and_submit_verification;
 use std::sync::Arc;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crate::execution::manager::ExecutionManager;
 use sui_sdk::types::base_types::ObjectID;
 use sui_sdk::types::crypto::SuiKeyPair;
-use serde_json::json;
+use serde_json::{json, Value};
+
+/// Environment variable holding the OpenWeatherMap API key
+pub const API_KEY_ENV: &str = "OPENWEATHERMAP_API_KEY";
 
-const CITY: &str = "London";
+/// `url` with the OpenWeatherMap `appid` query parameter appended.
+pub fn with_api_key(url: &str, api_key: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}appid={}", url, separator, api_key)
+}
+
+/// Canned weather data used when the live API can't be reached.
+pub fn offline_weather_data() -> Value {
+    json!({
+        "main": {
+            "temp": 22.5,  // Nice temperature for demo
+            "humidity": 65
+        },
+        "weather": [{
+            "main": "Clear",  // Good weather, not "Thunderstorm" which would block execution
+            "description": "clear sky"
+        }],
+        "wind": {
+            "speed": 3.1
+        }
+    })
+}
+
+/// The transaction submitted for `scenario` given the fetched `weather_data`,
+/// or `None` when the scenario's condition isn't met.
+///
+/// The temperature and description are passed as params, and the query is
+/// dropped since it has already been evaluated.
+pub fn weather_transaction(scenario: &Transaction, weather_data: &Value) -> Result<Option<Transaction>> {
+    let query = scenario.external_query.as_ref()
+        .ok_or_else(|| anyhow!("Weather scenario has no external_query"))?;
+    let value = extract_path_value(weather_data, &query.path)
+        .and_then(|value| value.as_f64())
+        .unwrap_or(-999.0); // Handle potential parse error
+    if let Some(condition) = &query.condition {
+        if !condition.is_met(value) {
+            return Ok(None);
+        }
+    }
+
+    let mut tx = scenario.clone();
+    tx.external_query = None;
+    tx.python_params = Some(json!({
+        "temperature": weather_data["main"]["temp"].as_f64(),
+        "description": weather_data["weather"][0]["description"].as_str()
+    }));
+    Ok(Some(tx))
+}
 
 pub async fn run_weather_based_transaction_demo(
+    scenario: &Transaction,
     transaction_handler: &Arc<TransactionHandler>,
     execution_manager: &Arc<ExecutionManager>,
     metrics_storage: Option<&Arc<MetricsStorage>>,
@@ -23,89 +82,51 @@ pub async fn run_weather_based_transaction_demo(
     gas_object_id: &ObjectID,
 ) -> Result<()> {
     println!("\n--- RUNNING WEATHER-BASED TRANSACTION DEMO ---\n");
-    
+
     let tx_name = "weather_demo";
     let metrics = metrics_storage.map(|_s| PerformanceMetrics::new(tx_name));
-    
-    // 1. Get current weather data or use demo data
-    let weather_data = cached_api_call("https://api.openweathermap.org/data/2.5/weather?q=London,uk&appid=d0e7b0a99a2af7075f4f705e1112c66c&units=metric").await
-        .unwrap_or_else(|_| {
-            // Use demo data that will pass our conditions
-            serde_json::json!({
-                "main": {
-                    "temp": 22.5,  // Nice temperature for demo
-                    "humidity": 65
-                },
-                "weather": [{
-                    "main": "Clear",  // Good weather, not "Thunderstorm" which would block execution
-                    "description": "clear sky"
-                }],
-                "wind": {
-                    "speed": 3.1
-                }
-            })
-        });
-    
-    println!("Current weather data: {:?}", weather_data);
-    
-    let condition_met = weather_data["main"]["temp"].as_f64().unwrap_or(-999.0) > 25.0; // Handle potential parse error
 
-    if condition_met {
-        println!("Weather condition met (temp > 25.0). Preparing transaction...");
-
-        // Create a transaction representing the intent
-        let transaction = Transaction {
-            tx_type: TransactionType::Custom("weather_trigger".to_string()),
-            sender: config::SUBMITTER_ADDRESS.to_string(),
-            receiver: config::SUBMITTER_ADDRESS.to_string(),
-            amount: 1, 
-            gas_payment: config::SUBMITTER_GAS_OBJECT_ID.to_string(),
-            gas_budget: 2000000,
-            commands: vec!["process_weather_event".to_string()],
-            python_params: Some(json!({
-                "temperature": weather_data["main"]["temp"].as_f64(),
-                "description": weather_data["weather"][0]["description"].as_str()
-             })),
-            language: Some("native".to_string()), // Assume native middleware logic handles this
-            signatures: None, timestamp: 0, script: None, external_query: None,
-            python_code: None, websocket_endpoint: None, websocket_message: None,
-            time_condition: None,
-        };
+    // 1. Get current weather data or use demo data
+    let query = scenario.external_query.as_ref()
+        .ok_or_else(|| anyhow!("Weather scenario has no external_query"))?;
+    let live_data = match std::env::var(API_KEY_ENV) {
+        Ok(api_key) if !api_key.is_empty() => cached_api_call(&with_api_key(&query.url, &api_key)).await.ok(),
+        _ => {
+            println!("{} is not set; set it to an OpenWeatherMap API key to use live weather.", API_KEY_ENV);
+            None
+        }
+    };
+    let weather_data = live_data.unwrap_or_else(|| {
+        println!("Weather API unavailable, using demo weather data.");
+        offline_weather_data()
+    });
 
-        // Call the main processing and submission function
-        process_and_submit_verification(
-            &transaction,
-            tx_name,
-            transaction_handler,
-            execution_manager,
-            metrics_storage,
-            security_audit_log,
-            submitter_keypair,
-            gas_object_id,
-        ).await?;
+    println!("Current weather data: {:?}", weather_data);
 
-    } else {
-        println!("Weather condition not met (temp <= 25.0). No transaction processed.");
+    match weather_transaction(scenario, &weather_data)? {
+        Some(transaction) => {
+            println!("Weather condition met. Preparing transaction...");
+            // Call the main processing and submission function
+            process_and_submit_verification(
+                &transaction,
+                tx_name,
+                transaction_handler,
+                execution_manager,
+                metrics_storage,
+                security_audit_log,
+                submitter_keypair,
+                gas_object_id,
+            ).await?;
+        }
+        None => println!("Weather condition not met. No transaction processed."),
     }
 
     // Store metrics (only if metrics_storage was Some)
     if let (Some(m), Some(storage)) = (metrics, metrics_storage) {
         storage.add_metrics(m);
     }
-    
+
     println!("\n--- WEATHER-BASED TRANSACTION DEMO COMPLETE ---\n");
-    
+
     Ok(())
 }
-
-// Mock function if actual function is missing
-async fn get_weather_data(_api_key: &str, city: &str) -> Result<serde_json::Value> {
-    // This is a mock implementation. Replace with actual implementation.
-    Ok(json!({
-        "city": city,
-        "temperature": 22.5,
-        "conditions": "Partly Cloudy",
-        "humidity": 65,
-        "wind_speed": 10
-    }))
-}
\ No newline at end of file
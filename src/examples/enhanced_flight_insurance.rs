@@ -1,20 +1,27 @@
 use serde_json::json;
-use anyhow::{Result};
+use anyhow::{anyhow, Result};
 use crate::execution::manager::ExecutionManager;
-use crate::transaction::types::{Transaction, TransactionType};
+use crate::transaction::types::{Transaction, TransactionType, ValidationIssue};
 use crate::metrics::storage::MetricsStorage;
 use crate::security::audit::{SecurityAuditLog, AuditSeverity};
 use crate::external::flight_api::{get_cached_flight_status};
 use crate::sui::network::NetworkManager;
 use crate::sui::verification::VerificationManager;
-use crate::transaction::utils::process_and_submit_verification;
 use std::sync::Arc;
+use serde::Serialize;
 use sui_sdk::types::base_types::ObjectID;
 use sui_sdk::types::crypto::SuiKeyPair;
-use crate::config::{SUBMITTER_ADDRESS, SUBMITTER_GAS_OBJECT_ID, generate_test_sui_keypair};
+use crate::config::{SUBMITTER_ADDRESS, SUBMITTER_GAS_OBJECT_ID};
 use crate::metrics::performance::PerformanceMetrics;
 use crate::transaction::handler::TransactionHandler;
-use std::env;
+
+/// What the quorum attests to for a paid claim, BCS-encoded so the contract can decode it
+#[derive(Debug, Serialize)]
+struct ClaimRecord {
+    flight: String,
+    delay_minutes: i32,
+    payout: u64,
+}
 
 /// Enhanced flight insurance example that demonstrates our full security model
 /// 
@@ -26,13 +33,13 @@ use std::env;
 /// 5. Comprehensive audit logging
 pub async fn run_enhanced_flight_insurance_demo(
     transaction_handler: &Arc<TransactionHandler>,
-    execution_manager: &Arc<ExecutionManager>,
+    _execution_manager: &Arc<ExecutionManager>,
     metrics_storage: Option<&Arc<MetricsStorage>>,
     security_audit_log: &Arc<SecurityAuditLog>,
     _verification_manager: &Arc<VerificationManager>,
     _network_manager: &Arc<NetworkManager>,
     _submitter_keypair: &SuiKeyPair,
-    gas_object_id: &ObjectID,
+    _gas_object_id: &ObjectID,
 ) -> Result<()> {
     println!("\n--- RUNNING ENHANCED FLIGHT INSURANCE DEMO (REFACTORED) ---");
     let tx_name = "enhanced_flight_insurance";
@@ -50,15 +57,13 @@ pub async fn run_enhanced_flight_insurance_demo(
     if compensation > 0 {
         println!("Flight status warrants compensation ({}). Processing enhanced claim for policy {}...", compensation, policy_id);
 
-        let claim_transaction = Transaction {
-            tx_type: TransactionType::Custom("enhanced_flight_claim".to_string()),
-            sender: SUBMITTER_ADDRESS.to_string(),
-            receiver: SUBMITTER_ADDRESS.to_string(),
-            amount: compensation,
-            gas_payment: SUBMITTER_GAS_OBJECT_ID.to_string(),
-            gas_budget: 2000000,
-            commands: vec!["process_claim_enhanced".to_string()],
-            python_params: Some(json!({
+        let claim_transaction = Transaction::builder(TransactionType::Custom("enhanced_flight_claim".to_string()))
+            .sender(SUBMITTER_ADDRESS)
+            .receiver(SUBMITTER_ADDRESS)
+            .amount(compensation)
+            .gas_payment(SUBMITTER_GAS_OBJECT_ID)
+            .command("process_claim_enhanced")
+            .params(json!({
                 "policy_id": policy_id,
                 "flight_number": flight_number,
                 "status": flight_status.status,
@@ -67,31 +72,35 @@ pub async fn run_enhanced_flight_insurance_demo(
                 "policy_type": policy_type,
                 "passenger_details": { "name": "Jane Doe", "booking_ref": "ABCDEF" },
                 "security_level": "enhanced"
-            })),
-            language: Some("native".to_string()),
-            signatures: None,
-            timestamp: 0,
-            script: None,
-            external_query: None,
-            python_code: None,
-            websocket_endpoint: None,
-            websocket_message: None,
-            time_condition: None,
-        };
+            }))
+            .build()
+            .map_err(|issues| anyhow!("Invalid enhanced claim: {}", ValidationIssue::summarize(&issues)))?;
+
+        if !transaction_handler.validate_transaction(&claim_transaction, None).await? {
+            return Err(anyhow!("Enhanced claim for policy {} failed validation", policy_id));
+        }
 
-        // For test purposes, generate a test SuiKeyPair
-        let sui_keypair = generate_test_sui_keypair()?;
+        // The quorum attests to the claim record itself rather than opaque bytes
+        let record = ClaimRecord {
+            flight: flight_number.to_string(),
+            delay_minutes: flight_status.delay_minutes,
+            payout: compensation,
+        };
+        let (payload, verification_input) = transaction_handler.collect_quorum_signatures_over(&record).await
+            .map_err(|e| anyhow!("Quorum signature collection failed: {}", e))?;
+        println!(
+            "Collected {} signatures over {:?} ({} BCS bytes).",
+            verification_input.quorum_signatures.len(), record, payload.len()
+        );
 
-        process_and_submit_verification(
-            &claim_transaction,
+        let l1_digest = transaction_handler.submit_verification_for(&claim_transaction, verification_input).await?;
+        println!("Claim verification submitted. L1 Digest: {}", l1_digest);
+        security_audit_log.log_network(
             tx_name,
-            transaction_handler,
-            execution_manager,
-            metrics_storage,
-            security_audit_log,
-            &sui_keypair,
-            gas_object_id,
-        ).await?;
+            &format!("Submitted claim record verification to SUI. L1 Digest: {}", l1_digest),
+            None,
+            AuditSeverity::Info,
+        )?;
 
     } else {
         println!("Flight status does not warrant compensation for policy {}. No action taken.", policy_id);
@@ -1,13 +1,13 @@
 // use std::sync::Arc;
 // use serde_json::json;
-use crate::transaction::types::{Transaction, TransactionType};
+use crate::transaction::types::{Transaction, TransactionType, ValidationIssue};
 use crate::transaction::handler::TransactionHandler;
 use crate::execution::manager::ExecutionManager;
 use crate::external::flight_api::{get_cached_flight_status};
 use crate::metrics::storage::MetricsStorage;
 use crate::metrics::performance::PerformanceMetrics;
 // use crate::sui::verification::VerificationStatus;
-use anyhow::{Result};
+use anyhow::{anyhow, Result};
 // use rand::Rng;
 // use tokio::time;
 use std::env; // Needed for API key
@@ -44,25 +44,19 @@ pub async fn run_flight_delay_demo(
                  flight_number, flight_status.delay_minutes);
 
         let policy_type = "standard";
-        let transaction = Transaction {
-            tx_type: TransactionType::Custom("flight_delay_claim".to_string()),
-            sender: SUBMITTER_ADDRESS.to_string(), // Use config constant
-            receiver: SUBMITTER_ADDRESS.to_string(), // Use config constant (send to self for demo)
-            amount: 0, 
-            gas_payment: SUBMITTER_GAS_OBJECT_ID.to_string(), // Use config constant
-            gas_budget: 2000000, // Increased from 10000 to 2000000 to meet minimum requirement
-            commands: vec!["process_delay_claim".to_string()],
-            python_params: Some(json!({
+        // Native logic calculates the payout from the params
+        let transaction = Transaction::builder(TransactionType::Custom("flight_delay_claim".to_string()))
+            .sender(SUBMITTER_ADDRESS)
+            .receiver(SUBMITTER_ADDRESS) // Send to self for demo
+            .gas_payment(SUBMITTER_GAS_OBJECT_ID)
+            .command("process_delay_claim")
+            .params(json!({
                 "flight_number": flight_status.flight_number,
                 "delay_minutes": flight_status.delay_minutes,
                 "policy_type": policy_type
-            })),
-            language: Some("native".to_string()), // Assume native logic calculates payout based on params
-            // ... other fields null/None ...
-            signatures: None, timestamp: 0, script: None, external_query: None,
-            python_code: None, websocket_endpoint: None, websocket_message: None,
-            time_condition: None,
-        };
+            }))
+            .build()
+            .map_err(|issues| anyhow!("Invalid delay claim: {}", ValidationIssue::summarize(&issues)))?;
 
         // Call the main processing and submission function
         process_and_submit_verification(
@@ -0,0 +1,87 @@
+//! Degraded-mode operation for the execution pipeline.
+//!
+//! `ExecutionManager` derives a `DegradationLevel` from the health of the
+//! subsystems it depends on and adjusts what it will do:
+//! - `Full`: everything runs normally.
+//! - `Degraded`: attestations are still produced, but flagged with the reason
+//!   so verifiers can tell they were made on reduced inputs, and non-essential
+//!   work (WebSocket streaming) is skipped.
+//! - `ReadOnly`: nothing that would be submitted to L1 is processed; reads and
+//!   simulations still work.
+
+use crate::external::oracle::OracleSourceStatus;
+use crate::sui::network::NodeStatus;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How much of the pipeline is currently allowed to run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(tag = "level", rename_all = "snake_case")]
+pub enum DegradationLevel {
+    #[default]
+    Full,
+    Degraded { reason: String },
+    ReadOnly { reason: String },
+}
+
+impl fmt::Display for DegradationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DegradationLevel::Full => write!(f, "full"),
+            DegradationLevel::Degraded { reason } => write!(f, "degraded ({})", reason),
+            DegradationLevel::ReadOnly { reason } => write!(f, "read-only ({})", reason),
+        }
+    }
+}
+
+impl DegradationLevel {
+    /// Level implied by the oracle's status and the RPC endpoint health, either of which may be unknown.
+    ///
+    /// No healthy RPC endpoint means nothing can reach L1, so the pipeline goes
+    /// read-only. A failed or degraded oracle, or RPC endpoints that are only
+    /// partly healthy, leave it degraded.
+    pub fn assess(oracle: Option<&OracleSourceStatus>, rpc: Option<&HashMap<String, NodeStatus>>) -> Self {
+        let mut reasons = Vec::new();
+
+        if let Some(nodes) = rpc {
+            let healthy = nodes.values().filter(|status| **status == NodeStatus::Healthy).count();
+            if healthy == 0 {
+                return DegradationLevel::ReadOnly { reason: format!("no healthy RPC endpoint (of {})", nodes.len()) };
+            }
+            if healthy < nodes.len() {
+                reasons.push(format!("{} of {} RPC endpoints unhealthy", nodes.len() - healthy, nodes.len()));
+            }
+        }
+
+        match oracle {
+            Some(OracleSourceStatus::Failed(reason)) => reasons.push(format!("oracle failed: {}", reason)),
+            Some(OracleSourceStatus::Degraded(reason)) => reasons.push(format!("oracle degraded: {}", reason)),
+            _ => {}
+        }
+
+        if reasons.is_empty() {
+            DegradationLevel::Full
+        } else {
+            DegradationLevel::Degraded { reason: reasons.join("; ") }
+        }
+    }
+
+    /// Whether transactions may be processed into attestations for L1.
+    pub fn allows_attestation(&self) -> bool {
+        !matches!(self, DegradationLevel::ReadOnly { .. })
+    }
+
+    /// Whether non-essential work such as WebSocket streaming should run.
+    pub fn allows_optional_work(&self) -> bool {
+        matches!(self, DegradationLevel::Full)
+    }
+
+    /// The reason attached to degraded attestations, `None` at full service.
+    pub fn degraded_reason(&self) -> Option<&str> {
+        match self {
+            DegradationLevel::Full => None,
+            DegradationLevel::Degraded { reason } | DegradationLevel::ReadOnly { reason } => Some(reason),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Bounded admission of concurrent submissions.
+//!
+//! Each in-flight transaction holds a `SubmissionPermit` until it leaves the
+//! pipeline, so concurrent callers can't open unbounded RPC connections.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// What to do with a submission that arrives while the gateway is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverloadPolicy {
+    /// Queue until a slot frees up.
+    Wait,
+    /// Fail immediately with `Busy`.
+    Reject,
+}
+
+/// Returned when the gateway is full and its policy is `Reject`.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Submission gateway busy: {in_flight}/{max_in_flight} submissions in flight")]
+pub struct Busy {
+    pub in_flight: usize,
+    pub max_in_flight: usize,
+}
+
+/// Parse a `--max-in-flight` value, which must be a positive integer.
+///
+/// Zero is rejected rather than raised to one, so a typo doesn't quietly
+/// serialize every submission.
+pub fn parse_max_in_flight(value: &str) -> Result<usize, String> {
+    match value.trim().parse::<usize>() {
+        Ok(0) => Err("--max-in-flight must be at least 1".to_string()),
+        Ok(limit) => Ok(limit),
+        Err(_) => Err(format!("--max-in-flight must be a positive integer, got '{}'", value)),
+    }
+}
+
+/// Slot in the gateway, released when dropped.
+#[derive(Debug)]
+pub struct SubmissionPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+/// Counts a waiter for as long as it's alive, including if its future is dropped mid-wait.
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl<'a> QueuedGuard<'a> {
+    fn enter(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Semaphore-guarded limit on in-flight submissions.
+#[derive(Debug)]
+pub struct SubmissionGateway {
+    semaphore: Arc<Semaphore>,
+    max_in_flight: usize,
+    policy: OverloadPolicy,
+    /// Callers currently waiting for a slot under `OverloadPolicy::Wait`
+    queued: AtomicUsize,
+}
+
+impl SubmissionGateway {
+    pub fn new(max_in_flight: usize, policy: OverloadPolicy) -> Self {
+        // A zero limit would make every submission wait forever
+        let max_in_flight = max_in_flight.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_in_flight)),
+            max_in_flight,
+            policy,
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    pub fn policy(&self) -> OverloadPolicy {
+        self.policy
+    }
+
+    /// Submissions currently holding a permit.
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight - self.semaphore.available_permits()
+    }
+
+    /// Submissions waiting for a permit.
+    pub fn queue_depth(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// Take a slot, waiting or failing with `Busy` according to the policy.
+    pub async fn acquire(&self) -> Result<SubmissionPermit, Busy> {
+        match self.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => return Ok(SubmissionPermit { _permit: permit }),
+            Err(_) if self.policy == OverloadPolicy::Reject => {
+                return Err(Busy { in_flight: self.in_flight(), max_in_flight: self.max_in_flight });
+            }
+            Err(_) => {}
+        }
+
+        let permit = {
+            let _queued = QueuedGuard::enter(&self.queued);
+            self.semaphore.clone().acquire_owned().await
+        };
+        // The semaphore is never closed, so acquiring can only fail if that changes
+        permit
+            .map(|permit| SubmissionPermit { _permit: permit })
+            .map_err(|_| Busy { in_flight: self.in_flight(), max_in_flight: self.max_in_flight })
+    }
+}
@@ -1,18 +1,32 @@
 use anyhow::{Result, anyhow};
 use reqwest;
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use serde_json::{Value, json};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+use tracing::{debug, error, info, warn};
 
-use crate::transaction::types::{Transaction, ExternalQuery, MiddlewareAttestation, VerificationInput};
+use crate::transaction::types::{Transaction, TransactionType, ExternalQuery, MiddlewareAttestation, ValidationIssue, VerificationInput};
 use crate::languages::python::PythonExecutor;
 use crate::languages::javascript::JavaScriptExecutor;
+use crate::external::oracle::{extract_path_value, OracleSource};
 use crate::external::websocket::WebSocketClient;
 use crate::conditions::time::TimeBasedEvaluator;
-use crate::metrics::performance::PerformanceMetrics;
+use crate::metrics::performance::{PerformanceMetrics, WindowedStats};
+use crate::metrics::trace::ExecutionTrace;
+use crate::execution::degradation::DegradationLevel;
+use crate::execution::gateway::{Busy, OverloadPolicy, SubmissionGateway, SubmissionPermit};
+use crate::execution::simulation::{OracleReading, SimulationResult};
 use crate::sui::verification::VerificationManager;
 use crate::sui::network::NetworkManager;
 use crate::security::audit::{SecurityAuditLog, AuditSeverity};
+use crate::transaction::tracker::{LifecycleState, TransactionTracker};
+use crate::transaction::dead_letter::{DeadLetter, DeadLetterQueue};
+use crate::transaction::amount::Amount;
+
+/// Traces kept in memory unless configured otherwise
+pub const DEFAULT_TRACE_CAPACITY: usize = 1024;
 
 pub struct ExecutionManager {
     pub client: reqwest::Client,
@@ -20,6 +34,72 @@ pub struct ExecutionManager {
     network_manager: Option<Arc<NetworkManager>>,
     security_audit_log: Option<Arc<SecurityAuditLog>>,
     pub client_manager: ClientManager,
+    tracker: Arc<TransactionTracker>,
+    /// Answers external queries instead of fetching `query.url` over HTTP
+    oracle_source: Option<Arc<dyn OracleSource>>,
+    /// Span timelines of the most recent transactions, by tracking id
+    traces: Mutex<TraceStore>,
+    /// Where finished transactions' traces are saved in Chrome tracing format
+    trace_dir: Option<PathBuf>,
+    /// Recent span durations across all traces
+    operation_window: Arc<WindowedStats>,
+    /// Caps concurrent submissions; unbounded when `None`
+    submission_gateway: Option<Arc<SubmissionGateway>>,
+    /// Receives transactions that reach `LifecycleState::Failed`
+    dead_letters: Option<Arc<DeadLetterQueue>>,
+    /// Set by `assess_degradation` or an operator via `set_degradation`
+    degradation: Mutex<DegradationLevel>,
+}
+
+/// `ExecutionTrace`s by tracking id, oldest evicted first.
+#[derive(Debug)]
+struct TraceStore {
+    capacity: usize,
+    traces: HashMap<String, Arc<ExecutionTrace>>,
+    order: VecDeque<String>,
+}
+
+impl TraceStore {
+    fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), traces: HashMap::new(), order: VecDeque::new() }
+    }
+
+    fn get_or_insert(&mut self, id: &str, create: impl FnOnce() -> ExecutionTrace) -> Arc<ExecutionTrace> {
+        if let Some(trace) = self.traces.get(id) {
+            return trace.clone();
+        }
+        let trace = Arc::new(create());
+        self.traces.insert(id.to_string(), trace.clone());
+        self.order.push_back(id.to_string());
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.traces.remove(&oldest);
+            }
+        }
+        trace
+    }
+}
+
+/// What evaluating a transaction decided.
+enum Decision {
+    Attest(Value),
+    /// Nothing to attest, with the script output when a script decided so
+    Skip { reason: String, outcome: Option<Value> },
+}
+
+impl Decision {
+    fn skip(reason: &str) -> Self {
+        Decision::Skip { reason: reason.to_string(), outcome: None }
+    }
+}
+
+/// State threaded through `ExecutionManager::decide`.
+struct DecisionContext<'a> {
+    /// Track, trace and audit as a real run would; off for `simulate`
+    live: bool,
+    metrics: Option<&'a mut PerformanceMetrics>,
+    /// Every oracle value read on the way to the decision
+    oracle_values: Vec<OracleReading>,
 }
 
 impl ExecutionManager {
@@ -34,30 +114,323 @@ impl ExecutionManager {
             network_manager: network_manager,
             security_audit_log: security_audit_log,
             client_manager: ClientManager::new(),
+            tracker: Arc::new(TransactionTracker::new()),
+            oracle_source: None,
+            traces: Mutex::new(TraceStore::new(DEFAULT_TRACE_CAPACITY)),
+            trace_dir: None,
+            operation_window: Arc::new(WindowedStats::default()),
+            submission_gateway: None,
+            dead_letters: None,
+            degradation: Mutex::new(DegradationLevel::Full),
         }
     }
 
+    /// The level processing currently runs at.
+    pub fn current_degradation(&self) -> DegradationLevel {
+        self.degradation.lock().unwrap().clone()
+    }
+
+    /// Switch to `level`, auditing the change if it is one.
+    pub fn set_degradation(&self, level: DegradationLevel) {
+        let previous = std::mem::replace(&mut *self.degradation.lock().unwrap(), level.clone());
+        if previous == level {
+            return;
+        }
+        warn!(%previous, %level, "Execution degradation level changed");
+        if let Some(audit_log) = &self.security_audit_log {
+            let severity = match level {
+                DegradationLevel::Full => AuditSeverity::Info,
+                DegradationLevel::Degraded { .. } => AuditSeverity::Warning,
+                DegradationLevel::ReadOnly { .. } => AuditSeverity::Error,
+            };
+            let _ = audit_log.log_execution(
+                "ExecutionManager",
+                &format!("Degradation level changed from {} to {}", previous, level),
+                None,
+                severity,
+            );
+        }
+    }
+
+    /// Re-derive the level from the oracle source's status and RPC endpoint health.
+    ///
+    /// Subsystems that aren't configured are treated as healthy.
+    pub async fn assess_degradation(&self) -> DegradationLevel {
+        let oracle_status = self.oracle_source.as_ref().map(|source| source.status());
+        let rpc_health = match &self.network_manager {
+            Some(network_manager) => Some(network_manager.health_check_all().await),
+            None => None,
+        };
+        let level = DegradationLevel::assess(oracle_status.as_ref(), rpc_health.as_ref());
+        self.set_degradation(level.clone());
+        level
+    }
+
+    /// Keep the level current by re-assessing it every `interval`, in the background.
+    ///
+    /// An operator's `set_degradation` only lasts until the next assessment.
+    pub fn spawn_degradation_monitor(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut interval_timer = tokio::time::interval(interval);
+            loop {
+                interval_timer.tick().await;
+                manager.assess_degradation().await;
+            }
+        })
+    }
+
+    /// Fail if the current level doesn't allow producing attestations.
+    fn ensure_attestation_allowed(&self, tx: &Transaction) -> Result<()> {
+        let level = self.current_degradation();
+        if level.allows_attestation() {
+            return Ok(());
+        }
+        self.track(tx, LifecycleState::Failed(format!("Refused while {}", level)));
+        Err(anyhow!("Middleware is {}; not processing transactions for L1", level))
+    }
+
+    /// `outcome` flagged with the degradation reason, if processing isn't at full service.
+    fn flag_degraded(&self, mut outcome: Value) -> Value {
+        if let (Some(reason), Some(fields)) = (self.current_degradation().degraded_reason(), outcome.as_object_mut()) {
+            fields.insert("degraded".to_string(), json!(reason));
+        }
+        outcome
+    }
+
+    /// Record every terminally failed transaction in `queue`.
+    pub fn with_dead_letter_queue(mut self, queue: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Some(queue);
+        self
+    }
+
+    pub fn dead_letter_queue(&self) -> Option<Arc<DeadLetterQueue>> {
+        self.dead_letters.clone()
+    }
+
+    /// Allow at most `max_in_flight` concurrent submissions, handling the excess per `policy`.
+    pub fn with_submission_limit(mut self, max_in_flight: usize, policy: OverloadPolicy) -> Self {
+        self.submission_gateway = Some(Arc::new(SubmissionGateway::new(max_in_flight, policy)));
+        self
+    }
+
+    pub fn submission_gateway(&self) -> Option<Arc<SubmissionGateway>> {
+        self.submission_gateway.clone()
+    }
+
+    /// Reserve a submission slot, to be held until the transaction leaves the pipeline.
+    ///
+    /// Returns `Ok(None)` when no limit is configured.
+    pub async fn acquire_submission_slot(&self) -> Result<Option<SubmissionPermit>, Busy> {
+        match &self.submission_gateway {
+            Some(gateway) => gateway.acquire().await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Submissions waiting for a slot (always 0 without a limit).
+    pub fn submission_queue_depth(&self) -> usize {
+        self.submission_gateway.as_ref().map_or(0, |gateway| gateway.queue_depth())
+    }
+
+    /// Resolve external queries through `source` rather than plain HTTP.
+    ///
+    /// The source is called with `{"url": query.url}` and `query.path` is applied
+    /// to what it returns, exactly as for an HTTP response.
+    pub fn with_oracle_source(mut self, source: Arc<dyn OracleSource>) -> Self {
+        self.oracle_source = Some(source);
+        self
+    }
+
+    /// Share an existing tracker (e.g. one also read by the HTTP API).
+    pub fn with_tracker(mut self, tracker: Arc<TransactionTracker>) -> Self {
+        self.tracker = tracker;
+        self
+    }
+
+    /// Lifecycle tracker updated as transactions move through the pipeline.
+    pub fn tracker(&self) -> Arc<TransactionTracker> {
+        self.tracker.clone()
+    }
+
+    /// Record a lifecycle transition for `tx`, keyed by its payload hash.
+    ///
+    /// The first `Failed` state of a run also goes to the dead-letter queue, if one is set.
+    pub fn track(&self, tx: &Transaction, state: LifecycleState) {
+        let hash = match tx.payload_hash() {
+            Ok(hash) => hash,
+            Err(e) => {
+                warn!(error = %e, "Failed to hash transaction for lifecycle tracking");
+                return;
+            }
+        };
+        // Callers often report the same failure at several layers; record it once
+        let already_failed = matches!(self.tracker.current_state(&hash), Some(LifecycleState::Failed(_)));
+        self.tracker.update(&hash, state.clone());
+        if state.is_terminal() && !already_failed {
+            self.save_trace(&hash);
+        }
+
+        if let (LifecycleState::Failed(reason), Some(queue), false) = (&state, &self.dead_letters, already_failed) {
+            let history = self.tracker.get(&hash).map(|record| record.history).unwrap_or_default();
+            match queue.record(&DeadLetter::new(tx.clone(), &hash, reason, history)) {
+                Ok(()) => {
+                    if let Some(audit_log) = &self.security_audit_log {
+                        let _ = audit_log.log_execution(
+                            "ExecutionManager",
+                            &format!("Transaction moved to dead-letter log {}: {}", queue.path().display(), reason),
+                            Some(&hash),
+                            AuditSeverity::Warning,
+                        );
+                    }
+                }
+                Err(e) => error!(payload_hash = %hash, "Failed to record dead letter: {:#}", e),
+            }
+        }
+    }
+
+    /// Attach the L1 verification digest to `tx`'s lifecycle record.
+    pub fn track_l1_digest(&self, tx: &Transaction, l1_digest: &str) {
+        if let Ok(hash) = tx.payload_hash() {
+            self.tracker.set_l1_digest(&hash, l1_digest);
+        }
+    }
+
+    /// Keep the traces of the `capacity` most recent transactions instead of `DEFAULT_TRACE_CAPACITY`.
+    pub fn with_trace_capacity(mut self, capacity: usize) -> Self {
+        self.traces = Mutex::new(TraceStore::new(capacity));
+        self
+    }
+
+    /// Save each transaction's trace to `<dir>/<tracking id>.trace.json` once it
+    /// is confirmed or failed, for `chrome://tracing` or Perfetto.
+    pub fn with_trace_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.trace_dir = Some(dir.into());
+        self
+    }
+
+    /// Timeline for `tx`, created on first use and shared by every stage that handles it.
+    pub fn trace_for(&self, tx: &Transaction) -> Arc<ExecutionTrace> {
+        match tx.payload_hash() {
+            Ok(hash) => self.traces.lock().unwrap()
+                .get_or_insert(&hash, || ExecutionTrace::new(&hash).with_window(self.operation_window.clone())),
+            // Still trace the call, just without keeping the result
+            Err(_) => Arc::new(ExecutionTrace::new("untracked")),
+        }
+    }
+
+    /// Stage durations of every transaction processed in the last minute.
+    pub fn operation_window(&self) -> Arc<WindowedStats> {
+        self.operation_window.clone()
+    }
+
+    /// Timeline recorded for `tx`, if it has been processed recently.
+    pub fn trace(&self, tx: &Transaction) -> Option<Arc<ExecutionTrace>> {
+        self.trace_by_id(&tx.payload_hash().ok()?)
+    }
+
+    /// Timeline recorded under tracking id `id` (`Transaction::payload_hash`).
+    pub fn trace_by_id(&self, id: &str) -> Option<Arc<ExecutionTrace>> {
+        self.traces.lock().unwrap().traces.get(id).cloned()
+    }
+
+    /// Write the finished trace under `id` to the trace directory, if there is one.
+    fn save_trace(&self, id: &str) {
+        let (Some(dir), Some(trace)) = (&self.trace_dir, self.trace_by_id(id)) else { return };
+        let path = dir.join(format!("{}.trace.json", id));
+        if let Err(e) = trace.save_chrome_trace(&path) {
+            warn!(payload_hash = %id, "Failed to save trace to {}: {:#}", path.display(), e);
+        }
+    }
+
+    /// Explorer link for an L1 digest on the active network, if one is known.
+    pub fn tx_explorer_link(&self, l1_digest: &str) -> Option<String> {
+        self.network_manager.as_ref()?.get_active_config().tx_explorer_link(l1_digest)
+    }
+
+    /// Run `Transaction::validate`, reporting all issues in one error.
+    fn validate_transaction_fields(&self, tx: &Transaction) -> Result<()> {
+        let issues = match tx.validate() {
+            Ok(()) => return Ok(()),
+            Err(issues) => issues,
+        };
+        let summary = ValidationIssue::summarize(&issues);
+        if let Some(audit_log) = &self.security_audit_log {
+            let _ = audit_log.log_execution(
+                "ExecutionManager",
+                &format!("Rejected malformed transaction: {}", summary),
+                None,
+                AuditSeverity::Warning,
+            );
+        }
+        self.track(tx, LifecycleState::Failed(format!("Invalid transaction: {}", summary)));
+        Err(anyhow!("Invalid transaction ({} issue(s)): {}", issues.len(), summary))
+    }
+
+    /// Refuse `tx` once its `valid_until` deadline has passed.
+    fn ensure_not_expired(&self, tx: &Transaction) -> Result<()> {
+        let expired = match tx.check_not_expired() {
+            Ok(()) => return Ok(()),
+            Err(expired) => expired,
+        };
+        if let Some(audit_log) = &self.security_audit_log {
+            let _ = audit_log.log_execution(
+                "ExecutionManager",
+                &format!("Rejected stale transaction: {}", expired),
+                tx.payload_hash().ok().as_deref(),
+                AuditSeverity::Warning,
+            );
+        }
+        self.track(tx, LifecycleState::Failed(expired.to_string()));
+        Err(expired.into())
+    }
+
     pub async fn fetch_external_data(&self, query: &ExternalQuery, _metrics: Option<&mut PerformanceMetrics>) -> Result<f64> {
-        let response = self.client.get(&query.url)
-            .send()
-            .await?
-            .json::<Value>()
-            .await?;
-        
-        let mut current = &response;
-        for key in &query.path {
-            current = &current[key];
-        }
-        
-        current.as_f64()
+        let response = match &self.oracle_source {
+            Some(source) => source.fetch(&json!({ "url": query.url })).await?,
+            None => self.client.get(&query.url)
+                .send()
+                .await?
+                .json::<Value>()
+                .await?,
+        };
+
+        // Same path syntax as oracle sources, so array indices like "0" work
+        extract_path_value(&response, &query.path)
+            .and_then(|value| value.as_f64())
             .ok_or_else(|| anyhow!("Invalid response format"))
     }
 
+    /// Decide `tx` and attest the outcome.
+    ///
+    /// Callers record `Received` before validating, so this only tracks later states.
     pub async fn process_transaction_and_attest(
         &self,
         tx: &Transaction,
-        mut metrics: Option<&mut PerformanceMetrics>
+        metrics: Option<&mut PerformanceMetrics>
     ) -> Result<Option<MiddlewareAttestation>, anyhow::Error> {
+        self.attest(tx, metrics, &mut Vec::new()).await
+    }
+
+    /// `process_transaction_and_attest`, appending the oracle values it read to `oracle_values`.
+    #[tracing::instrument(
+        name = "transaction",
+        skip_all,
+        fields(payload_hash = %tx.payload_hash().unwrap_or_default(), sender = %tx.sender)
+    )]
+    async fn attest(
+        &self,
+        tx: &Transaction,
+        mut metrics: Option<&mut PerformanceMetrics>,
+        oracle_values: &mut Vec<OracleReading>,
+    ) -> Result<Option<MiddlewareAttestation>> {
+        let trace = self.trace_for(tx);
+        {
+            let _span = trace.span("validation");
+            self.validate_transaction_fields(tx)?;
+        }
+        self.ensure_not_expired(tx)?;
+        self.ensure_attestation_allowed(tx)?;
         if let Some(audit_log) = &self.security_audit_log {
             audit_log.log_execution(
                 "ExecutionManager",
@@ -78,177 +451,241 @@ impl ExecutionManager {
             m.execution_start_time = Some(SystemTime::now());
         }
 
+        let mut context = DecisionContext { live: true, metrics: metrics.as_deref_mut(), oracle_values: Vec::new() };
+        let decision = self.decide(tx, &mut context).await;
+        oracle_values.append(&mut context.oracle_values);
+        if let Some(m) = metrics.as_mut() {
+            m.execution_end_time = Some(SystemTime::now());
+        }
+
+        let scripted = !matches!(tx.tx_type, TransactionType::ConditionalPayout { .. });
+        let outcome = match decision? {
+            Decision::Attest(outcome) => outcome,
+            Decision::Skip { reason, outcome } => {
+                if scripted && outcome.is_some() {
+                    self.stream_to_websocket(tx)?;
+                }
+                info!("Skipping attestation: {}", reason);
+                return Ok(None);
+            }
+        };
+        if scripted {
+            self.stream_to_websocket(tx)?;
+        }
+
+        let attestation = MiddlewareAttestation::for_transaction(tx, outcome)
+            .map_err(|e| anyhow!("Failed to serialize attestation: {}", e))?;
+
+        if let Some(audit_log) = &self.security_audit_log {
+            audit_log.log_execution(
+                "ExecutionManager",
+                "Successfully processed transaction and generated attestation",
+                None,
+                AuditSeverity::Info
+            )?;
+        }
+
+        Ok(Some(attestation))
+    }
+
+    /// Send `tx`'s WebSocket message, unless degradation rules out optional work.
+    fn stream_to_websocket(&self, tx: &Transaction) -> Result<()> {
+        if let Some(ws_endpoint) = &tx.websocket_endpoint {
+            let level = self.current_degradation();
+            if level.allows_optional_work() {
+                let ws_client = WebSocketClient::new(ws_endpoint);
+                ws_client.start_listening()?;
+                if let Some(message) = &tx.websocket_message {
+                    ws_client.send_message(message)?;
+                }
+            } else {
+                warn!(%level, "Skipping WebSocket streaming to {}", ws_endpoint);
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate `tx`'s time condition, oracle queries and script into the outcome to attest.
+    ///
+    /// Shared by `process_transaction_and_attest` and `simulate`, so a preview
+    /// reaches the same decision a real run would. Tracking, tracing and
+    /// auditing only happen when `context.live` is set.
+    async fn decide(&self, tx: &Transaction, context: &mut DecisionContext<'_>) -> Result<Decision> {
         if let Some(time_condition) = &tx.time_condition {
             match TimeBasedEvaluator::evaluate(time_condition) {
-                Ok(true) => println!("Time condition satisfied"),
-                Ok(false) => {
-                    println!("Time condition not satisfied, skipping middleware processing");
-                    if let Some(m) = metrics.as_mut() { m.execution_end_time = Some(SystemTime::now()); }
-                    return Ok(None);
-                },
-                Err(e) => {
-                    println!("Error evaluating time condition: {}", e);
-                    if let Some(m) = metrics.as_mut() { m.execution_end_time = Some(SystemTime::now()); }
-                    return Err(anyhow!("Time condition evaluation error: {}", e));
-                }
+                Ok(true) => debug!("Time condition satisfied"),
+                Ok(false) => return Ok(Decision::skip("Time condition not satisfied")),
+                Err(e) => return Err(self.failed(tx, context, format!("Time condition evaluation error: {}", e))),
+            }
+        }
+
+        // Conditional payouts are settled natively from the oracle value; no scripts run
+        if let TransactionType::ConditionalPayout { oracle_query, condition, payout_amount } = &tx.tx_type {
+            let oracle_value = match self.read_oracle(tx, oracle_query, context).await {
+                Ok(value) => value,
+                Err(e) => return Err(self.failed(tx, context, format!("Payout oracle query failed: {}", e))),
+            };
+            if !condition.is_met(oracle_value) {
+                return Ok(Decision::skip(&format!("Payout condition not met ({} {} {})", oracle_value, condition.operator, condition.threshold)));
+            }
+            info!(payout_amount, "Payout condition met ({} {} {})", oracle_value, condition.operator, condition.threshold);
+            if let (true, Some(audit_log)) = (context.live, &self.security_audit_log) {
+                let _ = audit_log.log_execution(
+                    "ExecutionManager",
+                    &format!("Conditional payout of {} to {} approved (oracle value {})", payout_amount, tx.receiver, oracle_value),
+                    None,
+                    AuditSeverity::Info,
+                );
             }
+            return Ok(Decision::Attest(self.flag_degraded(Self::payout_outcome(tx, oracle_value, *payout_amount))));
         }
 
-        let mut continue_execution = true;
         if let Some(query) = &tx.external_query {
+            let fetched = self.read_oracle(tx, query, context).await;
             if let Some(condition) = &query.condition {
-                println!("External query condition present: {:?}", query);
-                let external_value_result = self.fetch_external_data(query, metrics.as_deref_mut()).await;
-                match external_value_result {
-                    Ok(external_value) => {
-                        println!("External value from query {:?}", external_value);
-                        let threshold = condition.threshold as f64;
-                        continue_execution = match condition.operator.as_str() {
-                            "gt" => external_value > threshold,
-                            "lt" => external_value < threshold,
-                            "eq" => (external_value - threshold).abs() < f64::EPSILON,
-                            _ => false,
-                        };
-                        if !continue_execution {
-                            println!("External query condition NOT met ({:?} {} {} = {}). Skipping middleware processing.",
-                                external_value, condition.operator, threshold, continue_execution);
-                        }
-                    },
-                    Err(e) => {
-                        println!("Error fetching external data for condition: {}", e);
-                        if let Some(m) = metrics.as_mut() { m.execution_end_time = Some(SystemTime::now()); }
-                        return Err(anyhow!("External query failed: {}", e));
-                    }
+                let value = match fetched {
+                    Ok(value) => value,
+                    Err(e) => return Err(self.failed(tx, context, format!("External query failed: {}", e))),
+                };
+                if !condition.is_met(value) {
+                    return Ok(Decision::skip(&format!("External query condition not met ({} {} {})", value, condition.operator, condition.threshold)));
                 }
-            } else {
-                let _ = self.fetch_external_data(query, metrics.as_deref_mut()).await;
             }
         }
 
-        if !continue_execution {
-            if let Some(m) = metrics.as_mut() { m.execution_end_time = Some(SystemTime::now()); }
-            return Ok(None);
-        }
-
-        let mut middleware_outcome: Value = json!({});
-        let mut should_generate_attestation = true;
-
-        let language = tx.language.as_deref().unwrap_or("native");
-
-        match language {
-            "python" => {
-                if let Some(code) = &tx.python_code {
-                    println!("Executing Python code: {:?}", code);
-                    match PythonExecutor::execute(code, tx.python_params.clone()) {
-                        Ok(result) => {
-                            println!("Python execution successful: {:?}", result.output);
-                            middleware_outcome = result.output;
-                            if let Some(Value::Bool(execute)) = middleware_outcome.get("should_execute") {
-                                if !execute {
-                                    println!("Python script decided not to generate attestation");
-                                    should_generate_attestation = false;
-                                }
-                            }
-                        },
-                        Err(e) => {
-                            println!("Error executing Python: {}", e);
-                            if let Some(m) = metrics.as_mut() { m.execution_end_time = Some(SystemTime::now()); }
-                            return Err(anyhow!("Python execution error: {}", e));
-                        }
-                    }
-                }
-            },
-            "javascript" => {
-                if let Some(script) = &tx.script {
-                    println!("Executing JavaScript code: {:?}", script);
-                    match JavaScriptExecutor::execute(script, None) {
-                        Ok(result) => {
-                            println!("JavaScript execution successful: {:?}", result.output);
-                            middleware_outcome = result.output;
-                            if let Some(Value::Bool(execute)) = middleware_outcome.get("should_execute") {
-                                if !execute {
-                                    println!("JavaScript script decided not to generate attestation");
-                                    should_generate_attestation = false;
-                                }
-                            }
-                        },
-                        Err(e) => {
-                            println!("Error executing JavaScript: {}", e);
-                            if let Some(m) = metrics.as_mut() { m.execution_end_time = Some(SystemTime::now()); }
-                            return Err(anyhow!("JavaScript execution error: {}", e));
-                        }
-                    }
-                }
+        let output = match tx.language.as_deref().unwrap_or("native") {
+            "python" => match &tx.python_code {
+                Some(code) => match PythonExecutor::execute(code, tx.python_params.clone()) {
+                    Ok(result) => result.output,
+                    Err(e) => return Err(self.failed(tx, context, format!("Python execution error: {}", e))),
+                },
+                None => json!({}),
             },
-            "native" => {
-                middleware_outcome = json!({ "executed_natively": true });
+            "javascript" => match &tx.script {
+                Some(script) => match JavaScriptExecutor::execute(script, None) {
+                    Ok(result) => result.output,
+                    Err(e) => return Err(self.failed(tx, context, format!("JavaScript execution error: {}", e))),
+                },
+                None => json!({}),
             },
-            _ => {
-                if let Some(m) = metrics.as_mut() { m.execution_end_time = Some(SystemTime::now()); }
-                return Err(anyhow!("Unsupported language: {}", language));
-            }
+            "native" => json!({ "executed_natively": true }),
+            other => return Err(anyhow!("Unsupported language: {}", other)),
+        };
+        if let Some(Value::Bool(false)) = output.get("should_execute") {
+            return Ok(Decision::Skip { reason: "Script decided not to generate an attestation".to_string(), outcome: Some(output) });
         }
+        Ok(Decision::Attest(self.flag_degraded(output)))
+    }
 
-        if let Some(ws_endpoint) = &tx.websocket_endpoint {
-            let ws_client = WebSocketClient::new(ws_endpoint);
-            ws_client.start_listening()?;
-            if let Some(message) = &tx.websocket_message {
-                ws_client.send_message(message)?;
+    /// Fetch `query`, recording the value in `context` and, when live, tracing and tracking it.
+    async fn read_oracle(&self, tx: &Transaction, query: &ExternalQuery, context: &mut DecisionContext<'_>) -> Result<f64> {
+        let fetched = {
+            let trace = context.live.then(|| self.trace_for(tx));
+            let _span = trace.as_ref().map(|trace| trace.span("oracle_fetch"));
+            self.fetch_external_data(query, context.metrics.as_deref_mut()).await
+        };
+        if let Ok(value) = fetched {
+            context.oracle_values.push(OracleReading { url: query.url.clone(), path: query.path.clone(), value });
+            if context.live {
+                self.track(tx, LifecycleState::OracleResolved);
             }
         }
+        fetched
+    }
 
-        if let Some(m) = metrics.as_mut() {
-            m.execution_end_time = Some(SystemTime::now());
+    /// `message` as an error, tracked as `tx`'s failure when live.
+    fn failed(&self, tx: &Transaction, context: &DecisionContext<'_>, message: String) -> anyhow::Error {
+        if context.live {
+            error!("{}", message);
+            self.track(tx, LifecycleState::Failed(message.clone()));
         }
+        anyhow!(message)
+    }
 
-        if !should_generate_attestation {
-            println!("Skipping attestation generation as per middleware logic.");
-            return Ok(None);
+    /// Attested outcome of an approved conditional payout.
+    ///
+    /// `payout_amount` is in base units of the transaction's coin, which is
+    /// attested alongside it so the amount can't be read at the wrong scale.
+    fn payout_outcome(tx: &Transaction, oracle_value: f64, payout_amount: u64) -> Value {
+        let payout = Amount { value: payout_amount, ..tx.denominated_amount() };
+        json!({
+            "condition_met": true,
+            "oracle_value": oracle_value,
+            "payout_amount": payout.value,
+            "coin_type": payout.coin_type,
+            "decimals": payout.decimals,
+            "recipient": tx.receiver,
+        })
+    }
+
+    /// Preview what processing `tx` would do, without tracking, tracing, auditing or submitting.
+    ///
+    /// Oracle queries are really made and scripts really run, so the decision
+    /// matches what `process_transaction_and_attest` would reach right now.
+    /// The L1 side is previewed separately by `TransactionHandler::dry_run_verification`.
+    #[tracing::instrument(name = "simulate", skip_all, fields(payload_hash = %tx.payload_hash().unwrap_or_default()))]
+    pub async fn simulate(&self, tx: &Transaction) -> Result<SimulationResult> {
+        let mut result = SimulationResult::new(&tx.payload_hash()?, tx.gas_budget);
+        if let Err(issues) = tx.validate() {
+            result.issues = issues.iter().map(|issue| issue.to_string()).collect();
+            return Ok(result.skipped("Transaction failed validation"));
+        }
+        if let Err(expired) = tx.check_not_expired() {
+            return Ok(result.skipped(&expired.to_string()));
         }
 
-        let payload_hash = match tx.payload_digest() {
-            Ok(hash) => hash,
-            Err(e) => return Err(anyhow!("Failed to compute transaction payload digest: {}", e)),
+        let mut context = DecisionContext { live: false, metrics: None, oracle_values: Vec::new() };
+        let decision = self.decide(tx, &mut context).await;
+        result.oracle_values = context.oracle_values;
+        let outcome = match decision? {
+            Decision::Attest(outcome) => outcome,
+            Decision::Skip { reason, outcome } => {
+                result.outcome = outcome;
+                return Ok(result.skipped(&reason));
+            }
         };
 
-        let attestation = MiddlewareAttestation::new(payload_hash, middleware_outcome);
-
-        if let Some(audit_log) = &self.security_audit_log {
-            audit_log.log_execution(
-                "ExecutionManager",
-                &format!("Successfully processed transaction and generated attestation"),
-                None,
-                AuditSeverity::Info
-            )?;
+        let level = self.current_degradation();
+        if !level.allows_attestation() {
+            result.outcome = Some(outcome);
+            return Ok(result.skipped(&format!("Middleware is {}", level)));
         }
-
-        Ok(Some(attestation))
+        let attestation_payload = MiddlewareAttestation::for_transaction(tx, outcome.clone())
+            .and_then(|attestation| attestation.to_bytes_for_signing())
+            .map_err(|e| anyhow!("Failed to serialize attestation: {}", e))?;
+        result.would_attest = true;
+        result.outcome = Some(outcome);
+        result.verification_input = Some(VerificationInput { attestation_payload, quorum_signatures: Vec::new() });
+        Ok(result)
     }
 
-    // Placeholder method - Replace with actual implementation!
+    /// The attestation payload `process_transaction_and_attest` produces for `tx`, ready for signing.
     pub async fn prepare_verification_input(&self, tx: &Transaction) -> Result<Option<VerificationInput>> {
-        println!("[WARN] Using placeholder prepare_verification_input in ExecutionManager.");
-        // TODO: Implement the actual logic based on tx type, script execution, etc.
-        // This should involve:
-        // 1. Potentially executing tx.script/tx.python_code if present.
-        // 2. Determining the middleware_outcome (as a serde_json::Value).
-        // 3. Calculating the original_payload_hash using tx.payload_digest().
-        // 4. Constructing the MiddlewareAttestation.
-        // 5. Serializing the MiddlewareAttestation to get the attestation_payload bytes for signing.
-        // 6. Deciding if verification is needed (e.g., based on outcome or conditions).
-        // 7. Returning Some(VerificationInput { attestation_payload, quorum_signatures: vec![] }) or Ok(None).
-
-        // Example placeholder returning Some (replace with real logic):
-        let dummy_payload_hash = tx.payload_digest().unwrap_or_default();
-        let dummy_outcome = serde_json::json!({ "placeholder_outcome": true });
-        let attestation = MiddlewareAttestation::new(dummy_payload_hash, dummy_outcome);
-        let attestation_payload = attestation.to_bytes_for_signing()
-            .map_err(|e| anyhow!("Failed to serialize placeholder attestation: {}", e))?;
+        Ok(self.prepare_attestation(tx).await?.map(|(input, _)| input))
+    }
+
+    /// `prepare_verification_input`, also returning the outcome being attested.
+    pub async fn prepare_attestation(&self, tx: &Transaction) -> Result<Option<(VerificationInput, Value)>> {
+        Ok(self.prepare_attestation_with_readings(tx).await?.0)
+    }
 
-        Ok(Some(VerificationInput {
+    /// `prepare_attestation`, also returning every oracle value read on the way
+    /// to the decision, including when it decided not to attest.
+    pub async fn prepare_attestation_with_readings(
+        &self,
+        tx: &Transaction,
+    ) -> Result<(Option<(VerificationInput, Value)>, Vec<OracleReading>)> {
+        let mut oracle_values = Vec::new();
+        let attestation = match self.attest(tx, None, &mut oracle_values).await? {
+            Some(attestation) => attestation,
+            None => return Ok((None, oracle_values)),
+        };
+        let attestation_payload = attestation.to_bytes_for_signing()
+            .map_err(|e| anyhow!("Failed to serialize attestation: {}", e))?;
+        Ok((Some((VerificationInput {
             attestation_payload,
             quorum_signatures: Vec::new(), // Signatures added later by handler
-        }))
+        }, attestation.middleware_outcome)), oracle_values))
     }
 }
 
@@ -1 +1,4 @@
-pub mod manager;
\ No newline at end of file
+pub mod manager;
+pub mod degradation;
+pub mod gateway;
+pub mod simulation;
\ No newline at end of file
@@ -0,0 +1,84 @@
+//! Dry-run previews of what a transaction would do.
+//!
+//! `ExecutionManager::simulate` evaluates validation, conditions, oracle
+//! queries and scripts exactly as processing would, but records nothing and
+//! submits nothing. `TransactionHandler::dry_run_verification` adds the Sui
+//! side: gas and effects of the would-be L1 call. `transaction::utils::simulate_transaction`
+//! combines both.
+
+use crate::transaction::types::VerificationInput;
+use serde::Serialize;
+use serde_json::Value;
+
+/// An oracle value read on the way to a decision, in simulation or a real run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OracleReading {
+    pub url: String,
+    pub path: Vec<String>,
+    pub value: f64,
+}
+
+/// Predicted outcome of the L1 verification call, from `dryRunTransactionBlock`.
+#[derive(Debug, Clone, Serialize)]
+pub struct L1Preview {
+    /// "success" or the execution failure message
+    pub status: String,
+    /// Address the call would be sent from: the submitter, or the multisig for critical transactions
+    pub sender: String,
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    /// Net gas (computation + storage - rebate) in MIST
+    pub net_gas_mist: i64,
+    /// Object and balance changes the call would make
+    pub object_changes: Value,
+    pub balance_changes: Value,
+    pub events: Value,
+}
+
+/// Everything a transaction would do, without side effects.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationResult {
+    pub payload_hash: String,
+    /// Validation issues; empty when the transaction is well formed
+    pub issues: Vec<String>,
+    /// Whether processing would produce an attestation and submit it
+    pub would_attest: bool,
+    /// Why nothing would be submitted, when `would_attest` is false
+    pub skip_reason: Option<String>,
+    /// The attested outcome (payout details or script output)
+    pub outcome: Option<Value>,
+    pub oracle_values: Vec<OracleReading>,
+    /// Gas budget the L1 call would be submitted with
+    pub gas_budget: u64,
+    pub l1_preview: Option<L1Preview>,
+    /// Why the L1 dry run couldn't be performed, if it was attempted
+    pub l1_preview_error: Option<String>,
+    /// Input the L1 call would be built from (signatures not yet collected)
+    #[serde(skip)]
+    pub verification_input: Option<VerificationInput>,
+}
+
+impl SimulationResult {
+    pub fn new(payload_hash: &str, gas_budget: u64) -> Self {
+        Self {
+            payload_hash: payload_hash.to_string(),
+            issues: Vec::new(),
+            would_attest: false,
+            skip_reason: None,
+            outcome: None,
+            oracle_values: Vec::new(),
+            gas_budget,
+            l1_preview: None,
+            l1_preview_error: None,
+            verification_input: None,
+        }
+    }
+
+    /// Mark the transaction as one that would not be submitted.
+    pub fn skipped(mut self, reason: &str) -> Self {
+        self.would_attest = false;
+        self.skip_reason = Some(reason.to_string());
+        self
+    }
+}
@@ -0,0 +1,162 @@
+//! Retained oracle consensus results, for audits and contested payouts.
+//!
+//! `OracleManager` only caches the latest value per query. `ConsensusHistory`
+//! keeps every result it returns together with the sources behind it, in a
+//! bounded in-memory ring that can be mirrored to an append-only JSONL file
+//! so the trail survives restarts.
+
+use super::oracle::ConsensusStrategy;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Records kept in memory unless configured otherwise
+pub const DEFAULT_HISTORY_CAPACITY: usize = 1024;
+
+/// One value returned by `OracleManager`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusRecord {
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub query_id: String,
+    pub params: Value,
+    pub value: Value,
+    pub strategy: ConsensusStrategy,
+    /// Sources whose valid responses went into the value
+    pub sources: Vec<String>,
+}
+
+impl ConsensusRecord {
+    pub fn new(query_id: &str, params: &Value, value: &Value, strategy: ConsensusStrategy, sources: Vec<String>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            query_id: query_id.to_string(),
+            params: params.clone(),
+            value: value.clone(),
+            strategy,
+            sources,
+        }
+    }
+}
+
+/// Bounded history of `ConsensusRecord`s, oldest evicted first.
+#[derive(Debug)]
+pub struct ConsensusHistory {
+    capacity: usize,
+    records: Mutex<VecDeque<ConsensusRecord>>,
+    /// Every record is also appended here; the file itself is never truncated
+    file: Option<PathBuf>,
+}
+
+impl Default for ConsensusHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl ConsensusHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, records: Mutex::new(VecDeque::new()), file: None }
+    }
+
+    /// History mirrored to `path`, starting from the newest `capacity` records already in it.
+    pub fn with_file(capacity: usize, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let records = read_newest_records(&path, capacity)?;
+        Ok(Self { capacity, records: Mutex::new(records), file: Some(path) })
+    }
+
+    /// Keep `record`, evicting the oldest in memory when full.
+    ///
+    /// A failed file append is reported but doesn't lose the in-memory record.
+    pub fn record(&self, record: ConsensusRecord) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(path) = &self.file {
+            if let Err(e) = append_record(path, &record) {
+                eprintln!("WARNING: Failed to persist consensus record for '{}': {:#}", record.query_id, e);
+            }
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Records for `query_id` at or after `since`, oldest first.
+    pub fn query(&self, query_id: &str, since: DateTime<Utc>) -> Vec<ConsensusRecord> {
+        self.records.lock().unwrap()
+            .iter()
+            .filter(|record| record.query_id == query_id && record.timestamp >= since)
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn append_record(path: &Path, record: &ConsensusRecord) -> Result<()> {
+    let line = serde_json::to_string(record)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open consensus history {}", path.display()))?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+/// The newest `capacity` records in `path`, oldest first. A missing file means no records.
+///
+/// The file grows without bound, so it is read a line at a time rather than all at once.
+/// A last line that doesn't parse is taken to be a write cut short by a crash and skipped.
+fn read_newest_records(path: &Path, capacity: usize) -> Result<VecDeque<ConsensusRecord>> {
+    let mut records = VecDeque::new();
+    if !path.exists() {
+        return Ok(records);
+    }
+    let file = File::open(path)
+        .with_context(|| format!("Failed to read consensus history {}", path.display()))?;
+    // A bad line is only an error once a later line shows it wasn't the last
+    let mut unparsed: Option<(usize, serde_json::Error)> = None;
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read consensus history {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some((line_number, e)) = unparsed.take() {
+            return Err(anyhow!("Invalid record on line {} of {}: {}", line_number, path.display(), e));
+        }
+        let record = match serde_json::from_str(&line) {
+            Ok(record) => record,
+            Err(e) => {
+                unparsed = Some((i + 1, e));
+                continue;
+            }
+        };
+        if records.len() == capacity {
+            records.pop_front();
+        }
+        if capacity > 0 {
+            records.push_back(record);
+        }
+    }
+    if let Some((line_number, e)) = unparsed {
+        eprintln!("WARNING: Skipping truncated last line {} of consensus history {}: {}", line_number, path.display(), e);
+    }
+    Ok(records)
+}
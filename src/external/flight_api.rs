@@ -126,7 +126,8 @@ impl AviationStackApi {
         }
     }
 
-    fn parse_flight_data(&self, flight: &Value, flight_number: &str) -> Result<FlightStatus> {
+    /// Parse one entry of an AviationStack `/flights` response's `data` array.
+    pub fn parse_flight_data(&self, flight: &Value, flight_number: &str) -> Result<FlightStatus> {
         // Parse departure and arrival times
         let scheduled_departure = self.parse_datetime(&flight["departure"]["scheduled"]);
         let estimated_departure = self.parse_datetime(&flight["departure"]["estimated"]);
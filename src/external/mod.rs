@@ -1,4 +1,5 @@
 pub mod api;
+pub mod consensus_history;
 pub mod websocket;
 pub mod flight_api;
 pub mod oracle;
\ No newline at end of file
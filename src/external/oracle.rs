@@ -7,6 +7,12 @@ use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use reqwest;
 use crate::security::audit::{SecurityAuditLog, AuditSeverity};
+use super::consensus_history::{ConsensusHistory, ConsensusRecord};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use tracing::{debug, info, warn};
+use crate::utils::{canonical_json, HttpClientFactory};
+use crate::utils::backoff::{retry_async, Backoff};
 
 /// Data cached from an oracle source
 #[derive(Clone)]
@@ -23,6 +29,34 @@ pub enum OracleSourceStatus {
     Failed(String),
 }
 
+/// Why a source's HTTP request failed, so callers can tell outages from refusals.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SourceFetchError {
+    /// The request never got a response (connection, DNS, timeout)
+    #[error("Request failed: {0}")]
+    Transport(String),
+    /// The API answered with a non-success status
+    #[error("API returned error status: {0}")]
+    Status(reqwest::StatusCode),
+}
+
+impl SourceFetchError {
+    /// Whether trying again could succeed: transport failures and 5xx can,
+    /// while auth, quota and other 4xx answers won't change.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SourceFetchError::Transport(_) => true,
+            SourceFetchError::Status(status) => status.is_server_error(),
+        }
+    }
+
+    /// Whether `error` is a `SourceFetchError` worth retrying. Other errors,
+    /// like a parse failure or a local rate limit, are not.
+    pub fn is_retryable_error(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<SourceFetchError>().is_some_and(SourceFetchError::is_retryable)
+    }
+}
+
 /// Data validation rule type
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ValidationRuleType {
@@ -41,6 +75,17 @@ pub struct ValidationRule {
     pub rule_type: ValidationRuleType,
     pub parameters: Value,
     pub error_message: String,
+    /// Path of the one field this rule checks; every top-level field when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_field: Option<Vec<String>>,
+}
+
+impl ValidationRule {
+    /// Check only the field at `path` (see `PathSegment` for the syntax).
+    pub fn with_target_field(mut self, path: &[&str]) -> Self {
+        self.target_field = Some(path.iter().map(|segment| segment.to_string()).collect());
+        self
+    }
 }
 
 /// Result of data validation
@@ -61,24 +106,209 @@ pub struct OracleSourceConfig {
     pub source_type: String, // "REST", "GraphQL", "WebSocket"
     pub auth_header: Option<String>,
     pub default_params: Option<Value>,
+    /// "GET" sends the params as a query string, "POST" as a JSON body. GraphQL sources always POST.
+    #[serde(default = "default_http_method")]
+    pub http_method: String,
+    /// Base of the POST body, with the params laid over it. For GraphQL, either the
+    /// query string or `{ "query": ..., "variables": {...} }`; the params become variables.
+    /// For WebSocket sources, the subscription message sent after connecting (strings are sent as-is).
+    #[serde(default)]
+    pub body_template: Option<Value>,
     pub validation_rules: Vec<ValidationRule>,
     pub weight: u8, // 1-100
-    pub timeout_ms: u64,
+    pub timeout_ms: u64, // Per-request timeout for consensus queries
+    /// Per-request timeout for background refreshes, which can afford to wait longer; `timeout_ms` when unset
+    #[serde(default)]
+    pub background_timeout_ms: Option<u64>,
     pub rate_limit: Option<u32>, // requests per minute
     pub requires_auth: bool,
-    pub path: Vec<String>, // Path to extract data from response (e.g., ["data", "temperature"])
+    pub path: Vec<String>, // Path to extract data from response (e.g., ["data", "temperature"]); see PathSegment for syntax
     pub required_fields: Vec<String>, // Fields that must be present in the extracted data
 }
 
+fn default_http_method() -> String {
+    "GET".to_string()
+}
+
+impl OracleSourceConfig {
+    /// `default_params` overlaid with the call-time `params`.
+    pub fn merged_params(&self, params: &Value) -> Value {
+        self.default_params.as_ref()
+            .and_then(Value::as_object)
+            .map(|default| {
+                params.as_object().map_or_else(
+                    || Value::Object(default.clone()),
+                    |p| {
+                        let mut merged = default.clone();
+                        merged.extend(p.iter().map(|(k, v)| (k.clone(), v.clone())));
+                        Value::Object(merged)
+                    }
+                )
+            })
+            .unwrap_or_else(|| params.clone())
+    }
+
+    /// JSON body for a request with `merged_params`, or `None` when the params go in the query string.
+    pub fn request_body(&self, merged_params: &Value) -> Result<Option<Value>> {
+        if self.source_type == "GraphQL" {
+            let (query, mut variables) = match &self.body_template {
+                Some(Value::String(query)) => (query.clone(), serde_json::Map::new()),
+                Some(template) => (
+                    template.get("query").and_then(Value::as_str).unwrap_or_default().to_string(),
+                    template.get("variables").and_then(Value::as_object).cloned().unwrap_or_default(),
+                ),
+                None => (String::new(), serde_json::Map::new()),
+            };
+            if query.is_empty() {
+                return Err(anyhow!("GraphQL source {} has no query in its body_template", self.name));
+            }
+            if let Some(params) = merged_params.as_object() {
+                variables.extend(params.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+            return Ok(Some(json!({ "query": query, "variables": variables })));
+        }
+
+        match self.http_method.to_ascii_uppercase().as_str() {
+            "GET" => Ok(None),
+            "POST" => {
+                let mut body = match &self.body_template {
+                    Some(Value::Object(template)) => template.clone(),
+                    Some(other) => return Err(anyhow!("body_template of {} must be a JSON object, got {}", self.name, other)),
+                    None => serde_json::Map::new(),
+                };
+                if let Some(params) = merged_params.as_object() {
+                    body.extend(params.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+                Ok(Some(Value::Object(body)))
+            }
+            other => Err(anyhow!("Unsupported HTTP method '{}' for {}", other, self.name)),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    pub fn background_timeout(&self) -> Duration {
+        Duration::from_millis(self.background_timeout_ms.unwrap_or(self.timeout_ms))
+    }
+
+    /// Longest timeout any request may be given, which the HTTP client must not cut short
+    fn client_timeout(&self) -> Duration {
+        self.timeout().max(self.background_timeout())
+    }
+}
+
+/// A single segment of an `OracleSourceConfig::path`
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    /// Object key, or array index when the segment is numeric
+    Key(String),
+    /// `*`: every element of an array (or every value of an object)
+    Wildcard,
+    /// `[field=value]`: first array element whose `field` equals `value`
+    Filter { field: String, value: String },
+}
+
+impl PathSegment {
+    /// Parse one path segment.
+    ///
+    /// Path syntax:
+    /// - `name` looks up an object key; on arrays a numeric segment such as `0` is an index
+    /// - `*` fans out over all elements and yields an array of the results
+    /// - `[field=value]` picks the first array element whose `field` matches `value`,
+    ///   compared against the field's string form (so `[delay=15]` matches a number)
+    pub fn parse(segment: &str) -> Self {
+        if segment == "*" {
+            return PathSegment::Wildcard;
+        }
+        if segment.starts_with('[') && segment.ends_with(']') {
+            let inner = &segment[1..segment.len() - 1];
+            if let Some((field, value)) = inner.split_once('=') {
+                return PathSegment::Filter {
+                    field: field.trim().to_string(),
+                    value: value.trim().to_string(),
+                };
+            }
+        }
+        PathSegment::Key(segment.to_string())
+    }
+}
+
+/// Compare a JSON value against a filter literal
+fn value_matches_literal(value: &Value, literal: &str) -> bool {
+    match value {
+        Value::String(s) => s == literal,
+        Value::Null => literal == "null",
+        other => other.to_string() == literal,
+    }
+}
+
+/// Walk `path` through `data`, see `PathSegment::parse` for the syntax.
+///
+/// Wildcards skip elements where the remainder of the path doesn't resolve,
+/// so `["data", "*", "flight_status"]` returns the status of every flight that has one.
+pub fn extract_path_value(data: &Value, path: &[String]) -> Option<Value> {
+    let (first, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return Some(data.clone()),
+    };
+
+    match PathSegment::parse(first) {
+        PathSegment::Key(key) => {
+            if let Some(obj) = data.as_object() {
+                extract_path_value(obj.get(&key)?, rest)
+            } else if let Some(arr) = data.as_array() {
+                let index = key.parse::<usize>().ok()?; // Non-numeric key on an array
+                extract_path_value(arr.get(index)?, rest)
+            } else {
+                None // Current value is not an object or array
+            }
+        }
+        PathSegment::Wildcard => {
+            let children: Vec<&Value> = match data {
+                Value::Array(arr) => arr.iter().collect(),
+                Value::Object(obj) => obj.values().collect(),
+                _ => return None,
+            };
+            let matches = children.into_iter()
+                .filter_map(|child| extract_path_value(child, rest))
+                .collect();
+            Some(Value::Array(matches))
+        }
+        PathSegment::Filter { field, value } => {
+            let arr = data.as_array()?;
+            let matched = arr.iter().find(|element| {
+                element.get(&field).map_or(false, |v| value_matches_literal(v, &value))
+            })?;
+            extract_path_value(matched, rest)
+        }
+    }
+}
+
 /// Generic Oracle Source trait
+///
+/// Background polling ownership: once a source is registered with an
+/// `OracleManager`, the manager's single background loop is the only thing
+/// that polls it. `run_background_updates` exists for standalone sources and
+/// must return immediately after `set_managed(true)` has been called.
 #[async_trait]
 pub trait OracleSource: Send + Sync {
     fn name(&self) -> &str;
     fn config(&self) -> &OracleSourceConfig;
     async fn fetch(&self, params: &Value) -> Result<Value>;
+    /// `fetch`, failing once `timeout` has passed however long the source itself would wait
+    async fn fetch_with_timeout(&self, params: &Value, timeout: Duration) -> Result<Value> {
+        match tokio::time::timeout(timeout, self.fetch(params)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("{} did not respond within {}ms", self.name(), timeout.as_millis())),
+        }
+    }
     fn validate(&self, data: &Value) -> Vec<ValidationResult>;
     fn status(&self) -> OracleSourceStatus;
     async fn run_background_updates(&self, update_interval: Duration);
+    /// Mark the source as polled by a manager so it doesn't run its own loop
+    fn set_managed(&self, _managed: bool) {}
 }
 
 /// REST API data source implementation
@@ -91,6 +321,8 @@ pub struct RestApiOracleSource {
     audit_log: Option<Arc<SecurityAuditLog>>,
     cache: Arc<Mutex<HashMap<String, CachedData>>>,
     cache_duration: Duration,
+    managed: Arc<Mutex<bool>>, // Set when an OracleManager owns background polling
+    validator: RuleValidator,
 }
 
 impl RestApiOracleSource {
@@ -100,13 +332,14 @@ impl RestApiOracleSource {
         cache: Arc<Mutex<HashMap<String, CachedData>>>,
         cache_duration: Duration,
     ) -> Result<Self> {
-        if config.source_type != "REST" {
+        if config.source_type != "REST" && config.source_type != "GraphQL" {
             return Err(anyhow!("Invalid source type for RestApiSource"));
         }
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_millis(config.timeout_ms))
-            .build()
-            .map_err(|e| anyhow!("Failed to build HTTP client: {}", e))?;
+        // Surface a bad method or template now rather than on the first fetch
+        config.request_body(&config.merged_params(&json!({})))?;
+        let client = HttpClientFactory::from_env()?
+            .with_audit_log(audit_log.clone())
+            .build_with_timeout(config.client_timeout())?;
 
         Ok(Self {
             client,
@@ -117,9 +350,17 @@ impl RestApiOracleSource {
             audit_log,
             cache,
             cache_duration,
+            managed: Arc::new(Mutex::new(false)),
+            validator: RuleValidator::default(),
         })
     }
 
+    /// Rebuild the HTTP client with `factory`'s shared settings, keeping the source's timeouts
+    pub fn with_http_client_factory(mut self, factory: &HttpClientFactory) -> Result<Self> {
+        self.client = factory.build_with_timeout(self.config.client_timeout())?;
+        Ok(self)
+    }
+
     fn check_rate_limit(&self) -> bool {
         if let Some(rate_limit) = self.config.rate_limit {
             let mut count = self.request_count.lock().unwrap();
@@ -142,8 +383,25 @@ impl RestApiOracleSource {
         }
         false
     }
+}
+
+/// Applies a source's `ValidationRule`s to the data it returned
+#[derive(Default)]
+pub(crate) struct RuleValidator {
+    /// Compiled `pattern` parameters of string rules; compile errors are cached too
+    regex_cache: Arc<Mutex<HashMap<String, Result<Regex, String>>>>,
+}
+
+impl RuleValidator {
+    /// Results of every rule in `rules` against `data`
+    pub(crate) fn validate(&self, rules: &[ValidationRule], data: &Value) -> Vec<ValidationResult> {
+        rules.iter().flat_map(|rule| self.apply_rule(rule, data)).collect()
+    }
 
     fn validate_numeric_range(&self, value: &Value, params: &Value) -> bool {
+        if value.is_null() && params.get("nullable").and_then(Value::as_bool) == Some(true) {
+            return true;
+        }
         if let Some(num) = value.as_f64() {
             let min = params.get("min").and_then(Value::as_f64);
             let max = params.get("max").and_then(Value::as_f64);
@@ -158,63 +416,82 @@ impl RestApiOracleSource {
         }
     }
 
-    fn validate_string_pattern(&self, value: &Value, params: &Value) -> bool {
+    /// `Err` names the pattern when it isn't a valid regular expression.
+    fn validate_string_pattern(&self, value: &Value, params: &Value) -> Result<bool, String> {
         if let Some(str_val) = value.as_str() {
             if let Some(pattern) = params.get("pattern").and_then(Value::as_str) {
-                // Basic substring check; use regex crate for real patterns
-                str_val.contains(pattern)
+                self.with_regex(pattern, |regex| regex.is_match(str_val))
             } else if let Some(allowed) = params.get("allowed").and_then(Value::as_array) {
-                allowed.iter().any(|v| v.as_str() == Some(str_val))
+                Ok(allowed.iter().any(|v| v.as_str() == Some(str_val)))
             } else {
-                true
+                Ok(true)
             }
         } else {
-            false
+            Ok(false)
+        }
+    }
+
+    /// Run `check` against `pattern`, compiling it on first use only.
+    fn with_regex(&self, pattern: &str, check: impl FnOnce(&Regex) -> bool) -> Result<bool, String> {
+        let mut cache = self.regex_cache.lock().unwrap();
+        let compiled = cache.entry(pattern.to_string()).or_insert_with(|| {
+            Regex::new(pattern).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+        });
+        match compiled {
+            Ok(regex) => Ok(check(regex)),
+            Err(e) => Err(e.clone()),
+        }
+    }
+
+    /// Pass or fail of `rule` for one value; `Err` carries a message that replaces the rule's own.
+    fn check_value(&self, rule: &ValidationRule, value: &Value) -> Result<bool, String> {
+        match rule.rule_type {
+            ValidationRuleType::NumericRange => Ok(self.validate_numeric_range(value, &rule.parameters)),
+            ValidationRuleType::StringPattern => self.validate_string_pattern(value, &rule.parameters),
+            _ => Ok(true), // Assume pass for unimplemented rules
         }
     }
 
     fn apply_rule(&self, rule: &ValidationRule, data: &Value) -> Vec<ValidationResult> {
+        let result = |field: &str, value: &Value, outcome: Result<bool, String>| ValidationResult {
+            passed: matches!(outcome, Ok(true)),
+            rule_name: rule.name.clone(),
+            error_message: match outcome {
+                Ok(true) => None,
+                Ok(false) => Some(rule.error_message.clone()),
+                Err(e) => Some(e),
+            },
+            data_field: field.to_string(),
+            value: value.clone(),
+        };
+
+        if let Some(path) = &rule.target_field {
+            let field = path.join(".");
+            return match extract_path_value(data, path) {
+                Some(value) => vec![result(&field, &value, self.check_value(rule, &value))],
+                None => vec![result(&field, &Value::Null, Err(format!("{}: field '{}' not found", rule.error_message, field)))],
+            };
+        }
+
+        // Untargeted rules apply to every top-level field
         let mut results = Vec::new();
-        // Simplified: apply rule to all fields. Enhance to target specific fields.
         if let Some(obj) = data.as_object() {
             for (field, value) in obj {
-                let passed = match rule.rule_type {
-                    ValidationRuleType::NumericRange => self.validate_numeric_range(value, &rule.parameters),
-                    ValidationRuleType::StringPattern => self.validate_string_pattern(value, &rule.parameters),
-                    _ => true, // Assume pass for unimplemented rules
-                };
-
+                let outcome = self.check_value(rule, value);
+                let passed = matches!(outcome, Ok(true));
                 if !passed || value.is_number() || value.is_string() { // Only log results for relevant types or failures
-                    results.push(ValidationResult {
-                        passed,
-                        rule_name: rule.name.clone(),
-                        error_message: if passed { None } else { Some(rule.error_message.clone()) },
-                        data_field: field.clone(),
-                        value: value.clone(),
-                    });
+                    results.push(result(field, value, outcome));
                 }
             }
         }
         results
     }
+}
 
+impl RestApiOracleSource {
     /// Extracts a value from a JSON object using a path.
-    fn extract_value<'a>(&self, data: &'a Value, path: &[String]) -> Option<&'a Value> {
-        let mut current = data;
-        for key in path {
-            if let Some(obj) = current.as_object() {
-                current = obj.get(key)?;
-            } else if let Some(arr) = current.as_array() {
-                if let Ok(index) = key.parse::<usize>() {
-                    current = arr.get(index)?;
-                } else {
-                    return None; // Path element is not a valid index for an array
-                }
-            } else {
-                return None; // Path element encountered but current value is not an object or array
-            }
-        }
-        Some(current)
+    fn extract_value(&self, data: &Value, path: &[String]) -> Option<Value> {
+        extract_path_value(data, path)
     }
 
     /// Checks if all required fields are present in the extracted data.
@@ -241,7 +518,11 @@ impl OracleSource for RestApiOracleSource {
     }
 
     async fn fetch(&self, params: &Value) -> Result<Value> {
-        let cache_key = format!("{}:{}", self.config.name, serde_json::to_string(params)?);
+        self.fetch_with_timeout(params, self.config.timeout()).await
+    }
+
+    async fn fetch_with_timeout(&self, params: &Value, timeout: Duration) -> Result<Value> {
+        let cache_key = format!("{}:{}", self.config.name, canonical_json(params));
 
         // Check cache first
         {
@@ -258,27 +539,22 @@ impl OracleSource for RestApiOracleSource {
             return Err(anyhow!("Rate limit reached for {}", self.config.name));
         }
 
-        let merged_params = self.config.default_params.as_ref()
-            .and_then(Value::as_object)
-            .map(|default| {
-                params.as_object().map_or_else(
-                    || Value::Object(default.clone()),
-                    |p| {
-                        let mut merged = default.clone();
-                        merged.extend(p.iter().map(|(k, v)| (k.clone(), v.clone())));
-                        Value::Object(merged)
-                    }
-                )
-            })
-            .unwrap_or_else(|| params.clone());
+        let merged_params = self.config.merged_params(params);
 
-        let mut request = self.client.get(&self.config.url);
+        // Replaces the client-wide timeout, which is only an upper bound
+        let mut request = match self.config.request_body(&merged_params)? {
+            Some(body) => self.client.post(&self.config.url).json(&body),
+            None => {
+                let request = self.client.get(&self.config.url);
+                match merged_params.as_object() {
+                    Some(obj) => request.query(obj),
+                    None => request,
+                }
+            }
+        }.timeout(timeout);
         if let Some(auth) = &self.config.auth_header {
             request = request.header("Authorization", auth);
         }
-        if let Some(obj) = merged_params.as_object() {
-            request = request.query(obj);
-        }
 
         let response_result = request.send().await;
         *self.last_request.lock().unwrap() = Some(Instant::now());
@@ -290,7 +566,7 @@ impl OracleSource for RestApiOracleSource {
                 if let Some(log) = &self.audit_log {
                     let _ = log.log_external_api("RestApiOracleSource", &format!("{} request failed: {}", self.config.name, e), AuditSeverity::Error);
                 }
-                return Err(anyhow!("Request failed: {}", e));
+                return Err(SourceFetchError::Transport(e.to_string()).into());
             }
         };
 
@@ -300,7 +576,7 @@ impl OracleSource for RestApiOracleSource {
             if let Some(log) = &self.audit_log {
                 let _ = log.log_external_api("RestApiOracleSource", &format!("{} returned error: {}", self.config.name, status), AuditSeverity::Error);
             }
-            return Err(anyhow!("API returned error status: {}", status));
+            return Err(SourceFetchError::Status(status).into());
         }
 
         let data = match response.json::<Value>().await {
@@ -314,12 +590,23 @@ impl OracleSource for RestApiOracleSource {
             }
         };
 
+        // GraphQL reports query errors in the body of a 200 response
+        if self.config.source_type == "GraphQL" {
+            if let Some(errors) = data.get("errors").and_then(Value::as_array).filter(|errors| !errors.is_empty()) {
+                *self.status.lock().unwrap() = OracleSourceStatus::Failed(format!("GraphQL errors: {}", errors.len()));
+                if let Some(log) = &self.audit_log {
+                    let _ = log.log_external_api("RestApiOracleSource", &format!("{} returned GraphQL errors: {}", self.config.name, Value::Array(errors.clone())), AuditSeverity::Error);
+                }
+                return Err(anyhow!("GraphQL query to {} failed: {}", self.config.name, Value::Array(errors.clone())));
+            }
+        }
+
         // Extract the relevant part of the data using the path
         let extracted_data = self.extract_value(&data, &self.config.path)
                                  .ok_or_else(|| anyhow!("Failed to extract data using path for {}", self.config.name))?;
 
         // Check for required fields in the extracted data
-        if !self.check_required_fields(extracted_data) {
+        if !self.check_required_fields(&extracted_data) {
              *self.status.lock().unwrap() = OracleSourceStatus::Failed("Missing required fields".to_string());
              if let Some(log) = &self.audit_log {
                  let _ = log.log_external_api("RestApiOracleSource", &format!("{} missing required fields", self.config.name), AuditSeverity::Error);
@@ -341,14 +628,11 @@ impl OracleSource for RestApiOracleSource {
             });
         }
 
-        Ok(extracted_data.clone())
+        Ok(extracted_data)
     }
 
     fn validate(&self, data: &Value) -> Vec<ValidationResult> {
-        let mut results = Vec::new();
-        for rule in &self.config.validation_rules {
-            results.extend(self.apply_rule(rule, data));
-        }
+        let results = self.validator.validate(&self.config.validation_rules, data);
 
         if let Some(log) = &self.audit_log {
             let failures = results.iter().filter(|r| !r.passed).count();
@@ -367,21 +651,25 @@ impl OracleSource for RestApiOracleSource {
         if update_interval.is_zero() {
             return;
         }
+        // The manager's loop already polls managed sources; a second loop here
+        // would double the request rate against the provider.
+        if *self.managed.lock().unwrap() {
+            return;
+        }
         let params = self.config.default_params.clone().unwrap_or(json!({}));
         loop {
             tokio::time::sleep(update_interval).await;
-            // Use a unique key for background updates to avoid conflicting with specific requests
-            let background_key = format!("{}:background_update", self.config.name);
+            // Stop if a manager adopted this source while we were running
+            if *self.managed.lock().unwrap() {
+                return;
+            }
             let params_for_update = params.clone(); // Clone params for the async block
 
             // Construct a query_id similar to how get_consensus_data might do it
             // This ensures the cache key matches potential direct queries.
-            let query_id = match serde_json::to_string(&params_for_update) {
-                Ok(s) => format!("{}:{}", self.config.name, s),
-                Err(_) => background_key, // Fallback if params serialization fails
-            };
+            let query_id = format!("{}:{}", self.config.name, canonical_json(&params_for_update));
 
-            match self.fetch(&params_for_update).await {
+            match self.fetch_with_timeout(&params_for_update, self.config.background_timeout()).await {
                 Ok(data) => {
                     // Cache the fetched data
                     let mut cache = self.cache.lock().unwrap();
@@ -401,6 +689,177 @@ impl OracleSource for RestApiOracleSource {
             }
         }
     }
+
+    fn set_managed(&self, managed: bool) {
+        *self.managed.lock().unwrap() = managed;
+    }
+}
+
+/// Weighted median of `(value, weight)` pairs, `None` if the total weight is zero.
+///
+/// Equivalent to repeating each value `weight` times and taking the ordinary
+/// median: when the cumulative weight lands exactly on half the total, the
+/// result is the average of the two values either side of the midpoint.
+pub fn weighted_median(values: &[(f64, u8)]) -> Option<f64> {
+    let mut sorted: Vec<(f64, u32)> = values.iter()
+        .filter(|(_, w)| *w > 0)
+        .map(|(v, w)| (*v, *w as u32))
+        .collect();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Compare doubled cumulative weight against the total to stay in integers
+    let total_weight: u32 = sorted.iter().map(|(_, w)| *w).sum();
+    let mut cumulative: u32 = 0;
+    for (i, (value, weight)) in sorted.iter().enumerate() {
+        cumulative += weight;
+        if 2 * cumulative > total_weight {
+            return Some(*value);
+        }
+        if 2 * cumulative == total_weight {
+            // Zero weights were filtered out, so a next value exists
+            return sorted.get(i + 1).map(|(next, _)| (value + next) / 2.0);
+        }
+    }
+    None
+}
+
+/// Weighted arithmetic mean of `(value, weight)` pairs, `None` if the total weight is zero.
+pub fn weighted_mean(values: &[(f64, u8)]) -> Option<f64> {
+    let total_weight: f64 = values.iter().map(|(_, w)| *w as f64).sum();
+    if total_weight == 0.0 {
+        return None;
+    }
+    Some(values.iter().map(|(v, w)| v * *w as f64).sum::<f64>() / total_weight)
+}
+
+/// Weighted mean after dropping `fraction` of the total weight from each end,
+/// `None` if the total weight is zero.
+///
+/// Trimming is by weight, not by count: a value straddling the cut keeps only
+/// the part of its weight inside it. With `fraction` at 0.5 or above nothing
+/// would remain, so the weighted median is returned instead.
+pub fn weighted_trimmed_mean(values: &[(f64, u8)], fraction: f64) -> Option<f64> {
+    let mut sorted: Vec<(f64, f64)> = values.iter()
+        .filter(|(_, w)| *w > 0)
+        .map(|(v, w)| (*v, *w as f64))
+        .collect();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Keep the weight between cumulative positions `low` and `high`
+    let total_weight: f64 = sorted.iter().map(|(_, w)| w).sum();
+    let low = total_weight * fraction.max(0.0);
+    let high = total_weight - low;
+    let (mut cumulative, mut kept_weight, mut kept_sum) = (0.0, 0.0, 0.0);
+    for (value, weight) in sorted {
+        let kept = ((cumulative + weight).min(high) - cumulative.max(low)).max(0.0);
+        kept_weight += kept;
+        kept_sum += value * kept;
+        cumulative += weight;
+    }
+    if kept_weight > 0.0 {
+        Some(kept_sum / kept_weight)
+    } else {
+        weighted_median(values)
+    }
+}
+
+/// Ordinary median of an ascending slice, `None` if empty
+fn sorted_median(sorted: &[f64]) -> Option<f64> {
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[mid]),
+        _ => Some((sorted[mid - 1] + sorted[mid]) / 2.0),
+    }
+}
+
+/// Scales a median absolute deviation to estimate the standard deviation of normal data
+const MAD_SCALE: f64 = 1.4826;
+
+/// How numerical consensus discards divergent values before taking the weighted median
+///
+/// Weights don't affect which values count as outliers, only the median afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OutlierStrategy {
+    /// Keep values within `k` interquartile ranges outside the first and third quartiles
+    Iqr { k: f64 },
+    /// Keep values within `threshold` standard deviations of the mean.
+    /// In a sample of n values no z-score can exceed sqrt(n-1), so small samples need a low threshold.
+    ZScore { threshold: f64 },
+    /// Keep values within `threshold` scaled median absolute deviations of the median.
+    /// Robust to the outliers themselves, which inflate the mean and deviation used by `ZScore`,
+    /// and usable with 2-3 sources where the quartiles of `Iqr` coincide with the extremes.
+    /// Values within `NUMERIC_AGREEMENT_TOLERANCE` of the median are always kept
+    MadBased { threshold: f64 },
+    /// Keep every value
+    None,
+}
+
+impl Default for OutlierStrategy {
+    fn default() -> Self {
+        OutlierStrategy::Iqr { k: 1.5 }
+    }
+}
+
+/// How numerical consensus combines the values left after outlier rejection
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum NumericConsensusMode {
+    /// `weighted_median`: ignores how far off the extreme values are
+    #[default]
+    WeightedMedian,
+    /// `weighted_mean`: every value moves the result, in proportion to its weight
+    WeightedMean,
+    /// `weighted_trimmed_mean` dropping this fraction of the weight at each end, in [0, 0.5)
+    TrimmedMean(f64),
+}
+
+impl OutlierStrategy {
+    /// The `(value, weight)` pairs this strategy keeps, in their original order
+    pub fn filter(&self, values: &[(f64, u8)]) -> Vec<(f64, u8)> {
+        values.iter()
+            .zip(self.keep_mask(values))
+            .filter(|(_, keep)| *keep)
+            .map(|(value, _)| *value)
+            .collect()
+    }
+
+    /// For each of `values`, whether this strategy keeps it
+    pub fn keep_mask(&self, values: &[(f64, u8)]) -> Vec<bool> {
+        let mut sorted: Vec<f64> = values.iter().map(|(v, _)| *v).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        if sorted.is_empty() {
+            return Vec::new();
+        }
+
+        let (lower_bound, upper_bound) = match *self {
+            OutlierStrategy::None => return vec![true; values.len()],
+            OutlierStrategy::Iqr { k } => {
+                let q1 = sorted[(sorted.len() as f64 * 0.25).floor() as usize];
+                let q3 = sorted[(sorted.len() as f64 * 0.75).floor() as usize];
+                let iqr = q3 - q1;
+                (q1 - k * iqr, q3 + k * iqr)
+            }
+            OutlierStrategy::ZScore { threshold } => {
+                let n = sorted.len() as f64;
+                let mean = sorted.iter().sum::<f64>() / n;
+                let std_dev = (sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+                (mean - threshold * std_dev, mean + threshold * std_dev)
+            }
+            OutlierStrategy::MadBased { threshold } => {
+                let median = sorted_median(&sorted).unwrap_or(0.0);
+                let mut deviations: Vec<f64> = sorted.iter().map(|v| (v - median).abs()).collect();
+                deviations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                let mad = sorted_median(&deviations).unwrap_or(0.0) * MAD_SCALE;
+                // When most sources agree exactly the MAD is 0, which would reject even a rounding difference
+                let spread = (threshold * mad).max(median.abs() * NUMERIC_AGREEMENT_TOLERANCE);
+                (median - spread, median + spread)
+            }
+        };
+
+        values.iter()
+            .map(|(v, _)| *v >= lower_bound && *v <= upper_bound)
+            .collect()
+    }
 }
 
 /// Wrapper to make OracleSource cloneable for Arc
@@ -414,15 +873,181 @@ impl Clone for CloneableOracleSource {
 
 // --- Oracle Manager --- (Coordinates multiple sources)
 
+/// Relative distance within which two numerical answers count as agreeing, for
+/// cost-optimized early stopping and `NamedQuorum` checks
+pub const NUMERIC_AGREEMENT_TOLERANCE: f64 = 0.01;
+
+/// Whether `value` agrees with `reference`: numbers within `NUMERIC_AGREEMENT_TOLERANCE`, anything else canonically equal
+fn values_agree(value: &Value, reference: &Value) -> bool {
+    match (value.as_f64(), reference.as_f64()) {
+        (Some(v), Some(r)) => (v - r).abs() <= r.abs() * NUMERIC_AGREEMENT_TOLERANCE,
+        _ => canonical_json(value) == canonical_json(reference),
+    }
+}
+
+/// "At least `min_agreeing` of these named sources must agree", checked on top of the weight threshold.
+///
+/// Weights say how much a source counts; a named quorum says whose answers are
+/// required at all, e.g. a contract naming the data providers it accepts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedQuorum {
+    pub required_sources: Vec<String>,
+    pub min_agreeing: usize,
+}
+
+/// Too few of a `NamedQuorum`'s sources backed the consensus value
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("Named quorum not met: {agreeing} of the required {min_agreeing} among {} agree; unavailable: {unavailable:?}", .required.join(", "))]
+pub struct NamedQuorumUnmet {
+    pub required: Vec<String>,
+    pub min_agreeing: usize,
+    /// Required sources that agreed; for a round failed before querying, those that still could
+    pub agreeing: usize,
+    /// Required sources that were down or gave no valid answer
+    pub unavailable: Vec<String>,
+}
+
+/// How an `OracleManager` arrived at a returned value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConsensusStrategy {
+    /// Weighted agreement across the operational sources
+    Consensus,
+    /// Consensus failed; the value is the single-sourced answer of a fallback source
+    Fallback { source: String },
+}
+
+/// How consensus picks a winner when several values tie on weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreakPolicy {
+    /// Take the value whose canonical form sorts first
+    PreferSmallest,
+    /// Fail with `AmbiguousConsensus`
+    Reject,
+}
+
+/// Several values tied for the highest weight under `TieBreakPolicy::Reject`
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("Ambiguous consensus: {} values tied at weight {weight}: {}", .candidates.len(), .candidates.join(", "))]
+pub struct AmbiguousConsensus {
+    /// Canonical forms of the tied values, sorted
+    pub candidates: Vec<String>,
+    pub weight: u32,
+}
+
+/// Value returned by `OracleManager` together with how it was obtained
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusResult {
+    pub value: Value,
+    pub strategy: ConsensusStrategy,
+}
+
+/// A consensus value with the evidence behind it, for auditing and dashboards
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusOutcome {
+    pub value: Value,
+    pub strategy: ConsensusStrategy,
+    /// Sources whose answers the value was computed from
+    pub participating_sources: Vec<String>,
+    /// Sources left out, with why: not operational, failed, invalid, outlier or disagreeing
+    pub rejected_sources: Vec<(String, String)>,
+    /// Combined weight of `participating_sources`
+    pub achieved_weight: u32,
+    /// Weight the consensus threshold called for; 0 for fallback answers, which bypass it
+    pub required_weight: u32,
+}
+
+/// Answers gathered from sources in one consensus round
+#[derive(Default)]
+struct SourceAnswers {
+    /// `(name, value, weight)` of every valid answer
+    valid: Vec<(String, Value, u8)>,
+    /// `(name, reason)` of every source that didn't give one
+    rejected: Vec<(String, String)>,
+}
+
+impl SourceAnswers {
+    fn extend(&mut self, other: SourceAnswers) {
+        self.valid.extend(other.valid);
+        self.rejected.extend(other.rejected);
+    }
+}
+
+/// How often an `OracleManager`'s sources have disagreed, since it was created.
+///
+/// Consensus rounds are counted only when sources are actually queried, not
+/// for cache hits. A source that is often the rejected outlier may be feeding
+/// bad or manipulated data; see `flagged_sources`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConsensusMetrics {
+    pub consensus_reached: u64,
+    /// Rounds that failed for any reason: too few sources, weight threshold, no agreement
+    pub consensus_failed: u64,
+    pub outliers_rejected: u64,
+    /// Source name -> times its value was rejected as an outlier
+    pub per_source_outlier_counts: HashMap<String, u64>,
+    /// Source name -> numerical rounds it contributed a valid value to
+    pub per_source_numeric_rounds: HashMap<String, u64>,
+    /// Source fetches made by consensus rounds
+    pub sources_queried: u64,
+    /// Operational sources a cost-optimized round didn't need to query
+    pub sources_skipped: u64,
+}
+
+impl ConsensusMetrics {
+    /// Fraction of rounds that failed to reach consensus
+    pub fn failure_rate(&self) -> f64 {
+        let rounds = self.consensus_reached + self.consensus_failed;
+        if rounds == 0 { 0.0 } else { self.consensus_failed as f64 / rounds as f64 }
+    }
+
+    /// Fraction of `source`'s numerical values that were rejected as outliers
+    pub fn outlier_rate(&self, source: &str) -> f64 {
+        let rounds = self.per_source_numeric_rounds.get(source).copied().unwrap_or(0);
+        let outliers = self.per_source_outlier_counts.get(source).copied().unwrap_or(0);
+        if rounds == 0 { 0.0 } else { outliers as f64 / rounds as f64 }
+    }
+
+    /// Sources with at least `min_rounds` numerical rounds whose outlier rate exceeds `max_outlier_rate`, sorted
+    pub fn flagged_sources(&self, max_outlier_rate: f64, min_rounds: u64) -> Vec<String> {
+        let mut flagged: Vec<String> = self.per_source_numeric_rounds.iter()
+            .filter(|(source, rounds)| **rounds >= min_rounds && self.outlier_rate(source) > max_outlier_rate)
+            .map(|(source, _)| source.clone())
+            .collect();
+        flagged.sort();
+        flagged
+    }
+}
+
+/// A consensus round's outcome, reused until it is older than the cache duration.
+#[derive(Debug, Clone)]
+struct CachedOutcome {
+    outcome: ConsensusOutcome,
+    timestamp: Instant,
+}
+
 pub struct OracleManager {
     sources: HashMap<String, Arc<dyn OracleSource>>,
     audit_log: Option<Arc<SecurityAuditLog>>,
     consensus_threshold: f64, // 0.0 to 1.0
     min_sources_for_consensus: usize,
-    cache: Arc<Mutex<HashMap<String, CachedData>>>,
+    /// Latest outcome per query and params; expired entries are dropped as new ones arrive
+    cache: Mutex<HashMap<String, CachedOutcome>>,
+    /// Raw per-source responses, shared with the sources built by helpers like `create_weather_oracle`
+    source_cache: Arc<Mutex<HashMap<String, CachedData>>>,
     cache_duration: Duration,
     background_update_interval: Duration,
-    background_tasks: tokio::task::JoinHandle<()>, // Handle for background tasks
+    polled_sources: Arc<Mutex<Vec<Arc<dyn OracleSource>>>>, // Sources iterated by the background loop
+    background_task: Option<tokio::task::JoinHandle<()>>, // Single shared polling loop
+    background_tasks_spawned: usize,
+    fallback_sources: Vec<String>, // Tried in order when consensus can't be reached
+    named_quorum: Option<NamedQuorum>,
+    tie_break: TieBreakPolicy,
+    outlier_strategy: OutlierStrategy,
+    numeric_mode: NumericConsensusMode,
+    consensus_metrics: Arc<Mutex<ConsensusMetrics>>,
+    history: ConsensusHistory,
+    cost_optimized: bool, // Query heaviest sources first and stop once they agree
+    fetch_backoff: Backoff, // Retries of a source whose fetch failed, within one round
 }
 
 impl OracleManager {
@@ -432,13 +1057,11 @@ impl OracleManager {
         min_sources_for_consensus: Option<usize>,
         cache_duration: Option<Duration>,
         background_update_interval: Option<Duration>,
+        outlier_strategy: Option<OutlierStrategy>,
     ) -> Self {
         let cache_duration = cache_duration.unwrap_or_else(|| Duration::from_secs(300)); // Default 5 mins
         let background_update_interval = background_update_interval.unwrap_or_else(|| Duration::from_secs(60)); // Default 1 min
-        let cache = Arc::new(Mutex::new(HashMap::new()));
-
-        // Spawn a dummy task initially, will be replaced when sources are added
-        let background_tasks = tokio::spawn(async {});
+        let cache = Mutex::new(HashMap::new());
 
         Self {
             sources: HashMap::new(),
@@ -446,21 +1069,89 @@ impl OracleManager {
             consensus_threshold: consensus_threshold.unwrap_or(0.51), // Default 51%
             min_sources_for_consensus: min_sources_for_consensus.unwrap_or(2), // Default 2
             cache,
+            source_cache: Arc::new(Mutex::new(HashMap::new())),
             cache_duration,
             background_update_interval,
-            background_tasks,
+            polled_sources: Arc::new(Mutex::new(Vec::new())),
+            background_task: None,
+            background_tasks_spawned: 0,
+            fallback_sources: Vec::new(),
+            named_quorum: None,
+            tie_break: TieBreakPolicy::PreferSmallest,
+            outlier_strategy: outlier_strategy.unwrap_or_default(), // Default IQR, k = 1.5
+            numeric_mode: NumericConsensusMode::default(),
+            consensus_metrics: Arc::new(Mutex::new(ConsensusMetrics::default())),
+            history: ConsensusHistory::default(),
+            cost_optimized: false,
+            fetch_backoff: Backoff::decorrelated_jitter(Duration::from_millis(100), Duration::from_secs(1)).with_max_attempts(2),
         }
     }
 
+    /// Replace the default in-memory history, e.g. with one persisted to disk
+    pub fn set_consensus_history(&mut self, history: ConsensusHistory) {
+        self.history = history;
+    }
+
+    /// Values returned for `query_id` at or after `since`, oldest first.
+    ///
+    /// Cache hits aren't recorded again; the value was recorded when it was computed.
+    pub fn consensus_history(&self, query_id: &str, since: DateTime<Utc>) -> Vec<ConsensusRecord> {
+        self.history.query(query_id, since)
+    }
+
+    /// Disagreement counters accumulated so far
+    pub fn consensus_metrics(&self) -> ConsensusMetrics {
+        self.consensus_metrics.lock().unwrap().clone()
+    }
+
+    /// Choose how categorical and object/array consensus resolve equal-weight winners
+    pub fn set_tie_break_policy(&mut self, policy: TieBreakPolicy) {
+        self.tie_break = policy;
+    }
+
+    /// Choose how numerical consensus rejects outliers (IQR with k = 1.5 by default)
+    pub fn set_outlier_strategy(&mut self, strategy: OutlierStrategy) {
+        self.outlier_strategy = strategy;
+    }
+
+    /// Choose how numerical consensus combines the remaining values (weighted median by default)
+    pub fn set_numeric_consensus_mode(&mut self, mode: NumericConsensusMode) -> Result<()> {
+        if let NumericConsensusMode::TrimmedMean(fraction) = mode {
+            if !(0.0..0.5).contains(&fraction) {
+                return Err(anyhow!("Trimmed mean fraction must be in [0, 0.5), got {}", fraction));
+            }
+        }
+        self.numeric_mode = mode;
+        Ok(())
+    }
+
+    /// Query sources in descending weight order and stop as soon as the agreeing
+    /// answers carry the threshold weight, instead of querying every source.
+    ///
+    /// More sources are only queried while answers fail or disagree, so rounds
+    /// where the heaviest sources agree cost a fraction of the requests.
+    pub fn set_cost_optimized(&mut self, enabled: bool) {
+        self.cost_optimized = enabled;
+    }
+
+    /// Schedule for refetching from a source whose fetch failed, before it counts as rejected.
+    /// Answers that fail validation are not refetched.
+    pub fn set_fetch_backoff(&mut self, backoff: Backoff) {
+        self.fetch_backoff = backoff;
+    }
+
     pub fn add_source(&mut self, source: Arc<dyn OracleSource>) -> Result<()> {
         let name = source.name().to_string();
         if self.sources.contains_key(&name) {
             return Err(anyhow!("Source '{}' already exists", name));
         }
+        // The manager takes over background polling for this source
+        source.set_managed(true);
         self.sources.insert(name.clone(), source.clone());
 
-        // Restart background tasks with the new source
-        self.restart_background_tasks();
+        // Register with the running loop rather than restarting it
+        self.polled_sources.lock().unwrap().push(source);
+        self.ensure_background_task();
 
         if let Some(log) = &self.audit_log {
             let _ = log.log_external_api("OracleManager", &format!("Added source '{}'", name), AuditSeverity::Info);
@@ -468,19 +1159,23 @@ impl OracleManager {
         Ok(())
     }
 
-    fn restart_background_tasks(&mut self) {
-        // Abort existing tasks
-        self.background_tasks.abort();
-
-        let sources_clone = self.sources.values().cloned().collect::<Vec<_>>();
+    /// Spawn the shared polling loop the first time it's needed.
+    ///
+    /// The loop re-reads `polled_sources` on every tick, so sources added
+    /// later are picked up without aborting in-flight fetches.
+    fn ensure_background_task(&mut self) {
+        if self.background_task.is_some() {
+            return;
+        }
         let interval = self.background_update_interval;
+        if interval.is_zero() {
+            return; // No background updates needed
+        }
+
+        let sources = self.polled_sources.clone();
         let audit_log_clone = self.audit_log.clone();
 
-        // Spawn new combined task
-        self.background_tasks = tokio::spawn(async move {
-            if interval.is_zero() {
-                return; // No background updates needed
-            }
+        self.background_task = Some(tokio::spawn(async move {
             let mut interval_timer = tokio::time::interval(interval);
             loop {
                 interval_timer.tick().await;
@@ -488,71 +1183,242 @@ impl OracleManager {
                      let _ = log.log_external_api("OracleManager", "Running background source updates", AuditSeverity::Info);
                  }
 
-                let futures = sources_clone.iter().map(|source| {
-                    let source = source.clone(); // Clone Arc for the async block
+                // Snapshot the current set so the lock isn't held across awaits
+                let current_sources = sources.lock().unwrap().clone();
+                let futures = current_sources.into_iter().map(|source| {
                     async move {
                         // Use default params if available, otherwise empty JSON object
                         let params = source.config().default_params.clone().unwrap_or_else(|| json!({}));
-                        match source.fetch(&params).await {
+                        match source.fetch_with_timeout(&params, source.config().background_timeout()).await {
                             Ok(_) => { /* Data is implicitly cached by fetch */ }
                             Err(e) => {
                                 // Log error, status is updated within fetch
-                                eprintln!("Background update failed for {}: {}", source.name(), e);
+                                warn!(source = source.name(), "Background update failed: {}", e);
                             }
                         }
                     }
                 });
                 futures::future::join_all(futures).await;
             }
-        });
+        }));
+        self.background_tasks_spawned += 1;
+    }
+
+    /// Number of background polling tasks this manager has spawned
+    pub fn background_task_count(&self) -> usize {
+        self.background_tasks_spawned
+    }
+
+    /// Number of sources the background loop is currently polling
+    pub fn polled_source_count(&self) -> usize {
+        self.polled_sources.lock().unwrap().len()
+    }
+
+    /// Consensus outcomes currently held for reuse, expired ones included until the next round.
+    pub fn cached_outcome_count(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
+    /// Number of registered sources
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Fail fast if fewer than `min` sources were registered, instead of
+    /// waiting for the first query to report insufficient sources
+    pub fn require_sources(&self, min: usize) -> Result<()> {
+        if self.sources.len() < min {
+            return Err(anyhow!(
+                "Only {} oracle source(s) registered, at least {} required",
+                self.sources.len(),
+                min
+            ));
+        }
+        Ok(())
+    }
+
+    /// Set the ordered list of sources to trust on their own when consensus fails
+    pub fn set_fallback_sources(&mut self, fallback_sources: Vec<String>) -> Result<()> {
+        if let Some(unknown) = fallback_sources.iter().find(|name| !self.sources.contains_key(*name)) {
+            return Err(anyhow!("Unknown fallback source '{}'", unknown));
+        }
+        self.fallback_sources = fallback_sources;
+        Ok(())
+    }
+
+    /// Require `quorum.min_agreeing` of `quorum.required_sources` to agree with every consensus value.
+    ///
+    /// A round that doesn't meet it fails with `NamedQuorumUnmet` and is not
+    /// answered by a fallback source, since that would bypass the requirement.
+    pub fn set_named_quorum(&mut self, quorum: NamedQuorum) -> Result<()> {
+        if let Some(unknown) = quorum.required_sources.iter().find(|name| !self.sources.contains_key(*name)) {
+            return Err(anyhow!("Unknown named quorum source '{}'", unknown));
+        }
+        if quorum.min_agreeing == 0 || quorum.min_agreeing > quorum.required_sources.len() {
+            return Err(anyhow!(
+                "Named quorum needs between 1 and {} agreeing sources, got {}",
+                quorum.required_sources.len(),
+                quorum.min_agreeing
+            ));
+        }
+        self.named_quorum = Some(quorum);
+        Ok(())
     }
 
     pub async fn get_consensus_data(&self, query_id: &str, params: &Value) -> Result<Value> {
-        let cache_key = format!("{}:{}", query_id, serde_json::to_string(params)?);
+        Ok(self.get_consensus_data_detailed(query_id, params).await?.value)
+    }
+
+    /// Like `get_consensus_data`, but reports whether the value came from a fallback source
+    pub async fn get_consensus_result(&self, query_id: &str, params: &Value) -> Result<ConsensusResult> {
+        let outcome = self.get_consensus_data_detailed(query_id, params).await?;
+        Ok(ConsensusResult { value: outcome.value, strategy: outcome.strategy })
+    }
+
+    /// The consensus value with which sources agreed, which were rejected and why,
+    /// and the weight achieved against the threshold.
+    ///
+    /// A cached value comes with the outcome of the round that computed it.
+    #[tracing::instrument(name = "oracle_consensus", skip(self, params))]
+    pub async fn get_consensus_data_detailed(&self, query_id: &str, params: &Value) -> Result<ConsensusOutcome> {
+        let consensus_error = match self.reach_consensus(query_id, params).await {
+            Ok(outcome) => return Ok(outcome),
+            Err(e) if e.is::<NamedQuorumUnmet>() => return Err(e),
+            Err(e) => e,
+        };
+
+        for name in &self.fallback_sources {
+            let source = match self.sources.get(name) {
+                Some(source) => source,
+                None => continue,
+            };
+            let data = match source.fetch(params).await {
+                Ok(data) => data,
+                Err(e) => {
+                    warn!(source = %name, "Fallback source failed: {}", e);
+                    continue;
+                }
+            };
+            if !source.validate(&data).iter().all(|r| r.passed) {
+                warn!(source = %name, "Fallback source returned invalid data");
+                continue;
+            }
+
+            warn!(source = %name, "Oracle consensus failed ({}); using single-sourced answer from fallback", consensus_error);
+            let strategy = ConsensusStrategy::Fallback { source: name.clone() };
+            self.history.record(ConsensusRecord::new(query_id, params, &data, strategy.clone(), vec![name.clone()]));
+            if let Some(log) = &self.audit_log {
+                let _ = log.log_external_api(
+                    "OracleManager",
+                    &format!("Consensus failed for '{}' ({}), degraded to fallback source '{}'", query_id, consensus_error, name),
+                    AuditSeverity::Warning,
+                );
+            }
+            return Ok(ConsensusOutcome {
+                value: data,
+                strategy,
+                participating_sources: vec![name.clone()],
+                rejected_sources: Vec::new(),
+                achieved_weight: source.config().weight as u32,
+                required_weight: 0,
+            });
+        }
+
+        Err(consensus_error)
+    }
+
+    async fn reach_consensus(&self, query_id: &str, params: &Value) -> Result<ConsensusOutcome> {
+        let cache_key = format!("{}:{}", query_id, canonical_json(params));
 
         // Check cache
         {
             let cache = self.cache.lock().unwrap();
             if let Some(cached) = cache.get(&cache_key) {
                 if cached.timestamp.elapsed() < self.cache_duration {
-                    return Ok(cached.value.clone());
+                    return Ok(cached.outcome.clone());
                 }
             }
         }
 
-        let operational_sources: Vec<_> = self.sources.values()
-            .filter(|s| matches!(s.status(), OracleSourceStatus::Operational | OracleSourceStatus::Degraded(_)))
-            .cloned()
-            .collect();
+        let outcome = self.query_sources_for_consensus(params).await;
+        {
+            let mut metrics = self.consensus_metrics.lock().unwrap();
+            match &outcome {
+                Ok(_) => metrics.consensus_reached += 1,
+                Err(_) => metrics.consensus_failed += 1,
+            }
+        }
+        let outcome = outcome?;
+        self.history.record(ConsensusRecord::new(
+            query_id,
+            params,
+            &outcome.value,
+            ConsensusStrategy::Consensus,
+            outcome.participating_sources.clone(),
+        ));
+
+        // Update cache, dropping entries too old to be served again
+        {
+            let mut cache = self.cache.lock().unwrap();
+            cache.retain(|_, cached| cached.timestamp.elapsed() < self.cache_duration);
+            cache.insert(cache_key, CachedOutcome { outcome: outcome.clone(), timestamp: Instant::now() });
+        }
+
+        if let Some(log) = &self.audit_log {
+             let _ = log.log_external_api(
+                 "OracleManager",
+                 &format!(
+                     "Consensus reached for '{}': weight {}/{} from {:?}, rejected {:?}",
+                     query_id, outcome.achieved_weight, outcome.required_weight, outcome.participating_sources, outcome.rejected_sources
+                 ),
+                 AuditSeverity::Info,
+             );
+         }
+
+        Ok(outcome)
+    }
+
+    /// Fetch from the operational sources (all of them, unless cost-optimized) and
+    /// combine the valid answers.
+    async fn query_sources_for_consensus(&self, params: &Value) -> Result<ConsensusOutcome> {
+        let mut rejected_sources = Vec::new();
+        let mut operational_sources = Vec::new();
+        for source in self.sources.values() {
+            match source.status() {
+                OracleSourceStatus::Operational | OracleSourceStatus::Degraded(_) => operational_sources.push(source.clone()),
+                status => rejected_sources.push((source.name().to_string(), format!("not operational: {:?}", status))),
+            }
+        }
 
         if operational_sources.len() < self.min_sources_for_consensus {
              return Err(anyhow!("Insufficient operational sources ({}/{})", operational_sources.len(), self.min_sources_for_consensus));
          }
+        if let Some(quorum) = &self.named_quorum {
+            // Fail before spending requests when too many named sources are down to ever agree
+            let operational: Vec<&str> = operational_sources.iter().map(|s| s.name()).collect();
+            self.check_named_quorum(quorum, &operational, |_| true)?;
+        }
 
-        let futures = operational_sources.iter().map(|source| {
-            let source_clone = source.clone();
-            let params_clone = params.clone();
-            async move {
-                match source_clone.fetch(&params_clone).await {
-                    Ok(data) => {
-                        let validation_results = source_clone.validate(&data);
-                        if validation_results.iter().all(|r| r.passed) {
-                            Some((data, source_clone.config().weight))
-                        } else {
-                            eprintln!("Validation failed for {}", source_clone.name());
-                            None
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Fetch failed for {}: {}", source_clone.name(), e);
-                        None
-                    }
-                }
-            }
-        });
+        // Calculate total possible weight from all originally operational sources
+        let max_possible_weight: u32 = operational_sources.iter().map(|s| s.config().weight as u32).sum();
+        let required_weight = (max_possible_weight as f64 * self.consensus_threshold) as u32;
 
-        let results: Vec<Option<(Value, u8)>> = futures::future::join_all(futures).await;
-        let valid_responses: Vec<(Value, u8)> = results.into_iter().flatten().collect();
+        let (answers, queried) = if self.cost_optimized {
+            self.fetch_until_agreement(&operational_sources, params, required_weight).await
+        } else {
+            (self.fetch_valid(&operational_sources, params).await, operational_sources.len())
+        };
+        rejected_sources.extend(answers.rejected);
+        debug!(queried, operational = operational_sources.len(), "Oracle sources queried");
+        {
+            let mut metrics = self.consensus_metrics.lock().unwrap();
+            metrics.sources_queried += queried as u64;
+            metrics.sources_skipped += (operational_sources.len() - queried) as u64;
+        }
+
+        let (response_sources, valid_responses): (Vec<String>, Vec<(Value, u8)>) = answers.valid.into_iter()
+            .map(|(name, data, weight)| (name, (data, weight)))
+            .unzip();
 
         if valid_responses.len() < self.min_sources_for_consensus {
             return Err(anyhow!("Insufficient valid responses after fetch/validation ({}/{})", valid_responses.len(), self.min_sources_for_consensus));
@@ -560,46 +1426,223 @@ impl OracleManager {
 
         // Calculate total weight of valid responses
         let total_weight: u32 = valid_responses.iter().map(|(_, w)| *w as u32).sum();
-        // Calculate total possible weight from all originally operational sources
-        let max_possible_weight: u32 = operational_sources.iter().map(|s| s.config().weight as u32).sum();
-        let required_weight = (max_possible_weight as f64 * self.consensus_threshold) as u32;
 
         if total_weight < required_weight {
              return Err(anyhow!("Consensus weight threshold not met ({} < {})", total_weight, required_weight));
          }
 
         // Determine consensus based on the type of the first valid response
-        let consensus_value = match valid_responses.get(0) {
+        let mut outliers = Vec::new();
+        let value = match valid_responses.get(0) {
             Some((first_value, _)) => match first_value {
-                 Value::Number(_) => self.numerical_consensus(&valid_responses)?,
-                 Value::String(_) | Value::Bool(_) | Value::Null => self.categorical_consensus(&valid_responses)?,
-                 Value::Object(_) => self.object_consensus(&valid_responses)?,
-                 Value::Array(_) => self.array_consensus(&valid_responses)?,
+                 Value::Number(_) => {
+                     outliers = self.record_outliers(&response_sources, &valid_responses);
+                     self.numerical_consensus(&valid_responses)
+                 }
+                 Value::String(_) | Value::Bool(_) | Value::Null => self.categorical_consensus(&valid_responses),
+                 Value::Object(_) => self.object_consensus(&valid_responses),
+                 Value::Array(_) => self.array_consensus(&valid_responses),
             },
-            None => return Err(anyhow!("No valid responses available to determine consensus type")),
+            None => Err(anyhow!("No valid responses available to determine consensus type")),
+        }?;
+        if let Some(quorum) = &self.named_quorum {
+            let answered: Vec<&str> = response_sources.iter().map(String::as_str).collect();
+            self.check_named_quorum(quorum, &answered, |name| {
+                response_sources.iter()
+                    .zip(&valid_responses)
+                    .any(|(source, (response, _))| source == name && values_agree(response, &value))
+            })?;
+        }
+
+        // Numbers count unless rejected as outliers; other values only if they match the winner
+        let mut participating_sources = Vec::new();
+        let mut achieved_weight = 0u32;
+        for (source, (response, weight)) in response_sources.iter().zip(&valid_responses) {
+            let reason = if outliers.contains(source) {
+                Some("outlier")
+            } else if !response.is_number() && canonical_json(response) != canonical_json(&value) {
+                Some("disagreed with consensus")
+            } else {
+                None
+            };
+            match reason {
+                Some(reason) => rejected_sources.push((source.clone(), reason.to_string())),
+                None => {
+                    participating_sources.push(source.clone());
+                    achieved_weight += *weight as u32;
+                }
+            }
+        }
+        participating_sources.sort();
+        rejected_sources.sort();
+
+        let outcome = ConsensusOutcome {
+            value,
+            strategy: ConsensusStrategy::Consensus,
+            participating_sources,
+            rejected_sources,
+            achieved_weight,
+            required_weight,
         };
+        Ok(outcome)
+    }
 
+    /// Fail unless `quorum.min_agreeing` of its sources are in `available` and satisfy `agrees`
+    fn check_named_quorum(&self, quorum: &NamedQuorum, available: &[&str], agrees: impl Fn(&str) -> bool) -> Result<()> {
+        let unavailable: Vec<String> = quorum.required_sources.iter()
+            .filter(|name| !available.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        let agreeing = quorum.required_sources.iter()
+            .filter(|name| available.contains(&name.as_str()) && agrees(name))
+            .count();
+        if agreeing >= quorum.min_agreeing {
+            return Ok(());
+        }
+        let unmet = NamedQuorumUnmet {
+            required: quorum.required_sources.clone(),
+            min_agreeing: quorum.min_agreeing,
+            agreeing,
+            unavailable,
+        };
+        if let Some(log) = &self.audit_log {
+            let _ = log.log_external_api("OracleManager", &unmet.to_string(), AuditSeverity::Warning);
+        }
+        Err(anyhow::Error::new(unmet))
+    }
 
-        // Update cache
+    /// Fetch from `sources` concurrently, sorting their answers into valid and rejected.
+    /// Fetches that failed on a transport error or 5xx are retried on `fetch_backoff`.
+    async fn fetch_valid(&self, sources: &[Arc<dyn OracleSource>], params: &Value) -> SourceAnswers {
+        let futures = sources.iter().map(|source| {
+            let source_clone = source.clone();
+            let params_clone = params.clone();
+            let backoff = self.fetch_backoff.clone();
+            async move {
+                let name = source_clone.name().to_string();
+                let fetched = retry_async(|| source_clone.fetch(&params_clone), backoff, SourceFetchError::is_retryable_error).await;
+                match fetched {
+                    Ok(data) => {
+                        let validation_results = source_clone.validate(&data);
+                        match validation_results.iter().find(|r| !r.passed) {
+                            None => Ok((name, data, source_clone.config().weight)),
+                            Some(failed) => {
+                                warn!(source = source_clone.name(), "Validation failed");
+                                Err((name, format!("invalid: {}", failed.error_message.as_deref().unwrap_or(&failed.rule_name))))
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        warn!(source = source_clone.name(), "Fetch failed: {}", e);
+                        Err((name, format!("fetch failed: {}", e)))
+                    }
+                }
+            }
+        });
+
+        let mut answers = SourceAnswers::default();
+        for answer in futures::future::join_all(futures).await {
+            match answer {
+                Ok(valid) => answers.valid.push(valid),
+                Err(rejected) => answers.rejected.push(rejected),
+            }
+        }
+        answers
+    }
+
+    /// Fetch from `sources` heaviest first until the agreeing answers carry `required_weight`.
+    /// Returns the valid answers and how many sources were queried.
+    async fn fetch_until_agreement(
+        &self,
+        sources: &[Arc<dyn OracleSource>],
+        params: &Value,
+        required_weight: u32,
+    ) -> (SourceAnswers, usize) {
+        let is_named = |source: &Arc<dyn OracleSource>| {
+            self.named_quorum.iter().any(|quorum| quorum.required_sources.iter().any(|name| name == source.name()))
+        };
+        // Named quorum sources always have to answer, so they go first
+        let mut ordered = sources.to_vec();
+        ordered.sort_by(|a, b| {
+            is_named(b).cmp(&is_named(a))
+                .then_with(|| b.config().weight.cmp(&a.config().weight))
+                .then_with(|| a.name().cmp(b.name()))
+        });
+        let named_count = ordered.iter().filter(|source| is_named(source)).count();
+
+        // Smallest heaviest-first set that could succeed if every answer agreed
+        let mut first_wave = 0;
+        let mut wave_weight = 0u32;
+        while first_wave < ordered.len()
+            && (wave_weight < required_weight || first_wave < self.min_sources_for_consensus || first_wave < named_count)
         {
-            let mut cache = self.cache.lock().unwrap();
-            cache.insert(cache_key.clone(), CachedData {
-                value: consensus_value.clone(),
-                timestamp: Instant::now()
-            });
+            wave_weight += ordered[first_wave].config().weight as u32;
+            first_wave += 1;
         }
+        let mut answers = self.fetch_valid(&ordered[..first_wave], params).await;
 
-        if let Some(log) = &self.audit_log {
-             let _ = log.log_external_api("OracleManager", &format!("Consensus reached for '{}'", query_id), AuditSeverity::Info);
-         }
+        // Widen one source at a time while answers are missing or disagree
+        let mut queried = first_wave;
+        while queried < ordered.len()
+            && (answers.valid.len() < self.min_sources_for_consensus || self.agreeing_weight(&answers.valid) < required_weight)
+        {
+            answers.extend(self.fetch_valid(&ordered[queried..queried + 1], params).await);
+            queried += 1;
+        }
+        (answers, queried)
+    }
+
+    /// Weight behind the best-supported answer in `responses`.
+    ///
+    /// Numbers agree when within `NUMERIC_AGREEMENT_TOLERANCE` of their weighted
+    /// median; other values only when canonically equal.
+    fn agreeing_weight(&self, responses: &[(String, Value, u8)]) -> u32 {
+        let numeric: Vec<(f64, u8)> = responses.iter().filter_map(|(_, v, w)| v.as_f64().map(|n| (n, *w))).collect();
+        if !numeric.is_empty() && numeric.len() == responses.len() {
+            let median = match weighted_median(&numeric) {
+                Some(median) => median,
+                None => return 0,
+            };
+            let tolerance = median.abs() * NUMERIC_AGREEMENT_TOLERANCE;
+            return numeric.iter()
+                .filter(|(v, _)| (v - median).abs() <= tolerance)
+                .map(|(_, w)| *w as u32)
+                .sum();
+        }
 
-        Ok(consensus_value)
+        let mut value_weights: HashMap<String, u32> = HashMap::new();
+        for (_, value, weight) in responses {
+            *value_weights.entry(canonical_json(value)).or_insert(0) += *weight as u32;
+        }
+        value_weights.into_values().max().unwrap_or(0)
+    }
+
+    /// Count which sources' numerical values the outlier strategy rejects, and return them
+    fn record_outliers(&self, sources: &[String], responses: &[(Value, u8)]) -> Vec<String> {
+        let numeric: Vec<(&String, (f64, u8))> = sources.iter()
+            .zip(responses)
+            .filter_map(|(source, (v, w))| v.as_f64().map(|n| (source, (n, *w))))
+            .collect();
+        let values: Vec<(f64, u8)> = numeric.iter().map(|(_, value)| *value).collect();
+        let keep = self.outlier_strategy.keep_mask(&values);
+
+        let mut metrics = self.consensus_metrics.lock().unwrap();
+        let mut outliers = Vec::new();
+        for ((source, _), kept) in numeric.iter().zip(keep) {
+            *metrics.per_source_numeric_rounds.entry((*source).clone()).or_insert(0) += 1;
+            if !kept {
+                metrics.outliers_rejected += 1;
+                *metrics.per_source_outlier_counts.entry((*source).clone()).or_insert(0) += 1;
+                outliers.push((*source).clone());
+            }
+        }
+        outliers
     }
 
     // --- Consensus Helper Functions ---
 
     fn numerical_consensus(&self, responses: &[(Value, u8)]) -> Result<Value> {
-        let mut weighted_values: Vec<(f64, u8)> = responses.iter()
+        let weighted_values: Vec<(f64, u8)> = responses.iter()
             .filter_map(|(v, w)| v.as_f64().map(|n| (n, *w)))
             .collect();
 
@@ -607,43 +1650,20 @@ impl OracleManager {
             return Err(anyhow!("No valid numerical values for consensus"));
         }
 
-        // Basic outlier rejection (IQR)
-        weighted_values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-        let values: Vec<f64> = weighted_values.iter().map(|(v, _)| *v).collect();
-        let q1_idx = (values.len() as f64 * 0.25).floor() as usize;
-        let q3_idx = (values.len() as f64 * 0.75).floor() as usize;
-        let q1 = values.get(q1_idx).cloned().unwrap_or(0.0);
-        let q3 = values.get(q3_idx).cloned().unwrap_or(0.0);
-        let iqr = q3 - q1;
-        let lower_bound = q1 - 1.5 * iqr;
-        let upper_bound = q3 + 1.5 * iqr;
-
-        let filtered_weighted_values: Vec<(f64, u8)> = weighted_values.into_iter()
-            .filter(|(v, _)| *v >= lower_bound && *v <= upper_bound)
-            .collect();
+        let filtered_weighted_values = self.outlier_strategy.filter(&weighted_values);
 
         if filtered_weighted_values.is_empty() {
             return Err(anyhow!("All numerical values rejected as outliers"));
         }
 
-        // Weighted Median
-        let mut sorted_filtered = filtered_weighted_values;
-        sorted_filtered.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-
-        let total_weight: u32 = sorted_filtered.iter().map(|(_, w)| *w as u32).sum();
-        let mid_weight = total_weight / 2;
-        let mut current_weight: u32 = 0;
-
-        for (value, weight) in &sorted_filtered {
-            current_weight += *weight as u32;
-            if current_weight > mid_weight {
-                // Safely create Number from f64
-                return Ok(serde_json::json!(*value));
-            }
-        }
-        // Fallback: return the last value if loop completes (shouldn't happen with non-zero weight)
-        let last_val = sorted_filtered.last().map(|(v,_)| *v).unwrap_or(0.0);
-        Ok(serde_json::json!(last_val))
+        let combined = match self.numeric_mode {
+            NumericConsensusMode::WeightedMedian => weighted_median(&filtered_weighted_values),
+            NumericConsensusMode::WeightedMean => weighted_mean(&filtered_weighted_values),
+            NumericConsensusMode::TrimmedMean(fraction) => weighted_trimmed_mean(&filtered_weighted_values, fraction),
+        };
+        combined
+            .map(|value| serde_json::json!(value))
+            .ok_or_else(|| anyhow!("Total weight is zero for numerical consensus"))
     }
 
     fn categorical_consensus(&self, responses: &[(Value, u8)]) -> Result<Value> {
@@ -667,7 +1687,7 @@ impl OracleManager {
 
         let threshold_weight = (total_weight as f64 * self.consensus_threshold) as u32;
 
-        let consensus_entry = value_weights.into_iter().max_by_key(|&(_, w)| w);
+        let consensus_entry = self.pick_heaviest(value_weights.into_iter().map(|(key, w)| (key.clone(), w, key)), threshold_weight)?;
 
         if let Some((value_str, weight)) = consensus_entry {
             if weight >= threshold_weight {
@@ -695,18 +1715,14 @@ impl OracleManager {
     }
 
     fn stringified_consensus(&self, responses: &[(Value, u8)], value_type: &str) -> Result<Value> {
-        let mut value_weights: HashMap<String, u32> = HashMap::new();
+        // Keyed by canonical JSON so equal values with different key order agree
+        let mut value_weights: HashMap<String, (u32, &Value)> = HashMap::new();
         let mut total_weight: u32 = 0;
 
         for (value, weight) in responses {
             if (value_type == "object" && value.is_object()) || (value_type == "array" && value.is_array()) {
-                match serde_json::to_string(value) {
-                    Ok(s) => {
-                        *value_weights.entry(s).or_insert(0) += *weight as u32;
-                        total_weight += *weight as u32;
-                    }
-                    Err(_) => continue, // Skip if cannot serialize
-                }
+                value_weights.entry(canonical_json(value)).or_insert((0, value)).0 += *weight as u32;
+                total_weight += *weight as u32;
             }
         }
 
@@ -718,11 +1734,11 @@ impl OracleManager {
          }
 
         let threshold_weight = (total_weight as f64 * self.consensus_threshold).ceil() as u32; // Use ceil for threshold
-        let consensus_entry = value_weights.into_iter().max_by_key(|&(_, w)| w);
+        let consensus_entry = self.pick_heaviest(value_weights.into_iter().map(|(key, (w, value))| (key, w, value)), threshold_weight)?;
 
-        if let Some((value_str, weight)) = consensus_entry {
+        if let Some((value, weight)) = consensus_entry {
             if weight >= threshold_weight {
-                serde_json::from_str(&value_str).map_err(|e| anyhow!("Failed to parse consensus {}: {}", value_type, e))
+                Ok(value.clone())
             } else {
                 Err(anyhow!("{} consensus threshold not met (max weight {} < threshold {})", value_type, weight, threshold_weight))
             }
@@ -732,14 +1748,41 @@ impl OracleManager {
     }
 }
 
+impl OracleManager {
+    /// Heaviest `(key, weight, item)` candidate, resolving ties by `tie_break`.
+    ///
+    /// A tie below `threshold_weight` is returned as-is so the caller reports the
+    /// threshold failure instead. `None` means there were no candidates.
+    fn pick_heaviest<T>(&self, candidates: impl Iterator<Item = (String, u32, T)>, threshold_weight: u32) -> Result<Option<(T, u32)>> {
+        let mut candidates: Vec<(String, u32, T)> = candidates.collect();
+        let max_weight = match candidates.iter().map(|(_, w, _)| *w).max() {
+            Some(w) => w,
+            None => return Ok(None),
+        };
+        candidates.retain(|(_, w, _)| *w == max_weight);
+        candidates.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if candidates.len() > 1 && max_weight >= threshold_weight && self.tie_break == TieBreakPolicy::Reject {
+            return Err(AmbiguousConsensus {
+                candidates: candidates.into_iter().map(|(key, _, _)| key).collect(),
+                weight: max_weight,
+            }.into());
+        }
+        Ok(candidates.into_iter().next().map(|(_, w, item)| (item, w)))
+    }
+}
+
 impl Drop for OracleManager {
     fn drop(&mut self) {
-        self.background_tasks.abort();
+        if let Some(task) = &self.background_task {
+            task.abort();
+        }
     }
 }
 
 // --- Factory Functions --- (Moved from specific API modules)
 
+/// Numeric rule over `[min, max]`; `"nullable": true` in its parameters lets `null` pass.
 pub fn create_numeric_range_rule(
     name: &str, min: Option<f64>, max: Option<f64>, error_message: &str
 ) -> ValidationRule {
@@ -749,9 +1792,11 @@ pub fn create_numeric_range_rule(
         rule_type: ValidationRuleType::NumericRange,
         parameters: params,
         error_message: error_message.to_string(),
+        target_field: None,
     }
 }
 
+/// String rule: `pattern` is a regular expression, `allowed` a list of exact values.
 pub fn create_string_pattern_rule(
     name: &str, pattern: Option<&str>, allowed_values: Option<Vec<&str>>, error_message: &str
 ) -> ValidationRule {
@@ -761,9 +1806,17 @@ pub fn create_string_pattern_rule(
         rule_type: ValidationRuleType::StringPattern,
         parameters: params,
         error_message: error_message.to_string(),
+        target_field: None,
     }
 }
 
+/// String rule passing values that match the regular expression `pattern`.
+///
+/// Matching is unanchored; use `^...$` to require the whole value to match.
+pub fn create_regex_rule(name: &str, pattern: &str, error_message: &str) -> ValidationRule {
+    create_string_pattern_rule(name, Some(pattern), None, error_message)
+}
+
 pub fn create_weather_api_source(
     api_key: &str,
     audit_log: Option<Arc<SecurityAuditLog>>,
@@ -776,12 +1829,15 @@ pub fn create_weather_api_source(
         source_type: "REST".to_string(),
         auth_header: None,
         default_params: Some(json!({ "appid": api_key, "units": "metric" })),
+        http_method: "GET".to_string(),
+        body_template: None,
         validation_rules: vec![
-            create_numeric_range_rule("temp_range", Some(-100.0), Some(100.0), "Temp out of range"),
-            create_numeric_range_rule("humidity_range", Some(0.0), Some(100.0), "Humidity out of range"),
+            create_numeric_range_rule("temp_range", Some(-100.0), Some(100.0), "Temp out of range").with_target_field(&["temp"]),
+            create_numeric_range_rule("humidity_range", Some(0.0), Some(100.0), "Humidity out of range").with_target_field(&["humidity"]),
         ],
         weight: 100,
         timeout_ms: 5000,
+        background_timeout_ms: None,
         rate_limit: Some(60),
         requires_auth: true,
         path: vec!["main".to_string()], // Extract the 'main' object
@@ -802,12 +1858,22 @@ pub fn create_flight_api_source(
         source_type: "REST".to_string(),
         auth_header: None, // Key is passed as query param
         default_params: Some(json!({ "access_key": api_key })),
+        http_method: "GET".to_string(),
+        body_template: None,
         validation_rules: vec![
-             create_numeric_range_rule("delay_range", Some(0.0), Some(86400.0*2.0), "Delay out of range"), // Allow up to 2 days delay
-             create_string_pattern_rule("status", None, Some(vec!["scheduled", "active", "landed", "cancelled", "incident", "diverted"]), "Invalid status"),
+             {
+                 // Allow up to 2 days delay; AviationStack reports `null` rather than 0 for flights on time
+                 let mut rule = create_numeric_range_rule("delay_range", Some(0.0), Some(86400.0*2.0), "Delay out of range")
+                     .with_target_field(&["arrival", "delay"]);
+                 rule.parameters["nullable"] = json!(true);
+                 rule
+             },
+             create_string_pattern_rule("status", None, Some(vec!["scheduled", "active", "landed", "cancelled", "incident", "diverted"]), "Invalid status")
+                 .with_target_field(&["flight_status"]),
         ],
         weight: 100,
         timeout_ms: 10000,
+        background_timeout_ms: None,
         rate_limit: Some(100), // Check free tier limits
         requires_auth: true,
          path: vec!["data".to_string(), "0".to_string()], // Extract the first flight object in the 'data' array
@@ -822,8 +1888,8 @@ pub fn create_weather_oracle(
     cache_duration: Option<Duration>,
     update_interval: Option<Duration>,
 ) -> Result<OracleManager> {
-    let mut manager = OracleManager::new(audit_log.clone(), Some(0.6), Some(1), cache_duration, update_interval);
-    let cache = manager.cache.clone(); // Use manager's cache
+    let mut manager = OracleManager::new(audit_log.clone(), Some(0.6), Some(1), cache_duration, update_interval, None);
+    let cache = manager.source_cache.clone(); // Sources share the manager's data cache
     let effective_cache_duration = manager.cache_duration;
 
     // Source 1: OpenWeatherMap
@@ -831,28 +1897,27 @@ pub fn create_weather_oracle(
         if !api_key.is_empty() {
             match create_weather_api_source(&api_key, audit_log.clone(), cache.clone(), effective_cache_duration) {
                 Ok(source) => {
-                    println!("Adding OpenWeatherMap source...");
+                    info!("Adding OpenWeatherMap source");
                     manager.add_source(Arc::new(source))?;
                 }
-                Err(e) => eprintln!("Failed to create OpenWeatherMap source: {}", e),
+                Err(e) => warn!("Failed to create OpenWeatherMap source: {}", e),
             }
         } else {
-             eprintln!("OPENWEATHERMAP_API_KEY is set but empty, skipping source.");
+             warn!("OPENWEATHERMAP_API_KEY is set but empty, skipping source");
         }
     } else {
-        eprintln!("OPENWEATHERMAP_API_KEY not set, skipping source.");
+        warn!("OPENWEATHERMAP_API_KEY not set, skipping source");
     }
 
     // Add more sources here if available (e.g., WeatherAPI, AccuWeather)
     // Ensure they use different API keys and potentially different weights/configs
 
+    // Callers that need a working oracle should check with `require_sources`
     if manager.sources.is_empty() {
-        eprintln!("WARN: No weather oracle sources could be created. Check API keys and environment variables.");
-        // Optionally return an error, or allow the manager to exist with no sources
-        // return Err(anyhow!("No weather oracle sources could be created."));
+        warn!("No weather oracle sources could be created. Set OPENWEATHERMAP_API_KEY to enable the OpenWeatherMap source");
     }
 
-    println!("Weather Oracle Manager created with {} sources.", manager.sources.len());
+    info!(sources = manager.sources.len(), "Weather Oracle Manager created");
     Ok(manager)
 }
 
@@ -863,15 +1928,12 @@ pub async fn create_weather_oracle_async(
     update_interval: Duration,
     cache_duration: Duration,
 ) -> Result<Box<dyn OracleSource>> { // Return Box<dyn OracleSource>
-    let _config = config.clone(); // Clone config for the closure
-    let url_template = config.url.clone();
-    let path_template = config.path.clone();
 
     // Background task to update cache
     let cache_clone = cache.clone();
     let client_clone = http_client.clone();
-    let url_clone = url_template.clone(); // Clone for the background task
-    let path_clone = path_template.clone(); // Clone for the background task
+    let url_clone = config.url.clone(); // Clone for the background task
+    let path_clone = config.path.clone(); // Clone for the background task
     tokio::spawn(async move {
         if update_interval == Duration::from_secs(0) {
             return; // No background updates needed
@@ -906,14 +1968,14 @@ pub async fn create_weather_oracle_async(
                                  timestamp: Instant::now(),
                              });
                          } else {
-                            eprintln!("Background weather update: Failed to extract data with path.");
+                            warn!("Background weather update: Failed to extract data with path");
                          }
                     } else {
-                        eprintln!("Background weather update: Failed to parse JSON.");
+                        warn!("Background weather update: Failed to parse JSON");
                     }
                 }
                 Err(e) => {
-                    eprintln!("Background weather update failed: {}", e);
+                    warn!("Background weather update failed: {}", e);
                 }
             }
         }
@@ -921,9 +1983,7 @@ pub async fn create_weather_oracle_async(
 
     // Return the OracleSource implementation
     let source = SimpleOracleSource {
-        name: config.name.clone(),
-        url_template,
-        path: path_template,
+        config: config.clone(),
         client: http_client,
         cache,
         cache_duration,
@@ -934,9 +1994,7 @@ pub async fn create_weather_oracle_async(
 
 // A simplified OracleSource for the async creation function
 struct SimpleOracleSource {
-    name: String,
-    url_template: String,
-    path: Vec<String>,
+    config: OracleSourceConfig,
     client: reqwest::Client,
     cache: Arc<Mutex<HashMap<String, CachedData>>>,
     cache_duration: Duration,
@@ -945,12 +2003,11 @@ struct SimpleOracleSource {
 #[async_trait]
 impl OracleSource for SimpleOracleSource {
     fn name(&self) -> &str {
-        &self.name
+        &self.config.name
     }
 
     fn config(&self) -> &OracleSourceConfig {
-        // This is simplified; a real implementation would store the full config
-         panic!("SimpleOracleSource does not fully store config");
+        &self.config
     }
 
     async fn fetch(&self, _params: &Value) -> Result<Value> { // Changed params to _params
@@ -966,13 +2023,13 @@ impl OracleSource for SimpleOracleSource {
             }
         }
 
-        // Fetch from API (replace with actual logic using url_template and params)
-        let response = self.client.get(&self.url_template).send().await?;
+        // Fetch from API (replace with actual logic using the url template and params)
+        let response = self.client.get(&self.config.url).send().await?;
         let data = response.json::<Value>().await?;
 
          // Extract data using path
          let mut current = &data;
-         for key in &self.path {
+         for key in &self.config.path {
              if let Some(obj) = current.as_object() {
                  current = obj.get(key).ok_or_else(|| anyhow!("Invalid path key: {}", key))?;
              } else {
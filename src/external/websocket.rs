@@ -1,9 +1,25 @@
 use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use serde_json::Value;
+use std::time::{Duration, Instant};
+use serde_json::{json, Value};
+use tokio::sync::watch;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, warn};
 use websocket::client::ClientBuilder;
 use websocket::OwnedMessage;
+use super::oracle::{
+    extract_path_value, CachedData, OracleSource, OracleSourceConfig, OracleSourceStatus, RuleValidator, ValidationResult,
+};
+use crate::security::audit::{AuditSeverity, SecurityAuditLog};
+use crate::utils::backoff::Backoff;
+use crate::utils::canonical_json;
 
 // WebSocket client for real-time data
 pub struct WebSocketClient {
@@ -104,4 +120,290 @@ impl WebSocketClient {
         client.send_message(&OwnedMessage::Text(message.to_string()))?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Reconnection schedule of a `WebSocketOracleSource`: unlimited attempts, since a feed is expected to come back
+fn default_stream_backoff() -> Backoff {
+    Backoff::decorrelated_jitter(Duration::from_millis(500), Duration::from_secs(30))
+}
+
+/// State shared between a `WebSocketOracleSource` and its connection task
+struct StreamShared {
+    config: OracleSourceConfig,
+    /// Key of the latest message in `cache`, as the REST source's background updates use
+    cache_key: String,
+    cache: Arc<Mutex<HashMap<String, CachedData>>>,
+    cache_duration: Duration,
+    status: Mutex<OracleSourceStatus>,
+    audit_log: Option<Arc<SecurityAuditLog>>,
+    /// Schedule each run of reconnects starts from
+    backoff: Mutex<Backoff>,
+    /// Set while a connection task is running, so there is only ever one
+    streaming: AtomicBool,
+    /// Bumped after every cached message, to wake waiting fetches
+    updates: watch::Sender<u64>,
+    /// Closed when the source is dropped, which stops the connection task
+    shutdown: watch::Receiver<()>,
+}
+
+impl StreamShared {
+    fn set_status(&self, status: OracleSourceStatus, severity: AuditSeverity) {
+        if let Some(log) = &self.audit_log {
+            let _ = log.log_external_api("WebSocketOracleSource", &format!("{}: {:?}", self.config.name, status), severity);
+        }
+        *self.status.lock().unwrap() = status;
+    }
+
+    /// The cached message, if it arrived within `cache_duration`
+    fn latest(&self) -> Option<Value> {
+        let cache = self.cache.lock().unwrap();
+        cache.get(&self.cache_key)
+            .filter(|cached| cached.timestamp.elapsed() < self.cache_duration)
+            .map(|cached| cached.value.clone())
+    }
+
+    /// Connect, subscribe, and cache messages until the connection drops (`Err`) or the source is dropped (`Ok`)
+    async fn session(&self, shutdown: &mut watch::Receiver<()>) -> Result<()> {
+        let mut request = self.config.url.as_str().into_client_request()?;
+        if let Some(auth) = &self.config.auth_header {
+            request.headers_mut().insert("Authorization", HeaderValue::from_str(auth)?);
+        }
+        let (mut socket, _) = tokio::time::timeout(self.config.timeout(), connect_async(request)).await
+            .map_err(|_| anyhow!("Connecting to {} timed out", self.config.url))??;
+
+        if let Some(subscription) = &self.config.body_template {
+            let text = match subscription {
+                Value::String(text) => text.clone(),
+                message => message.to_string(),
+            };
+            socket.send(Message::Text(text)).await?;
+        }
+        self.set_status(OracleSourceStatus::Operational, AuditSeverity::Info);
+
+        loop {
+            let message = tokio::select! {
+                message = socket.next() => message,
+                _ = shutdown.changed() => {
+                    let _ = socket.close(None).await;
+                    return Ok(());
+                }
+            };
+            match message {
+                Some(Ok(Message::Text(text))) => self.cache_message(&text),
+                Some(Ok(Message::Binary(bytes))) => match String::from_utf8(bytes) {
+                    Ok(text) => self.cache_message(&text),
+                    Err(_) => debug!(source = %self.config.name, "Ignoring non-UTF-8 binary message"),
+                },
+                Some(Ok(Message::Close(frame))) => return Err(anyhow!("Closed by server: {:?}", frame)),
+                // Pings are answered by tungstenite itself
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => return Err(anyhow!("Connection dropped")),
+            }
+        }
+    }
+
+    /// Cache the value at the configured path of `text`. Messages without it,
+    /// such as subscription acknowledgements, are skipped.
+    fn cache_message(&self, text: &str) {
+        let data = match serde_json::from_str::<Value>(text) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!(source = %self.config.name, "Ignoring non-JSON message: {}", e);
+                return;
+            }
+        };
+        let value = match extract_path_value(&data, &self.config.path) {
+            Some(value) => value,
+            None => {
+                debug!(source = %self.config.name, "Ignoring message without the configured path");
+                return;
+            }
+        };
+        if !self.config.required_fields.iter().all(|field| value.get(field).is_some()) {
+            warn!(source = %self.config.name, "Ignoring message missing required fields");
+            return;
+        }
+
+        self.cache.lock().unwrap().insert(self.cache_key.clone(), CachedData { value, timestamp: Instant::now() });
+        let count = *self.updates.borrow() + 1;
+        let _ = self.updates.send(count);
+    }
+}
+
+/// Keep a session open, reconnecting with backoff, until the source is dropped or the backoff gives up
+async fn maintain_stream(shared: Arc<StreamShared>) {
+    let mut shutdown = shared.shutdown.clone();
+    let mut backoff = shared.backoff.lock().unwrap().clone();
+    loop {
+        let received_before = *shared.updates.borrow();
+        if let Err(e) = shared.session(&mut shutdown).await {
+            shared.set_status(OracleSourceStatus::Degraded(format!("Reconnecting: {}", e)), AuditSeverity::Warning);
+        } else {
+            break;
+        }
+        // A connection that delivered messages worked, so the next failure starts the schedule over
+        if *shared.updates.borrow() != received_before {
+            backoff = shared.backoff.lock().unwrap().clone();
+        }
+        let delay = match backoff.next_delay() {
+            Some(delay) => delay,
+            None => {
+                let attempts = backoff.attempts();
+                shared.set_status(
+                    OracleSourceStatus::Failed(format!("Gave up reconnecting after {} attempts", attempts)),
+                    AuditSeverity::Error,
+                );
+                break;
+            }
+        };
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.changed() => break,
+        }
+    }
+    shared.streaming.store(false, Ordering::SeqCst);
+}
+
+/// Oracle source fed by a WebSocket subscription instead of polling.
+///
+/// A connection task keeps the latest message (at `config.path`) in the shared
+/// cache. `fetch` returns it while it is younger than `cache_duration`, waiting
+/// up to `config.timeout_ms` for one otherwise; its params are ignored, since
+/// the subscription fixes what the feed sends. The task is started by the first
+/// `fetch` or by `run_background_updates`, reconnects with backoff when the
+/// connection drops, and stops when the source is dropped.
+pub struct WebSocketOracleSource {
+    shared: Arc<StreamShared>,
+    validator: RuleValidator,
+    updates: watch::Receiver<u64>,
+    managed: AtomicBool,
+    /// Dropped with the source, which is the connection task's signal to stop
+    _shutdown: watch::Sender<()>,
+}
+
+impl WebSocketOracleSource {
+    pub fn new(
+        config: OracleSourceConfig,
+        audit_log: Option<Arc<SecurityAuditLog>>,
+        cache: Arc<Mutex<HashMap<String, CachedData>>>,
+        cache_duration: Duration,
+    ) -> Result<Self> {
+        if config.source_type != "WebSocket" {
+            return Err(anyhow!("Invalid source type for WebSocketOracleSource"));
+        }
+        if !config.url.starts_with("ws://") && !config.url.starts_with("wss://") {
+            return Err(anyhow!("WebSocket source '{}' needs a ws:// or wss:// URL, got {}", config.name, config.url));
+        }
+        let params = config.default_params.clone().unwrap_or_else(|| json!({}));
+        let cache_key = format!("{}:{}", config.name, canonical_json(&params));
+        let (updates_tx, updates) = watch::channel(0);
+        let (shutdown_tx, shutdown) = watch::channel(());
+
+        let shared = StreamShared {
+            config,
+            cache_key,
+            cache,
+            cache_duration,
+            status: Mutex::new(OracleSourceStatus::Degraded("Not connected yet".to_string())),
+            audit_log,
+            backoff: Mutex::new(default_stream_backoff()),
+            streaming: AtomicBool::new(false),
+            updates: updates_tx,
+            shutdown,
+        };
+        Ok(Self {
+            shared: Arc::new(shared),
+            validator: RuleValidator::default(),
+            updates,
+            managed: AtomicBool::new(false),
+            _shutdown: shutdown_tx,
+        })
+    }
+
+    /// Retry dropped connections on `backoff`'s schedule, giving up when it does.
+    /// A running connection task picks it up the next time its schedule restarts.
+    pub fn with_backoff(self, backoff: Backoff) -> Self {
+        *self.shared.backoff.lock().unwrap() = backoff;
+        self
+    }
+
+    /// Whether a connection task is running, connected or not
+    pub fn is_streaming(&self) -> bool {
+        self.shared.streaming.load(Ordering::SeqCst)
+    }
+
+    /// Start the connection task unless one is running
+    fn ensure_streaming(&self) {
+        if !self.shared.streaming.swap(true, Ordering::SeqCst) {
+            tokio::spawn(maintain_stream(self.shared.clone()));
+        }
+    }
+}
+
+#[async_trait]
+impl OracleSource for WebSocketOracleSource {
+    fn name(&self) -> &str {
+        &self.shared.config.name
+    }
+
+    fn config(&self) -> &OracleSourceConfig {
+        &self.shared.config
+    }
+
+    async fn fetch(&self, _params: &Value) -> Result<Value> {
+        self.ensure_streaming();
+        let deadline = tokio::time::Instant::now() + self.shared.config.timeout();
+        let mut updates = self.updates.clone();
+        loop {
+            updates.borrow_and_update();
+            if let Some(value) = self.shared.latest() {
+                return Ok(value);
+            }
+            match tokio::time::timeout_at(deadline, updates.changed()).await {
+                Ok(Ok(())) => continue,
+                _ => {
+                    return Err(anyhow!(
+                        "No message from {} within {}ms ({:?})",
+                        self.shared.config.name,
+                        self.shared.config.timeout_ms,
+                        self.status()
+                    ))
+                }
+            }
+        }
+    }
+
+    fn validate(&self, data: &Value) -> Vec<ValidationResult> {
+        self.validator.validate(&self.shared.config.validation_rules, data)
+    }
+
+    /// Connection health, degraded while connected when the latest message is older than `cache_duration`
+    fn status(&self) -> OracleSourceStatus {
+        let status = self.shared.status.lock().unwrap().clone();
+        if matches!(status, OracleSourceStatus::Operational) && self.shared.latest().is_none() {
+            return OracleSourceStatus::Degraded(format!(
+                "No message in the last {}s",
+                self.shared.cache_duration.as_secs()
+            ));
+        }
+        status
+    }
+
+    /// Run the connection task in place for a standalone source. Messages are
+    /// pushed, so `update_interval` only matters in that zero disables updates.
+    async fn run_background_updates(&self, update_interval: Duration) {
+        if update_interval.is_zero() || self.managed.load(Ordering::SeqCst) {
+            return;
+        }
+        if self.shared.streaming.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        maintain_stream(self.shared.clone()).await;
+    }
+
+    /// Managed sources connect on the manager's first fetch instead
+    fn set_managed(&self, managed: bool) {
+        self.managed.store(managed, Ordering::SeqCst);
+    }
+}
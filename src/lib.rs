@@ -11,6 +11,9 @@ pub mod security;
 pub mod tools;
 pub mod quorum;
 pub mod config;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
+pub mod utils;
 
 #[cfg(test)]
 pub mod tests;
@@ -14,23 +14,27 @@ mod metrics;
 mod quorum;
 mod security;
 mod sui;
+#[cfg(test)]
+mod testkit;
+#[cfg(test)]
 mod tests;
 mod tools;
 mod transaction;
+mod utils;
 
 // Use statements
 use crate::{ // Use crate:: prefix for local modules
     config::{load_submitter_keypair}, // Removed self import
-    demo::weather::run_weather_based_transaction_demo,
+    demo::{scenarios::{load_scenarios_or_builtin, run_scenarios, scenario_demo_name, DEFAULT_SCENARIOS_PATH}},
     examples::{enhanced_flight_insurance::run_enhanced_flight_insurance_demo, flight_delay::run_flight_delay_demo},
     execution::manager::ExecutionManager,
     external::oracle::create_weather_oracle,
     metrics::storage::MetricsStorage,
     quorum::simulation::QuorumSimulation,
-    security::{audit::{AuditSeverity, SecurityAuditLog, AuditEventType}, model::generate_security_documentation, verification::create_verification_framework}, // Added AuditEventType
-    sui::{byzantine::ByzantineDetector, cross_chain::create_chain_mapper, network::{NetworkManager, NetworkType}, verification::VerificationManager},
+    security::{alerts::{WebhookAlertSink, DEFAULT_WEBHOOK_QUEUE}, audit::{AuditRetention, AuditSeverity, SecurityAuditLog, AuditEventType}, model::generate_security_documentation, verification::create_verification_framework}, // Added AuditEventType
+    sui::{byzantine::ByzantineDetector, cross_chain::create_chain_mapper, events::{VerificationEventListener, VERIFICATION_EVENT}, network::{NetworkManager, NetworkType}, verification::VerificationManager},
     tools::benchmark_suite,
-    transaction::{handler::TransactionHandler, types::{Transaction, TransactionType}, utils::process_and_submit_verification},
+    transaction::{dead_letter::DeadLetterQueue, handler::TransactionHandler, types::Transaction, utils::replay_dead_letters},
 };
 use anyhow::{anyhow, Context, Result};
 use clap::{App, Arg};
@@ -54,10 +58,14 @@ use sui_sdk::{
     SuiClientBuilder,
 };
 
+/// Demos selectable with `--demo`, in the order they run by default.
+const DEMO_NAMES: [&str; 5] = ["js", "python", "weather", "flight-delay", "flight-insurance"];
+
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn Error>> {
+    utils::logging::init_tracing();
     // Parse command-line arguments using Clap
-    let matches = App::new("SUI Modular Middleware")
+    let app = App::new("SUI Modular Middleware")
         .version(env!("CARGO_PKG_VERSION")) // Use version from Cargo.toml
         .author("D. Lee <dongguk.lee@kcl.ac.uk>") // Replace with actual author info
         .about("A modular middleware framework for secure off-chain computation with Sui verification.")
@@ -77,6 +85,13 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
                 .long("setup-quorum")
                 .help("Set up the initial quorum configuration on the Testnet contract (requires AdminCap owner). Requires 10 nodes simulation."),
         )
+        .arg(
+            Arg::with_name("encrypt-keypair")
+                .long("encrypt-keypair")
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Encrypt the SUBMITTER_KEYPAIR_BASE64 key with a passphrase into FILE, for use as SUBMITTER_KEYFILE."),
+        )
         .arg(
             Arg::with_name("output-dir")
                 .long("output-dir")
@@ -84,30 +99,153 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
                 .default_value("benchmark_results_100_iter") // Default to the final results dir
                 .help("Directory to save benchmark results."),
         )
+        .arg(
+            Arg::with_name("assert-resilience")
+                .long("assert-resilience")
+                .takes_value(true)
+                .requires("benchmark")
+                .help("JSON file mapping Byzantine percentage to minimum success rate; exit non-zero if any is missed."),
+        )
+        .arg(
+            Arg::with_name("progress-json")
+                .long("progress-json")
+                .requires("benchmark")
+                .help("Also write one JSON progress event per benchmark iteration to stderr; other output stays on stdout."),
+        )
+        .arg(
+            Arg::with_name("force")
+                .long("force")
+                .requires("benchmark")
+                .help("Re-run every benchmark scenario instead of resuming from results in --output-dir."),
+        )
+        .arg(
+            Arg::with_name("sla")
+                .long("sla")
+                .takes_value(true)
+                .value_name("BUDGETS")
+                .requires("benchmark")
+                .help("End-to-end latency budgets in ms, e.g. total_iteration_p95=2000,l1_confirmation_p99=3000; exit non-zero if any is exceeded."),
+        )
+        .arg(
+            Arg::with_name("submit-below-threshold")
+                .long("submit-below-threshold")
+                .requires("benchmark")
+                .help("Submit to L1 even when fewer signatures than the quorum threshold were collected, recording whether the contract rejects them."),
+        )
         .arg(
             Arg::with_name("network")
                 .long("network")
                 .takes_value(true)
-                .possible_values(&["testnet", "devnet", "local"]) // Add mainnet later if needed
                 .default_value("testnet")
-                .help("Specify the Sui network to connect to (testnet, devnet, local)."),
+                .validator(|value| NetworkType::from_str(&value).map(|_| ()).map_err(|e| e.to_string()))
+                .help("Sui network to connect to: testnet, devnet, mainnet, local, or the http(s):// URL of a fullnode."),
+        )
+        .arg(
+            Arg::with_name("demo")
+                .long("demo")
+                .takes_value(true)
+                .possible_values(&DEMO_NAMES)
+                .help("Run only the named demo instead of all of them."),
+        )
+        .arg(
+            Arg::with_name("dead-letter-log")
+                .long("dead-letter-log")
+                .takes_value(true)
+                .help("JSONL file that permanently failed transactions are appended to."),
+        )
+        .arg(
+            Arg::with_name("trace-dir")
+                .long("trace-dir")
+                .takes_value(true)
+                .help("Directory each finished transaction's Chrome trace is saved to."),
+        )
+        .arg(
+            Arg::with_name("replay-dead-letters")
+                .long("replay-dead-letters")
+                .requires("dead-letter-log")
+                .help("Resubmit the transactions in --dead-letter-log instead of running the demos."),
+        )
+        .arg(
+            Arg::with_name("scenarios")
+                .long("scenarios")
+                .takes_value(true)
+                .default_value(DEFAULT_SCENARIOS_PATH)
+                .help("JSON file of demo transactions to run (the js, python and weather demos live here; the built-in copy is used if it is missing)."),
+        );
+    #[cfg(feature = "server")]
+    let app = app
+        .arg(
+            Arg::with_name("serve")
+                .long("serve")
+                .help("Run the WebSocket transaction ingestion server instead of the demos."),
+        )
+        .arg(
+            Arg::with_name("listen")
+                .long("listen")
+                .takes_value(true)
+                .default_value("127.0.0.1:9001")
+                .help("Address for the ingestion server to bind to (used with --serve)."),
+        )
+        .arg(
+            Arg::with_name("serve-http")
+                .long("serve-http")
+                .help("Run the REST transaction API instead of the demos (can be combined with --serve)."),
+        )
+        .arg(
+            Arg::with_name("http-listen")
+                .long("http-listen")
+                .takes_value(true)
+                .default_value("127.0.0.1:8080")
+                .help("Address for the REST API to bind to (used with --serve-http)."),
+        )
+        .arg(
+            Arg::with_name("max-in-flight")
+                .long("max-in-flight")
+                .takes_value(true)
+                .validator(|value| execution::gateway::parse_max_in_flight(&value).map(|_| ()))
+                .help("Maximum number of transactions the servers process concurrently (default: unbounded)."),
         )
-        .get_matches();
+        .arg(
+            Arg::with_name("reject-when-busy")
+                .long("reject-when-busy")
+                .requires("max-in-flight")
+                .help("Reject submissions over --max-in-flight instead of queueing them."),
+        );
+    let matches = app.get_matches();
 
     let output_dir = matches.value_of("output-dir").unwrap(); // Clap ensures default
     let network_arg = matches.value_of("network").unwrap();
+    // Clap has already validated it
+    let network_type = NetworkType::from_str(network_arg)?;
+    let selected_demo = matches.value_of("demo");
+    let should_run = |name: &str| selected_demo.map_or(true, |demo| demo == name);
 
     println!("--- SUI Modular Middleware --- Version: {} ---", env!("CARGO_PKG_VERSION"));
 
-    // Handle special commands first (verify, setup)
+    // Handle special commands first (encrypt, verify, setup)
+    if let Some(keyfile_path) = matches.value_of("encrypt-keypair") {
+        let base64_keypair = std::env::var("SUBMITTER_KEYPAIR_BASE64")
+            .map_err(|_| anyhow!("--encrypt-keypair reads the key from SUBMITTER_KEYPAIR_BASE64, which is not set"))?;
+        // Refuse to encrypt something that won't load later
+        let keypair = config::decode_submitter_keypair(&base64_keypair, None)?;
+        let passphrase = config::keyfile_passphrase("New keyfile passphrase: ")?;
+        if passphrase.is_empty() {
+            return Err(anyhow!("Refusing to encrypt the keypair with an empty passphrase").into());
+        }
+        if std::env::var("KEYPAIR_PASSPHRASE").is_err()
+            && rpassword::prompt_password("Confirm passphrase: ")? != passphrase
+        {
+            return Err(anyhow!("Passphrases do not match").into());
+        }
+        config::write_keyfile(keyfile_path, &config::encrypt_keypair(&base64_keypair, &passphrase)?)?;
+        println!("✅ Encrypted keypair for {} written to {}", SuiAddress::from(&keypair.public()), keyfile_path);
+        println!("   Set SUBMITTER_KEYFILE={} and unset SUBMITTER_KEYPAIR_BASE64.", keyfile_path);
+        return Ok(());
+    }
+
     if matches.is_present("verify-contract-objects") {
         println!("Verifying essential contract objects on {}...", network_arg);
-        let rpc_url = match network_arg {
-            "testnet" => config::SUI_TESTNET_RPC,
-            // Add URLs for devnet/local if needed, or use a NetworkManager approach
-            _ => return Err(anyhow!("Network '{}' RPC URL not configured for verification.", network_arg).into()),
-        };
-        let client = SuiClientBuilder::default().build(rpc_url).await?;
+        let client = SuiClientBuilder::default().build(network_type.get_rpc_url()).await?;
         // Call a hypothetical verification function (needs implementation if TransactionHandler one removed)
         match verify_contract_setup(&client).await {
             Ok(_) => println!("✅ Contract objects verified successfully on {}!", network_arg),
@@ -122,11 +260,7 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
 
     if matches.is_present("setup-quorum") {
          println!("Attempting to set up quorum configuration on {}...", network_arg);
-         let rpc_url = match network_arg {
-            "testnet" => config::SUI_TESTNET_RPC,
-            _ => return Err(anyhow!("Network '{}' RPC URL not configured for quorum setup.", network_arg).into()),
-         };
-         let sui_client = SuiClientBuilder::default().build(rpc_url).await?;
+         let sui_client = SuiClientBuilder::default().build(network_type.get_rpc_url()).await?;
 
          // Quorum setup requires interaction; consider moving this to a dedicated tool/script
          // or carefully implementing it here.
@@ -147,7 +281,21 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
             network_arg, output_dir
         );
         // Pass network info if benchmarks need it, otherwise assume testnet focus
-        return benchmark_suite::run_comprehensive_benchmarks(output_dir).await;
+        let progress_json = matches.is_present("progress-json");
+        let result = benchmark_suite::run_comprehensive_benchmarks(
+            output_dir,
+            matches.value_of("assert-resilience"),
+            progress_json,
+            matches.is_present("force"),
+            matches.value_of("sla"),
+            matches.is_present("submit-below-threshold"),
+        ).await;
+        // Returning the error would print it to stderr, which holds only progress events
+        if let (true, Err(e)) = (progress_json, &result) {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return result;
     }
 
     // --- Default Execution: Run Demos --- 
@@ -158,30 +306,45 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
     dotenv::dotenv().ok(); // Load .env file if present
 
     // Initialize shared components
-    let security_audit_log = Arc::new(SecurityAuditLog::new());
-    let network_type = match network_arg {
-        "devnet" => NetworkType::Devnet,
-        "local" => NetworkType::Local,
-        _ => NetworkType::Testnet, // Default to testnet
-    };
-
+    let mut security_audit_log = SecurityAuditLog::new().with_retention(AuditRetention::from_env()?);
+    // Keep a durable JSON-lines audit trail when a path is configured
+    if let Ok(audit_file) = env::var("AUDIT_LOG_FILE") {
+        security_audit_log = match env::var("AUDIT_LOG_MAX_BYTES") {
+            Ok(max_bytes) => {
+                let max_bytes = max_bytes.parse()
+                    .map_err(|_| anyhow!("AUDIT_LOG_MAX_BYTES must be a positive integer, got '{}'", max_bytes))?;
+                security_audit_log.with_rotating_file(&audit_file, max_bytes)?
+            }
+            Err(_) => security_audit_log.with_file(&audit_file)?,
+        };
+        println!("Audit events will be appended to {}", audit_file);
+    }
+    let security_audit_log = Arc::new(security_audit_log);
+    // Page on Error and Critical events when a webhook is configured
+    if let Ok(webhook_url) = env::var("AUDIT_ALERT_WEBHOOK_URL") {
+        let sink = WebhookAlertSink::new(&webhook_url, DEFAULT_WEBHOOK_QUEUE)?;
+        security_audit_log.add_alert_sink(Arc::new(sink), AuditSeverity::Error);
+        println!("Audit alerts for Error and above will be posted to {}", webhook_url);
+    }
     println!("Initializing components for network: {:?}...", network_type);
     let network_manager = Arc::new(NetworkManager::new(network_type.clone()).await?);
     let rpc_url = network_manager.get_active_rpc_url()?;
     let verification_manager = VerificationManager::new(&rpc_url);
     let byzantine_detector = Arc::new(ByzantineDetector::new(
-        network_manager.get_active_config().get_rpc_endpoints().clone(),
+        network_manager.cross_verification_endpoints().await,
         Some(security_audit_log.clone()),
         None,
         None,
-    ));
+        None,
+        None,
+    )?);
     // Unused variable warnings suppressed with `_`
     let _chain_mapper = create_chain_mapper(network_manager.clone(), Some(security_audit_log.clone()))?;
-    let _weather_oracle = create_weather_oracle(
+    let weather_oracle = Arc::new(create_weather_oracle(
         Some(security_audit_log.clone()),
         Some(Duration::from_secs(300)), // Cache duration
         Some(Duration::from_secs(60)), // Update interval
-    )?;
+    )?);
     let _verification_framework = create_verification_framework(Some(security_audit_log.clone()));
 
     security_audit_log.log_network(
@@ -208,6 +371,21 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
 
     // Initialize core components
     let sui_client = Arc::new(SuiClientBuilder::default().build(&rpc_url).await?);
+    // Confirm each submission independently through the contract's VerificationSuccess events
+    let event_listener = Arc::new(VerificationEventListener::for_verification_contract(sui_client.clone())?);
+    let mut confirmations = event_listener.subscribe();
+    let confirmation_log = security_audit_log.clone();
+    tokio::spawn(async move {
+        while let Some(confirmation) = confirmations.recv().await {
+            let matched = if confirmation.payload.is_some() { "tracked submission" } else { "untracked submission" };
+            let _ = confirmation_log.log_verification(
+                "VerificationEventListener",
+                &format!("{} event in {} confirms a {}", VERIFICATION_EVENT, confirmation.tx_digest, matched),
+                None,
+                AuditSeverity::Info,
+            );
+        }
+    });
     let transaction_handler = Arc::new(
         TransactionHandler::new(
             load_submitter_keypair().context("Failed to load keypair for TransactionHandler")?, // Load fresh keypair
@@ -217,19 +395,97 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
             quorum_sim.clone(),
             sui_client.clone(),
         )
-        .await?,
+        .await?
+        .with_event_listener(event_listener),
     );
-    let execution_manager = Arc::new(ExecutionManager::new(
+    let execution_manager = ExecutionManager::new(
         Some(verification_manager.clone()), // Pass clone of VM
         Some(network_manager.clone()),
         Some(security_audit_log.clone()),
-    ));
+    );
+    let execution_manager = match matches.value_of("dead-letter-log") {
+        Some(path) => execution_manager.with_dead_letter_queue(Arc::new(DeadLetterQueue::new(path))),
+        None => execution_manager,
+    };
+    let execution_manager = match matches.value_of("trace-dir") {
+        Some(dir) => {
+            fs::create_dir_all(dir).map_err(|e| anyhow!("Failed to create trace directory {}: {}", dir, e))?;
+            execution_manager.with_trace_dir(dir)
+        }
+        None => execution_manager,
+    };
+    #[cfg(feature = "server")]
+    let execution_manager = match matches.value_of("max-in-flight") {
+        Some(value) => {
+            // Clap has already validated it
+            let max_in_flight = execution::gateway::parse_max_in_flight(value).map_err(|e| anyhow!(e))?;
+            let policy = if matches.is_present("reject-when-busy") {
+                execution::gateway::OverloadPolicy::Reject
+            } else {
+                execution::gateway::OverloadPolicy::Wait
+            };
+            execution_manager.with_submission_limit(max_in_flight, policy)
+        }
+        None => execution_manager,
+    };
+    let execution_manager = Arc::new(execution_manager);
+    // Node health is cached for a minute, so re-assessing this often is cheap
+    let _degradation_monitor = execution_manager.spawn_degradation_monitor(Duration::from_secs(30));
+    #[cfg(feature = "server")]
+    let http_task = if matches.is_present("serve-http") {
+        let state = transaction::http_api::ApiState::new(
+            transaction_handler.clone(),
+            execution_manager.clone(),
+            security_audit_log.clone(),
+            network_manager.clone(),
+        )
+        .with_oracle_manager(weather_oracle.clone());
+        let bind_addr = matches.value_of("http-listen").unwrap().to_string(); // Clap ensures default
+        Some(tokio::spawn(async move { transaction::http_api::serve(&bind_addr, state).await }))
+    } else {
+        None
+    };
+
+    #[cfg(feature = "server")]
+    if matches.is_present("serve") {
+        let server = transaction::ingest::IngestServer::new(
+            matches.value_of("listen").unwrap(), // Clap ensures default
+            transaction_handler.clone(),
+            execution_manager.clone(),
+            security_audit_log.clone(),
+        );
+        // The accept loop blocks, so keep it off the async worker threads
+        tokio::task::spawn_blocking(move || server.run()).await??;
+        return Ok(());
+    }
+
+    #[cfg(feature = "server")]
+    if let Some(task) = http_task {
+        task.await??;
+        return Ok(());
+    }
+
+    if matches.is_present("replay-dead-letters") {
+        replay_dead_letters(
+            &transaction_handler,
+            &execution_manager,
+            Some(&metrics_storage),
+            &security_audit_log,
+            &load_submitter_keypair().context("Failed to load keypair for dead-letter replay")?,
+            &gas_object_id,
+        ).await?;
+        return Ok(());
+    }
+
     // Unused fallback manager
     // let _fallback_manager = Arc::new(FallbackManager::new());
     // Unused sequencing layer
     // let _sequencing_layer = Arc::new(SequencingLayer::new());
 
-    println!("\n--- Running Middleware Demos ---");
+    match selected_demo {
+        Some(demo) => println!("\n--- Running Middleware Demo: {} ---", demo),
+        None => println!("\n--- Running Middleware Demos ---"),
+    }
     println!("(These demos showcase different transaction types and execution paths)");
 
     // Generate and save security documentation (can be moved elsewhere)
@@ -245,113 +501,56 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
     // Note: The process_and_submit_verification utility now orchestrates the flow.
     // It needs the submitter keypair and gas object ID.
 
-    // JS Demo
-    let js_script = r#"({"shouldExecute": true, "outcome": "js_ok"})"#; // Use raw string literal
-    let js_txn = Transaction {
-        tx_type: TransactionType::Custom("js_demo".to_string()),
-        sender: submitter_address.to_string(),
-        receiver: submitter_address.to_string(),
-        amount: 0,
-        gas_payment: gas_object_id.to_string(),
-        gas_budget: 100_000_000, // Use consistent high budget
-        commands: vec![],
-        signatures: None,
-        timestamp: 0, // Timestamp handled by Transaction::new or digest
-        script: Some(js_script.to_string()),
-        language: Some("javascript".to_string()),
-        external_query: None,
-        python_code: None,
-        python_params: None,
-        websocket_endpoint: None,
-        websocket_message: None,
-        time_condition: None,
-    };
-    if let Err(e) = process_and_submit_verification(
-        &js_txn,
-        "JavaScript Demo",
-        &transaction_handler,
-        &execution_manager,
-        Some(&metrics_storage),
-        &security_audit_log,
-        &load_submitter_keypair().context("Failed to load keypair for JS Demo")?,
-        &gas_object_id,
-    ).await {
-        eprintln!("ERROR in JavaScript Demo: {:#}", e);
+    // JS, Python and weather demos, plus any user-defined scenarios, come from the scenario file
+    if should_run("js") || should_run("python") || should_run("weather") {
+        let scenarios_path = matches.value_of("scenarios").unwrap(); // Clap ensures default
+        let scenarios: Vec<Transaction> = load_scenarios_or_builtin(scenarios_path)?
+            .into_iter()
+            .filter(|tx| should_run(&scenario_demo_name(tx)))
+            .collect();
+        println!("Loaded {} demo scenario(s) from {}", scenarios.len(), scenarios_path);
+        run_scenarios(
+            &scenarios,
+            &transaction_handler,
+            &execution_manager,
+            Some(&metrics_storage),
+            &security_audit_log,
+            &load_submitter_keypair().context("Failed to load keypair for scenario demos")?,
+            &gas_object_id,
+        ).await;
     }
 
-    // Python Demo
-    let python_script = r#"result = {"should_execute": True, "outcome": "python_ok"}"#;
-    let python_txn = Transaction {
-        tx_type: TransactionType::Custom("python_demo".to_string()),
-        sender: submitter_address.to_string(),
-        receiver: submitter_address.to_string(),
-        amount: 0,
-        gas_payment: gas_object_id.to_string(),
-        gas_budget: 100_000_000,
-        commands: vec![],
-        signatures: None,
-        timestamp: 0,
-        script: None,
-        language: Some("python".to_string()),
-        python_code: Some(python_script.to_string()),
-        python_params: None,
-        external_query: None,
-        websocket_endpoint: None,
-        websocket_message: None,
-        time_condition: None,
-    };
-     if let Err(e) = process_and_submit_verification(
-        &python_txn,
-        "Python Demo",
-        &transaction_handler,
-        &execution_manager,
-        Some(&metrics_storage),
-        &security_audit_log,
-        &load_submitter_keypair().context("Failed to load keypair for Python Demo")?,
-        &gas_object_id,
-    ).await {
-         eprintln!("ERROR in Python Demo: {:#}", e);
-     }
-
-    // Weather Demo
-     if let Err(e) = run_weather_based_transaction_demo(
-        &transaction_handler,
-        &execution_manager,
-        Some(&metrics_storage),
-        &security_audit_log,
-        &load_submitter_keypair().context("Failed to load keypair for Weather Demo")?,
-        &gas_object_id,
-    ).await {
-         eprintln!("ERROR in Weather Demo: {:#}", e);
-     }
-
     // Flight Delay Demo
-    if let Err(e) = run_flight_delay_demo(
-        &transaction_handler,
-        &execution_manager,
-        Some(&metrics_storage),
-        &security_audit_log,
-        &load_submitter_keypair().context("Failed to load keypair for Flight Delay Demo")?,
-        &gas_object_id,
-    ).await {
-        eprintln!("ERROR in Flight Delay Demo: {:#}", e);
+    if should_run("flight-delay") {
+        if let Err(e) = run_flight_delay_demo(
+            &transaction_handler,
+            &execution_manager,
+            Some(&metrics_storage),
+            &security_audit_log,
+            &load_submitter_keypair().context("Failed to load keypair for Flight Delay Demo")?,
+            &gas_object_id,
+        ).await {
+            eprintln!("ERROR in Flight Delay Demo: {:#}", e);
+        }
     }
 
     // Enhanced Flight Insurance Demo
-    // Needs Arc<VerificationManager> and Arc<NetworkManager>
-    let vm_arc = Arc::new(verification_manager); // Create Arc for this call
-    let nm_arc = network_manager; // Already an Arc
-    if let Err(e) = run_enhanced_flight_insurance_demo(
-        &transaction_handler,
-        &execution_manager,
-        Some(&metrics_storage),
-        &security_audit_log,
-        &vm_arc, // Pass Arc
-        &nm_arc, // Pass Arc
-        &load_submitter_keypair().context("Failed to load keypair for Flight Insurance Demo")?,
-        &gas_object_id,
-    ).await {
-        eprintln!("ERROR in Enhanced Flight Insurance Demo: {:#}", e);
+    if should_run("flight-insurance") {
+        // Needs Arc<VerificationManager> and Arc<NetworkManager>
+        let vm_arc = Arc::new(verification_manager); // Create Arc for this call
+        let nm_arc = network_manager; // Already an Arc
+        if let Err(e) = run_enhanced_flight_insurance_demo(
+            &transaction_handler,
+            &execution_manager,
+            Some(&metrics_storage),
+            &security_audit_log,
+            &vm_arc, // Pass Arc
+            &nm_arc, // Pass Arc
+            &load_submitter_keypair().context("Failed to load keypair for Flight Insurance Demo")?,
+            &gas_object_id,
+        ).await {
+            eprintln!("ERROR in Enhanced Flight Insurance Demo: {:#}", e);
+        }
     }
 
     // --- Deprecated Demo Calls --- 
@@ -1,2 +1,4 @@
 pub mod performance;
-pub mod storage;
\ No newline at end of file
+pub mod prometheus;
+pub mod storage;
+pub mod trace;
\ No newline at end of file
@@ -1,7 +1,8 @@
 /// Performance measurement structures and utilities.
 
 use serde::{Deserialize, Serialize, Serializer, Deserializer};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
 use std::time::{Duration, Instant, SystemTime};
 
 // Removed unused Instant serialization/deserialization helpers and struct
@@ -183,8 +184,31 @@ pub struct ComponentBenchmark {
     /// Stores statistics (count, sum, etc.) for specific named operations within the benchmark.
     pub operation_stats: HashMap<String, OperationStats>,
     // Removed redundant operation_timings and operation_counts, consolidated into OperationStats
+    /// Executions per operation already returned by `snapshot`.
+    #[serde(skip)]
+    snapshot_marks: HashMap<String, usize>,
+}
+
+/// Operations whose time is attributed to the middleware itself.
+pub const MIDDLEWARE_OPERATIONS: [&str; 2] = ["middleware_processing_and_prep", "quorum_signing"];
+/// Operations whose time is attributed to the Sui L1.
+pub const L1_OPERATIONS: [&str; 2] = ["l1_submission", "l1_confirmation"];
+
+/// Split of recorded time between middleware work and L1 work.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MiddlewareOverhead {
+    /// Total time spent in `MIDDLEWARE_OPERATIONS` (in milliseconds).
+    pub middleware_ms: u64,
+    /// Total time spent in `L1_OPERATIONS` (in milliseconds).
+    pub l1_ms: u64,
+    /// Share of the combined time attributable to the middleware (0-100).
+    pub middleware_percent: f64,
 }
 
+/// Durations `OperationStats` keeps per operation for percentiles; the oldest
+/// are dropped first, so long runs report percentiles of their latest executions.
+pub const MAX_SAMPLES: usize = 10_000;
+
 /// Stores statistics for a specific timed operation within a benchmark.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct OperationStats {
@@ -196,9 +220,69 @@ pub struct OperationStats {
     pub min_duration_ms: u64,
     /// Maximum duration recorded (in milliseconds).
     pub max_duration_ms: u64,
+    /// The latest `MAX_SAMPLES` durations in recording order (in milliseconds), for percentiles.
+    #[serde(default)]
+    pub samples_ms: VecDeque<u64>,
 }
 
 impl OperationStats {
+    /// Adds one execution taking `duration_ms`.
+    pub fn record(&mut self, duration_ms: u64) {
+        self.count += 1;
+        self.total_duration_ms += duration_ms;
+        if self.count == 1 {
+            self.min_duration_ms = duration_ms;
+            self.max_duration_ms = duration_ms;
+        } else {
+            self.min_duration_ms = self.min_duration_ms.min(duration_ms);
+            self.max_duration_ms = self.max_duration_ms.max(duration_ms);
+        }
+        self.samples_ms.push_back(duration_ms);
+        self.trim_samples();
+    }
+
+    fn trim_samples(&mut self) {
+        let excess = self.samples_ms.len().saturating_sub(MAX_SAMPLES);
+        self.samples_ms.drain(..excess);
+    }
+
+    /// Folds `other`'s executions into these stats.
+    pub fn merge(&mut self, other: &OperationStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.min_duration_ms = other.min_duration_ms;
+            self.max_duration_ms = other.max_duration_ms;
+        } else {
+            self.min_duration_ms = self.min_duration_ms.min(other.min_duration_ms);
+            self.max_duration_ms = self.max_duration_ms.max(other.max_duration_ms);
+        }
+        self.count += other.count;
+        self.total_duration_ms += other.total_duration_ms;
+        self.samples_ms.extend(other.samples_ms.iter().copied());
+        self.trim_samples();
+    }
+
+    /// Nearest-rank percentile (0-100] of the recorded durations, `None` without samples.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        if self.samples_ms.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.samples_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    pub fn p95(&self) -> Option<u64> {
+        self.percentile(95.0)
+    }
+
+    pub fn p99(&self) -> Option<u64> {
+        self.percentile(99.0)
+    }
+
     /// Calculates the average duration for this operation.
     pub fn average_duration_ms(&self) -> f64 {
         if self.count == 0 {
@@ -221,6 +305,7 @@ impl ComponentBenchmark {
             total_duration_ms: None, // Calculated on end()
             configuration: HashMap::new(),
             operation_stats: HashMap::new(),
+            snapshot_marks: HashMap::new(),
         }
     }
 
@@ -233,16 +318,7 @@ impl ComponentBenchmark {
     /// Records the duration of a specific operation within the benchmark.
     /// Updates count, total, min, and max statistics for the operation.
     pub fn record_operation(&mut self, operation: &str, duration_ms: u64) -> &mut Self {
-        let stats = self.operation_stats.entry(operation.to_string()).or_default();
-        stats.count += 1;
-        stats.total_duration_ms += duration_ms;
-        if stats.count == 1 {
-            stats.min_duration_ms = duration_ms;
-            stats.max_duration_ms = duration_ms;
-        } else {
-            stats.min_duration_ms = stats.min_duration_ms.min(duration_ms);
-            stats.max_duration_ms = stats.max_duration_ms.max(duration_ms);
-        }
+        self.operation_stats.entry(operation.to_string()).or_default().record(duration_ms);
         self
     }
 
@@ -283,8 +359,51 @@ impl ComponentBenchmark {
         self.operation_stats.get(operation)
     }
 
+    /// Statistics of the operations recorded since the previous snapshot (or the start).
+    ///
+    /// `operation_stats` stays cumulative; operations with nothing new are left out.
+    /// An interval with more than `MAX_SAMPLES` executions only covers the latest of them.
+    pub fn snapshot(&mut self) -> HashMap<String, OperationStats> {
+        let mut delta = HashMap::new();
+        for (name, stats) in &self.operation_stats {
+            let mark = self.snapshot_marks.entry(name.clone()).or_insert(0);
+            let new_count = (stats.count as usize).saturating_sub(*mark).min(stats.samples_ms.len());
+            if new_count > 0 {
+                let mut interval = OperationStats::default();
+                for duration_ms in stats.samples_ms.iter().skip(stats.samples_ms.len() - new_count) {
+                    interval.record(*duration_ms);
+                }
+                delta.insert(name.clone(), interval);
+            }
+            *mark = stats.count as usize;
+        }
+        delta
+    }
+
     // Removed avg_operation_time and median_operation_time, use get_operation_stats().average_duration_ms()
 
+    /// Computes how much of the recorded time was spent in the middleware versus on L1.
+    /// Returns `None` if none of the relevant operations were recorded.
+    pub fn middleware_overhead(&self) -> Option<MiddlewareOverhead> {
+        let sum_ops = |ops: &[&str]| -> u64 {
+            ops.iter()
+                .filter_map(|op| self.operation_stats.get(*op))
+                .map(|stats| stats.total_duration_ms)
+                .sum()
+        };
+        let middleware_ms = sum_ops(&MIDDLEWARE_OPERATIONS);
+        let l1_ms = sum_ops(&L1_OPERATIONS);
+        let combined = middleware_ms + l1_ms;
+        if combined == 0 {
+            return None;
+        }
+        Some(MiddlewareOverhead {
+            middleware_ms,
+            l1_ms,
+            middleware_percent: middleware_ms as f64 / combined as f64 * 100.0,
+        })
+    }
+
     /// Converts the benchmark results into a serializable JSON value.
     pub fn to_json(&self) -> serde_json::Value {
         // Ensure end() has been called to calculate duration
@@ -312,6 +431,7 @@ impl ComponentBenchmark {
             "avg_duration_per_iteration_ms": avg_iter_duration,
             "configuration": self.configuration,
             "operation_stats": operation_summary, // Use the calculated summary map
+            "middleware_overhead": self.middleware_overhead(),
             // Removed deprecated fields like operation_counts, operation_avg_times, operation_median_times
         })
     }
@@ -351,6 +471,13 @@ impl ComponentBenchmark {
             }
         }
 
+        if let Some(overhead) = self.middleware_overhead() {
+            println!("\nMiddleware Overhead:");
+            println!("  Middleware Time: {} ms", overhead.middleware_ms);
+            println!("  L1 Time:         {} ms", overhead.l1_ms);
+            println!("  Overhead:        {:.2}% of middleware + L1 time", overhead.middleware_percent);
+        }
+
         if !self.configuration.is_empty() {
             println!("\nConfiguration:");
              // Sort config keys for consistent output
@@ -366,5 +493,115 @@ impl ComponentBenchmark {
     }
 }
 
+// --- Windowed Stats ---
+
+/// Default width of one `WindowedStats` bucket.
+pub const DEFAULT_BUCKET_WIDTH: Duration = Duration::from_secs(1);
+/// Default number of buckets: one minute of history at the default width.
+pub const DEFAULT_BUCKET_COUNT: usize = 60;
+
+#[derive(Debug, Default)]
+struct WindowBucket {
+    /// Bucket-width intervals since the origin that this bucket currently holds
+    tick: u64,
+    stats: HashMap<String, OperationStats>,
+}
+
+/// Operation statistics over a sliding time window, for long-running processes.
+///
+/// Durations go into a ring of fixed-width buckets; a bucket is cleared when
+/// the ring wraps around to it. Queries merge the buckets inside the requested
+/// window, so they describe the last `window` (to bucket precision), never
+/// more than `capacity()`.
+#[derive(Debug)]
+pub struct WindowedStats {
+    origin: Instant,
+    bucket_width: Duration,
+    buckets: Mutex<Vec<WindowBucket>>,
+}
+
+impl Default for WindowedStats {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUCKET_WIDTH, DEFAULT_BUCKET_COUNT)
+    }
+}
+
+impl WindowedStats {
+    pub fn new(bucket_width: Duration, bucket_count: usize) -> Self {
+        Self {
+            origin: Instant::now(),
+            bucket_width: bucket_width.max(Duration::from_millis(1)),
+            buckets: Mutex::new((0..bucket_count.max(1)).map(|_| WindowBucket::default()).collect()),
+        }
+    }
+
+    /// Longest window the ring can answer for.
+    pub fn capacity(&self) -> Duration {
+        self.bucket_width * self.buckets.lock().unwrap().len() as u32
+    }
+
+    fn tick(&self, at: Instant) -> u64 {
+        (at.saturating_duration_since(self.origin).as_nanos() / self.bucket_width.as_nanos()) as u64
+    }
+
+    /// Adds one execution of `operation` taking `duration_ms`, now.
+    pub fn record(&self, operation: &str, duration_ms: u64) {
+        self.record_at(operation, duration_ms, Instant::now());
+    }
+
+    /// Adds one execution finished at `at`. Executions older than the ring are dropped.
+    pub fn record_at(&self, operation: &str, duration_ms: u64, at: Instant) {
+        let tick = self.tick(at);
+        let mut buckets = self.buckets.lock().unwrap();
+        let slot = (tick % buckets.len() as u64) as usize;
+        let bucket = &mut buckets[slot];
+        if tick < bucket.tick {
+            return;
+        }
+        if tick > bucket.tick {
+            bucket.tick = tick;
+            bucket.stats.clear();
+        }
+        bucket.stats.entry(operation.to_string()).or_default().record(duration_ms);
+    }
+
+    /// Stats per operation over the last `window`.
+    pub fn stats(&self, window: Duration) -> HashMap<String, OperationStats> {
+        self.stats_at(window, Instant::now())
+    }
+
+    /// Stats per operation over the `window` ending at `now`.
+    pub fn stats_at(&self, window: Duration, now: Instant) -> HashMap<String, OperationStats> {
+        let now_tick = self.tick(now);
+        let buckets = self.buckets.lock().unwrap();
+        // The current, partly filled bucket counts as one
+        let span = window.as_nanos().div_ceil(self.bucket_width.as_nanos()).clamp(1, buckets.len() as u128) as u64;
+        let oldest = now_tick.saturating_sub(span - 1);
+
+        let mut merged: HashMap<String, OperationStats> = HashMap::new();
+        for bucket in buckets.iter().filter(|bucket| bucket.tick >= oldest && bucket.tick <= now_tick) {
+            for (operation, stats) in &bucket.stats {
+                merged.entry(operation.clone()).or_default().merge(stats);
+            }
+        }
+        merged
+    }
+
+    /// Executions of `operation` per second over the last `window`.
+    pub fn rate(&self, operation: &str, window: Duration) -> f64 {
+        self.rate_at(operation, window, Instant::now())
+    }
+
+    /// Executions of `operation` per second over the `window` ending at `now`.
+    pub fn rate_at(&self, operation: &str, window: Duration, now: Instant) -> f64 {
+        let window = window.min(self.capacity());
+        if window.is_zero() {
+            return 0.0;
+        }
+        let count = self.stats_at(window, now).get(operation).map_or(0, |stats| stats.count);
+        count as f64 / window.as_secs_f64()
+    }
+}
+
 // Removed old wrapper functions like benchmark_transaction_verification etc.
 // Benchmarks should be created directly using ComponentBenchmark::new().
\ No newline at end of file
@@ -0,0 +1,89 @@
+//! Prometheus text exposition of middleware metrics.
+//!
+//! Rendering only; serving is up to the caller (the HTTP API exposes it at
+//! `GET /metrics`). Counters are cumulative since the process started;
+//! operation gauges describe only a recent window, so they show current load.
+
+use crate::external::oracle::ConsensusMetrics;
+use crate::metrics::performance::WindowedStats;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Content type of the text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Oracle consensus counters, each metric name prefixed with `oracle_`.
+pub fn render_consensus_metrics(metrics: &ConsensusMetrics) -> String {
+    let mut out = String::new();
+    counter(&mut out, "oracle_consensus_reached_total", "Consensus rounds that produced a value", metrics.consensus_reached);
+    counter(&mut out, "oracle_consensus_failed_total", "Consensus rounds that failed", metrics.consensus_failed);
+    counter(&mut out, "oracle_outliers_rejected_total", "Numerical source values rejected as outliers", metrics.outliers_rejected);
+    counter(&mut out, "oracle_sources_queried_total", "Source fetches made by consensus rounds", metrics.sources_queried);
+    counter(&mut out, "oracle_sources_skipped_total", "Sources a cost-optimized round did not need to query", metrics.sources_skipped);
+    labeled_counter(
+        &mut out,
+        "oracle_source_outliers_total",
+        "Times a source's value was rejected as an outlier",
+        &metrics.per_source_outlier_counts,
+    );
+    labeled_counter(
+        &mut out,
+        "oracle_source_numeric_rounds_total",
+        "Numerical consensus rounds a source contributed to",
+        &metrics.per_source_numeric_rounds,
+    );
+    out
+}
+
+/// Per-operation throughput and latency over the last `window`, as gauges.
+pub fn render_operation_window(stats: &WindowedStats, window: Duration) -> String {
+    let window = window.min(stats.capacity());
+    let mut operations: Vec<_> = stats.stats(window).into_iter().collect();
+    // Sorted so scrapes are stable
+    operations.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let seconds = window.as_secs_f64().max(f64::EPSILON);
+    let rates: Vec<_> = operations.iter().map(|(op, s)| (op.as_str(), s.count as f64 / seconds)).collect();
+    let averages: Vec<_> = operations.iter().map(|(op, s)| (op.as_str(), s.average_duration_ms())).collect();
+    let p95s: Vec<_> = operations.iter()
+        .filter_map(|(op, s)| s.p95().map(|p95| (op.as_str(), p95 as f64)))
+        .collect();
+
+    let mut out = String::new();
+    let span = window.as_secs();
+    labeled_gauge(&mut out, "middleware_operation_rate", &format!("Executions per second over the last {}s", span), &rates);
+    labeled_gauge(&mut out, "middleware_operation_avg_ms", &format!("Mean duration over the last {}s", span), &averages);
+    labeled_gauge(&mut out, "middleware_operation_p95_ms", &format!("95th percentile duration over the last {}s", span), &p95s);
+    out
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+fn labeled_counter(out: &mut String, name: &str, help: &str, values: &HashMap<String, u64>) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+    // Sorted so scrapes are stable
+    let mut sources: Vec<_> = values.iter().collect();
+    sources.sort();
+    for (source, value) in sources {
+        let _ = writeln!(out, "{}{{source=\"{}\"}} {}", name, escape_label(source), value);
+    }
+}
+
+fn labeled_gauge(out: &mut String, name: &str, help: &str, values: &[(&str, f64)]) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (operation, value) in values {
+        let _ = writeln!(out, "{}{{operation=\"{}\"}} {}", name, escape_label(operation), value);
+    }
+}
+
+/// Escape a label value per the exposition format
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
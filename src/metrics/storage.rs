@@ -140,16 +140,29 @@ impl MetricsStorage {
     }
 
     /// Saves all stored component benchmarks to a JSON file.
+    ///
+    /// The file is written under a temporary name and renamed into place, so a
+    /// run killed mid-checkpoint leaves the previous results intact.
     pub fn save_benchmarks_to_json_file(&self, filename: &str) -> Result<()> {
         let benchmarks = self.get_all_benchmarks();
         let json_benchmarks: Vec<serde_json::Value> = benchmarks.iter().map(|b| b.to_json()).collect();
         let json = serde_json::to_string_pretty(&json_benchmarks)?;
 
-        let mut file = File::create(filename)?;
+        let temp_filename = format!("{}.tmp", filename);
+        let mut file = File::create(&temp_filename)?;
         file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_filename, filename)?;
         Ok(())
     }
 
+    /// Reads benchmarks written by `save_benchmarks_to_json_file`.
+    /// Timing instants aren't stored, so the durations come back as recorded.
+    pub fn read_benchmarks_from_json_file(filename: &str) -> Result<Vec<ComponentBenchmark>> {
+        let contents = std::fs::read_to_string(filename)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
     /// Groups benchmarks by component name and then by security level.
     /// Returns a nested HashMap: `ComponentName -> SecurityLevel -> Vec<ComponentBenchmark>`.
     pub fn get_benchmarks_by_component_and_level(&self) -> HashMap<String, HashMap<String, Vec<ComponentBenchmark>>> {
@@ -166,6 +179,67 @@ impl MetricsStorage {
         result
     }
 
+    /// Formats the Byzantine resilience runs as a single aligned table.
+    ///
+    /// One row per benchmark carrying a `byzantine_percentage` configuration,
+    /// sorted by percentage. Columns are the success rate, the average
+    /// `total_iteration` latency and one count per `failure_reason_*` entry.
+    pub fn byzantine_resilience_table(&self) -> String {
+        const FAILURE_PREFIX: &str = "failure_reason_";
+
+        let mut runs: Vec<(f64, ComponentBenchmark)> = self.get_all_benchmarks()
+            .into_iter()
+            .filter_map(|b| {
+                let pct = b.configuration.get("byzantine_percentage")?.parse::<f64>().ok()?;
+                Some((pct, b))
+            })
+            .collect();
+        if runs.is_empty() {
+            return "No Byzantine resilience results recorded.\n".to_string();
+        }
+        runs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut failure_keys: Vec<String> = runs.iter()
+            .flat_map(|(_, b)| b.configuration.keys())
+            .filter(|k| k.starts_with(FAILURE_PREFIX))
+            .cloned()
+            .collect();
+        failure_keys.sort();
+        failure_keys.dedup();
+
+        let mut header = vec!["Byzantine %".to_string(), "Success Rate".to_string(), "Avg Latency (ms)".to_string()];
+        header.extend(failure_keys.iter().map(|k| k.trim_start_matches(FAILURE_PREFIX).to_string()));
+
+        let rows: Vec<Vec<String>> = runs.iter().map(|(pct, b)| {
+            let success_rate = b.configuration.get("verification_success_rate")
+                .and_then(|r| r.parse::<f64>().ok())
+                .map(|r| format!("{:.1}%", r * 100.0))
+                .unwrap_or_else(|| "-".to_string());
+            let avg_latency = b.get_operation_stats("total_iteration")
+                .map(|stats| format!("{:.1}", stats.average_duration_ms()))
+                .unwrap_or_else(|| "-".to_string());
+            let mut row = vec![format!("{:.1}%", pct * 100.0), success_rate, avg_latency];
+            row.extend(failure_keys.iter().map(|k| b.configuration.get(k).cloned().unwrap_or_else(|| "0".to_string())));
+            row
+        }).collect();
+
+        let widths: Vec<usize> = (0..header.len())
+            .map(|col| rows.iter().map(|r| r[col].len()).chain(std::iter::once(header[col].len())).max().unwrap_or(0))
+            .collect();
+        let format_row = |cells: &[String]| -> String {
+            let padded: Vec<String> = cells.iter().zip(&widths).map(|(c, w)| format!("{:>width$}", c, width = *w)).collect();
+            format!("| {} |\n", padded.join(" | "))
+        };
+
+        let mut table = format_row(&header);
+        let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        table.push_str(&format!("|-{}-|\n", separator.join("-|-")));
+        for row in &rows {
+            table.push_str(&format_row(row));
+        }
+        table
+    }
+
     /// Prints a summary of the stored component benchmarks to the console.
     pub fn print_benchmark_summary(&self) {
         let grouped = self.get_benchmarks_by_component_and_level();
@@ -0,0 +1,152 @@
+//! Per-transaction timeline of named spans.
+//!
+//! `OperationStats` only keeps aggregate durations; an `ExecutionTrace`
+//! records when each stage of one transaction started and ended, and can be
+//! exported in the Chrome tracing format (`chrome://tracing`, Perfetto).
+
+use super::performance::{OperationStats, WindowedStats};
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// One named interval within a trace.
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceSpan {
+    pub name: String,
+    /// Microseconds from the start of the trace
+    pub start_us: u64,
+    /// `None` while the span is still open
+    pub duration_us: Option<u64>,
+}
+
+/// Ordered spans recorded while processing a single transaction.
+#[derive(Debug)]
+pub struct ExecutionTrace {
+    name: String,
+    origin: Instant,
+    spans: Mutex<Vec<TraceSpan>>,
+    /// Also receives every closed span, for process-wide recent stats
+    window: Option<Arc<WindowedStats>>,
+}
+
+/// Closes its span when dropped.
+pub struct SpanGuard<'a> {
+    trace: &'a ExecutionTrace,
+    index: usize,
+}
+
+impl Drop for SpanGuard<'_> {
+    fn drop(&mut self) {
+        self.trace.end_span(self.index);
+    }
+}
+
+impl ExecutionTrace {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            origin: Instant::now(),
+            spans: Mutex::new(Vec::new()),
+            window: None,
+        }
+    }
+
+    /// Record each span's duration in `window` as well when it closes.
+    pub fn with_window(mut self, window: Arc<WindowedStats>) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn elapsed_us(&self) -> u64 {
+        self.origin.elapsed().as_micros() as u64
+    }
+
+    /// Open a span and return its index for `end_span`.
+    pub fn start_span(&self, name: &str) -> usize {
+        let start_us = self.elapsed_us();
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(TraceSpan { name: name.to_string(), start_us, duration_us: None });
+        spans.len() - 1
+    }
+
+    /// Close the span at `index`. Closing an already closed span does nothing.
+    pub fn end_span(&self, index: usize) {
+        let now_us = self.elapsed_us();
+        let closed = match self.spans.lock().unwrap().get_mut(index) {
+            Some(span) if span.duration_us.is_none() => {
+                let duration_us = now_us.saturating_sub(span.start_us);
+                span.duration_us = Some(duration_us);
+                Some((span.name.clone(), duration_us))
+            }
+            _ => None,
+        };
+        if let (Some((name, duration_us)), Some(window)) = (closed, &self.window) {
+            window.record(&name, duration_us / 1000);
+        }
+    }
+
+    /// Open a span that closes when the returned guard goes out of scope.
+    pub fn span(&self, name: &str) -> SpanGuard<'_> {
+        SpanGuard { trace: self, index: self.start_span(name) }
+    }
+
+    /// Recorded spans in the order they were opened.
+    pub fn spans(&self) -> Vec<TraceSpan> {
+        self.spans.lock().unwrap().clone()
+    }
+
+    /// Closed spans aggregated by name, in milliseconds.
+    pub fn operation_stats(&self) -> HashMap<String, OperationStats> {
+        let mut stats: HashMap<String, OperationStats> = HashMap::new();
+        for span in self.spans() {
+            if let Some(duration_us) = span.duration_us {
+                stats.entry(span.name).or_default().record(duration_us / 1000);
+            }
+        }
+        stats
+    }
+
+    /// All closed spans folded into one set of stats: `count` spans,
+    /// `total_duration_ms` the time spent across them.
+    pub fn total_stats(&self) -> OperationStats {
+        let mut total = OperationStats::default();
+        for stats in self.operation_stats().values() {
+            total.merge(stats);
+        }
+        total
+    }
+
+    /// Chrome trace JSON: closed spans become complete (`X`) events, open ones begin (`B`) events.
+    pub fn to_chrome_trace(&self) -> Value {
+        let events: Vec<Value> = self.spans().into_iter().map(|span| {
+            let mut event = json!({
+                "name": span.name,
+                "cat": self.name,
+                "ph": if span.duration_us.is_some() { "X" } else { "B" },
+                "ts": span.start_us,
+                "pid": 1,
+                "tid": 1,
+            });
+            if let Some(duration_us) = span.duration_us {
+                event["dur"] = json!(duration_us);
+            }
+            event
+        }).collect();
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+
+    /// Write `to_chrome_trace()` to `path`.
+    pub fn save_chrome_trace(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(&self.to_chrome_trace())?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+//! Binding of quorum signature sets to the payload they were collected for.
+//!
+//! The verification contract checks signatures over the attestation payload,
+//! so a set collected for payload A fails on-chain if submitted with payload B.
+//! `SignatureBindings` catches that swap in the middleware, before anything is
+//! submitted: validators sign the payload itself, as the contract expects, and
+//! every set collected by `TransactionHandler` is recorded with
+//! `hash(payload || SIGNATURE_DOMAIN || nonce)`. The nonce never goes on-chain; a submission
+//! whose payload doesn't reproduce that digest is rejected. A set is claimed
+//! while its submission is in flight and consumed once it lands, so the same
+//! signatures can't be submitted twice.
+
+use crate::transaction::types::SignatureBytes;
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Domain separator mixed into every binding digest.
+pub const SIGNATURE_DOMAIN: &[u8] = b"sui-modular-middleware/quorum-attestation/v1";
+
+/// Bindings kept before the oldest are forgotten.
+pub const DEFAULT_BINDING_CAPACITY: usize = 4096;
+
+/// Blake2b-256 of `payload || SIGNATURE_DOMAIN || nonce` (nonce little-endian).
+pub fn binding_digest(payload: &[u8], nonce: u64) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(payload);
+    hasher.update(SIGNATURE_DOMAIN);
+    hasher.update(nonce.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Identifies a signature set regardless of the payload it is submitted with.
+fn fingerprint(signatures: &[SignatureBytes]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    for signature in signatures {
+        hasher.update((signature.len() as u32).to_le_bytes());
+        hasher.update(signature);
+    }
+    hasher.finalize().into()
+}
+
+/// The payload binding recorded for one collected signature set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureBinding {
+    pub nonce: u64,
+    pub digest: [u8; 32],
+}
+
+/// Why a signature set may not be submitted with a payload.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReplayError {
+    #[error("Quorum signatures (nonce {nonce}) were collected for a different payload")]
+    PayloadMismatch { nonce: u64 },
+    #[error("Quorum signatures (nonce {nonce}) are already being submitted")]
+    InFlight { nonce: u64 },
+    #[error("Quorum signatures (nonce {nonce}) were already submitted")]
+    AlreadySubmitted { nonce: u64 },
+    #[error("Quorum signatures were not collected by this middleware")]
+    Unbound,
+}
+
+/// Where a bound signature set is in its single submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BindingStatus {
+    Collected,
+    InFlight,
+    Submitted,
+}
+
+/// Signature sets collected so far and the payloads they are bound to.
+#[derive(Debug)]
+pub struct SignatureBindings {
+    capacity: usize,
+    state: Mutex<BindingState>,
+}
+
+#[derive(Debug, Default)]
+struct BindingState {
+    next_nonce: u64,
+    bindings: HashMap<[u8; 32], (SignatureBinding, BindingStatus)>,
+    order: VecDeque<[u8; 32]>,
+}
+
+impl Default for SignatureBindings {
+    fn default() -> Self {
+        Self::new(DEFAULT_BINDING_CAPACITY)
+    }
+}
+
+impl SignatureBindings {
+    /// Registry remembering the `capacity` most recent bindings.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), state: Mutex::new(BindingState::default()) }
+    }
+
+    /// Bind `signatures` to `payload` under a fresh nonce.
+    pub fn bind(&self, payload: &[u8], signatures: &[SignatureBytes]) -> SignatureBinding {
+        let nonce = self.reserve_nonce();
+        self.bind_reserved(payload, nonce, signatures)
+    }
+
+    /// A nonce no other binding uses, reserved before signing and then passed to `bind_reserved`.
+    pub fn reserve_nonce(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let nonce = state.next_nonce;
+        state.next_nonce += 1;
+        nonce
+    }
+
+    /// Bind `signatures` to `payload` under a nonce from `reserve_nonce`.
+    pub fn bind_reserved(&self, payload: &[u8], nonce: u64, signatures: &[SignatureBytes]) -> SignatureBinding {
+        let mut state = self.state.lock().unwrap();
+        let binding = SignatureBinding { nonce, digest: binding_digest(payload, nonce) };
+
+        let key = fingerprint(signatures);
+        if state.bindings.insert(key, (binding, BindingStatus::Collected)).is_none() {
+            state.order.push_back(key);
+        }
+        while state.order.len() > self.capacity {
+            if let Some(oldest) = state.order.pop_front() {
+                state.bindings.remove(&oldest);
+            }
+        }
+        binding
+    }
+
+    /// The binding of `signatures`, if it matches `payload`.
+    pub fn check(&self, payload: &[u8], signatures: &[SignatureBytes]) -> Result<SignatureBinding, ReplayError> {
+        let state = self.state.lock().unwrap();
+        let (binding, status) = *state.bindings.get(&fingerprint(signatures)).ok_or(ReplayError::Unbound)?;
+        Self::check_entry(payload, binding, status)
+    }
+
+    /// Like `check`, but also marks the set as in flight until `complete` or `release`.
+    pub fn claim(&self, payload: &[u8], signatures: &[SignatureBytes]) -> Result<SignatureBinding, ReplayError> {
+        let mut state = self.state.lock().unwrap();
+        let entry = state.bindings.get_mut(&fingerprint(signatures)).ok_or(ReplayError::Unbound)?;
+        let binding = Self::check_entry(payload, entry.0, entry.1)?;
+        entry.1 = BindingStatus::InFlight;
+        Ok(binding)
+    }
+
+    /// Mark a claimed set as submitted; it is rejected from then on.
+    pub fn complete(&self, signatures: &[SignatureBytes]) {
+        self.set_status(signatures, BindingStatus::Submitted);
+    }
+
+    /// Return a claimed set whose submission failed, so it can be retried.
+    pub fn release(&self, signatures: &[SignatureBytes]) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(entry) = state.bindings.get_mut(&fingerprint(signatures)) {
+            if entry.1 == BindingStatus::InFlight {
+                entry.1 = BindingStatus::Collected;
+            }
+        }
+    }
+
+    fn set_status(&self, signatures: &[SignatureBytes], status: BindingStatus) {
+        if let Some(entry) = self.state.lock().unwrap().bindings.get_mut(&fingerprint(signatures)) {
+            entry.1 = status;
+        }
+    }
+
+    fn check_entry(payload: &[u8], binding: SignatureBinding, status: BindingStatus) -> Result<SignatureBinding, ReplayError> {
+        if binding_digest(payload, binding.nonce) != binding.digest {
+            return Err(ReplayError::PayloadMismatch { nonce: binding.nonce });
+        }
+        match status {
+            BindingStatus::Collected => Ok(binding),
+            BindingStatus::InFlight => Err(ReplayError::InFlight { nonce: binding.nonce }),
+            BindingStatus::Submitted => Err(ReplayError::AlreadySubmitted { nonce: binding.nonce }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().bindings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
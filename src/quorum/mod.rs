@@ -1 +1,2 @@
-pub mod simulation; 
\ No newline at end of file
+pub mod simulation;
+pub mod binding;
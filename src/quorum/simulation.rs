@@ -10,7 +10,9 @@ use crate::config::generate_test_sui_keypair;
 use rand::{Rng, rngs::ThreadRng};
 use std::sync::{Arc, Mutex};
 use anyhow::{Result, anyhow};
+use blake2::{digest::consts::U32, Blake2b, Digest};
 use hex;
+use serde::Serialize;
 
 /// Manages simulated quorum nodes and their signing behavior.
 ///
@@ -32,6 +34,21 @@ pub struct QuorumSimulation {
 /// Format: (signature_bytes, is_valid_flag)
 pub type SimulatedSignature = (SignatureBytes, bool);
 
+/// Quorum signatures over a structured value, from `request_signatures_over`.
+#[derive(Debug, Clone)]
+pub struct SignedPayload {
+    /// BCS encoding of the value; what an on-chain consumer decodes.
+    pub payload: Vec<u8>,
+    /// Blake2b-256 of `payload`; the bytes the nodes actually signed.
+    pub digest: [u8; 32],
+    pub signatures: Vec<SimulatedSignature>,
+}
+
+/// Digest signed for a BCS payload, using the same Blake2b-256 hash as `Transaction::digest`.
+pub fn payload_signing_digest(payload: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(payload).into()
+}
+
 impl QuorumSimulation {
     /// Creates a new simulation instance with a given set of keypairs.
     pub fn new(keypairs: Vec<SuiKeyPair>) -> Self {
@@ -135,6 +152,19 @@ impl QuorumSimulation {
         Ok(simulated_signatures)
     }
 
+    /// Requests signatures over a structured value rather than raw bytes.
+    ///
+    /// The value is BCS-encoded and the nodes sign `payload_signing_digest` of the
+    /// encoding, so a verifier needs only the returned `payload` to recompute what
+    /// was signed. Byzantine behavior applies exactly as in `request_signatures`.
+    pub async fn request_signatures_over<T: Serialize>(&self, value: &T) -> Result<SignedPayload> {
+        let payload = bcs::to_bytes(value)
+            .map_err(|e| anyhow!("Failed to BCS-encode payload for signing: {}", e))?;
+        let digest = payload_signing_digest(&payload);
+        let signatures = self.request_signatures(digest.to_vec()).await?;
+        Ok(SignedPayload { payload, digest, signatures })
+    }
+
     /// Calculates the minimum number of signatures required for quorum (BFT threshold).
     /// Formula: floor(2n/3) + 1
     pub fn get_threshold(&self) -> usize {
@@ -0,0 +1,132 @@
+//! Alerting hooks for high-severity audit events.
+//!
+//! An `AlertSink` registered on `SecurityAuditLog` is called for every event at
+//! or above the severity it was registered with, before the event is written
+//! to the log. Sinks run on the logging path, so they must return quickly:
+//! `WebhookAlertSink` only enqueues and posts from a background thread.
+
+use super::audit::{AuditEvent, AuditSeverity};
+use crate::utils::http::HttpClientFactory;
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Alerts queued for a webhook before new ones are dropped
+pub const DEFAULT_WEBHOOK_QUEUE: usize = 64;
+
+/// Request timeout for webhook deliveries
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Receives audit events that warrant paging someone.
+pub trait AlertSink: Send + Sync {
+    /// Name used in diagnostics
+    fn name(&self) -> &str;
+
+    /// Deliver `event`. Called synchronously from `SecurityAuditLog::log_event`,
+    /// so it must not block; delivery failures are the sink's to report.
+    fn notify(&self, event: &AuditEvent);
+}
+
+/// Writes alerts to stderr, for operators watching the process output.
+#[derive(Debug, Default)]
+pub struct StderrAlertSink;
+
+impl AlertSink for StderrAlertSink {
+    fn name(&self) -> &str {
+        "stderr"
+    }
+
+    fn notify(&self, event: &AuditEvent) {
+        eprintln!("[ALERT] {}", event.to_log_string());
+    }
+}
+
+/// POSTs each alert as JSON (`AuditEvent::to_json`) to a webhook URL.
+///
+/// Deliveries happen on a dedicated thread fed by a bounded queue. When the
+/// webhook is slow or down and the queue is full, further alerts are dropped
+/// and counted rather than holding up the caller.
+pub struct WebhookAlertSink {
+    url: String,
+    sender: SyncSender<AuditEvent>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl WebhookAlertSink {
+    /// Sink for `url` holding up to `queue_capacity` undelivered alerts.
+    pub fn new(url: &str, queue_capacity: usize) -> Result<Self> {
+        let client = HttpClientFactory::from_env()?.build_with_timeout(WEBHOOK_TIMEOUT)?;
+        let (sender, receiver) = sync_channel::<AuditEvent>(queue_capacity);
+        let worker_url = url.to_string();
+
+        std::thread::Builder::new()
+            .name("audit-alert-webhook".to_string())
+            .spawn(move || {
+                // Own runtime, so delivery works whether or not the logger runs inside Tokio
+                let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        eprintln!("ERROR: Alert webhook {} disabled, no runtime: {}", worker_url, e);
+                        return;
+                    }
+                };
+                // Ends once the sink, and with it the sender, is dropped
+                for event in receiver {
+                    let delivery = runtime.block_on(client.post(&worker_url).json(&event.to_json()).send());
+                    match delivery {
+                        Ok(response) if !response.status().is_success() => {
+                            eprintln!("ERROR: Alert webhook {} returned {}", worker_url, response.status());
+                        }
+                        Ok(_) => {}
+                        Err(e) => eprintln!("ERROR: Alert webhook {} failed: {}", worker_url, e),
+                    }
+                }
+            })
+            .map_err(|e| anyhow!("Failed to start alert webhook worker: {}", e))?;
+
+        Ok(Self { url: url.to_string(), sender, dropped: Arc::new(AtomicUsize::new(0)) })
+    }
+
+    /// Alerts discarded because the queue was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::SeqCst)
+    }
+}
+
+impl AlertSink for WebhookAlertSink {
+    fn name(&self) -> &str {
+        &self.url
+    }
+
+    fn notify(&self, event: &AuditEvent) {
+        match self.sender.try_send(event.clone()) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::SeqCst) + 1;
+                eprintln!("WARNING: Alert queue for {} is full, dropped {} alert(s)", self.url, dropped);
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                eprintln!("ERROR: Alert webhook worker for {} has stopped", self.url);
+            }
+        }
+    }
+}
+
+/// A sink and the lowest severity it is notified for.
+#[derive(Clone)]
+pub(crate) struct AlertRoute {
+    pub(crate) sink: Arc<dyn AlertSink>,
+    pub(crate) min_severity: AuditSeverity,
+}
+
+impl fmt::Debug for AlertRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlertRoute")
+            .field("sink", &self.sink.name())
+            .field("min_severity", &self.min_severity)
+            .finish()
+    }
+}
@@ -2,16 +2,22 @@
 //!
 //! Provides capabilities for logging security-relevant events.
 //! Supports configurable destinations (console, file) and severity levels.
+//! Events can also be kept as a rotating JSON-lines file (`with_file`) that
+//! survives restarts and is read back with `load_from_file`.
+//! High-severity events can also be forwarded to alert sinks (see `alerts`).
 
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use super::alerts::{AlertRoute, AlertSink};
 
 /// Types of events recorded by the audit log.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -52,6 +58,12 @@ pub struct AuditEvent {
     pub message: String, // Detailed description of the event
     #[serde(skip_serializing_if = "Value::is_null")]
     pub context: Value, // Additional structured data (JSON)
+    /// Hash of the event logged before this one, in a log with `with_hash_chain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// SHA-256 over this event's other fields, `prev_hash` included
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 impl AuditEvent {
@@ -66,9 +78,19 @@ impl AuditEvent {
             chain_id: None,
             message: message.to_string(),
             context: Value::Null,
+            prev_hash: None,
+            hash: None,
         }
     }
 
+    /// Hex SHA-256 of the event as serialized without its own `hash`.
+    pub fn compute_hash(&self) -> String {
+        let unhashed = AuditEvent { hash: None, ..self.clone() };
+        // Serializing a struct of strings, enums and JSON values can't fail
+        let bytes = serde_json::to_vec(&unhashed).unwrap_or_default();
+        hex::encode(Sha256::digest(&bytes))
+    }
+
     /// Associates a transaction ID with the event.
     pub fn with_transaction_id(mut self, tx_id: &str) -> Self {
         self.transaction_id = Some(tx_id.to_string());
@@ -132,6 +154,38 @@ impl AuditEvent {
     }
 }
 
+/// Which events `SecurityAuditLog::query` returns. Unset criteria match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditFilter {
+    /// Only events at this severity or above
+    pub severity_at_least: Option<AuditSeverity>,
+    /// Only events of one of these types
+    pub event_types: Option<Vec<AuditEventType>>,
+    /// Only events whose `source` is exactly this component
+    pub component: Option<String>,
+    /// Only events at or after this UNIX time, in seconds
+    pub since: Option<i64>,
+    /// Only events before this UNIX time, in seconds
+    pub until: Option<i64>,
+}
+
+impl AuditFilter {
+    /// Whether `event` meets every criterion that is set.
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        let timestamp = event.timestamp.timestamp();
+        if matches!(&self.severity_at_least, Some(min) if event.severity < *min) {
+            return false;
+        }
+        if matches!(&self.event_types, Some(types) if !types.contains(&event.event_type)) {
+            return false;
+        }
+        if matches!(&self.component, Some(component) if event.source != *component) {
+            return false;
+        }
+        !matches!(self.since, Some(since) if timestamp < since) && !matches!(self.until, Some(until) if timestamp >= until)
+    }
+}
+
 /// Configuration for the `SecurityAuditLog`.
 #[derive(Debug, Clone)]
 pub struct AuditLogConfig {
@@ -152,12 +206,250 @@ impl Default for AuditLogConfig {
     }
 }
 
+/// Events kept in memory before low-severity ones are pruned.
+pub const DEFAULT_MAX_EVENTS: usize = 1000;
+
+/// Events kept in memory before even `Error` and `Critical` ones are pruned.
+pub const DEFAULT_HARD_MAX_EVENTS: usize = 10_000;
+
+/// How many audit events `SecurityAuditLog` keeps in memory, and for how long.
+///
+/// Beyond `max_events`, the oldest `Info` events are dropped first, then the
+/// oldest `Warning` events. `Error` and `Critical` events are only dropped,
+/// oldest first, once the log holds more than `hard_max_events`. With a
+/// `retention` period, events below `Error` older than it are dropped too.
+/// Pruning only affects the in-memory buffer, not the log file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRetention {
+    pub max_events: usize,
+    pub hard_max_events: usize,
+    pub retention: Option<Duration>,
+}
+
+impl Default for AuditRetention {
+    fn default() -> Self {
+        Self {
+            max_events: DEFAULT_MAX_EVENTS,
+            hard_max_events: DEFAULT_HARD_MAX_EVENTS,
+            retention: None,
+        }
+    }
+}
+
+impl AuditRetention {
+    /// Defaults overridden by `AUDIT_MAX_EVENTS`, `AUDIT_HARD_MAX_EVENTS` and
+    /// `AUDIT_RETENTION_SECS` when set.
+    pub fn from_env() -> Result<Self> {
+        let read = |name: &str| -> Result<Option<u64>> {
+            match std::env::var(name) {
+                Ok(value) => value.parse().map(Some)
+                    .map_err(|_| anyhow!("{} must be a non-negative integer, got '{}'", name, value)),
+                Err(_) => Ok(None),
+            }
+        };
+        let mut retention = Self::default();
+        if let Some(max_events) = read("AUDIT_MAX_EVENTS")? {
+            retention.max_events = max_events as usize;
+        }
+        if let Some(hard_max_events) = read("AUDIT_HARD_MAX_EVENTS")? {
+            retention.hard_max_events = hard_max_events as usize;
+        }
+        if let Some(secs) = read("AUDIT_RETENTION_SECS")? {
+            retention.retention = Some(Duration::from_secs(secs));
+        }
+        Ok(retention)
+    }
+
+    /// Drop from `events` (oldest first) whatever this policy doesn't keep at `now`.
+    fn prune(&self, events: &mut Vec<AuditEvent>, now: DateTime<Utc>) {
+        if let Some(retention) = self.retention.and_then(|r| chrono::Duration::from_std(r).ok()) {
+            let cutoff = now - retention;
+            events.retain(|e| e.severity >= AuditSeverity::Error || e.timestamp >= cutoff);
+        }
+
+        for severity in [AuditSeverity::Info, AuditSeverity::Warning] {
+            let mut excess = events.len().saturating_sub(self.max_events);
+            if excess == 0 {
+                break;
+            }
+            events.retain(|e| {
+                if excess > 0 && e.severity == severity {
+                    excess -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        let excess = events.len().saturating_sub(self.hard_max_events.max(self.max_events));
+        events.drain(..excess);
+    }
+}
+
+/// Size at which `SecurityAuditLog::with_file` rolls its file over, 10 MiB.
+pub const DEFAULT_AUDIT_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Append-only JSON-lines copy of the audit trail, rolled to `<path>.1` when full.
+///
+/// Rolling over discards the older `<path>.1`, so the hash its last event had
+/// is kept in `<path>.anchor` for the new `<path>.1` to be verified against.
+#[derive(Debug)]
+struct AuditFile {
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl AuditFile {
+    /// Where the previous generation of `path` is kept
+    fn rotated_path(path: &Path) -> PathBuf {
+        let mut rotated = path.as_os_str().to_owned();
+        rotated.push(".1");
+        PathBuf::from(rotated)
+    }
+
+    /// Where the hash the oldest kept generation links back to is recorded
+    fn anchor_path(path: &Path) -> PathBuf {
+        let mut anchor = path.as_os_str().to_owned();
+        anchor.push(".anchor");
+        PathBuf::from(anchor)
+    }
+
+    /// Hash of the newest hash-chained event in the file, or else in its
+    /// previous generation, which is where a restarted chain carries on from.
+    fn last_hash(path: &Path) -> Result<Option<String>> {
+        for file in [path.to_path_buf(), Self::rotated_path(path)] {
+            if !file.exists() {
+                continue;
+            }
+            if let Some(event) = read_audit_file(&file)?.pop() {
+                return Ok(event.hash);
+            }
+        }
+        Ok(None)
+    }
+
+    /// The anchor `rotate` recorded for `path`, if rotation ever discarded a generation.
+    fn anchor(path: &Path) -> Result<Option<String>> {
+        match std::fs::read_to_string(Self::anchor_path(path)) {
+            Ok(anchor) => Ok(Some(anchor.trim().to_string()).filter(|anchor| !anchor.is_empty())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(anyhow!("Failed to read audit anchor for {:?}: {}", path, e)),
+        }
+    }
+
+    /// Move the full file to `<path>.1`, first recording what the generation
+    /// it replaces ended with.
+    fn rotate(&self) -> Result<()> {
+        let rotated = Self::rotated_path(&self.path);
+        let anchor_path = Self::anchor_path(&self.path);
+        let discarded = if rotated.exists() { read_audit_file(&rotated)?.pop().and_then(|event| event.hash) } else { None };
+        match discarded {
+            Some(hash) => std::fs::write(&anchor_path, hash)
+                .map_err(|e| anyhow!("Failed to write audit anchor {:?}: {}", anchor_path, e))?,
+            None => match std::fs::remove_file(&anchor_path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(anyhow!("Failed to remove stale audit anchor {:?}: {}", anchor_path, e));
+                }
+                _ => {}
+            },
+        }
+        std::fs::rename(&self.path, &rotated).map_err(|e| anyhow!("Failed to rotate {:?}: {}", self.path, e))
+    }
+
+    fn append(&self, event: &AuditEvent) -> Result<()> {
+        let line = serde_json::to_string(event)?;
+        let size = std::fs::metadata(&self.path).map_or(0, |m| m.len());
+        // A single oversized event still gets a file of its own
+        if size > 0 && size + line.len() as u64 + 1 > self.max_bytes {
+            self.rotate()?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
+
+/// `prev_hash` of the first event a hash-chained log records.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Where a hash-chained log's chain has got to.
+#[derive(Debug)]
+struct HashChain {
+    /// Hash of the most recently logged event
+    last_hash: String,
+    /// Hashes of events no longer in memory that remaining events still link to
+    anchors: HashSet<String>,
+}
+
+impl HashChain {
+    fn new() -> Self {
+        Self { last_hash: GENESIS_HASH.to_string(), anchors: HashSet::new() }
+    }
+
+    /// Carry on from `last_hash`, an event that was persisted but isn't in memory.
+    fn resume(&mut self, last_hash: String) {
+        self.anchors = HashSet::from([last_hash.clone()]);
+        self.last_hash = last_hash;
+    }
+
+    /// Link `event` to the previous one and make it the new end of the chain.
+    fn append(&mut self, event: &mut AuditEvent) {
+        event.prev_hash = Some(self.last_hash.clone());
+        let hash = event.compute_hash();
+        event.hash = Some(hash.clone());
+        self.last_hash = hash;
+    }
+
+    /// Remember the links into events that pruning just removed from `events`.
+    ///
+    /// The in-memory buffer is only ever pruned by the log itself, so a link
+    /// that no longer meets its predecessor means the predecessor was pruned.
+    fn reanchor(&mut self, events: &[AuditEvent]) {
+        let mut anchors = HashSet::new();
+        for (i, event) in events.iter().enumerate() {
+            let Some(prev_hash) = &event.prev_hash else { continue };
+            let follows_previous = i > 0 && events[i - 1].hash.as_ref() == Some(prev_hash);
+            if !follows_previous && prev_hash != GENESIS_HASH {
+                anchors.insert(prev_hash.clone());
+            }
+        }
+        self.anchors = anchors;
+    }
+}
+
+/// Index of the first event in `events` that was altered, or whose predecessor was removed.
+///
+/// Each event's `hash` must match its contents, and its `prev_hash` the
+/// preceding event's hash. Only the first event may link to `GENESIS_HASH`;
+/// a chain that restarts there later on means everything before the restart
+/// could have been replaced. Events may also resume from one of `anchors`.
+fn first_broken_link(events: &[AuditEvent], anchors: &HashSet<String>) -> Option<usize> {
+    events.iter().enumerate().position(|(i, event)| {
+        let intact = event.hash.as_deref() == Some(event.compute_hash().as_str());
+        let linked = match &event.prev_hash {
+            Some(prev_hash) => {
+                (i == 0 && prev_hash == GENESIS_HASH)
+                    || anchors.contains(prev_hash)
+                    || (i > 0 && events[i - 1].hash.as_ref() == Some(prev_hash))
+            }
+            None => false,
+        };
+        !(intact && linked)
+    })
+}
+
 /// Thread-safe system for recording security audit events.
 #[derive(Debug, Clone)]
 pub struct SecurityAuditLog {
     config: Arc<Mutex<AuditLogConfig>>,
     events: Arc<Mutex<Vec<AuditEvent>>>,
-    max_events: usize,
+    retention: AuditRetention,
+    alert_routes: Arc<Mutex<Vec<AlertRoute>>>,
+    /// Durable copy of every logged event; the lock also orders concurrent appends
+    audit_file: Option<Arc<Mutex<AuditFile>>>,
+    /// Set by `with_hash_chain`; held while an event is stored so the chain matches the log order
+    hash_chain: Option<Arc<Mutex<HashChain>>>,
 }
 
 impl SecurityAuditLog {
@@ -171,7 +463,167 @@ impl SecurityAuditLog {
         Self {
             config: Arc::new(Mutex::new(config)),
             events: Arc::new(Mutex::new(Vec::new())),
-            max_events: 1000,
+            retention: AuditRetention::default(),
+            alert_routes: Arc::new(Mutex::new(Vec::new())),
+            audit_file: None,
+            hash_chain: None,
+        }
+    }
+
+    /// Chain events together by hash, so `verify_chain` can detect an event
+    /// that was edited or removed afterwards, e.g. in the `with_file` copy.
+    ///
+    /// Off by default: every event is hashed as it is logged, and logging is
+    /// serialized while it is stored. With a `with_file` copy, in either order,
+    /// the chain carries on from the last event already in the file.
+    pub fn with_hash_chain(mut self) -> Self {
+        self.hash_chain = Some(Arc::new(Mutex::new(HashChain::new())));
+        if let Err(e) = self.resume_chain() {
+            eprintln!("WARNING: Audit hash chain restarts at genesis: {}", e);
+        }
+        self
+    }
+
+    /// Continue the hash chain from the audit file's tail, if there are both.
+    fn resume_chain(&self) -> Result<()> {
+        let (Some(chain), Some(audit_file)) = (&self.hash_chain, &self.audit_file) else { return Ok(()) };
+        let path = audit_file.lock().map_err(|e| anyhow!("Audit file mutex poisoned: {}", e))?.path.clone();
+        if let Some(last_hash) = AuditFile::last_hash(&path)? {
+            chain.lock().map_err(|e| anyhow!("Hash chain mutex poisoned: {}", e))?.resume(last_hash);
+        }
+        Ok(())
+    }
+
+    /// Check the hash chain of the in-memory events, returning the index of the
+    /// first event that doesn't match its hash or link to its predecessor.
+    ///
+    /// Events pruned by the retention policy don't count as removed. A log
+    /// without `with_hash_chain` fails at its first event.
+    pub fn verify_chain(&self) -> Result<(), usize> {
+        let anchors = match &self.hash_chain {
+            Some(chain) => chain.lock().map_err(|_| 0usize)?.anchors.clone(),
+            None => HashSet::new(),
+        };
+        match first_broken_link(&self.get_events(), &anchors) {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
+
+    /// Also append each logged event to `path` as a JSON line, rotating at
+    /// `DEFAULT_AUDIT_FILE_MAX_BYTES`.
+    pub fn with_file(self, path: impl Into<PathBuf>) -> Result<Self> {
+        self.with_rotating_file(path, DEFAULT_AUDIT_FILE_MAX_BYTES)
+    }
+
+    /// Like `with_file`, but once appending would take the file past
+    /// `max_bytes` it is renamed to `<path>.1`, replacing any older one, and a
+    /// new file is started.
+    ///
+    /// Unlike the in-memory buffer the file is never pruned; events are written
+    /// before they are added to memory, so a crash loses at most the one being logged.
+    pub fn with_rotating_file(mut self, path: impl Into<PathBuf>, max_bytes: u64) -> Result<Self> {
+        let path = path.into();
+        if max_bytes == 0 {
+            return Err(anyhow!("Audit file size limit must be positive"));
+        }
+        // Fail now rather than on the first event
+        OpenOptions::new().create(true).append(true).open(&path)
+            .map_err(|e| anyhow!("Failed to open audit file {:?}: {}", path, e))?;
+        self.audit_file = Some(Arc::new(Mutex::new(AuditFile { path, max_bytes })));
+        self.resume_chain()?;
+        Ok(self)
+    }
+
+    /// A log holding the events recorded by `with_file` at `path`, for post-mortem analysis.
+    ///
+    /// Reads the rotated `<path>.1` first, if there is one, so events come
+    /// oldest first. A truncated final line, as a crash mid-write leaves, is
+    /// skipped with a warning; any other malformed line is an error. The
+    /// returned log keeps everything it loaded, writes nowhere, and can be
+    /// configured further like any other.
+    ///
+    /// Hash-chained events can be checked with `verify_chain`. The oldest one
+    /// must start the chain at `GENESIS_HASH`, or link to the last generation
+    /// rotation discarded, as recorded in `<path>.anchor`.
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let rotated = AuditFile::rotated_path(path);
+        let mut events = Vec::new();
+        if rotated.exists() {
+            events.extend(read_audit_file(&rotated)?);
+        }
+        events.extend(read_audit_file(path)?);
+
+        let log = Self::with_config(AuditLogConfig {
+            console_enabled: false,
+            file_enabled: false,
+            log_file_path: None,
+            min_severity: AuditSeverity::Info,
+        })
+        .with_retention(AuditRetention { max_events: usize::MAX, hard_max_events: usize::MAX, retention: None });
+        let log = match events.last().and_then(|event| event.hash.clone()) {
+            Some(last_hash) => {
+                let anchors = AuditFile::anchor(path)?.into_iter().collect();
+                Self { hash_chain: Some(Arc::new(Mutex::new(HashChain { last_hash, anchors }))), ..log }
+            }
+            None => log,
+        };
+        *log.events.lock().map_err(|e| anyhow!("Events lock poisoned: {}", e))? = events;
+        Ok(log)
+    }
+
+    /// Keep in-memory events according to `retention` instead of the defaults.
+    pub fn with_retention(mut self, retention: AuditRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn retention(&self) -> &AuditRetention {
+        &self.retention
+    }
+
+    /// Number of events currently held in memory.
+    pub fn event_count(&self) -> usize {
+        self.events.lock().map_or(0, |events| events.len())
+    }
+
+    /// Apply the retention policy now, without waiting for the next event.
+    pub fn prune_events(&self) {
+        let mut chain = self.hash_chain.as_ref().and_then(|chain| chain.lock().ok());
+        if let Ok(mut events_guard) = self.events.lock() {
+            self.prune_locked(&mut events_guard, chain.as_deref_mut());
+        }
+    }
+
+    /// Apply the retention policy, keeping the hash chain's anchors in step with what was dropped.
+    fn prune_locked(&self, events: &mut Vec<AuditEvent>, chain: Option<&mut HashChain>) {
+        let before = events.len();
+        self.retention.prune(events, Utc::now());
+        if let Some(chain) = chain {
+            if events.len() != before {
+                chain.reanchor(events);
+            }
+        }
+    }
+
+    /// Notify `sink` of every event at or above `min_severity`, regardless of
+    /// the log's own `min_severity`.
+    pub fn add_alert_sink(&self, sink: Arc<dyn AlertSink>, min_severity: AuditSeverity) {
+        match self.alert_routes.lock() {
+            Ok(mut routes) => routes.push(AlertRoute { sink, min_severity }),
+            Err(e) => eprintln!("ERROR: Alert routes mutex poisoned. Sink not added: {}", e),
+        }
+    }
+
+    fn dispatch_alerts(&self, event: &AuditEvent) {
+        // Clone the routes so a sink that logs doesn't deadlock on the lock
+        let routes = match self.alert_routes.lock() {
+            Ok(routes) => routes.clone(),
+            Err(_) => return,
+        };
+        for route in routes.iter().filter(|route| event.severity >= route.min_severity) {
+            route.sink.notify(event);
         }
     }
 
@@ -182,13 +634,24 @@ impl SecurityAuditLog {
     }
 
     /// Logs an `AuditEvent` if its severity meets the configured minimum.
-    pub fn log_event(&self, event: AuditEvent) -> Result<()> {
+    pub fn log_event(&self, mut event: AuditEvent) -> Result<()> {
+        self.dispatch_alerts(&event);
+
         let config = self.config.lock().map_err(|e| anyhow!("Config lock poisoned: {}", e))?;
 
         if event.severity < config.min_severity {
             return Ok(());
         }
 
+        // Hash before any output so every copy of the event carries its link
+        let mut chain = match &self.hash_chain {
+            Some(chain) => Some(chain.lock().map_err(|e| anyhow!("Hash chain lock poisoned: {}", e))?),
+            None => None,
+        };
+        if let Some(chain) = chain.as_deref_mut() {
+            chain.append(&mut event);
+        }
+
         if config.console_enabled {
             println!("{}", event.to_log_string());
         }
@@ -209,11 +672,20 @@ impl SecurityAuditLog {
             }
         }
 
+        if let Some(audit_file) = &self.audit_file {
+            match audit_file.lock() {
+                Ok(file) => {
+                    if let Err(e) = file.append(&event) {
+                        eprintln!("ERROR: Failed to append audit event to {:?}: {}", file.path, e);
+                    }
+                }
+                Err(e) => eprintln!("ERROR: Audit file mutex poisoned. Event not persisted: {}", e),
+            }
+        }
+
         if let Ok(mut events_guard) = self.events.lock() {
             events_guard.push(event);
-            if events_guard.len() > self.max_events {
-                events_guard.remove(0);
-            }
+            self.prune_locked(&mut events_guard, chain.as_deref_mut());
         } else {
             eprintln!("ERROR: Events mutex poisoned. Event not added to in-memory buffer.");
         }
@@ -232,6 +704,14 @@ impl SecurityAuditLog {
         )
     }
 
+    /// In-memory events matching `filter`, oldest first.
+    pub fn query(&self, filter: AuditFilter) -> Vec<AuditEvent> {
+        let mut events: Vec<AuditEvent> = self.get_events().into_iter().filter(|e| filter.matches(e)).collect();
+        // Events are logged in order, but callers may backdate timestamps
+        events.sort_by_key(|e| e.timestamp);
+        events
+    }
+
     /// Filters in-memory events by severity.
     pub fn get_events_by_severity(&self, severity: AuditSeverity) -> Vec<AuditEvent> {
         self.get_events().into_iter().filter(|e| e.severity == severity).collect()
@@ -262,8 +742,13 @@ impl SecurityAuditLog {
 
     /// Clears all events from the in-memory buffer.
     pub fn clear_events(&self) {
+        let chain = self.hash_chain.as_ref().and_then(|chain| chain.lock().ok());
         if let Ok(mut events_guard) = self.events.lock() {
             events_guard.clear();
+            // The next event links to one that is gone
+            if let Some(mut chain) = chain {
+                chain.anchors = HashSet::from([chain.last_hash.clone()]);
+            }
         } else {
             eprintln!("ERROR: Events mutex poisoned while clearing events.");
         }
@@ -376,6 +861,28 @@ impl SecurityAuditLog {
     }
 }
 
+/// Events in a JSON-lines audit file written by `AuditFile`
+fn read_audit_file(path: &Path) -> Result<Vec<AuditEvent>> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open audit file {:?}: {}", path, e))?;
+    let lines = BufReader::new(file).lines().collect::<std::io::Result<Vec<String>>>()?;
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+
+    let mut events = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(event) => events.push(event),
+            Err(e) if Some(i) == last => {
+                eprintln!("WARNING: Skipping truncated last line of audit file {:?}: {}", path, e);
+            }
+            Err(e) => return Err(anyhow!("Invalid audit event on line {} of {:?}: {}", i + 1, path, e)),
+        }
+    }
+    Ok(events)
+}
+
 impl Default for SecurityAuditLog {
     fn default() -> Self {
         Self::new()
@@ -1,6 +1,9 @@
-use anyhow::Result;
-use rand::{thread_rng, Rng};
-use serde_json::json;
+use anyhow::{anyhow, Result};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use serde_json::{json, Value};
+use std::fmt;
+use std::ops::Range;
 use std::time::{Duration, Instant};
 use std::sync::Arc;
 use crate::security::audit::{SecurityAuditLog, AuditSeverity, AuditEventType};
@@ -35,6 +38,209 @@ pub enum ByzantineBehavior {
     Unavailability(f64),
     /// Node gives inconsistent responses with given probability
     Inconsistency(f64),
+    /// Node picks a behavior each round after observing the other nodes' responses
+    Adaptive(Arc<dyn AdversaryStrategy>),
+}
+
+/// What an adaptive node observes before answering a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundView {
+    pub iteration: usize,
+    pub node_count: usize,
+    /// Responses already given that carry the honest value
+    pub agreeing: usize,
+    /// Adaptive nodes yet to answer, including the observing one
+    pub undecided: usize,
+}
+
+/// Decides how an adaptive Byzantine node behaves in a round.
+pub trait AdversaryStrategy: Send + Sync {
+    /// Name used in diagnostics
+    fn name(&self) -> &str;
+
+    /// The behavior for this round, or `None` to answer honestly. Returning
+    /// another `Adaptive` behavior is treated as honest.
+    fn choose(&self, view: &RoundView) -> Option<ByzantineBehavior>;
+}
+
+impl fmt::Debug for dyn AdversaryStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AdversaryStrategy({})", self.name())
+    }
+}
+
+/// Withholds only when the undecided adaptive nodes can decide whether the
+/// round reaches `threshold`, and otherwise answers honestly to stay unnoticed.
+#[derive(Debug, Clone)]
+pub struct PivotalWithholding {
+    pub threshold: usize,
+}
+
+impl AdversaryStrategy for PivotalWithholding {
+    fn name(&self) -> &str {
+        "pivotal_withholding"
+    }
+
+    fn choose(&self, view: &RoundView) -> Option<ByzantineBehavior> {
+        let pivotal = view.agreeing < self.threshold && view.agreeing + view.undecided >= self.threshold;
+        pivotal.then_some(ByzantineBehavior::Unavailability(1.0))
+    }
+}
+
+/// Applies `behavior` whenever `predicate` holds for the round.
+pub struct PredicateStrategy<F> {
+    name: String,
+    predicate: F,
+    behavior: ByzantineBehavior,
+}
+
+impl<F> PredicateStrategy<F>
+where
+    F: Fn(&RoundView) -> bool + Send + Sync,
+{
+    pub fn new(name: &str, predicate: F, behavior: ByzantineBehavior) -> Self {
+        Self { name: name.to_string(), predicate, behavior }
+    }
+}
+
+impl<F> AdversaryStrategy for PredicateStrategy<F>
+where
+    F: Fn(&RoundView) -> bool + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose(&self, view: &RoundView) -> Option<ByzantineBehavior> {
+        (self.predicate)(view).then(|| self.behavior.clone())
+    }
+}
+
+/// Apply a non-adaptive `behavior` to `response`. Returns whether the response changed.
+fn apply_behavior(response: &mut NodeResponse, behavior: &ByzantineBehavior, majority_value: u64, rng: &mut StdRng) -> bool {
+    match behavior {
+        ByzantineBehavior::DataManipulation(p) if rng.gen::<f64>() < *p => {
+            // Colluding manipulators all report the same wrong value
+            response.data = Some(json!(majority_value + 1000));
+            response.status = NodeResponseStatus::Inconsistent;
+            true
+        }
+        ByzantineBehavior::Inconsistency(p) if rng.gen::<f64>() < *p => {
+            response.data = Some(json!(rng.gen_range(1001, 2000)));
+            response.status = NodeResponseStatus::Inconsistent;
+            true
+        }
+        ByzantineBehavior::Unavailability(p) if rng.gen::<f64>() < *p => {
+            response.data = None;
+            response.error = Some("Node unavailable".to_string());
+            response.response_time_ms = None;
+            response.status = NodeResponseStatus::Unavailable;
+            true
+        }
+        ByzantineBehavior::TimingAttack(delay_ms) => {
+            response.response_time_ms = response.response_time_ms.map(|ms| ms + delay_ms);
+            if response.status == NodeResponseStatus::Valid {
+                response.status = NodeResponseStatus::Delayed;
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Whether `response` delivered `honest_value`, late or not
+fn agrees_with(response: &NodeResponse, honest_value: &Value) -> bool {
+    matches!(response.status, NodeResponseStatus::Valid | NodeResponseStatus::Delayed)
+        && response.data.as_ref() == Some(honest_value)
+}
+
+/// A behavior applied to one node over a range of iterations.
+#[derive(Debug, Clone)]
+pub struct ScheduledFault {
+    /// Index of the node, as in `node_{index}`
+    pub node: usize,
+    /// Iterations (0-based, end exclusive) the behavior is active for
+    pub iterations: Range<usize>,
+    pub behavior: ByzantineBehavior,
+}
+
+/// A reproducible adversarial timeline for `ByzantineSimulator::run_scenario`.
+///
+/// Nodes behave honestly except where a scheduled fault is active. Every
+/// random choice, including whether a probabilistic behavior fires, comes from
+/// an RNG seeded with `seed`, so the same scenario always yields the same
+/// responses. Use probability 1.0 for a fault that fires on every iteration.
+#[derive(Debug, Clone)]
+pub struct ByzantineScenario {
+    pub node_count: usize,
+    pub iterations: usize,
+    pub seed: u64,
+    pub faults: Vec<ScheduledFault>,
+}
+
+impl ByzantineScenario {
+    pub fn new(node_count: usize, iterations: usize) -> Self {
+        Self { node_count, iterations, seed: 0, faults: Vec::new() }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Make `node` exhibit `behavior` on `iterations`, e.g. `3, 10..21, Inconsistency(1.0)`.
+    pub fn with_fault(mut self, node: usize, iterations: Range<usize>, behavior: ByzantineBehavior) -> Self {
+        self.faults.push(ScheduledFault { node, iterations, behavior });
+        self
+    }
+
+    /// Behaviors active for `node` on `iteration`, in the order they were added
+    pub fn active_faults(&self, node: usize, iteration: usize) -> impl Iterator<Item = &ByzantineBehavior> {
+        self.faults.iter()
+            .filter(move |fault| fault.node == node && fault.iterations.contains(&iteration))
+            .map(|fault| &fault.behavior)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.node_count == 0 {
+            return Err(anyhow!("Scenario needs at least one node"));
+        }
+        if let Some(fault) = self.faults.iter().find(|fault| fault.node >= self.node_count) {
+            return Err(anyhow!("Fault targets node {} but the scenario has {} nodes", fault.node, self.node_count));
+        }
+        Ok(())
+    }
+}
+
+/// Responses produced for one iteration of a scenario.
+#[derive(Debug, Clone)]
+pub struct ScenarioIteration {
+    pub iteration: usize,
+    /// The value honest nodes reported
+    pub honest_value: Value,
+    pub responses: Vec<NodeResponse>,
+    /// Nodes whose response was altered by a fault this iteration
+    pub faulty_nodes: Vec<String>,
+}
+
+impl ScenarioIteration {
+    /// Responses carrying the honest value
+    pub fn agreeing(&self) -> usize {
+        self.responses.iter().filter(|response| agrees_with(response, &self.honest_value)).count()
+    }
+
+    /// Whether at least `threshold` nodes delivered the honest value
+    pub fn reaches_threshold(&self, threshold: usize) -> bool {
+        self.agreeing() >= threshold
+    }
+}
+
+/// Fraction of `run`'s iterations that reached `threshold`, for comparing adversaries.
+pub fn success_rate(run: &[ScenarioIteration], threshold: usize) -> f64 {
+    if run.is_empty() {
+        return 0.0;
+    }
+    run.iter().filter(|iteration| iteration.reaches_threshold(threshold)).count() as f64 / run.len() as f64
 }
 
 /// Result of a byzantine detection operation
@@ -131,6 +337,75 @@ impl ByzantineSimulator {
         
         responses
     }
+    
+    /// Play `scenario` iteration by iteration.
+    ///
+    /// Honest nodes agree on a value drawn per iteration; timing attacks are
+    /// reflected in `response_time_ms` rather than slept, so long timelines
+    /// run instantly. Adaptive nodes answer last, after every other node's
+    /// response for the round is known.
+    pub fn run_scenario(&self, scenario: &ByzantineScenario) -> Result<Vec<ScenarioIteration>> {
+        scenario.validate()?;
+        let mut rng = StdRng::seed_from_u64(scenario.seed);
+        let mut iterations = Vec::with_capacity(scenario.iterations);
+        
+        for iteration in 0..scenario.iterations {
+            let majority_value: u64 = rng.gen_range(1, 1000);
+            let honest_value = json!(majority_value);
+            let mut responses = Vec::with_capacity(scenario.node_count);
+            let mut altered = Vec::with_capacity(scenario.node_count);
+            let mut adaptive = Vec::new();
+            
+            for node in 0..scenario.node_count {
+                let mut response = NodeResponse {
+                    node_url: format!("node_{}", node),
+                    status: NodeResponseStatus::Valid,
+                    data: Some(honest_value.clone()),
+                    error: None,
+                    response_time_ms: Some(rng.gen_range(50, 200)),
+                    timestamp: Instant::now(),
+                };
+                let mut changed = false;
+                for behavior in scenario.active_faults(node, iteration) {
+                    match behavior {
+                        ByzantineBehavior::Adaptive(strategy) => adaptive.push((node, strategy.clone())),
+                        _ => changed |= apply_behavior(&mut response, behavior, majority_value, &mut rng),
+                    }
+                }
+                responses.push(response);
+                altered.push(changed);
+            }
+            
+            for (position, (node, strategy)) in adaptive.iter().enumerate() {
+                let pending: Vec<usize> = adaptive[position..].iter().map(|(node, _)| *node).collect();
+                let agreeing = responses.iter().enumerate()
+                    .filter(|(index, response)| !pending.contains(index) && agrees_with(response, &honest_value))
+                    .count();
+                let view = RoundView { iteration, node_count: scenario.node_count, agreeing, undecided: pending.len() };
+                if let Some(behavior) = strategy.choose(&view) {
+                    altered[*node] |= apply_behavior(&mut responses[*node], &behavior, majority_value, &mut rng);
+                }
+            }
+            
+            let faulty_nodes = responses.iter().zip(&altered)
+                .filter(|(_, altered)| **altered)
+                .map(|(response, _)| response.node_url.clone())
+                .collect();
+            iterations.push(ScenarioIteration { iteration, honest_value, responses, faulty_nodes });
+        }
+        
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.add_event(
+                "ByzantineSimulator",
+                AuditEventType::NetworkOperation,
+                AuditSeverity::Info,
+                &format!("Ran scenario with {} nodes, {} iterations, {} faults (seed {})",
+                    scenario.node_count, scenario.iterations, scenario.faults.len(), scenario.seed)
+            );
+        }
+        
+        Ok(iterations)
+    }
 }
 
 // Helper functions to create different Byzantine node configurations
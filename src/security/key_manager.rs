@@ -0,0 +1,179 @@
+//! Submitter key management and rotation.
+//!
+//! Holds the keypair currently used to sign L1 submissions plus an optional
+//! staged replacement. Rotating moves the gas coins owned by the current
+//! address to the next address, then switches signing over to the new key.
+
+use super::audit::{AuditEvent, AuditEventType, AuditSeverity, SecurityAuditLog};
+use crate::sui::signing::build_signed_transaction;
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use sui_sdk::{
+    rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions},
+    types::{
+        base_types::SuiAddress,
+        crypto::SuiKeyPair,
+        transaction::TransactionData,
+    },
+    SuiClient,
+};
+use sui_types::{
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    quorum_driver_types::ExecuteTransactionRequestType,
+};
+
+/// Owns the submitter keypair and handles rotation to a staged next key.
+pub struct KeyManager {
+    current: Mutex<Arc<SuiKeyPair>>,
+    next: Mutex<Option<Arc<SuiKeyPair>>>,
+    /// Addresses of retired keys, oldest first
+    previous: Mutex<Vec<SuiAddress>>,
+    audit_log: Option<Arc<SecurityAuditLog>>,
+}
+
+impl KeyManager {
+    pub fn new(current: SuiKeyPair, audit_log: Option<Arc<SecurityAuditLog>>) -> Self {
+        Self {
+            current: Mutex::new(Arc::new(current)),
+            next: Mutex::new(None),
+            previous: Mutex::new(Vec::new()),
+            audit_log,
+        }
+    }
+
+    /// Keypair to sign submissions with right now.
+    pub fn current_keypair(&self) -> Arc<SuiKeyPair> {
+        self.current.lock().unwrap().clone()
+    }
+
+    pub fn current_address(&self) -> SuiAddress {
+        SuiAddress::from(&self.current.lock().unwrap().public())
+    }
+
+    /// Addresses of keys that have been rotated out, oldest first.
+    pub fn previous_addresses(&self) -> Vec<SuiAddress> {
+        self.previous.lock().unwrap().clone()
+    }
+
+    /// Address of the staged next key, if one is set.
+    pub fn next_address(&self) -> Option<SuiAddress> {
+        self.next.lock().unwrap().as_ref().map(|kp| SuiAddress::from(&kp.public()))
+    }
+
+    /// Stage the key that the next `rotate()` will switch to, replacing any earlier one.
+    pub fn set_next_key(&self, next: SuiKeyPair) {
+        let next_address = SuiAddress::from(&next.public());
+        *self.next.lock().unwrap() = Some(Arc::new(next));
+        self.log(
+            AuditSeverity::Info,
+            &format!("Staged next submitter key {}", next_address),
+            json!({ "next_address": next_address.to_string() }),
+        );
+    }
+
+    /// Move every SUI coin from the current address to the staged key, then
+    /// start signing with it. Returns the digest of the transfer transaction.
+    ///
+    /// The current key stays active if the transfer fails, so a failed
+    /// rotation never leaves the submitter without gas.
+    pub async fn rotate(&self, sui_client: &SuiClient, gas_budget: u64) -> Result<String> {
+        let next = self.next.lock().unwrap().clone()
+            .ok_or_else(|| anyhow!("No next key staged for rotation"))?;
+        let current = self.current_keypair();
+        let old_address = SuiAddress::from(&current.public());
+        let new_address = SuiAddress::from(&next.public());
+        if old_address == new_address {
+            return Err(anyhow!("Next key has the same address as the current key ({})", old_address));
+        }
+
+        let result = self.transfer_gas(sui_client, current.as_ref(), old_address, new_address, gas_budget).await;
+        let digest = match result {
+            Ok(digest) => digest,
+            Err(e) => {
+                self.log(
+                    AuditSeverity::Error,
+                    &format!("Key rotation from {} to {} failed: {:#}", old_address, new_address, e),
+                    json!({ "old_address": old_address.to_string(), "new_address": new_address.to_string() }),
+                );
+                return Err(e);
+            }
+        };
+
+        *self.current.lock().unwrap() = next;
+        *self.next.lock().unwrap() = None;
+        self.previous.lock().unwrap().push(old_address);
+
+        println!("Rotated submitter key: {} -> {} (transfer digest {})", old_address, new_address, digest);
+        self.log(
+            AuditSeverity::Warning,
+            &format!("Rotated submitter key from {} to {}", old_address, new_address),
+            json!({
+                "old_address": old_address.to_string(),
+                "new_address": new_address.to_string(),
+                "transfer_digest": digest,
+            }),
+        );
+        Ok(digest)
+    }
+
+    /// Send all SUI owned by `from` to `to` in a single pay-all transaction.
+    async fn transfer_gas(
+        &self,
+        sui_client: &SuiClient,
+        signer: &SuiKeyPair,
+        from: SuiAddress,
+        to: SuiAddress,
+        gas_budget: u64,
+    ) -> Result<String> {
+        // Owners with many coin objects get them over several pages
+        let mut coin_refs = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = sui_client.coin_read_api().get_coins(from, None, cursor, None).await
+                .context(format!("Failed to fetch gas coins for {}", from))?;
+            coin_refs.extend(page.data.iter().map(|coin| coin.object_ref()));
+            if !page.has_next_page || page.next_cursor.is_none() {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        if coin_refs.is_empty() {
+            return Err(anyhow!("No gas coins owned by {} to transfer", from));
+        }
+
+        let gas_price = sui_client.read_api().get_reference_gas_price().await
+            .context("Failed to get reference gas price")?;
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.pay_all_sui(to);
+            builder.finish()
+        };
+        let tx_data = TransactionData::new_programmable(from, coin_refs, pt, gas_budget, gas_price);
+
+        let response = sui_client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                build_signed_transaction(tx_data, &[signer]),
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .context("Failed to execute gas transfer transaction")?;
+
+        let digest = response.digest.to_string();
+        let effects = response.effects.context("Missing effects in gas transfer response")?;
+        match effects.status() {
+            SuiExecutionStatus::Success => Ok(digest),
+            SuiExecutionStatus::Failure { error } => Err(anyhow!("Gas transfer {} failed: {}", digest, error)),
+        }
+    }
+
+    fn log(&self, severity: AuditSeverity, message: &str, context: serde_json::Value) {
+        if let Some(log) = &self.audit_log {
+            let event = AuditEvent::new(AuditEventType::ConfigChange, severity, "KeyManager", message)
+                .with_context("key_rotation", context);
+            let _ = log.log_event(event);
+        }
+    }
+}
@@ -1,15 +1,19 @@
 pub mod audit;
+pub mod alerts;
 pub mod model;
 pub mod verification;
 pub mod property_tests;
 pub mod config;
 pub mod byzantine_simulator;
+pub mod key_manager;
 
 // Re-export security types
-pub use audit::{SecurityAuditLog, AuditEvent, AuditEventType, AuditSeverity};
+pub use audit::{SecurityAuditLog, AuditEvent, AuditEventType, AuditFilter, AuditSeverity};
+pub use alerts::{AlertSink, StderrAlertSink, WebhookAlertSink};
 pub use model::{SecurityModel, TrustAssumption, SecurityThreat, SecurityGuarantee, SecurityDelegationWithVerification};
 pub use verification::{FormalProperty, PropertyType, VerificationStatus, VerificationResult, VerificationTechnique};
 pub use config::{SecurityConfiguration, SecurityLevel};
-pub use byzantine_simulator::{ByzantineSimulator, ByzantineNode, ByzantineBehavior};
+pub use byzantine_simulator::{ByzantineSimulator, ByzantineNode, ByzantineBehavior, ByzantineScenario, ScenarioIteration, AdversaryStrategy, RoundView};
+pub use key_manager::KeyManager;
 // Import Byzantine detector types directly from sui module
-pub use crate::sui::byzantine::{ByzantineDetector, NodeResponse, NodeResponseStatus};
+pub use crate::sui::byzantine::{ByzantineDetector, NodeResponse, NodeResponseStatus, NodeVerdict};
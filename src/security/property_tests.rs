@@ -2,12 +2,215 @@ use anyhow::Result;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use rand::Rng;
+use proptest::collection::{hash_map, vec};
+use proptest::option;
 use proptest::prelude::*;
+use proptest::test_runner::TestRunner;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 
 use crate::security::verification::{VerificationStatus};
 use crate::security::verification::create_verification_framework;
 use crate::security::model::{SecurityGuarantee};
-use crate::transaction::types::Transaction;
+use crate::transaction::types::{ExternalQuery, QueryCondition, Transaction, TransactionType};
+
+/// An invariant over a single transaction; the error describes the violation
+pub type TransactionInvariant = fn(&Transaction) -> Result<(), String>;
+
+/// Invariants `PropertyTestingProver` fuzzes for `integrity_verification`
+pub const INTEGRITY_INVARIANTS: [(&str, TransactionInvariant); 3] = [
+    ("validation_is_total", check_validation_is_total),
+    ("digest_ignores_field_order", check_digest_ignores_field_order),
+    ("digest_tracks_attested_fields", check_digest_tracks_attested_fields),
+];
+
+/// Addresses and object IDs: well formed, too short, or arbitrary text
+fn arb_address() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "0x[0-9a-f]{64}",
+        "0x[0-9a-f]{1,8}",
+        any::<String>(),
+    ]
+}
+
+/// Query paths and URLs that try to break out of their JSON context
+fn arb_adversarial_text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("{\"$ne\": null}".to_string()),
+        Just("__proto__".to_string()),
+        Just("\"]}\u{0}".to_string()),
+        Just("../../".to_string()),
+        any::<String>(),
+    ]
+}
+
+/// Arbitrary JSON nested up to three levels
+fn arb_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<String>().prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 16, 4, |inner| prop_oneof![
+        vec(inner.clone(), 0..4).prop_map(Value::Array),
+        hash_map(".{0,8}", inner, 0..4).prop_map(|map| Value::Object(map.into_iter().collect())),
+    ])
+}
+
+fn arb_condition() -> impl Strategy<Value = QueryCondition> {
+    let operator = prop_oneof![Just("gt".to_string()), Just("lt".to_string()), Just("eq".to_string()), ".{0,4}"];
+    (any::<u64>(), operator).prop_map(|(threshold, operator)| QueryCondition { threshold, operator })
+}
+
+fn arb_external_query() -> impl Strategy<Value = ExternalQuery> {
+    let url = prop_oneof!["https://[a-z]{1,10}\\.com/[a-z0-9/]{0,20}", arb_adversarial_text()];
+    (url, vec(arb_adversarial_text(), 0..4), option::of(arb_condition()))
+        .prop_map(|(url, path, condition)| ExternalQuery { url, path, condition })
+}
+
+fn arb_tx_type() -> impl Strategy<Value = TransactionType> {
+    prop_oneof![
+        Just(TransactionType::Transfer),
+        Just(TransactionType::Invoke),
+        "[a-z_]{0,12}".prop_map(TransactionType::Custom),
+        (arb_external_query(), arb_condition(), any::<u64>()).prop_map(|(oracle_query, condition, payout_amount)| {
+            TransactionType::ConditionalPayout { oracle_query, condition, payout_amount }
+        }),
+    ]
+}
+
+/// Fuzzed transactions: boundary amounts and gas budgets, malformed addresses,
+/// arbitrary script text in any (or an unknown) language, and adversarial
+/// external queries and Python parameters.
+pub fn arb_transaction() -> impl Strategy<Value = Transaction> {
+    let amount = prop_oneof![Just(0u64), Just(u64::MAX), any::<u64>()];
+    let gas_budget = prop_oneof![Just(0u64), Just(1u64), Just(u64::MAX), 1..100_000_000u64];
+    let language = option::of(prop_oneof![
+        Just("javascript".to_string()),
+        Just("python".to_string()),
+        Just("native".to_string()),
+        ".{0,10}",
+    ]);
+    let core = (arb_tx_type(), arb_address(), arb_address(), amount, arb_address(), gas_budget, vec(".{0,16}", 0..3), any::<u64>());
+    let execution = (language, option::of(".{0,64}"), option::of(".{0,64}"), option::of(arb_json()), option::of(arb_external_query()));
+    let extras = (
+        option::of(vec(".{0,16}", 0..3)),
+        option::of(any::<String>()),
+        option::of(any::<String>()),
+        any::<bool>(),
+        option::of("[a-z0-9-]{1,16}"),
+        option::of(any::<u64>()),
+        option::of("0x[0-9a-f]{1,4}::[a-z]{1,8}::[A-Z]{1,8}"),
+        option::of(any::<u8>()),
+    );
+
+    (core, execution, extras).prop_map(|(core, execution, extras)| {
+        let (tx_type, sender, receiver, amount, gas_payment, gas_budget, commands, timestamp) = core;
+        let (language, script, python_code, python_params, external_query) = execution;
+        let (signatures, websocket_endpoint, websocket_message, critical, client_request_id, valid_until, coin_type, decimals) = extras;
+        Transaction {
+            tx_type,
+            sender,
+            receiver,
+            amount,
+            gas_payment,
+            gas_budget,
+            commands,
+            signatures,
+            timestamp,
+            script,
+            language,
+            python_code,
+            python_params,
+            external_query,
+            websocket_endpoint,
+            websocket_message,
+            time_condition: None,
+            critical,
+            client_request_id,
+            valid_until,
+            coin_type,
+            decimals,
+        }
+    })
+}
+
+/// `validate` and the digest functions return for any input rather than panicking.
+pub fn check_validation_is_total(tx: &Transaction) -> Result<(), String> {
+    catch_unwind(AssertUnwindSafe(|| {
+        let _ = tx.validate();
+        let _ = tx.payload_hash();
+        let _ = tx.digest();
+    }))
+    .map_err(|_| "validation or hashing panicked".to_string())
+}
+
+/// Parsing the same JSON with its fields in a different order yields the same digest.
+pub fn check_digest_ignores_field_order(tx: &Transaction) -> Result<(), String> {
+    let value = serde_json::to_value(tx).map_err(|e| e.to_string())?;
+    let fields = value.as_object().ok_or("transaction did not serialize to an object")?;
+    // `Map` iterates in sorted order, so reversing it differs from the declaration order
+    let reordered: Vec<String> = fields.iter().rev()
+        .map(|(key, value)| format!("{}:{}", Value::String(key.clone()), value))
+        .collect();
+    let reordered = format!("{{{}}}", reordered.join(","));
+    let parsed: Transaction = serde_json::from_str(&reordered).map_err(|e| e.to_string())?;
+    if parsed.digest().map_err(|e| e.to_string())? == tx.digest().map_err(|e| e.to_string())? {
+        Ok(())
+    } else {
+        Err("digest changed when the JSON fields were reordered".to_string())
+    }
+}
+
+/// Mutating any attested field changes the digest; mutating a volatile one doesn't.
+pub fn check_digest_tracks_attested_fields(tx: &Transaction) -> Result<(), String> {
+    let original = tx.digest().map_err(|e| e.to_string())?;
+    let attested: [(&str, fn(&mut Transaction)); 9] = [
+        ("amount", |tx| tx.amount = tx.amount.wrapping_add(1)),
+        ("receiver", |tx| tx.receiver.push('0')),
+        ("gas_budget", |tx| tx.gas_budget ^= 1),
+        ("timestamp", |tx| tx.timestamp ^= 1),
+        ("commands", |tx| tx.commands.push(String::new())),
+        ("valid_until", |tx| tx.valid_until = Some(tx.valid_until.map_or(0, |deadline| deadline ^ 1))),
+        ("decimals", |tx| tx.decimals = Some(tx.decimals.map_or(0, |decimals| decimals ^ 1))),
+        ("coin_type", |tx| tx.coin_type = Some(format!("{}0", tx.coin_type.as_deref().unwrap_or_default()))),
+        ("script", |tx| tx.script = Some(format!("{}\n", tx.script.as_deref().unwrap_or_default()))),
+    ];
+    let volatile: [(&str, fn(&mut Transaction)); 4] = [
+        ("signatures", |tx| tx.signatures = Some(vec!["mutated".to_string()])),
+        ("python_params", |tx| tx.python_params = Some(json!({ "mutated": true }))),
+        ("critical", |tx| tx.critical = !tx.critical),
+        ("client_request_id", |tx| tx.client_request_id = Some("mutated".to_string())),
+    ];
+
+    for (field, mutate) in attested {
+        let mut mutant = tx.clone();
+        mutate(&mut mutant);
+        if mutant.digest().map_err(|e| e.to_string())? == original {
+            return Err(format!("changing {} did not change the digest", field));
+        }
+    }
+    for (field, mutate) in volatile {
+        let mut mutant = tx.clone();
+        mutate(&mut mutant);
+        if mutant.digest().map_err(|e| e.to_string())? != original {
+            return Err(format!("changing {} changed the digest", field));
+        }
+    }
+    Ok(())
+}
+
+/// Check `invariant` against `cases` generated transactions.
+///
+/// On failure the error includes the shrunk counterexample.
+pub fn fuzz_transactions(cases: u32, invariant: TransactionInvariant) -> Result<(), String> {
+    let mut config = ProptestConfig::with_cases(cases);
+    // Counterexamples are reported in the error instead of a regressions file
+    config.failure_persistence = None;
+    TestRunner::new(config)
+        .run(&arb_transaction(), |tx| invariant(&tx).map_err(TestCaseError::fail))
+        .map_err(|e| e.to_string())
+}
 
 /// Generate a random transaction for property testing
 pub fn generate_random_transaction() -> Transaction {
@@ -30,6 +233,11 @@ pub fn generate_random_transaction() -> Transaction {
         websocket_endpoint: None,
         websocket_message: None,
         time_condition: None,
+        critical: false,
+        client_request_id: None,
+        valid_until: None,
+        coin_type: None,
+        decimals: None,
         language: None,
     }
 }
@@ -90,7 +298,7 @@ fn generate_random_violations(count: usize) -> Vec<Value> {
 }
 
 /// Run property-based tests for the verification framework
-pub fn run_property_tests(iterations: usize) -> Result<PropertyTestResults> {
+pub async fn run_property_tests(iterations: usize) -> Result<PropertyTestResults> {
     println!("Running property-based tests for verification framework...");
     
     // Initialize verification framework
@@ -142,7 +350,7 @@ pub fn run_property_tests(iterations: usize) -> Result<PropertyTestResults> {
         let mut context = generate_random_security_context();
         
         // Verify transaction properties
-        let verification_results = framework.verify_transaction_properties(&tx, &mut context)?;
+        let verification_results = framework.verify_transaction_properties(&tx, &mut context).await?;
         
         // Count test as passed if at least one property is verified
         let any_verified = verification_results.values().any(|results| {
@@ -363,11 +571,11 @@ impl PropertyTestResults {
 }
 
 /// Example of running property-based tests
-pub fn demonstrate_property_testing() -> Result<()> {
+pub async fn demonstrate_property_testing() -> Result<()> {
     println!("Demonstrating property-based testing for security properties...");
     
     // Run a small number of tests for demonstration
-    let results = run_property_tests(100)?;
+    let results = run_property_tests(100).await?;
     
     // Print summary
     results.print_summary();
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use anyhow::{Result, anyhow};
 use serde::{Serialize, Deserialize};
 use serde_json::Value;
 use crate::security::model::{SecurityProperty, /* TrustActor, */ SecurityGuarantee};
 use crate::security::audit::{SecurityAuditLog, AuditSeverity};
 use crate::transaction::types::Transaction;
+use crate::security::property_tests::{fuzz_transactions, INTEGRITY_INVARIANTS};
 use crate::metrics::performance::PerformanceMetrics;
 use crate::security::audit::{AuditEventType};
 use sui_sdk::SuiClient;
@@ -107,6 +109,8 @@ pub enum VerificationTechnique {
     PropertyTesting,
     /// Manual proof
     ManualProof,
+    /// Technique of a user-supplied prover, e.g. "SMT Solving"
+    Custom(String),
 }
 
 impl fmt::Display for VerificationTechnique {
@@ -118,10 +122,114 @@ impl fmt::Display for VerificationTechnique {
             VerificationTechnique::RuntimeVerification => write!(f, "Runtime Verification"),
             VerificationTechnique::PropertyTesting => write!(f, "Property-Based Testing"),
             VerificationTechnique::ManualProof => write!(f, "Manual Proof"),
+            VerificationTechnique::Custom(name) => write!(f, "{}", name),
         }
     }
 }
 
+/// How per-prover results for one property collapse into a single verdict
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum AggregationPolicy {
+    /// Verified if any prover verified it (the historical behaviour)
+    #[default]
+    AnyVerified,
+    /// Verified only if every prover verified it; any falsification wins
+    AllVerified,
+    /// Verified or falsified by more than half of the provers
+    MajorityVerified,
+    /// Like `MajorityVerified`, but each result counts with the weight of its
+    /// technique (keyed by the technique's display name, 1.0 if unlisted)
+    WeightedByTechnique(HashMap<String, f64>),
+}
+
+impl AggregationPolicy {
+    /// Weighted policy favouring exhaustive techniques over sampling ones
+    pub fn default_technique_weights() -> Self {
+        let weights = [
+            (VerificationTechnique::ModelChecking, 3.0),
+            (VerificationTechnique::TheoremProving, 3.0),
+            (VerificationTechnique::AbstractInterpretation, 2.0),
+            (VerificationTechnique::RuntimeVerification, 2.0),
+            (VerificationTechnique::PropertyTesting, 1.0),
+            (VerificationTechnique::ManualProof, 1.0),
+        ];
+        AggregationPolicy::WeightedByTechnique(
+            weights.iter().map(|(technique, weight)| (technique.to_string(), *weight)).collect()
+        )
+    }
+
+    /// Single verdict for `results`, which should all be for the same property
+    pub fn aggregate(&self, results: &[VerificationResult]) -> VerificationStatus {
+        if results.is_empty() {
+            return VerificationStatus::Inconclusive("No prover results".to_string());
+        }
+
+        let first_falsification = results.iter().find_map(|r| match &r.status {
+            VerificationStatus::Falsified(reason) => Some(reason.clone()),
+            _ => None,
+        });
+        let verified = |r: &&VerificationResult| r.status == VerificationStatus::Verified;
+
+        match self {
+            AggregationPolicy::AnyVerified => {
+                if results.iter().any(|r| r.status == VerificationStatus::Verified) {
+                    VerificationStatus::Verified
+                } else if let Some(reason) = first_falsification {
+                    VerificationStatus::Falsified(reason)
+                } else {
+                    VerificationStatus::Inconclusive("No definitive result from any prover".to_string())
+                }
+            }
+            AggregationPolicy::AllVerified => {
+                if let Some(reason) = first_falsification {
+                    VerificationStatus::Falsified(reason)
+                } else {
+                    let count = results.iter().filter(verified).count();
+                    if count == results.len() {
+                        VerificationStatus::Verified
+                    } else {
+                        VerificationStatus::Inconclusive(format!("{} of {} provers verified", count, results.len()))
+                    }
+                }
+            }
+            AggregationPolicy::MajorityVerified => Self::weighted_verdict(results, |_| 1.0, first_falsification),
+            AggregationPolicy::WeightedByTechnique(weights) => Self::weighted_verdict(
+                results,
+                |r| result_technique(r).and_then(|t| weights.get(t)).copied().unwrap_or(1.0),
+                first_falsification,
+            ),
+        }
+    }
+
+    fn weighted_verdict(
+        results: &[VerificationResult],
+        weight: impl Fn(&VerificationResult) -> f64,
+        first_falsification: Option<String>,
+    ) -> VerificationStatus {
+        let total: f64 = results.iter().map(&weight).sum();
+        let verified: f64 = results.iter().filter(|r| r.status == VerificationStatus::Verified).map(&weight).sum();
+        let falsified: f64 = results.iter()
+            .filter(|r| matches!(r.status, VerificationStatus::Falsified(_)))
+            .map(&weight)
+            .sum();
+
+        if verified > total / 2.0 {
+            VerificationStatus::Verified
+        } else if falsified > total / 2.0 {
+            VerificationStatus::Falsified(first_falsification.unwrap_or_default())
+        } else {
+            VerificationStatus::Inconclusive(format!(
+                "No majority: {:.1} verified, {:.1} falsified of {:.1}", verified, falsified, total
+            ))
+        }
+    }
+}
+
+/// Technique a result was produced with, as recorded in its evidence
+fn result_technique(result: &VerificationResult) -> Option<&str> {
+    result.evidence.as_ref()?.get("technique")?.as_str()
+}
+
 /// Property prover trait
 pub trait PropertyProver: Send + Sync {
     /// Get the name of the prover
@@ -421,6 +529,22 @@ impl PropertyTestingProver {
         Ok(false)
     }
     
+    /// Fuzz the transaction invariants, and check them against the context's transaction too
+    ///
+    /// Returns the first violation, with its (shrunk) counterexample.
+    fn fuzz_integrity_invariants(&self, context: &Value) -> std::result::Result<(), String> {
+        let observed = context.get("transaction")
+            .and_then(|tx| serde_json::from_value::<Transaction>(tx.clone()).ok());
+        let cases = u32::try_from(self.num_test_cases).unwrap_or(u32::MAX);
+        for (name, invariant) in INTEGRITY_INVARIANTS {
+            if let Some(tx) = &observed {
+                invariant(tx).map_err(|reason| format!("{} violated by the transaction under test: {}", name, reason))?;
+            }
+            fuzz_transactions(cases, invariant).map_err(|reason| format!("{} violated: {}", name, reason))?;
+        }
+        Ok(())
+    }
+    
     /// Test external data validation
     fn test_external_data_validation(&self, context: &Value) -> Result<bool> {
         // In a real implementation, this would generate test cases
@@ -476,10 +600,13 @@ impl PropertyProver for PropertyTestingProver {
         // Test the property
         let status = match property.name.as_str() {
             "integrity_verification" => {
-                if self.test_transaction_integrity(context)? {
-                    VerificationStatus::Verified
-                } else {
+                if !self.test_transaction_integrity(context)? {
                     VerificationStatus::Falsified("Transaction integrity verification failed".to_string())
+                } else {
+                    match self.fuzz_integrity_invariants(context) {
+                        Ok(()) => VerificationStatus::Verified,
+                        Err(reason) => VerificationStatus::Falsified(reason),
+                    }
                 }
             },
             "external_data_validation" => {
@@ -706,10 +833,17 @@ impl PropertyProver for RuntimeVerificationProver {
     }
 }
 
+/// How long a single prover may spend on one property by default
+pub const DEFAULT_PROVER_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Formal verification framework
 pub struct FormalVerificationFramework {
     /// Available provers
-    provers: Vec<Box<dyn PropertyProver>>,
+    provers: Vec<Arc<dyn PropertyProver>>,
+    /// Time bound for each prover call
+    prover_timeout: Duration,
+    /// How results from several provers become one verdict
+    aggregation_policy: AggregationPolicy,
     /// Formal properties
     properties: Vec<FormalProperty>,
     /// Verification results
@@ -723,6 +857,8 @@ impl FormalVerificationFramework {
     pub fn new(audit_log: Option<Arc<SecurityAuditLog>>) -> Self {
         let mut framework = Self {
             provers: Vec::new(),
+            prover_timeout: DEFAULT_PROVER_TIMEOUT,
+            aggregation_policy: AggregationPolicy::default(),
             properties: Vec::new(),
             results: Arc::new(Mutex::new(Vec::new())),
             audit_log,
@@ -737,7 +873,7 @@ impl FormalVerificationFramework {
     /// Add a prover
     pub fn add_prover(&mut self, prover: Box<dyn PropertyProver>) {
         let prover_name = prover.name().to_string();
-        self.provers.push(prover);
+        self.provers.push(Arc::from(prover));
         
         if let Some(log) = &self.audit_log {
             let _ = log.log_verification(
@@ -749,6 +885,41 @@ impl FormalVerificationFramework {
         }
     }
     
+    /// Remove the prover called `name`, returning it if it was registered
+    pub fn remove_prover(&mut self, name: &str) -> Option<Arc<dyn PropertyProver>> {
+        let index = self.provers.iter().position(|p| p.name() == name)?;
+        let prover = self.provers.remove(index);
+
+        if let Some(log) = &self.audit_log {
+            let _ = log.log_verification(
+                "VerificationFramework",
+                &format!("Removed prover '{}'", name),
+                None,
+                AuditSeverity::Info
+            );
+        }
+        Some(prover)
+    }
+
+    /// Bound each prover call to `timeout`; slower calls come back inconclusive
+    pub fn set_prover_timeout(&mut self, timeout: Duration) {
+        self.prover_timeout = timeout;
+    }
+
+    pub fn set_aggregation_policy(&mut self, policy: AggregationPolicy) {
+        self.aggregation_policy = policy;
+    }
+
+    /// Verdict for one property's results under the framework's aggregation policy
+    pub fn aggregate(&self, results: &[VerificationResult]) -> VerificationStatus {
+        self.aggregation_policy.aggregate(results)
+    }
+
+    /// Names of the registered provers, in registration order
+    pub fn prover_names(&self) -> Vec<&str> {
+        self.provers.iter().map(|p| p.name()).collect()
+    }
+    
     /// Add a formal property to verify
     pub fn add_property(&mut self, property: FormalProperty) {
         let property_name = property.name.clone();
@@ -828,14 +999,14 @@ impl FormalVerificationFramework {
     }
     
     /// Verify a property using all suitable provers
-    pub fn verify_property(&self, property_name: &str, context: &Value) -> Result<Vec<VerificationResult>> {
+    pub async fn verify_property(&self, property_name: &str, context: &Value) -> Result<Vec<VerificationResult>> {
         // Find the property
         let property = self.properties.iter()
             .find(|p| p.name == property_name)
             .ok_or_else(|| anyhow!("Unknown property: {}", property_name))?;
         
         // Find suitable provers
-        let suitable_provers: Vec<&Box<dyn PropertyProver>> = self.provers.iter()
+        let suitable_provers: Vec<&Arc<dyn PropertyProver>> = self.provers.iter()
             .filter(|p| p.supports_property(property))
             .collect();
         
@@ -858,7 +1029,7 @@ impl FormalVerificationFramework {
         let mut results = Vec::new();
         
         for prover in suitable_provers {
-            match prover.verify_property(property, context) {
+            match self.run_prover(prover, property, context).await {
                 Ok(result) => {
                     // Store the result
                     let mut framework_results = self.results.lock().unwrap();
@@ -884,12 +1055,57 @@ impl FormalVerificationFramework {
         Ok(results)
     }
     
+    /// Run one prover on a blocking task, bounded by the prover timeout.
+    ///
+    /// A prover that overruns yields `Inconclusive("timeout")`. Its blocking
+    /// task can't be cancelled and keeps running in the background until the
+    /// prover returns, so provers should still check their own limits.
+    async fn run_prover(
+        &self,
+        prover: &Arc<dyn PropertyProver>,
+        property: &FormalProperty,
+        context: &Value,
+    ) -> Result<VerificationResult> {
+        let start_time = Instant::now();
+        let task = {
+            let (prover, property, context) = (prover.clone(), property.clone(), context.clone());
+            tokio::task::spawn_blocking(move || prover.verify_property(&property, &context))
+        };
+
+        match tokio::time::timeout(self.prover_timeout, task).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => Err(anyhow!("Prover '{}' panicked: {}", prover.name(), e)),
+            Err(_) => {
+                if let Some(log) = &self.audit_log {
+                    let _ = log.log_validation(
+                        "FormalVerificationFramework",
+                        &format!("Prover '{}' timed out after {:?} on property '{}'",
+                            prover.name(), self.prover_timeout, property.name),
+                        None,
+                        AuditSeverity::Warning
+                    );
+                }
+                Ok(VerificationResult {
+                    property: property.clone(),
+                    status: VerificationStatus::Inconclusive("timeout".to_string()),
+                    evidence: Some(serde_json::json!({
+                        "prover": prover.name(),
+                        "technique": prover.technique().to_string(),
+                        "timeout_ms": self.prover_timeout.as_millis() as u64,
+                    })),
+                    timestamp: chrono::Utc::now().timestamp() as u64,
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                })
+            }
+        }
+    }
+    
     /// Verify all properties using suitable provers
-    pub fn verify_all_properties(&self, context: &Value) -> Result<HashMap<String, Vec<VerificationResult>>> {
+    pub async fn verify_all_properties(&self, context: &Value) -> Result<HashMap<String, Vec<VerificationResult>>> {
         let mut all_results = HashMap::new();
         
         for property in &self.properties {
-            match self.verify_property(&property.name, context) {
+            match self.verify_property(&property.name, context).await {
                 Ok(results) => {
                     all_results.insert(property.name.clone(), results);
                 },
@@ -911,7 +1127,7 @@ impl FormalVerificationFramework {
     }
     
     /// Verify properties related to a security guarantee
-    pub fn verify_security_guarantee(
+    pub async fn verify_security_guarantee(
         &self, 
         guarantee: &SecurityGuarantee,
         context: &Value
@@ -942,7 +1158,7 @@ impl FormalVerificationFramework {
         let mut all_results = HashMap::new();
         
         for property in related_properties {
-            match self.verify_property(&property.name, context) {
+            match self.verify_property(&property.name, context).await {
                 Ok(results) => {
                     all_results.insert(property.name.clone(), results);
                 },
@@ -964,7 +1180,7 @@ impl FormalVerificationFramework {
     }
     
     /// Verify properties for a transaction
-    pub fn verify_transaction_properties(
+    pub async fn verify_transaction_properties(
         &self,
         tx: &Transaction,
         context: &mut Value
@@ -996,7 +1212,7 @@ impl FormalVerificationFramework {
         }
         
         // Verify all properties
-        self.verify_all_properties(context)
+        self.verify_all_properties(context).await
     }
     
     /// Check if a security guarantee is verified
@@ -1019,10 +1235,8 @@ impl FormalVerificationFramework {
         // Check if all related properties are verified
         for property in related_properties {
             if let Some(property_results) = results.get(&property.name) {
-                // Check if any prover verified the property
-                let verified = property_results.iter().any(|r| {
-                    r.status == VerificationStatus::Verified
-                });
+                // Collapse the provers' results according to the aggregation policy
+                let verified = self.aggregate(property_results) == VerificationStatus::Verified;
                 
                 if !verified {
                     return false;
@@ -1052,29 +1266,120 @@ impl FormalVerificationFramework {
     }
 }
 
+/// Provers shipped with the framework
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinProver {
+    /// `ModelCheckingProver` named "BasicModelChecker"
+    ModelChecking,
+    /// `PropertyTestingProver` named "PropertyTester", 100 iterations
+    PropertyTesting,
+    /// `RuntimeVerificationProver` named "RuntimeVerifier"
+    RuntimeVerification,
+}
+
+impl BuiltinProver {
+    /// All built-in provers, in the order `create_verification_framework` registers them
+    pub const ALL: [BuiltinProver; 3] = [
+        BuiltinProver::ModelChecking,
+        BuiltinProver::PropertyTesting,
+        BuiltinProver::RuntimeVerification,
+    ];
+
+    /// Name the prover registers under
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinProver::ModelChecking => "BasicModelChecker",
+            BuiltinProver::PropertyTesting => "PropertyTester",
+            BuiltinProver::RuntimeVerification => "RuntimeVerifier",
+        }
+    }
+
+    fn create(&self, audit_log: Option<Arc<SecurityAuditLog>>) -> Box<dyn PropertyProver> {
+        match self {
+            BuiltinProver::ModelChecking => Box::new(ModelCheckingProver::new(self.name(), audit_log)),
+            BuiltinProver::PropertyTesting => Box::new(PropertyTestingProver::new(self.name(), Some(100), audit_log)),
+            BuiltinProver::RuntimeVerification => Box::new(RuntimeVerificationProver::new(self.name(), audit_log)),
+        }
+    }
+}
+
+/// Assembles a `FormalVerificationFramework` from built-in and custom provers
+///
+/// Provers are registered in the order they were added. Removing a name drops
+/// it whether it came from a built-in or a custom prover.
+pub struct VerificationFrameworkBuilder {
+    audit_log: Option<Arc<SecurityAuditLog>>,
+    provers: Vec<Box<dyn PropertyProver>>,
+    prover_timeout: Duration,
+    aggregation_policy: AggregationPolicy,
+}
+
+impl VerificationFrameworkBuilder {
+    /// Builder with no provers
+    pub fn new(audit_log: Option<Arc<SecurityAuditLog>>) -> Self {
+        Self {
+            audit_log,
+            provers: Vec::new(),
+            prover_timeout: DEFAULT_PROVER_TIMEOUT,
+            aggregation_policy: AggregationPolicy::default(),
+        }
+    }
+
+    /// Include a built-in prover
+    pub fn with_builtin(mut self, builtin: BuiltinProver) -> Self {
+        self.provers.push(builtin.create(self.audit_log.clone()));
+        self
+    }
+
+    /// Include every built-in prover
+    pub fn with_builtins(self) -> Self {
+        BuiltinProver::ALL.iter().fold(self, |builder, builtin| builder.with_builtin(*builtin))
+    }
+
+    /// Include a custom prover, such as an SMT or domain-specific prover
+    pub fn with_prover(mut self, prover: Box<dyn PropertyProver>) -> Self {
+        self.provers.push(prover);
+        self
+    }
+
+    /// Drop any prover added so far under `name`
+    pub fn without_prover(mut self, name: &str) -> Self {
+        self.provers.retain(|p| p.name() != name);
+        self
+    }
+
+    /// Time bound for each prover call (`DEFAULT_PROVER_TIMEOUT` otherwise)
+    pub fn with_prover_timeout(mut self, timeout: Duration) -> Self {
+        self.prover_timeout = timeout;
+        self
+    }
+
+    /// How per-prover results are combined (`AggregationPolicy::AnyVerified` otherwise)
+    pub fn with_aggregation_policy(mut self, policy: AggregationPolicy) -> Self {
+        self.aggregation_policy = policy;
+        self
+    }
+
+    pub fn build(self) -> FormalVerificationFramework {
+        let mut framework = FormalVerificationFramework::new(self.audit_log);
+        framework.set_prover_timeout(self.prover_timeout);
+        framework.set_aggregation_policy(self.aggregation_policy);
+        for prover in self.provers {
+            framework.add_prover(prover);
+        }
+        framework
+    }
+}
+
 /// Create a verification framework with common provers
 pub fn create_verification_framework(
     audit_log: Option<Arc<SecurityAuditLog>>
 ) -> FormalVerificationFramework {
-    let mut framework = FormalVerificationFramework::new(audit_log.clone());
-    
-    // Add model checking prover
-    let model_checker = ModelCheckingProver::new("BasicModelChecker", audit_log.clone());
-    framework.add_prover(Box::new(model_checker));
-    
-    // Add property-based testing prover
-    let property_tester = PropertyTestingProver::new("PropertyTester", Some(100), audit_log.clone());
-    framework.add_prover(Box::new(property_tester));
-    
-    // Add runtime verification prover
-    let runtime_verifier = RuntimeVerificationProver::new("RuntimeVerifier", audit_log.clone());
-    framework.add_prover(Box::new(runtime_verifier));
-    
-    framework
+    VerificationFrameworkBuilder::new(audit_log).with_builtins().build()
 }
 
 /// Example of verifying security properties
-pub fn demonstrate_security_verification(
+pub async fn demonstrate_security_verification(
     framework: &FormalVerificationFramework
 ) -> Result<()> {
     println!("Demonstrating formal security verification");
@@ -1097,7 +1402,7 @@ pub fn demonstrate_security_verification(
     
     // Verify integrity property
     println!("\nVerifying integrity verification property:");
-    let results = framework.verify_property("integrity_verification", &context)?;
+    let results = framework.verify_property("integrity_verification", &context).await?;
     
     for result in &results {
         println!("  {} via {}: {}", 
@@ -1108,7 +1413,7 @@ pub fn demonstrate_security_verification(
     
     // Verify all properties
     println!("\nVerifying all security properties:");
-    let all_results = framework.verify_all_properties(&context)?;
+    let all_results = framework.verify_all_properties(&context).await?;
     
     for (property, results) in &all_results {
         let best_result = results.iter()
@@ -1139,24 +1444,12 @@ pub fn demonstrate_security_verification(
 }
 
 /// Verify a property with the given context
-pub fn verify_property_with_context(property: &str, context: &Value) -> Result<VerificationStatus, anyhow::Error> {
+pub async fn verify_property_with_context(property: &str, context: &Value) -> Result<VerificationStatus, anyhow::Error> {
     let framework = create_verification_framework(None);
     
     // Find the property by name
-    let property_results = framework.verify_property(property, context)?;
-    
-    // If at least one prover verified the property, consider it verified
-    if property_results.iter().any(|r| r.status == VerificationStatus::Verified) {
-        return Ok(VerificationStatus::Verified);
-    } 
-    
-    // If at least one prover falsified the property, consider it falsified
-    if let Some(result) = property_results.iter().find(|r| matches!(r.status, VerificationStatus::Falsified(_))) {
-        if let VerificationStatus::Falsified(reason) = &result.status {
-            return Ok(VerificationStatus::Falsified(reason.clone()));
-        }
-    }
+    let property_results = framework.verify_property(property, context).await?;
     
-    // Otherwise, inconclusive
-    Ok(VerificationStatus::Inconclusive("No definitive result from any prover".to_string()))
+    // One prover verifying is enough, otherwise any falsification wins
+    Ok(AggregationPolicy::AnyVerified.aggregate(&property_results))
 }
\ No newline at end of file
@@ -1,21 +1,32 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use std::time::{Duration, Instant};
 use std::collections::{HashMap};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use reqwest;
 use serde_json::{json, Value};
 use crate::security::audit::{SecurityAuditLog, AuditSeverity, AuditEventType};
 use crate::sui::verification::VerificationStatus;
 use crate::transaction::types::SignatureBytes;
+use crate::utils::{canonical_json, HttpClientFactory};
+use sha2::{Digest, Sha256};
 
-/// Maximum allowed discrepancy between node responses (in milliseconds)
-const MAX_TIME_DISCREPANCY_MS: u64 = 5000;  // 5 seconds
+/// Default maximum allowed discrepancy between node response times (in milliseconds)
+pub const DEFAULT_MAX_TIME_DISCREPANCY_MS: u64 = 5000;  // 5 seconds
 
-/// Minimum number of nodes required for quorum
-const MIN_QUORUM_SIZE: usize = 2;
+/// Default minimum number of valid node responses required for quorum
+pub const DEFAULT_MIN_QUORUM_SIZE: usize = 2;
+
+/// Size at which a persisted response history is rotated, unless configured otherwise
+pub const DEFAULT_HISTORY_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
 
 /// Response status from a blockchain node
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NodeResponseStatus {
     /// Response was successful and valid
     Valid,
@@ -46,6 +57,165 @@ pub struct NodeResponse {
     pub timestamp: Instant,
 }
 
+/// `NodeResponse` as written to a persisted history, with a wall-clock timestamp
+/// since an `Instant` means nothing after a restart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersistedNodeResponse {
+    pub node_url: String,
+    pub status: NodeResponseStatus,
+    pub data: Option<Value>,
+    pub error: Option<String>,
+    pub response_time_ms: Option<u64>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&NodeResponse> for PersistedNodeResponse {
+    fn from(response: &NodeResponse) -> Self {
+        let age = chrono::Duration::from_std(response.timestamp.elapsed()).unwrap_or_else(|_| chrono::Duration::zero());
+        Self {
+            node_url: response.node_url.clone(),
+            status: response.status.clone(),
+            data: response.data.clone(),
+            error: response.error.clone(),
+            response_time_ms: response.response_time_ms,
+            timestamp: Utc::now() - age,
+        }
+    }
+}
+
+impl From<PersistedNodeResponse> for NodeResponse {
+    /// The `Instant` as far in the past as the wall-clock timestamp, or now if the clock can't go back that far
+    fn from(response: PersistedNodeResponse) -> Self {
+        let age = (Utc::now() - response.timestamp).to_std().unwrap_or_default();
+        Self {
+            node_url: response.node_url,
+            status: response.status,
+            data: response.data,
+            error: response.error,
+            response_time_ms: response.response_time_ms,
+            timestamp: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+        }
+    }
+}
+
+/// One line of a persisted history: the responses recorded for a digest (or `object:<id>`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersistedResponseSet {
+    pub subject: String,
+    pub responses: Vec<PersistedNodeResponse>,
+}
+
+/// How a single node fared in a multi-node verification
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeVerdict {
+    /// URL of the node
+    pub node_url: String,
+    /// Status of the node's response; valid responses that disagree are marked `Inconsistent`
+    pub status: NodeResponseStatus,
+    /// Whether the node returned the consensus data
+    pub agreed_with_consensus: bool,
+}
+
+/// How `detect_data_inconsistencies` decides which responses differ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InconsistencyCheck {
+    /// Compare the transaction digest, status, gas used, balance changes and object changes, in the order nodes list them
+    #[default]
+    Fields,
+    /// Compare `effects_digest`s of the whole normalized response, so nodes listing
+    /// the same changes in a different order agree. Array indices in reported
+    /// paths refer to the elements in canonical order.
+    EffectsDigest,
+}
+
+/// A field on which valid node responses disagree
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inconsistency {
+    /// Path of the diverging field, e.g. `effects.gasUsed.computationCost` or `balanceChanges[1].amount`
+    pub field_path: String,
+    /// Each node that returned the field, with its value
+    pub values: Vec<(String, Value)>,
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self.values.iter().map(|(node, value)| format!("{}={}", node, value)).collect();
+        write!(f, "Inconsistency in {}: {}", self.field_path, values.join(", "))
+    }
+}
+
+/// One step of an `Inconsistency` path
+#[derive(Debug, Clone, PartialEq)]
+enum PathStep {
+    Key(String),
+    Index(usize),
+}
+
+fn format_path(path: &[PathStep]) -> String {
+    let mut formatted = String::new();
+    for step in path {
+        match step {
+            PathStep::Key(key) if formatted.is_empty() => formatted.push_str(key),
+            PathStep::Key(key) => {
+                formatted.push('.');
+                formatted.push_str(key);
+            }
+            PathStep::Index(i) => formatted.push_str(&format!("[{}]", i)),
+        }
+    }
+    formatted
+}
+
+fn value_at<'a>(value: &'a Value, path: &[PathStep]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |current, step| match step {
+        PathStep::Key(key) => current.get(key.as_str()),
+        PathStep::Index(i) => current.get(*i),
+    })
+}
+
+/// Paths under `path` at which `a` and `b` hold different values. Fields missing
+/// from either side are not compared; arrays of different lengths diverge as a whole.
+fn diverging_paths(a: &Value, b: &Value, path: &mut Vec<PathStep>, found: &mut Vec<Vec<PathStep>>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut keys: Vec<&String> = a.keys().filter(|key| b.contains_key(*key)).collect();
+            keys.sort();
+            for key in keys {
+                path.push(PathStep::Key(key.clone()));
+                diverging_paths(&a[key], &b[key], path, found);
+                path.pop();
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                path.push(PathStep::Index(i));
+                diverging_paths(a, b, path, found);
+                path.pop();
+            }
+        }
+        _ if a != b => {
+            if !found.contains(path) {
+                found.push(path.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `value` with every array sorted by the canonical form of its elements
+fn sort_arrays(value: &Value) -> Value {
+    match value {
+        Value::Object(obj) => Value::Object(obj.iter().map(|(k, v)| (k.clone(), sort_arrays(v))).collect()),
+        Value::Array(arr) => {
+            let mut sorted: Vec<Value> = arr.iter().map(sort_arrays).collect();
+            sorted.sort_by_cached_key(canonical_json);
+            Value::Array(sorted)
+        }
+        other => other.clone(),
+    }
+}
+
 /// Byzantine fault detector for blockchain nodes
 #[derive(Debug)]
 pub struct ByzantineDetector {
@@ -63,28 +233,119 @@ pub struct ByzantineDetector {
     response_cache: Arc<Mutex<HashMap<String, (NodeResponse, Instant)>>>,
     /// Cache TTL (time to live) in seconds
     cache_ttl_seconds: u64,
+    /// Minimum number of valid responses needed to check consensus, and of agreeing ones to reach it
+    min_quorum_size: usize,
+    /// Fraction of valid responses that must agree; a strict majority when unset
+    consensus_fraction: Option<f64>,
+    /// Response time deviation below which nodes are never flagged as timing outliers
+    max_time_discrepancy_ms: u64,
+    /// How responses are compared for data inconsistencies
+    inconsistency_check: InconsistencyCheck,
+    /// Every recorded response set is also appended here as a JSON line
+    history_file: Option<Mutex<HistoryFile>>,
+}
+
+/// JSON-lines response history that is rotated to `<path>.1` once it grows past `max_bytes`
+#[derive(Debug)]
+struct HistoryFile {
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl HistoryFile {
+    fn rotated_path(path: &Path) -> PathBuf {
+        let mut rotated = path.as_os_str().to_owned();
+        rotated.push(".1");
+        PathBuf::from(rotated)
+    }
+
+    fn append(&self, set: &PersistedResponseSet) -> Result<()> {
+        let line = serde_json::to_string(set)?;
+        let size = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 + 1 > self.max_bytes {
+            fs::rename(&self.path, Self::rotated_path(&self.path))
+                .with_context(|| format!("Failed to rotate node response history {}", self.path.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open node response history {}", self.path.display()))?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
 }
 
 impl ByzantineDetector {
     /// Create a new Byzantine fault detector
+    ///
+    /// `min_quorum_size` is the number of valid responses needed before they are
+    /// compared (at least 2), and `max_time_discrepancy_ms` the response time
+    /// deviation tolerated before a node counts as a timing outlier.
     pub fn new(
         endpoints: Vec<String>,
         audit_log: Option<Arc<SecurityAuditLog>>,
         max_response_time_ms: Option<u64>,
-        cache_ttl_seconds: Option<u64>
-    ) -> Self {
-        Self {
-            client: reqwest::Client::builder()
-                .timeout(Duration::from_secs(30))
-                .build()
-                .expect("Failed to create HTTP client"),
+        cache_ttl_seconds: Option<u64>,
+        min_quorum_size: Option<usize>,
+        max_time_discrepancy_ms: Option<u64>,
+    ) -> Result<Self> {
+        let min_quorum_size = min_quorum_size.unwrap_or(DEFAULT_MIN_QUORUM_SIZE);
+        if min_quorum_size < 2 {
+            return Err(anyhow!("Minimum quorum size must be at least 2, got {}", min_quorum_size));
+        }
+        let client = HttpClientFactory::from_env()
+            .and_then(|factory| factory.with_audit_log(audit_log.clone()).build_with_timeout(Duration::from_secs(30)))
+            .context("Failed to create HTTP client for Byzantine detection")?;
+        Ok(Self {
+            client,
             endpoints,
             response_history: Arc::new(Mutex::new(HashMap::new())),
             audit_log,
             max_response_time_ms: max_response_time_ms.unwrap_or(10000), // Default 10 seconds
             response_cache: Arc::new(Mutex::new(HashMap::new())),
             cache_ttl_seconds: cache_ttl_seconds.unwrap_or(60), // Default 60 seconds
+            min_quorum_size,
+            consensus_fraction: None,
+            max_time_discrepancy_ms: max_time_discrepancy_ms.unwrap_or(DEFAULT_MAX_TIME_DISCREPANCY_MS),
+            inconsistency_check: InconsistencyCheck::default(),
+            history_file: None,
+        })
+    }
+    
+    /// Require at least `fraction` (in (0, 1]) of the valid responses to agree instead of a
+    /// strict majority, e.g. 5 of 7 with 0.7. `min_quorum_size` agreeing responses are always required.
+    pub fn with_consensus_fraction(mut self, fraction: f64) -> Result<Self> {
+        if fraction <= 0.0 || !(0.0..=1.0).contains(&fraction) {
+            return Err(anyhow!("Consensus fraction must be in (0, 1], got {}", fraction));
         }
+        self.consensus_fraction = Some(fraction);
+        Ok(self)
+    }
+    
+    /// Rebuild the RPC client with `factory`'s shared HTTP settings
+    pub fn with_http_client_factory(mut self, factory: &HttpClientFactory) -> Result<Self> {
+        self.client = factory.build_with_timeout(Duration::from_secs(30))?;
+        Ok(self)
+    }
+    
+    /// Choose how `detect_data_inconsistencies` compares responses
+    pub fn with_inconsistency_check(mut self, check: InconsistencyCheck) -> Self {
+        self.inconsistency_check = check;
+        self
+    }
+    
+    pub fn min_quorum_size(&self) -> usize {
+        self.min_quorum_size
+    }
+    
+    /// Agreeing responses needed for consensus among `valid_responses` valid ones
+    pub fn required_agreement(&self, valid_responses: usize) -> usize {
+        let fractional = match self.consensus_fraction {
+            Some(fraction) => (valid_responses as f64 * fraction).ceil() as usize,
+            None => valid_responses / 2 + 1,
+        };
+        fractional.max(self.min_quorum_size)
     }
     
     /// Add endpoints to the detector
@@ -108,85 +369,33 @@ impl ByzantineDetector {
     
     /// Check if a transaction exists across multiple nodes
     pub async fn verify_transaction_existence(&self, digest: &str) -> Result<VerificationStatus> {
+        self.verify_with_node_report(digest).await.map(|(status, _)| status)
+    }
+    
+    /// Like `verify_transaction_existence`, but also reports how each node responded
+    /// relative to consensus, so persistently bad RPC providers can be identified.
+    pub async fn verify_with_node_report(&self, digest: &str) -> Result<(VerificationStatus, Vec<NodeVerdict>)> {
         // Check cache first
-        {
+        let cached = {
             let cache = self.response_cache.lock().unwrap();
-            if let Some((response, timestamp)) = cache.get(digest) {
-                // If cache entry is still valid
-                if timestamp.elapsed().as_secs() < self.cache_ttl_seconds {
-                    return Ok(self.response_to_verification_status(&response));
-                }
-            }
+            cache.get(digest)
+                .filter(|(_, timestamp)| timestamp.elapsed().as_secs() < self.cache_ttl_seconds)
+                .map(|(response, _)| response.clone())
+        };
+        if let Some(consensus_response) = cached {
+            // Rebuild the report from the responses that produced the cached result
+            let verdicts = self.response_history.lock().unwrap()
+                .get(digest)
+                .map(|responses| Self::node_verdicts(responses, &consensus_response))
+                .unwrap_or_default();
+            return Ok((self.response_to_verification_status(&consensus_response), verdicts));
         }
         
         // Not in cache or expired, query nodes
-        let mut responses = Vec::new();
-        
-        for endpoint in &self.endpoints {
-            let start_time = Instant::now();
-            
-            match self.query_transaction(endpoint, digest).await {
-                Ok(data) => {
-                    let elapsed = start_time.elapsed();
-                    let elapsed_ms = elapsed.as_millis() as u64;
-                    
-                    // Check if response time is acceptable
-                    let status = if elapsed_ms > self.max_response_time_ms {
-                        NodeResponseStatus::Delayed
-                    } else {
-                        NodeResponseStatus::Valid
-                    };
-                    
-                    let response = NodeResponse {
-                        node_url: endpoint.clone(),
-                        status,
-                        data: Some(data),
-                        error: None,
-                        response_time_ms: Some(elapsed_ms),
-                        timestamp: Instant::now(),
-                    };
-                    
-                    responses.push(response);
-                },
-                Err(e) => {
-                    let elapsed = start_time.elapsed();
-                    let elapsed_ms = elapsed.as_millis() as u64;
-                    
-                    let status = if e.to_string().contains("timeout") {
-                        NodeResponseStatus::Delayed
-                    } else {
-                        NodeResponseStatus::Unavailable
-                    };
-                    
-                    let response = NodeResponse {
-                        node_url: endpoint.clone(),
-                        status,
-                        data: None,
-                        error: Some(e.to_string()),
-                        response_time_ms: Some(elapsed_ms),
-                        timestamp: Instant::now(),
-                    };
-                    
-                    responses.push(response);
-                    
-                    // Log error
-                    if let Some(log) = &self.audit_log {
-                        let _ = log.log_validation(
-                            "ByzantineDetector",
-                            &format!("Node {} failed to respond: {}", endpoint, e),
-                            Some(digest),
-                            AuditSeverity::Warning
-                        );
-                    }
-                }
-            }
-        }
+        let responses = self.query_all_endpoints("sui_getTransactionBlock", &Self::transaction_params(digest), digest).await;
         
         // Update response history
-        {
-            let mut history = self.response_history.lock().unwrap();
-            history.insert(digest.to_string(), responses.clone());
-        }
+        self.record_responses(digest, responses.clone());
         
         // Check for Byzantine behavior
         let (_consensus_reached, consensus_response) = self.check_consensus(&responses, digest)?;
@@ -198,12 +407,129 @@ impl ByzantineDetector {
         }
         
         // Return verification status based on consensus
-        Ok(self.response_to_verification_status(&consensus_response))
+        let verdicts = Self::node_verdicts(&responses, &consensus_response);
+        Ok((self.response_to_verification_status(&consensus_response), verdicts))
     }
     
-    /// Query a transaction from a specific node
-    async fn query_transaction(&self, endpoint: &str, digest: &str) -> Result<Value> {
+    /// Check that nodes agree on the current state of an object (`sui_getObject`).
+    ///
+    /// Object state changes between reads, so unlike transaction checks the
+    /// result is not cached. Returns `Failed` if the nodes agree the object
+    /// doesn't exist and `Unverifiable` if there is no majority.
+    pub async fn verify_object_consistency(&self, object_id: &str) -> Result<(VerificationStatus, Vec<NodeVerdict>)> {
         let params = json!([
+            object_id,
+            {
+                "showType": true,
+                "showOwner": true,
+                "showContent": true,
+                "showPreviousTransaction": true
+            }
+        ]);
+        let history_key = format!("object:{}", object_id);
+        let responses = self.query_all_endpoints("sui_getObject", &params, object_id).await;
+        self.record_responses(&history_key, responses.clone());
+        
+        let (consensus_reached, consensus_response) = self.check_consensus(&responses, &history_key)?;
+        let verdicts = Self::node_verdicts(&responses, &consensus_response);
+        
+        let status = if !consensus_reached {
+            VerificationStatus::Unverifiable(format!("Nodes disagree on the state of object {}", object_id))
+        } else {
+            match consensus_response.data.as_ref().and_then(|d| d.get("error")) {
+                Some(error) => VerificationStatus::Failed(format!("Object {} not available: {}", object_id, error)),
+                None => VerificationStatus::Verified,
+            }
+        };
+        Ok((status, verdicts))
+    }
+    
+    /// Compare every response against the consensus response
+    fn node_verdicts(responses: &[NodeResponse], consensus_response: &NodeResponse) -> Vec<NodeVerdict> {
+        let consensus_data = consensus_response.data.as_ref().map(Self::normalize_data_for_comparison);
+        responses.iter()
+            .map(|response| {
+                let agreed = response.status == NodeResponseStatus::Valid
+                    && response.data.as_ref().map(Self::normalize_data_for_comparison) == consensus_data;
+                let status = if response.status == NodeResponseStatus::Valid && !agreed {
+                    NodeResponseStatus::Inconsistent
+                } else {
+                    response.status.clone()
+                };
+                NodeVerdict {
+                    node_url: response.node_url.clone(),
+                    status,
+                    agreed_with_consensus: agreed,
+                }
+            })
+            .collect()
+    }
+    
+    /// Send the same RPC request to every endpoint concurrently and classify each response,
+    /// in endpoint order. `subject` (a digest or object ID) is only used for audit logging.
+    async fn query_all_endpoints(&self, method: &str, params: &Value, subject: &str) -> Vec<NodeResponse> {
+        let queries = self.endpoints.iter().map(|endpoint| self.query_endpoint(endpoint, method, params, subject));
+        futures::future::join_all(queries).await
+    }
+    
+    /// Query one endpoint, timing only its own request
+    async fn query_endpoint(&self, endpoint: &str, method: &str, params: &Value, subject: &str) -> NodeResponse {
+        let start_time = Instant::now();
+        
+        match self.query_rpc(endpoint, method, params.clone()).await {
+            Ok(data) => {
+                let elapsed_ms = start_time.elapsed().as_millis() as u64;
+                
+                // Check if response time is acceptable
+                let status = if elapsed_ms > self.max_response_time_ms {
+                    NodeResponseStatus::Delayed
+                } else {
+                    NodeResponseStatus::Valid
+                };
+                
+                NodeResponse {
+                    node_url: endpoint.to_string(),
+                    status,
+                    data: Some(data),
+                    error: None,
+                    response_time_ms: Some(elapsed_ms),
+                    timestamp: Instant::now(),
+                }
+            },
+            Err(e) => {
+                let elapsed_ms = start_time.elapsed().as_millis() as u64;
+                
+                let status = if e.to_string().contains("timeout") {
+                    NodeResponseStatus::Delayed
+                } else {
+                    NodeResponseStatus::Unavailable
+                };
+                
+                // Log error
+                if let Some(log) = &self.audit_log {
+                    let _ = log.log_validation(
+                        "ByzantineDetector",
+                        &format!("Node {} failed to respond: {}", endpoint, e),
+                        Some(subject),
+                        AuditSeverity::Warning
+                    );
+                }
+                
+                NodeResponse {
+                    node_url: endpoint.to_string(),
+                    status,
+                    data: None,
+                    error: Some(e.to_string()),
+                    response_time_ms: Some(elapsed_ms),
+                    timestamp: Instant::now(),
+                }
+            }
+        }
+    }
+    
+    /// Params for `sui_getTransactionBlock` requesting everything the consistency checks compare
+    fn transaction_params(digest: &str) -> Value {
+        json!([
             digest,
             {
                 "showInput": true,
@@ -212,27 +538,36 @@ impl ByzantineDetector {
                 "showObjectChanges": true,
                 "showBalanceChanges": true
             }
-        ]);
-        
+        ])
+    }
+    
+    /// Call a JSON-RPC method on a specific node and return its `result`
+    pub async fn query_rpc(&self, endpoint: &str, method: &str, params: Value) -> Result<Value> {
         let response = self.client
             .post(endpoint)
             .json(&json!({
                 "jsonrpc": "2.0",
                 "id": 1,
-                "method": "sui_getTransactionBlock",
+                "method": method,
                 "params": params
             }))
             .send()
             .await?;
         
+        // A failed response may still carry a JSON body, which must not count as an answer
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("HTTP {} from {} for {}", status, endpoint, method));
+        }
+        
         let result: Value = response.json().await?;
         
         if let Some(error) = result.get("error") {
             return Err(anyhow!("RPC error: {}", error));
         }
         
-        if !result["result"].is_object() {
-            return Err(anyhow!("Invalid response format"));
+        if result["result"].is_null() {
+            return Err(anyhow!("Invalid response format: missing result for {}", method));
         }
         
         Ok(result["result"].clone())
@@ -253,7 +588,7 @@ impl ByzantineDetector {
         metrics_data.insert("filter_time_ms".to_string(), filter_time.as_millis().to_string());
         
         // If we don't have enough valid responses for quorum
-        if valid_responses.len() < MIN_QUORUM_SIZE {
+        if valid_responses.len() < self.min_quorum_size {
             if let Some(audit_log) = &self.audit_log {
                 audit_log.add_event(
                     "ByzantineDetector",
@@ -276,7 +611,7 @@ impl ByzantineDetector {
             if let Some(data) = &resp.data {
                 // Normalize data for comparison to handle irrelevant differences
                 let normalized = Self::normalize_data_for_comparison(data);
-                let data_str = normalized.to_string();
+                let data_str = canonical_json(&normalized);
                 
                 let entry = data_frequency.entry(data_str).or_insert((0, *resp));
                 entry.0 += 1;
@@ -302,9 +637,10 @@ impl ByzantineDetector {
         metrics_data.insert("consensus_calculation_time_ms".to_string(), 
                             consensus_calculation_time.as_millis().to_string());
         
-        // Check if we have a majority consensus
-        let quorum_size = (valid_responses.len() / 2) + 1;
+        // Check if enough of the valid responses agree
+        let quorum_size = self.required_agreement(valid_responses.len());
         let has_consensus = max_frequency >= quorum_size;
+        metrics_data.insert("required_agreement".to_string(), quorum_size.to_string());
         
         let total_time = start_time.elapsed();
         metrics_data.insert("total_consensus_check_time_ms".to_string(), 
@@ -316,9 +652,9 @@ impl ByzantineDetector {
                 "ByzantineDetector",
                 AuditEventType::TransactionVerification,
                 if has_consensus { AuditSeverity::Info } else { AuditSeverity::Warning },
-                &format!("Byzantine consensus check: {} (consensus: {}/{})", 
+                &format!("Byzantine consensus check: {} (consensus: {}/{}, required {})", 
                         if has_consensus { "success" } else { "failed" },
-                        max_frequency, valid_responses.len()),
+                        max_frequency, valid_responses.len(), quorum_size),
                 metrics_data
             );
         }
@@ -427,11 +763,11 @@ impl ByzantineDetector {
                 
                 let std_dev = variance.sqrt();
                 
-                // Check for outliers (more than 2 standard deviations)
+                // Check for outliers (more than 2 standard deviations, and beyond the tolerated discrepancy)
                 let outliers: Vec<(&String, &u64)> = response_times.iter()
                     .filter(|(_, time)| {
                         let diff = (*time as f64 - avg).abs();
-                        diff > 2.0 * std_dev
+                        diff > 2.0 * std_dev && diff > self.max_time_discrepancy_ms as f64
                     })
                     .map(|(url, time)| (url, time))
                     .collect();
@@ -467,59 +803,127 @@ impl ByzantineDetector {
         Ok(false)
     }
     
+    /// Store responses for a digest, replacing any previous ones.
+    ///
+    /// Used by `verify_transaction_existence`; also lets callers feed in
+    /// responses gathered elsewhere before running the history-based checks.
+    pub fn record_responses(&self, digest: &str, responses: Vec<NodeResponse>) {
+        if let Some(history_file) = &self.history_file {
+            let set = PersistedResponseSet {
+                subject: digest.to_string(),
+                responses: responses.iter().map(PersistedNodeResponse::from).collect(),
+            };
+            // The in-memory history is still updated if the file can't be
+            if let Err(e) = history_file.lock().unwrap().append(&set) {
+                eprintln!("WARNING: Failed to persist node responses for {}: {:#}", digest, e);
+            }
+        }
+        self.response_history.lock().unwrap().insert(digest.to_string(), responses);
+    }
+    
+    /// Append every response set recorded from now on to `path` (JSON lines), for
+    /// forensic replay with `load_history` after a restart. Rotates at
+    /// `DEFAULT_HISTORY_FILE_MAX_BYTES`.
+    pub fn enable_persistence(&mut self, path: PathBuf) -> Result<()> {
+        self.enable_rotating_persistence(path, DEFAULT_HISTORY_FILE_MAX_BYTES)
+    }
+    
+    /// Like `enable_persistence`, but once the file would grow past `max_bytes`
+    /// it is renamed to `<path>.1`, replacing any older one, and a new file is started.
+    pub fn enable_rotating_persistence(&mut self, path: PathBuf, max_bytes: u64) -> Result<()> {
+        if max_bytes == 0 {
+            return Err(anyhow!("Node response history max size must be greater than zero"));
+        }
+        // Fail now on an unwritable path rather than on every later record
+        OpenOptions::new().create(true).append(true).open(&path)
+            .with_context(|| format!("Failed to open node response history {}", path.display()))?;
+        self.history_file = Some(Mutex::new(HistoryFile { path, max_bytes }));
+        Ok(())
+    }
+    
+    /// Rehydrate the response history from a file written by `enable_persistence`,
+    /// and its rotated `<path>.1` if there is one.
+    /// When a subject was recorded more than once, the latest set wins, as it did in memory.
+    ///
+    /// Returns the sets read, oldest first. They keep the wall-clock time of each
+    /// response, which the rehydrated `NodeResponse`s can only approximate as an `Instant`.
+    pub fn load_history(&self, path: &Path) -> Result<Vec<PersistedResponseSet>> {
+        let mut sets = read_response_sets(&HistoryFile::rotated_path(path))?;
+        sets.extend(read_response_sets(path)?);
+        let mut history = self.response_history.lock().unwrap();
+        for set in &sets {
+            history.insert(set.subject.clone(), set.responses.iter().cloned().map(NodeResponse::from).collect());
+        }
+        Ok(sets)
+    }
+    
+    /// Responses last recorded for `digest` (or `object:<id>`), oldest node first
+    pub fn recorded_responses(&self, digest: &str) -> Option<Vec<NodeResponse>> {
+        self.response_history.lock().unwrap().get(digest).cloned()
+    }
+
     /// Detect data inconsistencies across nodes
-    pub fn detect_data_inconsistencies(&self, digest: &str) -> Result<Vec<String>> {
+    ///
+    /// Each response is normalized and digested once, so identical responses
+    /// are grouped without comparing them. Field-level diffs only run between
+    /// groups whose digests differ, and report every diverging leaf field with
+    /// the value each node returned for it.
+    pub fn detect_data_inconsistencies(&self, digest: &str) -> Result<Vec<Inconsistency>> {
         let history = self.response_history.lock().unwrap();
         
         if let Some(responses) = history.get(digest) {
-            // Filter valid responses with data
-            let valid_responses: Vec<&NodeResponse> = responses.iter()
-                .filter(|r| r.status == NodeResponseStatus::Valid && r.data.is_some())
+            // Filter valid responses with data, in the form they are compared in
+            let compared: Vec<(&str, Value)> = responses.iter()
+                .filter(|r| r.status == NodeResponseStatus::Valid)
+                .filter_map(|r| r.data.as_ref().map(|data| (r.node_url.as_str(), self.comparison_view(data))))
                 .collect();
             
             // If we don't have enough valid responses
-            if valid_responses.len() < 2 {
+            if compared.len() < self.min_quorum_size {
                 return Ok(Vec::new());
             }
             
-            // Compare each pair of responses for key fields
-            let mut inconsistencies = Vec::new();
+            // Group nodes by digest, keeping one representative per group
+            let mut groups: Vec<(String, &Value)> = Vec::new();
+            for (_, view) in &compared {
+                let digest = Self::view_digest(view);
+                if !groups.iter().any(|(d, _)| *d == digest) {
+                    groups.push((digest, view));
+                }
+            }
             
-            for i in 0..valid_responses.len() {
-                for j in i+1..valid_responses.len() {
-                    let response_i = valid_responses[i];
-                    let response_j = valid_responses[j];
-                    
-                    if let (Some(data_i), Some(data_j)) = (&response_i.data, &response_j.data) {
-                        // Check key fields for inconsistencies
-                        self.compare_transaction_fields(data_i, data_j)
-                            .into_iter()
-                            .for_each(|field| {
-                                let message = format!(
-                                    "Inconsistency in {} between nodes {} and {}",
-                                    field, response_i.node_url, response_j.node_url
-                                );
-                                
-                                if !inconsistencies.contains(&message) {
-                                    inconsistencies.push(message);
-                                }
-                            });
-                    }
+            let mut paths = Vec::new();
+            for i in 0..groups.len() {
+                for j in i+1..groups.len() {
+                    diverging_paths(groups[i].1, groups[j].1, &mut Vec::new(), &mut paths);
                 }
             }
             
+            let inconsistencies: Vec<Inconsistency> = paths.iter()
+                .map(|path| Inconsistency {
+                    field_path: format_path(path),
+                    values: compared.iter()
+                        .filter_map(|(node, view)| value_at(view, path).map(|value| (node.to_string(), value.clone())))
+                        .collect(),
+                })
+                .collect();
+            
             // Log inconsistencies
-            if !inconsistencies.is_empty() && self.audit_log.is_some() {
-                let log = self.audit_log.as_ref().unwrap();
-                
-                let _ = log.log_security_error(
-                    "ByzantineDetector",
-                    &format!("Data inconsistencies detected for {}: {} issues found",
-                        digest, inconsistencies.len()),
-                    Some(json!({
-                        "inconsistencies": inconsistencies
-                    }))
-                );
+            if !inconsistencies.is_empty() {
+                if let Some(log) = &self.audit_log {
+                    let diverged_fields: Vec<&str> = inconsistencies.iter().map(|i| i.field_path.as_str()).collect();
+                    let _ = log.log_security_error(
+                        "ByzantineDetector",
+                        &format!("Data inconsistencies detected for {}: {} fields diverge: [{}]",
+                            digest, inconsistencies.len(), diverged_fields.join(", ")),
+                        Some(json!({
+                            "inconsistencies": inconsistencies.iter()
+                                .map(|i| json!({ "field_path": i.field_path, "values": i.values }))
+                                .collect::<Vec<_>>(),
+                            "distinct_responses": groups.len()
+                        }))
+                    );
+                }
             }
             
             return Ok(inconsistencies);
@@ -528,74 +932,63 @@ impl ByzantineDetector {
         Ok(Vec::new())
     }
     
-    /// Compare transaction fields between two responses
-    fn compare_transaction_fields(&self, data_i: &Value, data_j: &Value) -> Vec<String> {
-        let mut inconsistencies = Vec::new();
-        
-        // Compare transaction digest
-        if let (Some(digest_i), Some(digest_j)) = (
-            data_i.get("digest").and_then(Value::as_str),
-            data_j.get("digest").and_then(Value::as_str)
-        ) {
-            if digest_i != digest_j {
-                inconsistencies.push("transaction digest".to_string());
-            }
-        }
-        
-        // Compare execution status
-        if let (Some(status_i), Some(status_j)) = (
-            data_i.get("status"),
-            data_j.get("status")
-        ) {
-            let status_str_i = serde_json::to_string(status_i).unwrap_or_default();
-            let status_str_j = serde_json::to_string(status_j).unwrap_or_default();
-            
-            if status_str_i != status_str_j {
-                inconsistencies.push("execution status".to_string());
-            }
-        }
-        
-        // Compare gas used
-        if let (Some(gas_i), Some(gas_j)) = (
-            data_i.get("effects").and_then(|e| e.get("gasUsed")),
-            data_j.get("effects").and_then(|e| e.get("gasUsed"))
-        ) {
-            let gas_str_i = serde_json::to_string(gas_i).unwrap_or_default();
-            let gas_str_j = serde_json::to_string(gas_j).unwrap_or_default();
-            
-            if gas_str_i != gas_str_j {
-                inconsistencies.push("gas used".to_string());
+    /// SHA-256 (hex) of the normalized response with every array in canonical order,
+    /// equal for nodes that report the same effects however they order or lay them out.
+    pub fn effects_digest(data: &Value) -> String {
+        Self::view_digest(&sort_arrays(&Self::normalize_data_for_comparison(data)))
+    }
+    
+    fn view_digest(view: &Value) -> String {
+        hex::encode(Sha256::digest(canonical_json(view).as_bytes()))
+    }
+    
+    /// The part of a response `detect_data_inconsistencies` compares, normalized
+    fn comparison_view(&self, data: &Value) -> Value {
+        let normalized = Self::normalize_data_for_comparison(data);
+        match self.inconsistency_check {
+            InconsistencyCheck::EffectsDigest => sort_arrays(&normalized),
+            InconsistencyCheck::Fields => {
+                let mut view = serde_json::Map::new();
+                for field in ["digest", "status", "balanceChanges", "objectChanges"] {
+                    if let Some(value) = normalized.get(field) {
+                        view.insert(field.to_string(), value.clone());
+                    }
+                }
+                if let Some(gas_used) = normalized.get("effects").and_then(|e| e.get("gasUsed")) {
+                    view.insert("effects".to_string(), json!({ "gasUsed": gas_used }));
+                }
+                Value::Object(view)
             }
         }
-        
-        // Compare balance changes
-        if let (Some(changes_i), Some(changes_j)) = (
-            data_i.get("balanceChanges"),
-            data_j.get("balanceChanges")
-        ) {
-            let changes_str_i = serde_json::to_string(changes_i).unwrap_or_default();
-            let changes_str_j = serde_json::to_string(changes_j).unwrap_or_default();
-            
-            if changes_str_i != changes_str_j {
-                inconsistencies.push("balance changes".to_string());
-            }
+    }
+}
+
+/// Response sets in `path`, oldest first. A missing file means none.
+///
+/// A last line that doesn't parse is taken to be a write cut short by a crash and skipped.
+fn read_response_sets(path: &Path) -> Result<Vec<PersistedResponseSet>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = File::open(path)
+        .with_context(|| format!("Failed to read node response history {}", path.display()))?;
+    let lines = BufReader::new(file).lines().collect::<std::io::Result<Vec<String>>>()?;
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+
+    let mut sets = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
         }
-        
-        // Compare object changes
-        if let (Some(obj_changes_i), Some(obj_changes_j)) = (
-            data_i.get("objectChanges"),
-            data_j.get("objectChanges")
-        ) {
-            let obj_changes_str_i = serde_json::to_string(obj_changes_i).unwrap_or_default();
-            let obj_changes_str_j = serde_json::to_string(obj_changes_j).unwrap_or_default();
-            
-            if obj_changes_str_i != obj_changes_str_j {
-                inconsistencies.push("object changes".to_string());
+        match serde_json::from_str(line) {
+            Ok(set) => sets.push(set),
+            Err(e) if Some(i) == last => {
+                eprintln!("WARNING: Skipping truncated last line of node response history {}: {}", path.display(), e);
             }
+            Err(e) => return Err(anyhow!("Invalid response set on line {} of {}: {}", i + 1, path.display(), e)),
         }
-        
-        inconsistencies
     }
+    Ok(sets)
 }
 
 /// Integration with VerificationManager
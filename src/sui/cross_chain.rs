@@ -1,13 +1,197 @@
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
+use std::future::Future;
 use std::time::Duration;
 use serde::{Serialize, Deserialize};
 use serde_json::Value;
+use thiserror::Error;
 use crate::transaction::types::Transaction;
+use crate::transaction::amount::{AmountError, ETH_COIN_TYPE, ETH_DECIMALS, SUI_COIN_TYPE, SUI_DECIMALS};
 use crate::security::audit::{SecurityAuditLog, AuditSeverity};
 use crate::sui::network::{NetworkManager, NetworkType, ChainConfig};
+use crate::utils::HttpClientFactory;
+use crate::utils::backoff::{retry_async, Backoff};
+
+/// Deadline for a single adapter RPC call unless the adapter or caller sets one
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Transport-level timeout of the adapters' HTTP clients; RPC deadlines are enforced separately
+const ADAPTER_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Jittered delays between sweeps of an EVM chain's endpoints when none of them could be reached
+const RPC_RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+const RPC_RETRY_MAX_DELAY: Duration = Duration::from_secs(2);
+const RPC_RETRY_ATTEMPTS: u32 = 2;
+
+/// HTTP client with the environment's shared settings, see `HttpClientFactory::from_env`
+fn adapter_http_client(audit_log: &Option<Arc<SecurityAuditLog>>) -> Result<reqwest::Client> {
+    HttpClientFactory::from_env()
+        .and_then(|factory| factory.with_audit_log(audit_log.clone()).build_with_timeout(ADAPTER_HTTP_TIMEOUT))
+        .map_err(|e| anyhow!("Failed to create HTTP client: {:#}", e))
+}
+
+/// Returned when an adapter call doesn't finish within its deadline
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{operation} on chain '{chain_id}' timed out after {} ms", .timeout.as_millis())]
+pub struct RpcTimeout {
+    pub chain_id: String,
+    pub operation: &'static str,
+    pub timeout: Duration,
+}
+
+/// Run an adapter call, failing with `RpcTimeout` if it exceeds `timeout`
+pub async fn with_rpc_timeout<T, F>(chain_id: &str, operation: &'static str, timeout: Duration, call: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match tokio::time::timeout(timeout, call).await {
+        Ok(result) => result,
+        Err(_) => Err(RpcTimeout { chain_id: chain_id.to_string(), operation, timeout }.into()),
+    }
+}
+
+/// Address encoding a chain expects for senders and receivers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AddressFormat {
+    /// `0x` followed by 64 hex digits (32 bytes)
+    Sui,
+    /// `0x` followed by 40 hex digits (20 bytes); EIP-55 checksums are not verified
+    Ethereum,
+    /// Base58 encoding of 32 bytes
+    Solana,
+}
+
+impl AddressFormat {
+    /// Whether `address` is well formed for this chain
+    pub fn is_valid(&self, address: &str) -> bool {
+        match self {
+            AddressFormat::Sui => is_prefixed_hex(address, 64),
+            AddressFormat::Ethereum => is_prefixed_hex(address, 40),
+            AddressFormat::Solana => matches!(decode_base58(address), Some(bytes) if bytes.len() == 32),
+        }
+    }
+
+    /// Human-readable description, for error messages
+    pub fn description(&self) -> &'static str {
+        match self {
+            AddressFormat::Sui => "0x followed by 64 hex digits",
+            AddressFormat::Ethereum => "0x followed by 40 hex digits",
+            AddressFormat::Solana => "base58-encoded 32 bytes",
+        }
+    }
+}
+
+fn is_prefixed_hex(address: &str, digits: usize) -> bool {
+    match address.strip_prefix("0x") {
+        Some(hex) => hex.len() == digits && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
+/// Value of a JSON-RPC hex quantity such as `"0x1a"`
+fn parse_quantity(value: &Value) -> Result<u128> {
+    value.as_str()
+        .and_then(|text| text.strip_prefix("0x"))
+        .and_then(|hex| u128::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| anyhow!("expected a hex quantity, got {}", value))
+}
+
+/// `0x` followed by a non-empty, whole number of hex-encoded bytes
+fn is_hex_bytes(data: &str) -> bool {
+    match data.strip_prefix("0x") {
+        Some(hex) => !hex.is_empty() && hex.len() % 2 == 0 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Decode Bitcoin-alphabet base58, as used for Solana addresses
+fn decode_base58(input: &str) -> Option<Vec<u8>> {
+    if input.is_empty() {
+        return None;
+    }
+    // Little-endian base-256 digits of the value decoded so far
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in input.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' encodes a leading zero byte
+    let leading_zeros = input.bytes().take_while(|&c| c == b'1').count();
+    bytes.resize(bytes.len() + leading_zeros, 0);
+    bytes.reverse();
+    Some(bytes)
+}
+
+/// Returned when a sender or receiver isn't a valid address on the target chain
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{field} '{address}' is not a valid address for chain '{chain_id}' (expected {})", .format.description())]
+pub struct InvalidAddress {
+    pub chain_id: String,
+    pub field: &'static str,
+    pub address: String,
+    pub format: AddressFormat,
+}
+
+/// Returned when an address isn't valid on the target chain and has no registered counterpart there
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("No '{target_chain}' address registered for {field} '{address}' on '{origin_chain}'")]
+pub struct UnmappedAddress {
+    pub origin_chain: String,
+    pub target_chain: String,
+    pub field: &'static str,
+    pub address: String,
+}
+
+/// Registry of which address a user controls on each chain
+///
+/// A Sui address means nothing on Ethereum, so mapping a transaction replaces
+/// its sender and receiver with the addresses registered here.
+#[derive(Debug, Default)]
+pub struct AddressMapping {
+    /// (origin chain, origin address, target chain) -> target address
+    entries: Mutex<HashMap<(String, String, String), String>>,
+}
+
+impl AddressMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record that `origin_addr` on `origin_chain` corresponds to `target_addr` on `target_chain`
+    ///
+    /// Replaces any earlier registration for the same origin address and target chain.
+    pub fn register(&self, origin_chain: &str, origin_addr: &str, target_chain: &str, target_addr: &str) {
+        let key = (origin_chain.to_string(), origin_addr.to_string(), target_chain.to_string());
+        self.entries.lock().unwrap().insert(key, target_addr.to_string());
+    }
+    
+    /// The address registered for `origin_addr` on `target_chain`, if any
+    pub fn resolve(&self, origin_chain: &str, origin_addr: &str, target_chain: &str) -> Option<String> {
+        let key = (origin_chain.to_string(), origin_addr.to_string(), target_chain.to_string());
+        self.entries.lock().unwrap().get(&key).cloned()
+    }
+    
+    /// Number of registered mappings
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
 
 /// Cross-chain transaction mapping status
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -71,16 +255,95 @@ pub trait ChainAdapter: Send + Sync {
     async fn format_transaction(&self, tx: &Transaction) -> Result<Value>;
     
     /// Execute a transaction on this chain
-    async fn execute_transaction(&self, tx_data: &Value) -> Result<String>;
+    ///
+    /// `timeout` overrides `rpc_timeout()` for this call.
+    async fn execute_transaction(&self, tx_data: &Value, timeout: Option<Duration>) -> Result<String>;
     
     /// Get transaction status
-    async fn get_transaction_status(&self, tx_hash: &str) -> Result<Value>;
+    ///
+    /// `timeout` overrides `rpc_timeout()` for this call.
+    async fn get_transaction_status(&self, tx_hash: &str, timeout: Option<Duration>) -> Result<Value>;
+    
+    /// Default deadline for this adapter's RPC calls
+    fn rpc_timeout(&self) -> Duration {
+        DEFAULT_RPC_TIMEOUT
+    }
     
     /// Check if a transaction type is supported
     fn supports_transaction_type(&self, tx_type: &str) -> bool;
     
     /// Get the chain's config
     fn get_config(&self) -> ChainConfig;
+    
+    /// Address format senders and receivers must use on this chain
+    fn address_format(&self) -> AddressFormat;
+    
+    /// Decimal places of the chain's native coin (9 for MIST, 18 for wei)
+    fn native_decimals(&self) -> u8;
+    
+    /// Coin type of the chain's native coin, as carried in `Transaction.coin_type`
+    fn native_coin_type(&self) -> &str;
+    
+    /// Whether amounts of `coin_type` may be paid out in the native coin
+    fn accepts_coin(&self, coin_type: &str) -> bool {
+        coin_type == self.native_coin_type()
+    }
+    
+    /// `tx.amount` converted from its coin's decimals into this chain's native units
+    ///
+    /// Fails with `UnmappedCoin` unless the coin is one `accepts_coin` allows.
+    fn native_amount(&self, tx: &Transaction) -> Result<u128, AmountError> {
+        let amount = tx.denominated_amount();
+        if !self.accepts_coin(&amount.coin_type) {
+            return Err(AmountError::UnmappedCoin { coin_type: amount.coin_type, chain_id: self.chain_id().to_string() });
+        }
+        amount.to_base_units(self.native_decimals())
+    }
+    
+    /// Check that `tx`'s sender and receiver are valid addresses on this chain
+    fn check_addresses(&self, tx: &Transaction) -> Result<(), InvalidAddress> {
+        let format = self.address_format();
+        for (field, address) in [("sender", &tx.sender), ("receiver", &tx.receiver)] {
+            if !format.is_valid(address) {
+                return Err(InvalidAddress {
+                    chain_id: self.chain_id().to_string(),
+                    field,
+                    address: address.clone(),
+                    format,
+                });
+            }
+        }
+        Ok(())
+    }
+    
+    /// Check whether the chain's RPC is currently reachable
+    ///
+    /// Defaults to `true` so adapters without a health probe keep working.
+    async fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Send a parameterless JSON-RPC request and report whether it returned a result
+async fn ping_json_rpc(client: &reqwest::Client, rpc_url: &str, method: &str) -> bool {
+    let response = client
+        .post(rpc_url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": []
+        }))
+        .send()
+        .await;
+    
+    match response {
+        Ok(resp) if resp.status().is_success() => match resp.json::<Value>().await {
+            Ok(json) => json.get("result").map_or(false, |r| !r.is_null()),
+            Err(_) => false,
+        },
+        _ => false,
+    }
 }
 
 /// SUI chain adapter
@@ -91,6 +354,8 @@ pub struct SuiAdapter {
     chain_id: String,
     /// Client for API calls
     client: reqwest::Client,
+    /// Deadline for each RPC call
+    rpc_timeout: Duration,
     /// Audit log
     audit_log: Option<Arc<SecurityAuditLog>>,
 }
@@ -100,18 +365,28 @@ impl SuiAdapter {
     pub fn new(
         network_manager: Arc<NetworkManager>,
         audit_log: Option<Arc<SecurityAuditLog>>,
-    ) -> Self {
+    ) -> Result<Self> {
         let config = network_manager.get_active_config();
         let chain_id = config.get_chain_id().unwrap_or_else(|| format!("sui-{:?}", config.network_type));
-        Self {
+        Ok(Self {
             network_manager,
             chain_id,
-            client: reqwest::Client::builder()
-                .timeout(Duration::from_secs(30))
-                .build()
-                .expect("Failed to create HTTP client"),
+            client: adapter_http_client(&audit_log)?,
+            rpc_timeout: DEFAULT_RPC_TIMEOUT,
             audit_log,
-        }
+        })
+    }
+    
+    /// Rebuild the HTTP client with `factory`'s shared settings
+    pub fn with_http_client_factory(mut self, factory: &HttpClientFactory) -> Result<Self> {
+        self.client = factory.build_with_timeout(ADAPTER_HTTP_TIMEOUT)?;
+        Ok(self)
+    }
+    
+    /// Set the deadline for each RPC call
+    pub fn with_rpc_timeout(mut self, rpc_timeout: Duration) -> Self {
+        self.rpc_timeout = rpc_timeout;
+        self
     }
 }
 
@@ -122,7 +397,10 @@ impl ChainAdapter for SuiAdapter {
     }
     
     async fn format_transaction(&self, tx: &Transaction) -> Result<Value> {
-        // For simplicity, we'll just convert the transaction to JSON
+        self.check_addresses(tx)?;
+        
+        // Sui counts every coin in its own base units, which is what `amount` already holds,
+        // and `coin_type`/`decimals` travel with the JSON
         let tx_json = serde_json::to_value(tx)?;
         
         // In a real implementation, we would format this for SUI's specific requirements
@@ -131,7 +409,7 @@ impl ChainAdapter for SuiAdapter {
         Ok(tx_json)
     }
     
-    async fn execute_transaction(&self, _tx_data: &Value) -> Result<String> {
+    async fn execute_transaction(&self, _tx_data: &Value, timeout: Option<Duration>) -> Result<String> {
         // In a real implementation, this would make an RPC call to SUI
         // For now, we'll just return a mock transaction hash
         
@@ -145,22 +423,28 @@ impl ChainAdapter for SuiAdapter {
             );
         }
         
-        // Mock transaction hash
-        let tx_hash = format!("sui_tx_{}", rand::random::<u64>());
-        
-        Ok(tx_hash)
+        with_rpc_timeout(self.chain_id(), "execute_transaction", timeout.unwrap_or(self.rpc_timeout), async {
+            // Mock transaction hash
+            Ok(format!("sui_tx_{}", rand::random::<u64>()))
+        }).await
     }
     
-    async fn get_transaction_status(&self, tx_hash: &str) -> Result<Value> {
+    async fn get_transaction_status(&self, tx_hash: &str, timeout: Option<Duration>) -> Result<Value> {
         // In a real implementation, this would query the transaction status from SUI
         // For now, we'll just return a mock status
         
-        Ok(serde_json::json!({
-            "digest": tx_hash,
-            "status": "success",
-            "confirmed": true,
-            "timestamp_ms": chrono::Utc::now().timestamp_millis()
-        }))
+        with_rpc_timeout(self.chain_id(), "get_transaction_status", timeout.unwrap_or(self.rpc_timeout), async {
+            Ok(serde_json::json!({
+                "digest": tx_hash,
+                "status": "success",
+                "confirmed": true,
+                "timestamp_ms": chrono::Utc::now().timestamp_millis()
+            }))
+        }).await
+    }
+    
+    fn rpc_timeout(&self) -> Duration {
+        self.rpc_timeout
     }
     
     fn supports_transaction_type(&self, tx_type: &str) -> bool {
@@ -171,26 +455,81 @@ impl ChainAdapter for SuiAdapter {
     fn get_config(&self) -> ChainConfig {
         self.network_manager.get_active_config()
     }
+    
+    fn address_format(&self) -> AddressFormat {
+        AddressFormat::Sui
+    }
+    
+    fn native_decimals(&self) -> u8 {
+        SUI_DECIMALS
+    }
+    
+    fn native_coin_type(&self) -> &str {
+        SUI_COIN_TYPE
+    }
+    
+    async fn is_available(&self) -> bool {
+        let rpc_url = match self.network_manager.get_active_rpc_url() {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+        
+        // Asking for the latest checkpoint proves the node is serving chain data
+        let ping = ping_json_rpc(&self.client, &rpc_url, "sui_getLatestCheckpointSequenceNumber");
+        tokio::time::timeout(self.rpc_timeout, ping).await.unwrap_or(false)
+    }
 }
 
-/// Ethereum chain adapter
-pub struct EthereumAdapter {
+/// Adapter for any EVM chain reachable over Ethereum JSON-RPC
+///
+/// `new` builds one for the Ethereum networks; `from_config` for any other
+/// EVM chain (Polygon, Arbitrum, Base, ...) from caller-supplied values.
+pub struct EvmChainAdapter {
     /// Chain config
     config: ChainConfig,
     /// Client for API calls
     client: reqwest::Client,
+    /// Deadline for each RPC call
+    rpc_timeout: Duration,
     /// Audit log
     audit_log: Option<Arc<SecurityAuditLog>>,
     /// Cached chain ID
     chain_id: String,
+    /// Return generated hashes and statuses instead of calling the RPC endpoints
+    simulated: bool,
+    /// Coin types besides ether that are paid out as ether, e.g. bridged WETH
+    mapped_coins: HashSet<String>,
+    /// Delays between sweeps of the endpoints when all of them are unreachable
+    rpc_backoff: Backoff,
+}
+
+/// Why one endpoint didn't answer a JSON-RPC call
+#[derive(Debug, Error)]
+enum EndpointError {
+    /// The endpoint couldn't be reached or sent something other than a JSON-RPC response
+    #[error("{0}")]
+    Transport(String),
+    /// The node answered with a JSON-RPC error, which asking again won't change
+    #[error("RPC error: {0}")]
+    Rejected(String),
 }
 
-impl EthereumAdapter {
+/// Every endpoint failed one sweep; `rejected` when at least one of them answered with an error
+#[derive(Debug)]
+struct SweepFailure {
+    error: anyhow::Error,
+    rejected: bool,
+}
+
+/// The Ethereum presets of `EvmChainAdapter`, under the name callers already use
+pub type EthereumAdapter = EvmChainAdapter;
+
+impl EvmChainAdapter {
     /// Create a new Ethereum adapter
     pub fn new(
         network_type: NetworkType,
         audit_log: Option<Arc<SecurityAuditLog>>,
-    ) -> Self {
+    ) -> Result<Self> {
         let config = match network_type {
             NetworkType::Mainnet => Self::mainnet_config(),
             NetworkType::Testnet => Self::testnet_config(),
@@ -203,23 +542,213 @@ impl EthereumAdapter {
         let chain_id = config.get_chain_id()
             .unwrap_or_else(|| format!("ethereum-{:?}", network_type));
         
-        Self {
+        Self::with_chain_config(config, chain_id, audit_log)
+    }
+    
+    /// Adapter for the EVM chain `chain_id` served by `rpc_endpoints`, tried in order
+    ///
+    /// API keys belong in the endpoint URLs, e.g.
+    /// `https://base-sepolia.g.alchemy.com/v2/<key>`.
+    pub fn from_config(
+        chain_id: &str,
+        rpc_endpoints: Vec<String>,
+        block_time_ms: u64,
+        audit_log: Option<Arc<SecurityAuditLog>>,
+    ) -> Result<Self> {
+        if chain_id.trim().is_empty() {
+            return Err(anyhow!("EVM chain ID must not be empty"));
+        }
+        if rpc_endpoints.is_empty() || rpc_endpoints.iter().any(|url| url.trim().is_empty()) {
+            return Err(anyhow!("EVM chain '{}' needs at least one non-empty RPC endpoint", chain_id));
+        }
+        
+        let config = ChainConfig::new(NetworkType::Custom(rpc_endpoints[0].clone()))
+            .with_param("chain_id", chain_id)
+            .with_param("rpc_endpoints", &rpc_endpoints.join(","))
+            .with_param("block_time_ms", &block_time_ms.to_string());
+        Self::with_chain_config(config, chain_id.to_string(), audit_log)
+    }
+    
+    fn with_chain_config(config: ChainConfig, chain_id: String, audit_log: Option<Arc<SecurityAuditLog>>) -> Result<Self> {
+        Ok(Self {
             config,
-            client: reqwest::Client::builder()
-                .timeout(Duration::from_secs(30))
-                .build()
-                .expect("Failed to create HTTP client"),
+            client: adapter_http_client(&audit_log)?,
+            rpc_timeout: DEFAULT_RPC_TIMEOUT,
             audit_log,
             chain_id,
+            simulated: false,
+            mapped_coins: HashSet::new(),
+            rpc_backoff: Backoff::decorrelated_jitter(RPC_RETRY_BASE_DELAY, RPC_RETRY_MAX_DELAY)
+                .with_max_attempts(RPC_RETRY_ATTEMPTS),
+        })
+    }
+    
+    /// Rebuild the HTTP client with `factory`'s shared settings
+    pub fn with_http_client_factory(mut self, factory: &HttpClientFactory) -> Result<Self> {
+        self.client = factory.build_with_timeout(ADAPTER_HTTP_TIMEOUT)?;
+        Ok(self)
+    }
+    
+    /// Set the deadline for each RPC call
+    pub fn with_rpc_timeout(mut self, rpc_timeout: Duration) -> Self {
+        self.rpc_timeout = rpc_timeout;
+        self
+    }
+    
+    /// Schedule for sweeping the endpoints again after all of them were unreachable.
+    /// JSON-RPC errors are never retried.
+    pub fn with_rpc_backoff(mut self, rpc_backoff: Backoff) -> Self {
+        self.rpc_backoff = rpc_backoff;
+        self
+    }
+    
+    /// Block explorer used for `tx_explorer_link` and `object_explorer_link`
+    pub fn with_explorer_url(mut self, explorer_url: &str) -> Self {
+        self.config = self.config.with_param("explorer_url", explorer_url);
+        self
+    }
+    
+    /// Replace the configured RPC endpoints, tried in order
+    pub fn with_rpc_endpoints(mut self, endpoints: &[String]) -> Self {
+        self.config = self.config.with_param("rpc_endpoints", &endpoints.join(","));
+        self
+    }
+    
+    /// Simulate submissions and receipts without touching the network, for offline demos.
+    ///
+    /// Also recorded as the `simulated_rpc` config parameter, so `get_config` shows it.
+    pub fn with_simulated_rpc(mut self, simulated: bool) -> Self {
+        self.simulated = simulated;
+        self.config = self.config.with_param("simulated_rpc", &simulated.to_string());
+        self
+    }
+    
+    /// Pay out amounts of `coin_type` as ether, one to one after rescaling to wei
+    pub fn with_mapped_coin(mut self, coin_type: &str) -> Self {
+        self.mapped_coins.insert(coin_type.to_string());
+        self
+    }
+    
+    /// Whether calls are simulated rather than sent to the RPC endpoints
+    pub fn is_simulated(&self) -> bool {
+        self.simulated
+    }
+    
+    /// Call `method` on each configured endpoint in turn until one answers.
+    ///
+    /// Transport failures and JSON-RPC errors both move on to the next endpoint;
+    /// resubmitting a signed transaction is harmless since its hash doesn't change.
+    /// When no endpoint could be reached at all, the sweep is repeated on `rpc_backoff`.
+    async fn eth_rpc(&self, method: &str, params: Value) -> Result<Value> {
+        retry_async(
+            || self.sweep_endpoints(method, &params),
+            self.rpc_backoff.clone(),
+            |failure: &SweepFailure| !failure.rejected,
+        )
+        .await
+        .map_err(|failure| failure.error)
+    }
+    
+    /// One pass of `eth_rpc` over the configured endpoints
+    async fn sweep_endpoints(&self, method: &str, params: &Value) -> std::result::Result<Value, SweepFailure> {
+        let endpoints = self.config.get_rpc_endpoints();
+        let mut failures = Vec::new();
+        let mut rejected = false;
+        
+        for rpc_url in &endpoints {
+            match self.eth_rpc_at(rpc_url, method, params).await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    rejected |= matches!(e, EndpointError::Rejected(_));
+                    if let Some(log) = &self.audit_log {
+                        let _ = log.log_network(
+                            "EvmChainAdapter",
+                            &format!("{} failed on {}: {}", method, rpc_url, e),
+                            Some(self.chain_id()),
+                            AuditSeverity::Warning
+                        );
+                    }
+                    failures.push(format!("{}: {}", rpc_url, e));
+                }
+            }
+        }
+        
+        Err(SweepFailure {
+            error: anyhow!("{} failed on all {} endpoint(s) of {}: {}", method, endpoints.len(), self.chain_id, failures.join("; ")),
+            rejected,
+        })
+    }
+    
+    /// Next nonce for `address`, counting its transactions still in the mempool
+    async fn pending_nonce(&self, address: &str) -> Result<u64> {
+        let count = self.eth_rpc("eth_getTransactionCount", serde_json::json!([address, "pending"])).await?;
+        let nonce = parse_quantity(&count)
+            .map_err(|e| anyhow!("Could not read the nonce of {} on {}: {}", address, self.chain_id, e))?;
+        u64::try_from(nonce).map_err(|_| anyhow!("Nonce {} of {} is out of range", nonce, address))
+    }
+    
+    /// Current gas price in wei, or the configured recommendation when the node can't say
+    async fn gas_price(&self) -> u128 {
+        let quoted = match self.eth_rpc("eth_gasPrice", serde_json::json!([])).await {
+            Ok(result) => parse_quantity(&result),
+            Err(e) => Err(e),
+        };
+        match quoted {
+            Ok(price) => price,
+            Err(e) => {
+                let fallback = self.fallback_gas_price();
+                if let Some(log) = &self.audit_log {
+                    let _ = log.log_network(
+                        "EvmChainAdapter",
+                        &format!("eth_gasPrice unavailable ({}); using the recommended {} wei", e, fallback),
+                        Some(self.chain_id()),
+                        AuditSeverity::Warning
+                    );
+                }
+                fallback
+            }
         }
     }
     
+    /// `recommended_gas_price` from the chain config, which is in gwei, as wei
+    fn fallback_gas_price(&self) -> u128 {
+        u128::from(self.config.get_recommended_gas_price()) * 1_000_000_000
+    }
+    
+    /// One JSON-RPC request to `rpc_url`, returning its `result` (which may be null)
+    async fn eth_rpc_at(&self, rpc_url: &str, method: &str, params: &Value) -> std::result::Result<Value, EndpointError> {
+        let response = self.client
+            .post(rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params
+            }))
+            .send()
+            .await
+            .map_err(|e| EndpointError::Transport(format!("request failed: {}", e)))?;
+        
+        if !response.status().is_success() {
+            return Err(EndpointError::Transport(format!("HTTP {}", response.status())));
+        }
+        let mut body: Value = response.json().await
+            .map_err(|e| EndpointError::Transport(format!("invalid JSON-RPC response: {}", e)))?;
+        
+        if let Some(error) = body.get("error") {
+            let message = error.get("message").and_then(Value::as_str).map(str::to_string)
+                .unwrap_or_else(|| error.to_string());
+            return Err(EndpointError::Rejected(message));
+        }
+        Ok(body.get_mut("result").map(Value::take).unwrap_or(Value::Null))
+    }
+    
     /// Create Ethereum mainnet config
     fn mainnet_config() -> ChainConfig {
         let mut config = ChainConfig::new(NetworkType::Mainnet);
         config = config.with_param("chain_id", "ethereum-mainnet")
-            .with_param("rpc_endpoints", "https://mainnet.infura.io/v3/YOUR_INFURA_KEY,https://eth-mainnet.alchemyapi.io/v2/YOUR_ALCHEMY_KEY")
-            .with_param("explorer_url", "https://etherscan.io/tx")
+            .with_param("rpc_endpoints", "https://ethereum-rpc.publicnode.com,https://cloudflare-eth.com")
+            .with_param("explorer_url", "https://etherscan.io")
             .with_param("min_gas_price", "1")
             .with_param("recommended_gas_price", "50")
             .with_param("max_gas_price", "500")
@@ -231,8 +760,8 @@ impl EthereumAdapter {
     fn testnet_config() -> ChainConfig {
         let mut config = ChainConfig::new(NetworkType::Testnet);
         config = config.with_param("chain_id", "ethereum-sepolia")
-            .with_param("rpc_endpoints", "https://sepolia.infura.io/v3/YOUR_INFURA_KEY,https://eth-sepolia.g.alchemy.com/v2/YOUR_ALCHEMY_KEY")
-            .with_param("explorer_url", "https://sepolia.etherscan.io/tx")
+            .with_param("rpc_endpoints", "https://ethereum-sepolia-rpc.publicnode.com,https://rpc.sepolia.org")
+            .with_param("explorer_url", "https://sepolia.etherscan.io")
             .with_param("min_gas_price", "1")
             .with_param("recommended_gas_price", "20")
             .with_param("max_gas_price", "100")
@@ -240,12 +769,12 @@ impl EthereumAdapter {
         config
     }
     
-    /// Create Ethereum devnet config
+    /// Create Ethereum devnet (Holesky) config
     fn devnet_config() -> ChainConfig {
         let mut config = ChainConfig::new(NetworkType::Devnet);
-        config = config.with_param("chain_id", "ethereum-goerli")
-            .with_param("rpc_endpoints", "https://goerli.infura.io/v3/YOUR_INFURA_KEY")
-            .with_param("explorer_url", "https://goerli.etherscan.io/tx")
+        config = config.with_param("chain_id", "ethereum-holesky")
+            .with_param("rpc_endpoints", "https://ethereum-holesky-rpc.publicnode.com")
+            .with_param("explorer_url", "https://holesky.etherscan.io")
             .with_param("min_gas_price", "1")
             .with_param("recommended_gas_price", "10")
             .with_param("max_gas_price", "50")
@@ -267,7 +796,7 @@ impl EthereumAdapter {
 }
 
 #[async_trait]
-impl ChainAdapter for EthereumAdapter {
+impl ChainAdapter for EvmChainAdapter {
     fn chain_id(&self) -> &str {
         &self.chain_id
     }
@@ -276,71 +805,149 @@ impl ChainAdapter for EthereumAdapter {
         // Convert SUI transaction to Ethereum transaction format
         // In a real implementation, this would map SUI concepts to Ethereum
         
+        // A Sui address copied into `from`/`to` would produce a transaction no node accepts
+        self.check_addresses(tx)?;
+        
+        // `amount` is in the coin's own decimals (MIST by default), `value` must be wei
+        let value = self.native_amount(tx)?;
+        
+        // A stale nonce replays or collides, a made-up gas price gets stuck
+        let (nonce, gas_price) = if self.simulated {
+            (0, self.fallback_gas_price())
+        } else {
+            with_rpc_timeout(self.chain_id(), "format_transaction", self.rpc_timeout, async {
+                Ok((self.pending_nonce(&tx.sender).await?, self.gas_price().await))
+            }).await?
+        };
+        
         // For now, we'll create a simple Ethereum transfer transaction
         let eth_tx = serde_json::json!({
             "from": tx.sender,
             "to": tx.receiver,
-            "value": format!("0x{:x}", tx.amount),
-            "gasPrice": format!("0x{:x}", tx.gas_budget * 1_000_000_000), // Convert to wei
+            "value": format!("0x{:x}", value),
+            "gasPrice": format!("0x{:x}", gas_price),
             "gasLimit": "0x5208", // 21000 for standard transfer
             "data": "0x", // Empty data for standard transfer
-            "nonce": "0x0", // Would be fetched from the network in real implementation
+            "nonce": format!("0x{:x}", nonce),
         });
         
         Ok(eth_tx)
     }
     
-    async fn execute_transaction(&self, _tx_data: &Value) -> Result<String> {
-        // In a real implementation, this would send the transaction to Ethereum
-        // For now, we'll just return a mock transaction hash
+    async fn execute_transaction(&self, tx_data: &Value, timeout: Option<Duration>) -> Result<String> {
+        // Signing happens outside the adapter: callers hand over the encoded transaction
+        let raw = match tx_data.get("raw").and_then(Value::as_str) {
+            Some(raw) if is_hex_bytes(raw) => Some(raw),
+            Some(_) => return Err(anyhow!("'raw' must be a 0x-prefixed hex encoding of a signed transaction")),
+            None if self.simulated => None,
+            None => return Err(anyhow!("Transaction data for {} needs a pre-signed transaction under 'raw'", self.chain_id)),
+        };
         
         // Log the operation
         if let Some(log) = &self.audit_log {
             let _ = log.log_network(
-                "EthereumAdapter",
-                "Executing transaction on Ethereum",
+                "EvmChainAdapter",
+                &format!("Executing transaction on {}", self.chain_id),
                 Some(self.chain_id()),
                 AuditSeverity::Info
             );
         }
         
-        // Mock transaction hash (Ethereum uses 0x-prefixed hex)
-        let tx_hash = format!("0x{:064x}", rand::random::<u64>());
-        
-        Ok(tx_hash)
+        with_rpc_timeout(self.chain_id(), "execute_transaction", timeout.unwrap_or(self.rpc_timeout), async {
+            if self.simulated {
+                // Mock transaction hash (Ethereum uses 0x-prefixed hex)
+                return Ok(format!("0x{:064x}", rand::random::<u64>()));
+            }
+            
+            let result = self.eth_rpc("eth_sendRawTransaction", serde_json::json!([raw])).await?;
+            result.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("eth_sendRawTransaction returned {} instead of a transaction hash", result))
+        }).await
     }
     
-    async fn get_transaction_status(&self, tx_hash: &str) -> Result<Value> {
-        // In a real implementation, this would query the transaction status from Ethereum
-        // For now, we'll just return a mock status
-        
-        Ok(serde_json::json!({
-            "hash": tx_hash,
-            "status": "0x1", // 0x1 = success, 0x0 = failure
-            "blockNumber": format!("0x{:x}", rand::random::<u32>()),
-            "confirmations": 10,
-            "timestamp": chrono::Utc::now().timestamp()
-        }))
+    async fn get_transaction_status(&self, tx_hash: &str, timeout: Option<Duration>) -> Result<Value> {
+        with_rpc_timeout(self.chain_id(), "get_transaction_status", timeout.unwrap_or(self.rpc_timeout), async {
+            if self.simulated {
+                return Ok(serde_json::json!({
+                    "hash": tx_hash,
+                    "status": "0x1", // 0x1 = success, 0x0 = failure
+                    "blockNumber": format!("0x{:x}", rand::random::<u32>()),
+                    "confirmations": 10,
+                    "timestamp": chrono::Utc::now().timestamp()
+                }));
+            }
+            
+            let receipt = self.eth_rpc("eth_getTransactionReceipt", serde_json::json!([tx_hash])).await?;
+            // No receipt yet means the transaction hasn't been mined
+            if receipt.is_null() {
+                return Ok(serde_json::json!({
+                    "hash": tx_hash,
+                    "status": "pending",
+                    "confirmed": false
+                }));
+            }
+            Ok(serde_json::json!({
+                "hash": tx_hash,
+                "status": receipt.get("status").cloned().unwrap_or(Value::Null),
+                "blockNumber": receipt.get("blockNumber").cloned().unwrap_or(Value::Null),
+                "gasUsed": receipt.get("gasUsed").cloned().unwrap_or(Value::Null),
+                "confirmed": true
+            }))
+        }).await
+    }
+    
+    fn rpc_timeout(&self) -> Duration {
+        self.rpc_timeout
     }
     
     fn supports_transaction_type(&self, tx_type: &str) -> bool {
-        // Ethereum supports basic transfer transactions
+        // EVM chains support basic transfer transactions
         matches!(tx_type, "Transfer")
     }
     
     fn get_config(&self) -> ChainConfig {
         self.config.clone()
     }
+    
+    fn address_format(&self) -> AddressFormat {
+        AddressFormat::Ethereum
+    }
+    
+    fn native_decimals(&self) -> u8 {
+        ETH_DECIMALS
+    }
+    
+    fn native_coin_type(&self) -> &str {
+        ETH_COIN_TYPE
+    }
+    
+    fn accepts_coin(&self, coin_type: &str) -> bool {
+        coin_type == ETH_COIN_TYPE || self.mapped_coins.contains(coin_type)
+    }
+    
+    async fn is_available(&self) -> bool {
+        // Any configured endpoint answering eth_blockNumber is good enough
+        for rpc_url in self.config.get_rpc_endpoints() {
+            let ping = ping_json_rpc(&self.client, &rpc_url, "eth_blockNumber");
+            if tokio::time::timeout(self.rpc_timeout, ping).await.unwrap_or(false) {
+                return true;
+            }
+        }
+        false
+    }
 }
 
 /// Cross-chain transaction mapper implementation
 pub struct CrossChainMapperImpl {
     /// Map of chain adapters by chain ID
-    adapters: Arc<Mutex<HashMap<String, Box<dyn ChainAdapter>>>>,
+    adapters: Arc<Mutex<HashMap<String, Arc<dyn ChainAdapter>>>>,
     /// Map of mapped transactions
     mappings: Arc<Mutex<HashMap<String, CrossChainTransaction>>>,
     /// Network manager for primary chain
     network_manager: Arc<NetworkManager>,
+    /// Target-chain counterparts of origin-chain addresses
+    address_mapping: Arc<AddressMapping>,
     /// Audit log
     audit_log: Option<Arc<SecurityAuditLog>>,
 }
@@ -355,10 +962,58 @@ impl CrossChainMapperImpl {
             adapters: Arc::new(Mutex::new(HashMap::new())),
             mappings: Arc::new(Mutex::new(HashMap::new())),
             network_manager,
+            address_mapping: Arc::new(AddressMapping::new()),
             audit_log,
         }
     }
     
+    /// Use a shared address registry instead of the mapper's own
+    pub fn with_address_mapping(mut self, address_mapping: Arc<AddressMapping>) -> Self {
+        self.address_mapping = address_mapping;
+        self
+    }
+    
+    /// The registry consulted when translating addresses
+    pub fn address_mapping(&self) -> &AddressMapping {
+        &self.address_mapping
+    }
+    
+    /// Chain ID of the active Sui network
+    fn origin_chain_id(&self) -> String {
+        let config = self.network_manager.get_active_config();
+        config.get_chain_id().unwrap_or_else(|| format!("sui-{:?}", config.network_type))
+    }
+    
+    /// `tx` with its sender and receiver replaced by their `target_adapter` counterparts
+    ///
+    /// Addresses already valid on the target chain are kept; any other address
+    /// must have a registered mapping.
+    fn translate_addresses(&self, tx: &Transaction, target_adapter: &dyn ChainAdapter) -> Result<Transaction> {
+        let origin_chain = self.origin_chain_id();
+        let target_chain = target_adapter.chain_id();
+        let format = target_adapter.address_format();
+        let translate = |field: &'static str, address: &str| -> Result<String> {
+            if format.is_valid(address) {
+                return Ok(address.to_string());
+            }
+            self.address_mapping.resolve(&origin_chain, address, target_chain).ok_or_else(|| {
+                UnmappedAddress {
+                    origin_chain: origin_chain.clone(),
+                    target_chain: target_chain.to_string(),
+                    field,
+                    address: address.to_string(),
+                }.into()
+            })
+        };
+        
+        let mut translated = tx.clone();
+        translated.sender = translate("sender", &tx.sender)?;
+        translated.receiver = translate("receiver", &tx.receiver)?;
+        // A registered address could still be malformed
+        target_adapter.check_addresses(&translated)?;
+        Ok(translated)
+    }
+    
     /// Add a chain adapter
     pub fn add_adapter(&self, adapter: Box<dyn ChainAdapter>) -> Result<()> {
         let chain_id = adapter.chain_id().to_string();
@@ -369,7 +1024,7 @@ impl CrossChainMapperImpl {
             return Err(anyhow!("Adapter for chain '{}' already exists", chain_id));
         }
         
-        adapters.insert(chain_id.clone(), adapter);
+        adapters.insert(chain_id.clone(), Arc::from(adapter));
         
         if let Some(log) = &self.audit_log {
             let _ = log.log_network(
@@ -383,32 +1038,12 @@ impl CrossChainMapperImpl {
         Ok(())
     }
     
-    /// Get a chain adapter by chain ID
-    fn get_adapter(&self, chain_id: &str) -> Result<Box<dyn ChainAdapter + Send + Sync>> {
-        let adapters = self.adapters.lock().unwrap();
-        
-        if let Some(adapter) = adapters.get(chain_id) {
-            // We can't return a reference to the adapter due to lifetime issues,
-            // so we'll create a new adapter of the same type
-            if chain_id.contains("sui") {
-                // Create a new SUI adapter
-                return Ok(Box::new(SuiAdapter::new(
-                    self.network_manager.clone(),
-                    self.audit_log.clone(),
-                )));
-            } else if chain_id.contains("ethereum") {
-                // Create a new Ethereum adapter with the same network type
-                let config = adapter.get_config();
-                return Ok(Box::new(EthereumAdapter::new(
-                    config.network_type,
-                    self.audit_log.clone(),
-                )));
-            } else {
-                return Err(anyhow!("Unsupported chain type for cloning: {}", chain_id));
-            }
-        } else {
-            Err(anyhow!("No adapter found for chain '{}'", chain_id))
-        }
+    /// The registered adapter for `chain_id`, shared rather than rebuilt so its settings carry over
+    fn get_adapter(&self, chain_id: &str) -> Result<Arc<dyn ChainAdapter>> {
+        self.adapters.lock().unwrap()
+            .get(chain_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("No adapter found for chain '{}'", chain_id))
     }
     
     /// Create a mapping key from origin and transaction IDs
@@ -430,15 +1065,16 @@ impl CrossChainMapperImpl {
         let sui_adapter = SuiAdapter::new(
             self.network_manager.clone(),
             self.audit_log.clone(),
-        );
+        )?;
         
         self.add_adapter(Box::new(sui_adapter))?;
         
-        // Add Ethereum adapter (testnet)
+        // Add Ethereum adapter (testnet), simulated so the demo runs offline;
+        // register a live adapter with `add_adapter` to submit for real
         let eth_adapter = EthereumAdapter::new(
             NetworkType::Testnet,
             self.audit_log.clone(),
-        );
+        )?.with_simulated_rpc(true);
         
         self.add_adapter(Box::new(eth_adapter))?;
         
@@ -454,6 +1090,22 @@ impl CrossChainMapperImpl {
         Ok(())
     }
     
+    /// Record a failed adapter call on the stored mapping
+    ///
+    /// Timeouts are reported as such so routing can tell a slow chain from a rejected transaction.
+    fn mark_failed(&self, key: &str, error: &anyhow::Error) {
+        let reason = match error.downcast_ref::<RpcTimeout>() {
+            Some(timeout) => format!("timeout: {}", timeout),
+            None => error.to_string(),
+        };
+        let mut mappings = self.mappings.lock().unwrap();
+        if let Some(tx) = mappings.get_mut(key) {
+            tx.status = CrossChainStatus::Failed(reason.clone());
+            tx.error = Some(reason);
+            tx.updated_at = chrono::Utc::now().timestamp() as u64;
+        }
+    }
+    
     /// Get all supported chain IDs
     pub fn get_supported_chains(&self) -> Vec<String> {
         let adapters = self.adapters.lock().unwrap();
@@ -486,20 +1138,68 @@ impl CrossChainMapper for self::CrossChainMapperImpl {
             );
         }
         
-        Ok(supported)
+        if !supported {
+            return Ok(false);
+        }
+        
+        if let Err(e) = self.translate_addresses(tx, target_adapter.as_ref()) {
+            if let Some(log) = &self.audit_log {
+                let _ = log.log_network(
+                    "CrossChainMapper",
+                    &e.to_string(),
+                    Some(target_chain),
+                    AuditSeverity::Warning
+                );
+            }
+            return Ok(false);
+        }
+        
+        // Reject coins the chain can't pay out now, rather than failing later in `format_transaction`
+        let coin_type = tx.denominated_amount().coin_type;
+        if !target_adapter.accepts_coin(&coin_type) {
+            if let Some(log) = &self.audit_log {
+                let unmapped = AmountError::UnmappedCoin { coin_type, chain_id: target_chain.to_string() };
+                let _ = log.log_network(
+                    "CrossChainMapper",
+                    &unmapped.to_string(),
+                    Some(target_chain),
+                    AuditSeverity::Warning
+                );
+            }
+            return Ok(false);
+        }
+        
+        // Don't map onto a chain we can't reach
+        let available = target_adapter.is_available().await;
+        
+        if !available {
+            if let Some(log) = &self.audit_log {
+                let _ = log.log_network(
+                    "CrossChainMapper",
+                    &format!("Target chain '{}' is not reachable", target_chain),
+                    Some(target_chain),
+                    AuditSeverity::Warning
+                );
+            }
+        }
+        
+        Ok(available)
     }
     
     async fn map_transaction(&self, tx: &Transaction, target_chain: &str) -> Result<CrossChainTransaction> {
+        // Get the target chain adapter
+        let target_adapter = self.get_adapter(target_chain)?;
+        
+        // Report untranslatable addresses as such rather than as a generic mapping failure
+        let translated = self.translate_addresses(tx, target_adapter.as_ref())?;
+        
         // First check if mapping is possible
         if !self.can_map(tx, target_chain).await? {
             return Err(anyhow!("Cannot map transaction to chain '{}'", target_chain));
         }
         
-        // Get the target chain adapter
-        let target_adapter = self.get_adapter(target_chain)?;
-        
         // Format the transaction for the target chain
-        let target_tx_data = target_adapter.format_transaction(tx).await?;
+        let target_tx_data = target_adapter.format_transaction(&translated).await?;
         
         // Get the original chain ID
         let config = self.network_manager.get_active_config();
@@ -548,17 +1248,23 @@ impl CrossChainMapper for self::CrossChainMapperImpl {
         let target_adapter = self.get_adapter(&mapped_tx.target_chain_id)?;
         
         // Execute the transaction on the target chain
-        let target_tx_id = target_adapter.execute_transaction(&mapped_tx.target_tx_data).await?;
-        
-        // Update the mapping with the target transaction ID
         let key = Self::mapping_key(&mapped_tx.origin_chain_id, &mapped_tx.origin_tx_id);
+        let target_tx_id = match target_adapter.execute_transaction(&mapped_tx.target_tx_data, None).await {
+            Ok(id) => id,
+            Err(e) => {
+                self.mark_failed(&key, &e);
+                return Err(e);
+            }
+        };
         
+        // Update the mapping with the target transaction ID
         let mut mappings = self.mappings.lock().unwrap();
         if let Some(tx) = mappings.get_mut(&key) {
             tx.target_tx_id = Some(target_tx_id.clone());
             tx.status = CrossChainStatus::Available;
             tx.updated_at = chrono::Utc::now().timestamp() as u64;
         }
+        drop(mappings);
         
         // Log the execution
         if let Some(log) = &self.audit_log {
@@ -589,13 +1295,21 @@ impl CrossChainMapper for self::CrossChainMapperImpl {
         let config = self.network_manager.get_active_config();
         let _origin_chain_id = config.get_chain_id()
             .unwrap_or_else(|| format!("sui-{:?}", config.network_type));
-        let status = target_adapter.get_transaction_status(target_tx_id).await?;
+        let status = match target_adapter.get_transaction_status(target_tx_id, None).await {
+            Ok(status) => status,
+            Err(e) => {
+                let key = Self::mapping_key(&mapped_tx.origin_chain_id, &mapped_tx.origin_tx_id);
+                self.mark_failed(&key, &e);
+                return Err(e);
+            }
+        };
         
         // Check if the transaction was successful
         // The exact check would depend on the chain-specific response format
         let success = if let Some(status_field) = status.get("status") {
             if status_field.is_string() {
-                status_field.as_str().unwrap() == "success"
+                // Ethereum receipts report 0x1 for success and 0x0 for a revert
+                matches!(status_field.as_str().unwrap(), "success" | "0x1")
             } else if status_field.is_boolean() {
                 status_field.as_bool().unwrap()
             } else {
@@ -676,4 +1390,4 @@ pub async fn demonstrate_cross_chain_mapping(
     }
     
     Err(anyhow!("Could not downcast mapper to CrossChainMapperImpl"))
-}
\ No newline at end of file
+}
\ No newline at end of file
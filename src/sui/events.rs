@@ -0,0 +1,251 @@
+//! Event-based confirmation of L1 verifications.
+//!
+//! Submission already checks the effects of each verification transaction.
+//! `VerificationEventListener` adds an independent path: it follows the
+//! verification contract's `VerificationSuccess` events through the Sui
+//! event query API and reports each one as a `VerificationConfirmation`,
+//! matched to the submission it confirms where possible.
+//!
+//! Events are polled rather than taken from the websocket subscription API,
+//! which needs a websocket-enabled client and isn't offered by every fullnode.
+
+use crate::config;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sui_sdk::rpc_types::{EventFilter, SuiEvent};
+use sui_sdk::SuiClient;
+use sui_types::parse_sui_struct_tag;
+use tokio::sync::mpsc;
+
+/// Name of the event the verification contract emits for each successful verification
+pub const VERIFICATION_EVENT: &str = "VerificationSuccess";
+
+/// Submissions awaiting their event before the oldest are forgotten
+pub const DEFAULT_PENDING_CAPACITY: usize = 4096;
+
+/// How long a submission waits for its event before it is forgotten
+pub const DEFAULT_PENDING_TTL: Duration = Duration::from_secs(600);
+
+/// Delay between event queries once the listener has caught up
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Events requested per query
+const EVENT_PAGE_SIZE: usize = 50;
+
+/// Confirmations buffered for a slow consumer before polling waits
+const CONFIRMATION_BUFFER: usize = 256;
+
+/// A `VerificationSuccess` event seen on-chain.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerificationConfirmation {
+    /// Digest of the L1 transaction that emitted the event
+    pub tx_digest: String,
+    pub event_seq: u64,
+    pub timestamp_ms: Option<u64>,
+    /// Attestation payload of the tracked submission this event confirms, if any
+    pub payload: Option<Vec<u8>>,
+    /// The event's fields as parsed by the node
+    pub fields: Value,
+}
+
+/// The attestation payload carried by an event's fields, if present.
+///
+/// Accepts a `payload` or `attestation_payload` field holding a Move
+/// `vector<u8>`, which nodes render as an array of numbers or a hex string.
+pub fn event_payload(fields: &Value) -> Option<Vec<u8>> {
+    let value = fields.get("payload").or_else(|| fields.get("attestation_payload"))?;
+    match value {
+        Value::Array(items) => items.iter().map(|item| item.as_u64().and_then(|b| u8::try_from(b).ok())).collect(),
+        Value::String(encoded) => hex::decode(encoded.trim_start_matches("0x")).ok(),
+        _ => None,
+    }
+}
+
+/// Submissions waiting for their verification event, oldest first.
+///
+/// Bounded two ways, so submissions whose event never arrives don't pile up:
+/// entries older than `ttl` are dropped, and past `capacity` the oldest go first.
+#[derive(Debug)]
+pub struct PendingConfirmations {
+    entries: VecDeque<PendingEntry>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+#[derive(Debug)]
+struct PendingEntry {
+    payload: Vec<u8>,
+    l1_digest: Option<String>,
+    tracked_at: Instant,
+}
+
+impl Default for PendingConfirmations {
+    fn default() -> Self {
+        Self::new(DEFAULT_PENDING_CAPACITY, DEFAULT_PENDING_TTL)
+    }
+}
+
+impl PendingConfirmations {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self { entries: VecDeque::new(), capacity, ttl }
+    }
+
+    /// Expect an event for `payload`, submitted in L1 transaction `l1_digest` when known.
+    pub fn track(&mut self, payload: &[u8], l1_digest: Option<&str>) {
+        let now = Instant::now();
+        self.expire(now);
+        if let Some(digest) = l1_digest {
+            self.entries.retain(|entry| entry.l1_digest.as_deref() != Some(digest));
+        }
+        self.entries.push_back(PendingEntry {
+            payload: payload.to_vec(),
+            l1_digest: l1_digest.map(str::to_string),
+            tracked_at: now,
+        });
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    /// The tracked payload an event from `tx_digest` with `fields` confirms, which is no longer pending.
+    ///
+    /// Matches on the transaction digest first, then on the payload in the event.
+    pub fn resolve(&mut self, tx_digest: &str, fields: &Value) -> Option<Vec<u8>> {
+        self.expire(Instant::now());
+        let index = match self.entries.iter().position(|entry| entry.l1_digest.as_deref() == Some(tx_digest)) {
+            Some(index) => index,
+            None => {
+                let payload = event_payload(fields)?;
+                self.entries.iter().position(|entry| entry.l1_digest.is_none() && entry.payload == payload)?
+            }
+        };
+        self.entries.remove(index).map(|entry| entry.payload)
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while matches!(self.entries.front(), Some(entry) if now.duration_since(entry.tracked_at) > self.ttl) {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Follows `VerificationSuccess` events of one verification contract.
+pub struct VerificationEventListener {
+    client: Arc<SuiClient>,
+    event_type: String,
+    filter: EventFilter,
+    poll_interval: Duration,
+    pending: Arc<Mutex<PendingConfirmations>>,
+}
+
+impl VerificationEventListener {
+    /// Listener for the events of `module` in `package_id`.
+    pub fn new(client: Arc<SuiClient>, package_id: &str, module: &str) -> Result<Self> {
+        let event_type = format!("{}::{}::{}", package_id, module, VERIFICATION_EVENT);
+        let struct_tag = parse_sui_struct_tag(&event_type)
+            .map_err(|e| anyhow!("Invalid event type {}: {}", event_type, e))?;
+        Ok(Self {
+            client,
+            event_type,
+            filter: EventFilter::MoveEventType(struct_tag),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            pending: Arc::new(Mutex::new(PendingConfirmations::default())),
+        })
+    }
+
+    /// Listener for the configured verification contract.
+    pub fn for_verification_contract(client: Arc<SuiClient>) -> Result<Self> {
+        Self::new(client, config::VERIFICATION_CONTRACT_PACKAGE_ID, config::VERIFICATION_CONTRACT_MODULE)
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Fully qualified Move type of the events followed.
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    /// Correlate the next event for `payload` (or from `l1_digest`) with this submission.
+    pub fn track(&self, payload: &[u8], l1_digest: Option<&str>) {
+        self.pending.lock().unwrap().track(payload, l1_digest);
+    }
+
+    /// Submissions still waiting for their event.
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Stream of confirmations for events emitted from now on.
+    ///
+    /// Polling runs on a background task until the receiver is dropped. Query
+    /// failures are logged and retried at the next interval.
+    pub fn subscribe(&self) -> mpsc::Receiver<VerificationConfirmation> {
+        let (sender, receiver) = mpsc::channel(CONFIRMATION_BUFFER);
+        let client = self.client.clone();
+        let filter = self.filter.clone();
+        let event_type = self.event_type.clone();
+        let pending = self.pending.clone();
+        let poll_interval = self.poll_interval;
+
+        tokio::spawn(async move {
+            // Start after the newest existing event so only new verifications are reported
+            let mut cursor = match client.event_api().query_events(filter.clone(), None, Some(1), true).await {
+                Ok(page) => page.data.first().map(|event| event.id),
+                Err(e) => {
+                    eprintln!("WARNING: Could not read latest {} event, starting from the beginning: {}", event_type, e);
+                    None
+                }
+            };
+            loop {
+                match client.event_api().query_events(filter.clone(), cursor, Some(EVENT_PAGE_SIZE), false).await {
+                    Ok(page) => {
+                        for event in page.data {
+                            cursor = Some(event.id);
+                            let confirmation = Self::confirmation(event, &pending);
+                            if sender.send(confirmation).await.is_err() {
+                                return;
+                            }
+                        }
+                        if page.has_next_page {
+                            continue;
+                        }
+                    }
+                    Err(e) => eprintln!("WARNING: Polling {} events failed: {}", event_type, e),
+                }
+                if sender.is_closed() {
+                    return;
+                }
+                tokio::time::sleep(poll_interval).await;
+            }
+        });
+
+        receiver
+    }
+
+    fn confirmation(event: SuiEvent, pending: &Mutex<PendingConfirmations>) -> VerificationConfirmation {
+        let tx_digest = event.id.tx_digest.to_string();
+        let payload = pending.lock().unwrap().resolve(&tx_digest, &event.parsed_json);
+        VerificationConfirmation {
+            tx_digest,
+            event_seq: event.id.event_seq,
+            timestamp_ms: event.timestamp_ms,
+            payload,
+            fields: event.parsed_json,
+        }
+    }
+}
@@ -5,6 +5,8 @@ pub mod network;
 pub mod byzantine;
 pub mod cross_chain;
 pub mod types;
+pub mod signing;
+pub mod events;
 
 // Re-export tracker function
 pub use tracker::track_sui_interaction;
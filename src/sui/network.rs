@@ -8,8 +8,23 @@ use std::str::FromStr;
 use sui_sdk::SuiClient;
 use sui_sdk::SuiClientBuilder;
 use async_trait::async_trait;
+use futures::future::join_all;
+use serde_json::{json, Value};
 use crate::sui::SuiClientProvider;
 
+/// How long endpoints found by `NetworkManager::discover_peers` are reused
+pub const DEFAULT_DISCOVERY_TTL: Duration = Duration::from_secs(600);
+
+/// Most discovered candidates health-checked per discovery
+const MAX_DISCOVERY_PROBES: usize = 32;
+
+/// Public testnet fullnodes used when discovery finds no reachable RPC peer
+pub const TESTNET_PUBLIC_NODES: [&str; 3] = [
+    "https://fullnode.testnet.sui.io:443",
+    "https://sui-testnet-rpc.publicnode.com",
+    "https://sui-testnet-endpoint.blockvision.org",
+];
+
 /// Enum representing different network types that the system can connect to
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum NetworkType {
@@ -95,6 +110,25 @@ impl ChainConfig {
         }
     }
 
+    /// Config for a Sui network, with Sui Explorer links where the explorer supports the network.
+    pub fn for_sui(network_type: NetworkType) -> Self {
+        let explorer_network = match &network_type {
+            NetworkType::Testnet => Some("testnet"),
+            NetworkType::Devnet => Some("devnet"),
+            NetworkType::Mainnet => Some("mainnet"),
+            NetworkType::Local => Some("local"),
+            NetworkType::Custom(_) => None,
+        };
+        let config = Self::new(network_type);
+        match explorer_network {
+            Some(network) => config
+                .with_param("explorer_url", "https://suiexplorer.com")
+                .with_param("explorer_tx_path", &format!("/txblock/{{id}}?network={}", network))
+                .with_param("explorer_object_path", &format!("/object/{{id}}?network={}", network)),
+            None => config,
+        }
+    }
+
     pub fn with_param(mut self, key: &str, value: &str) -> Self {
         self.params.insert(key.to_string(), value.to_string());
         self
@@ -117,6 +151,26 @@ impl ChainConfig {
         self.params.get("explorer_url").cloned()
     }
     
+    /// Explorer link for a transaction, if this chain has an explorer configured.
+    ///
+    /// Built from `explorer_url` plus the `explorer_tx_path` template (default `/tx/{id}`).
+    pub fn tx_explorer_link(&self, digest: &str) -> Option<String> {
+        self.explorer_link("explorer_tx_path", "/tx/{id}", digest)
+    }
+    
+    /// Explorer link for an object (or account, on account-based chains).
+    ///
+    /// Built from `explorer_url` plus the `explorer_object_path` template (default `/address/{id}`).
+    pub fn object_explorer_link(&self, id: &str) -> Option<String> {
+        self.explorer_link("explorer_object_path", "/address/{id}", id)
+    }
+    
+    fn explorer_link(&self, path_param: &str, default_path: &str, id: &str) -> Option<String> {
+        let base = self.get_explorer_url()?;
+        let path = self.params.get(path_param).map(String::as_str).unwrap_or(default_path);
+        Some(format!("{}{}", base.trim_end_matches('/'), path.replace("{id}", id)))
+    }
+    
     pub fn get_min_gas_price(&self) -> u64 {
         self.params.get("min_gas_price")
             .and_then(|s| s.parse::<u64>().ok())
@@ -142,12 +196,70 @@ impl ChainConfig {
     }
 }
 
+/// Curated public RPC nodes for `network`; empty where none are maintained.
+pub fn curated_public_nodes(network: &NetworkType) -> &'static [&'static str] {
+    match network {
+        NetworkType::Testnet => &TESTNET_PUBLIC_NODES,
+        _ => &[],
+    }
+}
+
+/// HTTP URL for a multiaddr such as `/dns/node.example.com/tcp/9000/https`.
+///
+/// Returns `None` for addresses without a host and TCP port, or that use a
+/// transport other than HTTP(S). Without a trailing protocol, HTTPS is assumed.
+pub fn rpc_url_from_multiaddr(multiaddr: &str) -> Option<String> {
+    let parts: Vec<&str> = multiaddr.split('/').filter(|part| !part.is_empty()).collect();
+    let mut host = None;
+    let mut port = None;
+    let mut scheme = "https";
+    let mut i = 0;
+    while i < parts.len() {
+        match (parts[i], parts.get(i + 1)) {
+            ("dns" | "dns4" | "dns6" | "ip4", Some(value)) => host = Some(value.to_string()),
+            ("ip6", Some(value)) => host = Some(format!("[{}]", value)),
+            ("tcp", Some(value)) => port = Some(value.parse::<u16>().ok()?),
+            ("http", _) => {
+                scheme = "http";
+                i += 1;
+                continue;
+            }
+            ("https", _) => {
+                i += 1;
+                continue;
+            }
+            _ => return None,
+        }
+        i += 2;
+    }
+    Some(format!("{}://{}:{}", scheme, host?, port?))
+}
+
+/// Candidate RPC URLs for the active validators in a `suix_getLatestSuiSystemState` result.
+///
+/// Validators advertise network addresses, not JSON-RPC endpoints, so the
+/// candidates still need a health check before use. Duplicates are dropped.
+pub fn fullnode_candidates(system_state: &Value) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let validators = system_state["activeValidators"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    for validator in validators {
+        if let Some(url) = validator["netAddress"].as_str().and_then(rpc_url_from_multiaddr) {
+            if !candidates.contains(&url) {
+                candidates.push(url);
+            }
+        }
+    }
+    candidates
+}
+
 /// Manager for handling network connections and client instances
 pub struct NetworkManager {
     /// Default network type to use when not specified
     default_network: NetworkType,
     /// Map of network type to SuiClient
     clients: HashMap<NetworkType, Arc<SuiClient>>,
+    /// Clients handed out by `SuiClientProvider::get_client`, by the endpoint they were built for
+    endpoint_clients: Arc<Mutex<HashMap<String, Arc<SuiClient>>>>,
     /// Current active network configuration
     active_config: Arc<Mutex<ChainConfig>>,
     /// HTTP client for making RPC requests
@@ -156,16 +268,21 @@ pub struct NetworkManager {
     node_status_cache: Arc<Mutex<HashMap<String, (NodeStatus, Instant)>>>,
     /// How long to cache node status (in seconds)
     status_cache_ttl: u64,
+    /// Endpoints found by `discover_peers`, per seed, and when they were found
+    discovered_peers: Arc<Mutex<HashMap<String, (Vec<String>, Instant)>>>,
+    /// How long discovered endpoints are reused before asking the seed again
+    discovery_ttl: Duration,
 }
 
 impl NetworkManager {
     /// Create a new NetworkManager with the specified default network
     pub async fn new(default_network: NetworkType) -> Result<Self> {
-        let config = ChainConfig::new(default_network.clone());
+        let config = ChainConfig::for_sui(default_network.clone());
         
         let mut manager = Self {
             default_network: default_network.clone(),
             clients: HashMap::new(),
+            endpoint_clients: Arc::new(Mutex::new(HashMap::new())),
             active_config: Arc::new(Mutex::new(config)),
             client: reqwest::Client::builder()
                 .timeout(Duration::from_secs(10))
@@ -173,10 +290,13 @@ impl NetworkManager {
                 .expect("Failed to create HTTP client"),
             node_status_cache: Arc::new(Mutex::new(HashMap::new())),
             status_cache_ttl: 60, // Cache node status for 60 seconds
+            discovered_peers: Arc::new(Mutex::new(HashMap::new())),
+            discovery_ttl: DEFAULT_DISCOVERY_TTL,
         };
 
         // Initialize the default client
-        let client = manager.init_client(default_network).await?;
+        let client = manager.init_client(default_network.clone()).await?;
+        manager.endpoint_clients.lock().unwrap().insert(default_network.get_rpc_url(), client.clone());
         manager.clients.insert(manager.default_network.clone(), client);
 
         Ok(manager)
@@ -222,7 +342,7 @@ impl NetworkManager {
     
     /// Switch to a different network
     pub fn switch_network(&self, network_type: NetworkType) -> Result<ChainConfig> {
-        let config = ChainConfig::new(network_type);
+        let config = ChainConfig::for_sui(network_type);
         
         // Update active configuration
         let mut active_config = self.active_config.lock().unwrap();
@@ -270,13 +390,13 @@ impl NetworkManager {
             }
         }
         
-        // Perform health check
+        // Perform health check; the chain identifier is cheap and only served by a synced node
         match self.client
             .post(rpc_url)
             .json(&serde_json::json!({
                 "jsonrpc": "2.0",
                 "id": 1,
-                "method": "rpc.discover",
+                "method": "sui_getChainIdentifier",
                 "params": []
             }))
             .send()
@@ -287,7 +407,7 @@ impl NetworkManager {
                     match response.json::<serde_json::Value>().await {
                         Ok(json) => {
                             // Check if the response is valid
-                            if json.get("result").is_some() {
+                            if matches!(json.get("result"), Some(result) if !result.is_null()) {
                                 let status = NodeStatus::Healthy;
                                 
                                 // Update cache
@@ -338,39 +458,48 @@ impl NetworkManager {
         }
     }
     
-    /// Get a healthy RPC endpoint, trying multiple endpoints if needed
+    /// First endpoint that passes a health check, trying the configured endpoints in order
+    ///
+    /// The network's curated public nodes are tried last. Statuses come from the
+    /// node status cache while fresh, so a node found down is skipped without
+    /// being probed again until `status_cache_ttl` runs out.
     pub async fn get_healthy_rpc_url(&self) -> Result<String> {
-        let config = self.active_config.lock().unwrap();
-        let endpoints = config.get_rpc_endpoints();
+        // Don't hold the config lock across the health check awaits
+        let config = self.get_active_config();
+        let mut candidates = config.get_rpc_endpoints();
+        for node in curated_public_nodes(&config.network_type) {
+            if !candidates.iter().any(|endpoint| endpoint == node) {
+                candidates.push(node.to_string());
+            }
+        }
         
-        // Try each endpoint until we find a healthy one
-        for endpoint in &endpoints {
+        let mut failures = Vec::new();
+        for endpoint in &candidates {
             match self.is_node_healthy(endpoint).await {
-                Ok(NodeStatus::Healthy) => {
-                    return Ok(endpoint.clone());
-                },
-                Ok(NodeStatus::Degraded(_)) => {
-                    // If degraded, try the next endpoint
-                    continue;
-                },
-                Ok(NodeStatus::Down(_)) => {
-                    // If down, try the next endpoint
-                    continue;
-                },
-                Err(_) => {
-                    // If error, try the next endpoint
-                    continue;
+                Ok(NodeStatus::Healthy) => return Ok(endpoint.clone()),
+                Ok(NodeStatus::Degraded(reason)) | Ok(NodeStatus::Down(reason)) => {
+                    failures.push(format!("{}: {}", endpoint, reason));
+                }
+                Err(e) => {
+                    self.mark_node_status(endpoint, NodeStatus::Down(e.to_string()));
+                    failures.push(format!("{}: {}", endpoint, e));
                 }
             }
         }
         
-        // If we couldn't find a healthy endpoint, return the first one as a fallback
-        if !endpoints.is_empty() {
-            println!("Warning: No healthy endpoints found. Using first endpoint as fallback.");
-            return Ok(endpoints[0].clone());
-        }
-        
-        Err(anyhow!("No RPC endpoints available for {:?} network", config.network_type))
+        Err(anyhow!("No healthy RPC endpoint for {:?} network ({})", config.network_type, failures.join("; ")))
+    }
+    
+    /// Replace the active network's RPC endpoints, tried in order by `get_healthy_rpc_url`
+    pub fn set_rpc_endpoints(&self, endpoints: &[String]) {
+        let mut config = self.active_config.lock().unwrap();
+        config.params.insert("rpc_endpoints".to_string(), endpoints.join(","));
+        self.node_status_cache.lock().unwrap().clear();
+    }
+    
+    /// Record `status` for `rpc_url` in the node status cache
+    fn mark_node_status(&self, rpc_url: &str, status: NodeStatus) {
+        self.node_status_cache.lock().unwrap().insert(rpc_url.to_string(), (status, Instant::now()));
     }
     
     /// Verify that a transaction is targeting the correct chain
@@ -398,8 +527,8 @@ impl NetworkManager {
     
     /// Check the health of all configured endpoints
     pub async fn health_check_all(&self) -> HashMap<String, NodeStatus> {
-        let config = self.active_config.lock().unwrap();
-        let endpoints = config.get_rpc_endpoints();
+        // Don't hold the config lock across the health check awaits
+        let endpoints = self.active_config.lock().unwrap().get_rpc_endpoints();
         let mut results = HashMap::new();
         
         for endpoint in &endpoints {
@@ -416,6 +545,102 @@ impl NetworkManager {
         results
     }
     
+    /// Reuse discovered endpoints for `ttl` before querying the seed again.
+    pub fn set_discovery_ttl(&mut self, ttl: Duration) {
+        self.discovery_ttl = ttl;
+    }
+
+    /// Additional fullnode RPC URLs for the active network, found through `seed`.
+    ///
+    /// Asks the seed for the current validator set, turns the advertised
+    /// addresses into RPC URLs and keeps those that pass a health check. When
+    /// none do, or the seed can't be queried, the network's curated public
+    /// nodes are returned instead. Configured endpoints and the seed itself are
+    /// never included. Results are cached per seed for the discovery TTL.
+    pub async fn discover_peers(&self, seed: &str) -> Result<Vec<String>> {
+        if let Some((peers, found_at)) = self.discovered_peers.lock().unwrap().get(seed) {
+            if found_at.elapsed() < self.discovery_ttl {
+                return Ok(peers.clone());
+            }
+        }
+
+        let config = self.get_active_config();
+        let known = config.get_rpc_endpoints();
+        let is_new = |url: &str| url != seed && !known.iter().any(|endpoint| endpoint == url);
+
+        let mut peers = match self.query_system_state(seed).await {
+            Ok(state) => {
+                let candidates: Vec<String> = fullnode_candidates(&state)
+                    .into_iter()
+                    .filter(|url| is_new(url))
+                    .take(MAX_DISCOVERY_PROBES)
+                    .collect();
+                let statuses = join_all(candidates.iter().map(|url| self.is_node_healthy(url))).await;
+                candidates
+                    .into_iter()
+                    .zip(statuses)
+                    .filter(|(_, status)| matches!(status, Ok(NodeStatus::Healthy)))
+                    .map(|(url, _)| url)
+                    .collect()
+            }
+            Err(e) if !curated_public_nodes(&config.network_type).is_empty() => {
+                eprintln!("WARNING: Peer discovery via {} failed: {}", seed, e);
+                Vec::new()
+            }
+            Err(e) => return Err(anyhow!("Peer discovery via {} failed: {}", seed, e)),
+        };
+
+        if peers.is_empty() {
+            peers = curated_public_nodes(&config.network_type)
+                .iter()
+                .filter(|url| is_new(url))
+                .map(|url| url.to_string())
+                .collect();
+            if !peers.is_empty() {
+                println!("No RPC peers discovered via {}; using {} curated {} node(s)", seed, peers.len(), config.network_type);
+            }
+        }
+
+        self.discovered_peers.lock().unwrap().insert(seed.to_string(), (peers.clone(), Instant::now()));
+        Ok(peers)
+    }
+
+    /// Configured RPC endpoints plus peers discovered from the first of them,
+    /// for cross-node checks such as the `ByzantineDetector`.
+    pub async fn cross_verification_endpoints(&self) -> Vec<String> {
+        let mut endpoints = self.get_active_config().get_rpc_endpoints();
+        let Some(seed) = endpoints.first().cloned() else {
+            return endpoints;
+        };
+        match self.discover_peers(&seed).await {
+            Ok(peers) => endpoints.extend(peers),
+            Err(e) => eprintln!("WARNING: {}; using configured endpoints only", e),
+        }
+        endpoints
+    }
+
+    /// The `suix_getLatestSuiSystemState` result from `rpc_url`.
+    async fn query_system_state(&self, rpc_url: &str) -> Result<Value> {
+        let response: Value = self.client
+            .post(rpc_url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "suix_getLatestSuiSystemState",
+                "params": []
+            }))
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to query system state from {}: {}", rpc_url, e))?
+            .json()
+            .await
+            .map_err(|e| anyhow!("Invalid system state response from {}: {}", rpc_url, e))?;
+        match response.get("result") {
+            Some(result) => Ok(result.clone()),
+            None => Err(anyhow!("System state request to {} failed: {}", rpc_url, response["error"])),
+        }
+    }
+
     /// Calculate the estimated transaction finality time in milliseconds
     pub fn get_estimated_finality_ms(&self) -> u64 {
         let config = self.active_config.lock().unwrap();
@@ -436,8 +661,20 @@ impl NetworkManager {
 
 #[async_trait]
 impl SuiClientProvider for NetworkManager {
+    /// Client for the first healthy endpoint, so a dead node is failed over rather than reused
     async fn get_client(&self) -> Result<Arc<SuiClient>> {
-        self.get_default_client().await
+        let rpc_url = self.get_healthy_rpc_url().await?;
+        if let Some(client) = self.endpoint_clients.lock().unwrap().get(&rpc_url) {
+            return Ok(client.clone());
+        }
+        
+        let client = SuiClientBuilder::default()
+            .build(&rpc_url)
+            .await
+            .map_err(|e| anyhow!("Failed to create SuiClient for {}: {}", rpc_url, e))?;
+        let client = Arc::new(client);
+        self.endpoint_clients.lock().unwrap().insert(rpc_url, client.clone());
+        Ok(client)
     }
 
     async fn get_client_for_endpoint(&self, endpoint: &str) -> Result<Arc<SuiClient>> {
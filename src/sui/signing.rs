@@ -0,0 +1,38 @@
+//! Signing of Sui transaction data.
+//!
+//! Submitters sign the BCS bytes of `TransactionData` wrapped in the
+//! `Intent::sui_transaction()` intent. Building that message here keeps the
+//! intent scope and version in one place for every signing path.
+
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_sdk::types::{
+    crypto::{Signature, SuiKeyPair},
+    transaction::{Transaction, TransactionData},
+};
+use sui_types::signature::GenericSignature;
+
+/// The intent message a Sui transaction signature commits to.
+pub fn transaction_intent(tx_data: &TransactionData) -> IntentMessage<TransactionData> {
+    IntentMessage::new(Intent::sui_transaction(), tx_data.clone())
+}
+
+/// Sign `tx_data` with `keypair`, returning the signed intent message alongside
+/// the signature so callers can verify or re-sign without rebuilding it.
+pub fn sign_transaction_data(tx_data: &TransactionData, keypair: &SuiKeyPair) -> (IntentMessage<TransactionData>, Signature) {
+    let intent_msg = transaction_intent(tx_data);
+    let signature = Signature::new_secure(&intent_msg, keypair);
+    (intent_msg, signature)
+}
+
+/// A transaction signed by each of `keypairs` in order, e.g. sender then gas sponsor.
+///
+/// Each key signs separately; for a single M-of-N multisig signature use
+/// `transaction::multisig::MultisigSubmitter` instead.
+pub fn build_signed_transaction(tx_data: TransactionData, keypairs: &[&SuiKeyPair]) -> Transaction {
+    let intent_msg = transaction_intent(&tx_data);
+    let signatures: Vec<GenericSignature> = keypairs
+        .iter()
+        .map(|keypair| Signature::new_secure(&intent_msg, *keypair).into())
+        .collect();
+    Transaction::from_generic_sig_data(tx_data, signatures)
+}
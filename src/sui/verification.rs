@@ -9,6 +9,7 @@ use tokio::time::sleep;
 use crate::transaction::types::Transaction;
 use crate::metrics::performance::PerformanceMetrics;
 use crate::transaction::types::TransactionType;
+use crate::utils::backoff::Backoff;
 
 
 /// Maximum number of verification attempts before failing
@@ -122,6 +123,7 @@ impl VerificationManager {
         let start_time = Instant::now();
         let timeout = Duration::from_secs(VERIFICATION_TIMEOUT_SECS);
         let mut attempts = 0;
+        let mut retry_delay = Backoff::fixed(Duration::from_millis(VERIFICATION_RETRY_DELAY_MS));
         
         // Poll the status until verified or max attempts reached
         while attempts < MAX_VERIFICATION_ATTEMPTS && start_time.elapsed() < timeout {
@@ -184,7 +186,9 @@ impl VerificationManager {
             }
             
             // Wait before retrying
-            sleep(Duration::from_millis(VERIFICATION_RETRY_DELAY_MS)).await;
+            if let Some(delay) = retry_delay.next_delay() {
+                sleep(delay).await;
+            }
         }
         
         // If we got here, we timed out
@@ -290,7 +294,7 @@ impl VerificationManager {
                     return VerificationStatus::Failed("No balance changes found in effects".to_string());
                 }
             },
-            TransactionType::Invoke | TransactionType::Custom(_) => {
+            TransactionType::Invoke | TransactionType::Custom(_) | TransactionType::ConditionalPayout { .. } => {
                 // In a real implementation, we would verify specific effects based on the contract or custom logic
                 VerificationStatus::Verified
             }
@@ -0,0 +1,13 @@
+//! In-memory stand-ins for the network-bound parts of the pipeline.
+//!
+//! `MockPipelineBuilder` assembles an `ExecutionManager` backed by a scripted
+//! oracle, a local quorum and an in-memory L1, so a transaction can be run
+//! end to end without Sui RPC, real oracles or the deployed contract.
+
+pub mod oracle;
+pub mod pipeline;
+pub mod sui;
+
+pub use oracle::MockOracleSource;
+pub use pipeline::{MockPipeline, MockPipelineBuilder};
+pub use sui::{MockL1Transaction, MockSuiClient, MockVerifier};
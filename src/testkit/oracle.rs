@@ -0,0 +1,171 @@
+//! Oracle source that answers from a script instead of the network.
+
+use crate::external::oracle::{OracleSource, OracleSourceConfig, OracleSourceStatus, SourceFetchError, ValidationResult};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Deterministic `OracleSource` with no network I/O.
+///
+/// Returns its scripted values in order, repeating the last one once the
+/// script is exhausted. Status and validation outcomes are configurable so
+/// `OracleManager` filtering and consensus can be driven precisely.
+pub struct MockOracleSource {
+    config: OracleSourceConfig,
+    values: Vec<Value>,
+    /// Error returned by every fetch instead of a value
+    failure: Option<String>,
+    /// Number of fetches that fail before the script starts
+    failing_fetches: usize,
+    /// HTTP status those failing fetches report
+    outage_status: reqwest::StatusCode,
+    status: Mutex<OracleSourceStatus>,
+    validation_failures: Vec<String>,
+    /// Params of every `fetch`, in call order
+    requests: Mutex<Vec<Value>>,
+}
+
+impl MockOracleSource {
+    pub fn new(name: &str, values: Vec<Value>) -> Self {
+        Self {
+            config: OracleSourceConfig {
+                name: name.to_string(),
+                url: format!("mock://{}", name),
+                source_type: "REST".to_string(),
+                auth_header: None,
+                default_params: None,
+                http_method: "GET".to_string(),
+                body_template: None,
+                validation_rules: vec![],
+                weight: 100,
+                timeout_ms: 1000,
+                background_timeout_ms: None,
+                rate_limit: None,
+                requires_auth: false,
+                path: vec![],
+                required_fields: vec![],
+            },
+            values,
+            failure: None,
+            failing_fetches: 0,
+            outage_status: reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            status: Mutex::new(OracleSourceStatus::Operational),
+            validation_failures: Vec::new(),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// A source that always returns `value`.
+    pub fn returning(value: Value) -> Self {
+        Self::new("mock_oracle", vec![value])
+    }
+
+    /// A source that returns `values` across successive calls.
+    pub fn sequence(values: Vec<Value>) -> Self {
+        Self::new("mock_oracle", values)
+    }
+
+    /// A source whose every fetch fails.
+    pub fn failing() -> Self {
+        let mut source = Self::new("mock_oracle", Vec::new());
+        source.failure = Some("scripted failure".to_string());
+        source
+    }
+
+    /// Fail the first `count` fetches, as a source with a brief outage would.
+    pub fn with_failing_fetches(mut self, count: usize) -> Self {
+        self.failing_fetches = count;
+        self
+    }
+
+    /// Report `status` from the failing fetches instead of 503, e.g. 401 for a bad key.
+    pub fn with_outage_status(mut self, status: reqwest::StatusCode) -> Self {
+        self.outage_status = status;
+        self
+    }
+
+    /// Rename the source; `OracleManager` requires unique names.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.config.name = name.to_string();
+        self.config.url = format!("mock://{}", name);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u8) -> Self {
+        self.config.weight = weight;
+        self
+    }
+
+    pub fn with_status(self, status: OracleSourceStatus) -> Self {
+        *self.status.lock().unwrap() = status;
+        self
+    }
+
+    /// Make `validate()` report `rule_name` as failed for every response.
+    pub fn with_validation_failure(mut self, rule_name: &str) -> Self {
+        self.validation_failures.push(rule_name.to_string());
+        self
+    }
+
+    /// Change the reported status mid-test, e.g. to simulate an outage.
+    pub fn set_status(&self, status: OracleSourceStatus) {
+        *self.status.lock().unwrap() = status;
+    }
+
+    /// Params passed to each fetch so far.
+    pub fn requests(&self) -> Vec<Value> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn fetch_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl OracleSource for MockOracleSource {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn config(&self) -> &OracleSourceConfig {
+        &self.config
+    }
+
+    async fn fetch(&self, params: &Value) -> Result<Value> {
+        let mut requests = self.requests.lock().unwrap();
+        let attempt = requests.len();
+        requests.push(params.clone());
+        if let Some(failure) = &self.failure {
+            return Err(anyhow!("MockOracleSource '{}': {}", self.config.name, failure));
+        }
+        if attempt < self.failing_fetches {
+            return Err(anyhow::Error::new(SourceFetchError::Status(self.outage_status))
+                .context(format!("MockOracleSource '{}': scripted outage", self.config.name)));
+        }
+        let index = (attempt - self.failing_fetches).min(self.values.len().saturating_sub(1));
+        self.values.get(index)
+            .cloned()
+            .ok_or_else(|| anyhow!("MockOracleSource '{}' has no scripted values", self.config.name))
+    }
+
+    fn validate(&self, data: &Value) -> Vec<ValidationResult> {
+        self.validation_failures.iter()
+            .map(|rule_name| ValidationResult {
+                passed: false,
+                rule_name: rule_name.clone(),
+                error_message: Some(format!("Scripted failure of rule '{}'", rule_name)),
+                data_field: String::new(),
+                value: data.clone(),
+            })
+            .collect()
+    }
+
+    fn status(&self) -> OracleSourceStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    async fn run_background_updates(&self, _update_interval: Duration) {}
+}
@@ -0,0 +1,148 @@
+//! Builder that wires the execution pipeline entirely from mocks.
+
+use super::oracle::MockOracleSource;
+use super::sui::{MockSuiClient, MockVerifier};
+use crate::execution::manager::ExecutionManager;
+use crate::quorum::simulation::QuorumSimulation;
+use crate::security::audit::SecurityAuditLog;
+use crate::sui::verification::VerificationStatus;
+use crate::transaction::ingest::{ingest_transaction, IngestBackend};
+use crate::transaction::tracker::LifecycleState;
+use crate::transaction::types::{QuorumError, SignatureBytes, Transaction, VerificationInput};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_sdk::types::crypto::SuiKeyPair;
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme};
+
+/// Assembles a `MockPipeline`.
+#[derive(Debug, Clone)]
+pub struct MockPipelineBuilder {
+    oracle_values: Vec<Value>,
+    quorum_size: usize,
+    byzantine_percentage: f64,
+}
+
+impl Default for MockPipelineBuilder {
+    fn default() -> Self {
+        Self {
+            oracle_values: Vec::new(),
+            quorum_size: 5,
+            byzantine_percentage: 0.0,
+        }
+    }
+}
+
+impl MockPipelineBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Responses the oracle returns for external queries, in order.
+    pub fn with_oracle_values(mut self, values: Vec<Value>) -> Self {
+        self.oracle_values = values;
+        self
+    }
+
+    pub fn with_quorum_size(mut self, quorum_size: usize) -> Self {
+        self.quorum_size = quorum_size;
+        self
+    }
+
+    pub fn with_byzantine_percentage(mut self, percentage: f64) -> Self {
+        self.byzantine_percentage = percentage;
+        self
+    }
+
+    pub fn build(self) -> Result<MockPipeline> {
+        if self.quorum_size == 0 {
+            return Err(anyhow!("Mock quorum needs at least one node"));
+        }
+        // Fixed Ed25519 seeds so runs are repeatable and the verifier can check every signature
+        let keypairs = (1..=self.quorum_size)
+            .map(|seed| {
+                let mut bytes = vec![SignatureScheme::ED25519.flag()];
+                bytes.extend_from_slice(&[seed as u8; 32]);
+                SuiKeyPair::decode_base64(&base64::encode(bytes))
+                    .map_err(|e| anyhow!("Failed to build mock quorum key {}: {}", seed, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut quorum = QuorumSimulation::new(keypairs);
+        quorum.set_byzantine_percentage(self.byzantine_percentage);
+
+        let audit_log = Arc::new(SecurityAuditLog::new());
+        let oracle = Arc::new(MockOracleSource::new("mock_oracle", self.oracle_values));
+        let sui_client = Arc::new(MockSuiClient::new(MockVerifier::for_quorum(&quorum)));
+        let execution_manager = Arc::new(
+            ExecutionManager::new(None, None, Some(audit_log.clone())).with_oracle_source(oracle.clone()),
+        );
+
+        Ok(MockPipeline {
+            execution_manager,
+            quorum: Arc::new(quorum),
+            oracle,
+            sui_client,
+            audit_log,
+        })
+    }
+}
+
+/// The execution pipeline with every external dependency replaced by a mock.
+///
+/// Runs the same stages as the ingestion endpoint, with itself as the
+/// `IngestBackend` in place of `TransactionHandler`.
+pub struct MockPipeline {
+    pub execution_manager: Arc<ExecutionManager>,
+    pub quorum: Arc<QuorumSimulation>,
+    pub oracle: Arc<MockOracleSource>,
+    pub sui_client: Arc<MockSuiClient>,
+    pub audit_log: Arc<SecurityAuditLog>,
+}
+
+impl MockPipeline {
+    /// Process, sign, submit and confirm `tx` through `ingest_transaction`.
+    ///
+    /// Returns the mock L1 digest, or `None` if processing decided nothing
+    /// needed to be submitted (e.g. a payout condition wasn't met).
+    pub async fn run(&self, tx: &Transaction) -> Result<Option<String>> {
+        ingest_transaction(tx, self, &self.execution_manager, &self.audit_log, |_| {}).await
+    }
+
+    /// Current lifecycle state of `tx` as recorded by the execution manager.
+    pub fn state_of(&self, tx: &Transaction) -> Option<LifecycleState> {
+        let hash = tx.payload_hash().ok()?;
+        self.execution_manager.tracker().current_state(&hash)
+    }
+}
+
+#[async_trait]
+impl IngestBackend for MockPipeline {
+    /// Nothing to check without a chain: no gas objects, no rate limits.
+    async fn validate(&self, _tx: &Transaction) -> Result<bool> {
+        Ok(true)
+    }
+
+    async fn collect_signatures(&self, attestation_payload: &[u8]) -> Result<Vec<SignatureBytes>> {
+        let threshold = self.quorum.get_threshold();
+        let signatures = self.quorum.request_signatures(attestation_payload.to_vec()).await?;
+        if signatures.len() < threshold {
+            return Err(QuorumError::InsufficientSignatures { got: signatures.len(), needed: threshold }.into());
+        }
+        Ok(signatures.into_iter().take(threshold).map(|(bytes, _is_valid)| bytes).collect())
+    }
+
+    async fn submit(&self, _tx: &Transaction, input: VerificationInput) -> Result<String> {
+        Ok(self.sui_client.submit_verification(input))
+    }
+
+    async fn confirm(&self, l1_digest: &str) -> Result<Option<VerificationStatus>> {
+        Ok(Some(self.sui_client.transaction_status(l1_digest)))
+    }
+
+    /// Mock submissions are final immediately.
+    fn confirmation_delay(&self) -> Duration {
+        Duration::ZERO
+    }
+}
@@ -0,0 +1,116 @@
+//! In-memory L1: a verifier standing in for the on-chain contract and a
+//! client that records verification submissions.
+
+use crate::quorum::simulation::QuorumSimulation;
+use crate::sui::verification::VerificationStatus;
+use crate::transaction::types::{SignatureBytes, VerificationInput};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use std::sync::Mutex;
+
+/// Checks quorum signatures the way the verification contract does: each
+/// signature must be a valid Ed25519 signature over the attestation payload
+/// from a distinct quorum member, and at least `threshold` must be valid.
+#[derive(Debug, Clone)]
+pub struct MockVerifier {
+    public_keys: Vec<Vec<u8>>,
+    threshold: usize,
+}
+
+impl MockVerifier {
+    pub fn new(public_keys: Vec<Vec<u8>>, threshold: usize) -> Self {
+        Self { public_keys, threshold }
+    }
+
+    /// Verifier for the members and threshold of `quorum`.
+    pub fn for_quorum(quorum: &QuorumSimulation) -> Self {
+        Self::new(quorum.get_public_keys(), quorum.get_threshold())
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Number of distinct quorum members with a valid signature over `payload`.
+    pub fn valid_signature_count(&self, payload: &[u8], signatures: &[SignatureBytes]) -> usize {
+        let mut used = vec![false; self.public_keys.len()];
+        for signature in signatures {
+            let signature = match Signature::try_from(signature.as_slice()) {
+                Ok(sig) => sig,
+                Err(_) => continue,
+            };
+            let signer = (0..self.public_keys.len()).find(|&i| {
+                !used[i] && PublicKey::from_bytes(&self.public_keys[i]).map_or(false, |pk| pk.verify(payload, &signature).is_ok())
+            });
+            if let Some(i) = signer {
+                used[i] = true;
+            }
+        }
+        used.into_iter().filter(|u| *u).count()
+    }
+
+    /// Accept `input` if it carries enough valid signatures.
+    pub fn verify(&self, input: &VerificationInput) -> Result<(), String> {
+        let valid = self.valid_signature_count(&input.attestation_payload, &input.quorum_signatures);
+        if valid >= self.threshold {
+            Ok(())
+        } else {
+            Err(format!("Only {} valid quorum signatures, {} required", valid, self.threshold))
+        }
+    }
+}
+
+/// A verification transaction executed by `MockSuiClient`.
+#[derive(Debug, Clone)]
+pub struct MockL1Transaction {
+    pub digest: String,
+    pub input: VerificationInput,
+    pub status: VerificationStatus,
+}
+
+/// Records verification submissions in place of a Sui full node.
+///
+/// Like the real chain, a submission with bad signatures still gets a digest;
+/// it just ends up `Failed` instead of `Verified`.
+#[derive(Debug)]
+pub struct MockSuiClient {
+    verifier: MockVerifier,
+    transactions: Mutex<Vec<MockL1Transaction>>,
+}
+
+impl MockSuiClient {
+    pub fn new(verifier: MockVerifier) -> Self {
+        Self { verifier, transactions: Mutex::new(Vec::new()) }
+    }
+
+    /// Execute a verification transaction and return its digest.
+    pub fn submit_verification(&self, input: VerificationInput) -> String {
+        let mut transactions = self.transactions.lock().unwrap();
+
+        let mut hasher = Blake2b::<U32>::new();
+        hasher.update((transactions.len() as u64).to_le_bytes());
+        hasher.update(&input.attestation_payload);
+        let digest = hex::encode(hasher.finalize());
+
+        let status = match self.verifier.verify(&input) {
+            Ok(()) => VerificationStatus::Verified,
+            Err(reason) => VerificationStatus::Failed(reason),
+        };
+        transactions.push(MockL1Transaction { digest: digest.clone(), input, status });
+        digest
+    }
+
+    /// Status of a submitted transaction; unknown digests are `Unverifiable`.
+    pub fn transaction_status(&self, digest: &str) -> VerificationStatus {
+        self.transactions.lock().unwrap()
+            .iter()
+            .find(|tx| tx.digest == digest)
+            .map(|tx| tx.status.clone())
+            .unwrap_or_else(|| VerificationStatus::Unverifiable(format!("Unknown digest {}", digest)))
+    }
+
+    /// Every submission so far, oldest first.
+    pub fn submitted(&self) -> Vec<MockL1Transaction> {
+        self.transactions.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,414 @@
+use crate::security::byzantine_simulator::{
+    success_rate, ByzantineBehavior, ByzantineScenario, ByzantineSimulator, PivotalWithholding, PredicateStrategy, RoundView,
+};
+use std::sync::Arc;
+use crate::metrics::performance::ComponentBenchmark;
+use crate::metrics::storage::MetricsStorage;
+use crate::sui::byzantine::{ByzantineDetector, Inconsistency, InconsistencyCheck, NodeResponse, NodeResponseStatus};
+use crate::sui::verification::VerificationStatus;
+use super::support::{delayed_json_rpc_endpoint, json_rpc_endpoint, raw_http_endpoint};
+use serde_json::{json, Value};
+use std::time::Instant;
+
+const DIGEST: &str = "TestDigest111";
+
+fn valid_response(node_url: &str, data: Value) -> NodeResponse {
+    NodeResponse {
+        node_url: node_url.to_string(),
+        status: NodeResponseStatus::Valid,
+        data: Some(data),
+        error: None,
+        response_time_ms: Some(100),
+        timestamp: Instant::now(),
+    }
+}
+
+fn tx_data(gas_used: u64, timestamp: u64) -> Value {
+    json!({
+        "digest": DIGEST,
+        "timestamp": timestamp,
+        "status": { "status": "success" },
+        "effects": { "gasUsed": { "computationCost": gas_used } },
+        "balanceChanges": [],
+        "objectChanges": []
+    })
+}
+
+#[test]
+fn test_identical_responses_have_no_inconsistencies() {
+    let detector = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    // Volatile fields like timestamp are ignored by normalization
+    detector.record_responses(DIGEST, vec![
+        valid_response("node-a", tx_data(1000, 1)),
+        valid_response("node-b", tx_data(1000, 2)),
+        valid_response("node-c", tx_data(1000, 3)),
+    ]);
+
+    assert!(detector.detect_data_inconsistencies(DIGEST).unwrap().is_empty());
+}
+
+#[test]
+fn test_diverging_field_is_reported_per_node_pair() {
+    let detector = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    detector.record_responses(DIGEST, vec![
+        valid_response("node-a", tx_data(1000, 0)),
+        valid_response("node-b", tx_data(1000, 0)),
+        valid_response("node-c", tx_data(9999, 0)),
+    ]);
+
+    let inconsistencies = detector.detect_data_inconsistencies(DIGEST).unwrap();
+    assert_eq!(inconsistencies, vec![Inconsistency {
+        field_path: "effects.gasUsed.computationCost".to_string(),
+        values: vec![
+            ("node-a".to_string(), json!(1000)),
+            ("node-b".to_string(), json!(1000)),
+            ("node-c".to_string(), json!(9999)),
+        ],
+    }]);
+    assert_eq!(
+        inconsistencies[0].to_string(),
+        "Inconsistency in effects.gasUsed.computationCost: node-a=1000, node-b=1000, node-c=9999"
+    );
+}
+
+#[test]
+fn test_effects_digest_ignores_change_order_but_names_diverging_path() {
+    let with_changes = |changes: Value| {
+        let mut data = tx_data(1000, 0);
+        data["balanceChanges"] = changes;
+        data
+    };
+    let a = json!({ "owner": "0xa", "amount": "-10" });
+    let b = json!({ "owner": "0xb", "amount": "10" });
+    let responses = vec![
+        valid_response("node-a", with_changes(json!([a, b]))),
+        valid_response("node-b", with_changes(json!([b, a]))),
+    ];
+
+    // Listed in a different order: the field-by-field check flags it, the digest doesn't
+    let fields = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    fields.record_responses(DIGEST, responses.clone());
+    let reported: Vec<String> = fields.detect_data_inconsistencies(DIGEST).unwrap().into_iter().map(|i| i.field_path).collect();
+    assert!(reported.contains(&"balanceChanges[0].owner".to_string()), "{:?}", reported);
+    assert_eq!(ByzantineDetector::effects_digest(&responses[0].data.clone().unwrap()),
+               ByzantineDetector::effects_digest(&responses[1].data.clone().unwrap()));
+
+    let digest = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap().with_inconsistency_check(InconsistencyCheck::EffectsDigest);
+    digest.record_responses(DIGEST, responses.clone());
+    assert!(digest.detect_data_inconsistencies(DIGEST).unwrap().is_empty());
+
+    // A real difference is reported at its path, with each node's value
+    let mut responses = responses;
+    responses.push(valid_response("node-c", with_changes(json!([{ "owner": "0xb", "amount": "99" }, a]))));
+    digest.record_responses(DIGEST, responses);
+    let inconsistencies = digest.detect_data_inconsistencies(DIGEST).unwrap();
+    assert_eq!(inconsistencies.len(), 1);
+    assert_eq!(inconsistencies[0].field_path, "balanceChanges[1].amount");
+    let values: Vec<(&str, &Value)> = inconsistencies[0].values.iter().map(|(n, v)| (n.as_str(), v)).collect();
+    assert_eq!(values, vec![("node-a", &json!("10")), ("node-b", &json!("10")), ("node-c", &json!("99"))]);
+}
+
+#[test]
+fn test_min_quorum_size_is_validated_and_enforced() {
+    assert!(ByzantineDetector::new(vec![], None, None, None, Some(1), None).is_err());
+
+    let detector = ByzantineDetector::new(vec![], None, None, None, Some(3), None).unwrap();
+    assert_eq!(detector.min_quorum_size(), 3);
+
+    // Two diverging responses are below the quorum, so nothing is compared
+    detector.record_responses(DIGEST, vec![
+        valid_response("node-a", tx_data(1000, 0)),
+        valid_response("node-b", tx_data(9999, 0)),
+    ]);
+    assert!(detector.detect_data_inconsistencies(DIGEST).unwrap().is_empty());
+}
+
+#[test]
+fn test_scenario_replays_targeted_faults_deterministically() {
+    let simulator = ByzantineSimulator::new(Vec::new(), None);
+    let scenario = ByzantineScenario::new(5, 30)
+        .with_seed(42)
+        .with_fault(3, 10..21, ByzantineBehavior::Inconsistency(1.0))
+        .with_fault(1, 0..5, ByzantineBehavior::TimingAttack(5_000));
+
+    let run = simulator.run_scenario(&scenario).unwrap();
+    assert_eq!(run.len(), 30);
+    for iteration in &run {
+        let node_3 = &iteration.responses[3];
+        if (10..21).contains(&iteration.iteration) {
+            assert_eq!(node_3.status, NodeResponseStatus::Inconsistent);
+            assert_ne!(node_3.data, iteration.responses[0].data);
+        } else {
+            assert_eq!(node_3.status, NodeResponseStatus::Valid);
+        }
+        let delayed = iteration.iteration < 5;
+        assert_eq!(iteration.responses[1].status == NodeResponseStatus::Delayed, delayed);
+        assert_eq!(iteration.faulty_nodes.contains(&"node_1".to_string()), delayed);
+    }
+
+    let replay = simulator.run_scenario(&scenario).unwrap();
+    for (a, b) in run.iter().zip(&replay) {
+        let project = |responses: &[NodeResponse]| -> Vec<(Option<Value>, Option<u64>)> {
+            responses.iter().map(|r| (r.data.clone(), r.response_time_ms)).collect()
+        };
+        assert_eq!(project(&a.responses), project(&b.responses));
+    }
+
+    let out_of_range = ByzantineScenario::new(3, 1).with_fault(3, 0..1, ByzantineBehavior::Unavailability(1.0));
+    assert!(simulator.run_scenario(&out_of_range).is_err());
+}
+
+#[test]
+fn test_adaptive_adversary_breaks_more_rounds_than_static() {
+    const NODES: usize = 7;
+    const THRESHOLD: usize = 5;
+    let simulator = ByzantineSimulator::new(Vec::new(), None);
+    // Honest node 0 is flaky, so some rounds are close to the threshold
+    let base = ByzantineScenario::new(NODES, 200).with_seed(7).with_fault(0, 0..200, ByzantineBehavior::Unavailability(0.5));
+
+    let static_scenario = (5..NODES).fold(base.clone(), |scenario, node| {
+        scenario.with_fault(node, 0..200, ByzantineBehavior::Unavailability(0.5))
+    });
+    let adaptive_scenario = (5..NODES).fold(base, |scenario, node| {
+        scenario.with_fault(node, 0..200, ByzantineBehavior::Adaptive(Arc::new(PivotalWithholding { threshold: THRESHOLD })))
+    });
+
+    let static_run = simulator.run_scenario(&static_scenario).unwrap();
+    let adaptive_run = simulator.run_scenario(&adaptive_scenario).unwrap();
+    let static_rate = success_rate(&static_run, THRESHOLD);
+    let adaptive_rate = success_rate(&adaptive_run, THRESHOLD);
+    let storage = MetricsStorage::new();
+    for (adversary, rate) in [("static", static_rate), ("adaptive", adaptive_rate)] {
+        let mut benchmark = ComponentBenchmark::new(&format!("byzantine_{}_adversary", adversary), "2_of_7_byzantine", 200);
+        benchmark
+            .add_config("byzantine_percentage", &(2.0 / NODES as f64).to_string())
+            .add_config("verification_success_rate", &rate.to_string());
+        storage.add_benchmark(benchmark);
+    }
+    assert_eq!(storage.byzantine_resilience_table().lines().count(), 4);
+
+    // Whenever node 0 drops out, the two adversaries are pivotal and withhold;
+    // otherwise they answer honestly and are never seen misbehaving
+    assert!(adaptive_rate < static_rate);
+    for iteration in &adaptive_run {
+        let node_0_answered = iteration.responses[0].status == NodeResponseStatus::Valid;
+        assert_eq!(iteration.reaches_threshold(THRESHOLD), node_0_answered);
+        assert_eq!(iteration.faulty_nodes.iter().any(|node| node != "node_0"), !node_0_answered);
+    }
+
+    let late_rounds = PredicateStrategy::new("late_rounds", |view: &RoundView| view.iteration >= 2, ByzantineBehavior::Inconsistency(1.0));
+    let run = simulator
+        .run_scenario(&ByzantineScenario::new(3, 4).with_fault(2, 0..4, ByzantineBehavior::Adaptive(Arc::new(late_rounds))))
+        .unwrap();
+    let flipped: Vec<bool> = run.iter().map(|iteration| !iteration.faulty_nodes.is_empty()).collect();
+    assert_eq!(flipped, vec![false, false, true, true]);
+}
+
+#[tokio::test]
+async fn test_endpoints_are_queried_concurrently_and_timed_separately() {
+    let delays_ms = [50, 100, 150, 600, 700];
+    let mut endpoints = Vec::new();
+    for delay in delays_ms {
+        endpoints.push(delayed_json_rpc_endpoint(std::time::Duration::from_millis(delay), |_, _| tx_data(100, 1)).await);
+    }
+    let mut detector = ByzantineDetector::new(endpoints.clone(), None, None, None, None, None).unwrap();
+    detector.set_max_response_time(400);
+
+    let start = Instant::now();
+    let (_, verdicts) = detector.verify_with_node_report(DIGEST).await.unwrap();
+    let elapsed = start.elapsed();
+
+    // Close to the slowest node's 700ms, well short of the 1600ms sum
+    assert!(elapsed >= std::time::Duration::from_millis(700), "{:?}", elapsed);
+    assert!(elapsed < std::time::Duration::from_millis(1200), "{:?}", elapsed);
+
+    // Each node's own latency decides whether it was too slow
+    let urls: Vec<&str> = verdicts.iter().map(|v| v.node_url.as_str()).collect();
+    assert_eq!(urls, endpoints.iter().map(String::as_str).collect::<Vec<_>>());
+    let statuses: Vec<NodeResponseStatus> = verdicts.iter().map(|v| v.status.clone()).collect();
+    assert_eq!(statuses, vec![
+        NodeResponseStatus::Valid,
+        NodeResponseStatus::Valid,
+        NodeResponseStatus::Valid,
+        NodeResponseStatus::Delayed,
+        NodeResponseStatus::Delayed,
+    ]);
+}
+
+#[tokio::test]
+async fn test_consensus_fraction_demands_a_supermajority() {
+    let majority = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    assert_eq!(majority.required_agreement(7), 4);
+    assert_eq!(majority.required_agreement(2), 2);
+    let strict = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap().with_consensus_fraction(0.7).unwrap();
+    assert_eq!(strict.required_agreement(7), 5);
+    // The minimum quorum still applies when the fraction asks for less
+    assert_eq!(strict.required_agreement(2), 2);
+    assert!(ByzantineDetector::new(vec![], None, None, None, None, None).unwrap().with_consensus_fraction(0.0).is_err());
+    assert!(ByzantineDetector::new(vec![], None, None, None, None, None).unwrap().with_consensus_fraction(1.5).is_err());
+
+    let object = |version: u64| json!({ "data": { "objectId": "0x5", "version": version } });
+    let mut endpoints = Vec::new();
+    for version in [1, 1, 1, 1, 2, 2, 2] {
+        endpoints.push(json_rpc_endpoint(move |_, _| object(version)).await);
+    }
+
+    // 4 of 7 is a majority but not 70%
+    let (status, _) = ByzantineDetector::new(endpoints.clone(), None, None, None, None, None).unwrap()
+        .verify_object_consistency("0x5").await.unwrap();
+    assert_eq!(status, VerificationStatus::Verified);
+    let (status, _) = ByzantineDetector::new(endpoints, None, None, None, None, None).unwrap()
+        .with_consensus_fraction(0.7).unwrap()
+        .verify_object_consistency("0x5").await.unwrap();
+    assert!(matches!(status, VerificationStatus::Unverifiable(_)), "{:?}", status);
+}
+
+#[tokio::test]
+async fn test_object_consistency_flags_nodes_with_a_diverging_version() {
+    let object_node = |version: u64| json_rpc_endpoint(move |method, params| {
+        assert_eq!(method, "sui_getObject");
+        assert_eq!(params[0], "0x5");
+        json!({ "data": { "objectId": "0x5", "version": version, "digest": format!("digest-{}", version) } })
+    });
+
+    let agreeing = vec![object_node(3).await, object_node(3).await, object_node(3).await];
+    let (status, verdicts) = ByzantineDetector::new(agreeing, None, None, None, None, None).unwrap()
+        .verify_object_consistency("0x5").await.unwrap();
+    assert_eq!(status, VerificationStatus::Verified);
+    assert!(verdicts.iter().all(|v| v.agreed_with_consensus && v.status == NodeResponseStatus::Valid));
+
+    // A node serving an older version is outvoted and marked inconsistent
+    let diverging = vec![object_node(3).await, object_node(2).await, object_node(3).await];
+    let (status, verdicts) = ByzantineDetector::new(diverging.clone(), None, None, None, None, None).unwrap()
+        .verify_object_consistency("0x5").await.unwrap();
+    assert_eq!(status, VerificationStatus::Verified);
+    let agreed: Vec<bool> = verdicts.iter().map(|v| v.agreed_with_consensus).collect();
+    assert_eq!(agreed, vec![true, false, true]);
+    assert_eq!(verdicts[1].node_url, diverging[1]);
+    assert_eq!(verdicts[1].status, NodeResponseStatus::Inconsistent);
+
+    // With no majority version the object can't be verified
+    let split = vec![object_node(1).await, object_node(2).await, object_node(3).await];
+    let (status, verdicts) = ByzantineDetector::new(split, None, None, None, None, None).unwrap()
+        .verify_object_consistency("0x5").await.unwrap();
+    assert!(matches!(status, VerificationStatus::Unverifiable(_)), "{:?}", status);
+    assert_eq!(verdicts.iter().filter(|v| v.agreed_with_consensus).count(), 1);
+}
+
+#[tokio::test]
+async fn test_query_rpc_rejects_error_bodies_and_failed_responses() {
+    let detector = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    let params = json!(["0x5"]);
+
+    let answering = json_rpc_endpoint(|_, _| json!({ "data": { "objectId": "0x5" } })).await;
+    let result = detector.query_rpc(&answering, "sui_getObject", params.clone()).await.unwrap();
+    assert_eq!(result["data"]["objectId"], "0x5");
+
+    // A null result is a malformed answer, not an empty one
+    let empty = json_rpc_endpoint(|_, _| Value::Null).await;
+    let err = detector.query_rpc(&empty, "sui_getObject", params.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("missing result"), "{}", err);
+
+    let rpc_error = raw_http_endpoint(
+        "200 OK",
+        json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32602, "message": "Invalid params" } }).to_string(),
+    ).await;
+    let err = detector.query_rpc(&rpc_error, "sui_getObject", params.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("Invalid params"), "{}", err);
+
+    // Non-2xx responses fail even when the body looks like an answer
+    let unavailable = raw_http_endpoint(
+        "503 Service Unavailable",
+        json!({ "jsonrpc": "2.0", "id": 1, "result": { "data": {} } }).to_string(),
+    ).await;
+    let err = detector.query_rpc(&unavailable, "sui_getObject", params.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("503"), "{}", err);
+    let bad_gateway = raw_http_endpoint("502 Bad Gateway", "<html>Bad Gateway</html>").await;
+    assert!(detector.query_rpc(&bad_gateway, "sui_getObject", params).await.is_err());
+}
+
+#[test]
+fn test_persisted_history_survives_a_restart() {
+    let path = std::env::temp_dir().join(format!("suimodular_node_history_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut detector = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    detector.enable_persistence(path.clone()).unwrap();
+    detector.record_responses("other", vec![valid_response("node-a", tx_data(1, 0))]);
+    detector.record_responses(DIGEST, vec![valid_response("node-a", tx_data(1000, 0))]);
+    // Re-recorded: the later set is the one that counts
+    detector.record_responses(DIGEST, vec![
+        valid_response("node-a", tx_data(1000, 0)),
+        valid_response("node-b", tx_data(1000, 0)),
+        valid_response("node-c", tx_data(9999, 0)),
+    ]);
+    drop(detector);
+
+    let started = chrono::Utc::now();
+    let restarted = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    let sets = restarted.load_history(&path).unwrap();
+    assert_eq!(sets.len(), 3);
+    // The wall-clock time each response was received is kept for forensics
+    assert_eq!(sets[2].subject, DIGEST);
+    let received = sets[2].responses[2].timestamp;
+    assert!(received <= started && started - received < chrono::Duration::seconds(60));
+    let responses = restarted.recorded_responses(DIGEST).unwrap();
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[2].response_time_ms, Some(100));
+    assert!(responses[2].timestamp.elapsed() < std::time::Duration::from_secs(60));
+
+    // The bad node can still be identified
+    let inconsistencies = restarted.detect_data_inconsistencies(DIGEST).unwrap();
+    assert_eq!(inconsistencies.len(), 1);
+    assert_eq!(inconsistencies[0].values[2], ("node-c".to_string(), json!(9999)));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_torn_last_history_line_is_skipped() {
+    let path = std::env::temp_dir().join(format!("suimodular_node_history_torn_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut detector = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    detector.enable_persistence(path.clone()).unwrap();
+    detector.record_responses(DIGEST, vec![valid_response("node-a", tx_data(1000, 0))]);
+    // A crash in the middle of the next write
+    let mut contents = std::fs::read_to_string(&path).unwrap();
+    contents.push_str("{\"subject\":\"other\",\"respon");
+    std::fs::write(&path, &contents).unwrap();
+
+    let restarted = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    assert_eq!(restarted.load_history(&path).unwrap().len(), 1);
+    assert!(restarted.recorded_responses(DIGEST).is_some());
+
+    // Corruption anywhere else is still an error
+    let valid_line = contents.lines().next().unwrap().to_string();
+    contents.push('\n');
+    contents.push_str(&valid_line);
+    std::fs::write(&path, &contents).unwrap();
+    assert!(restarted.load_history(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_history_is_rotated_and_both_generations_are_loaded() {
+    let path = std::env::temp_dir().join(format!("suimodular_node_history_rotated_{}.jsonl", std::process::id()));
+    let rotated = std::path::PathBuf::from(format!("{}.1", path.display()));
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(&rotated);
+
+    let mut detector = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    assert!(detector.enable_rotating_persistence(path.clone(), 0).is_err());
+    // Small enough that every set after the first rotates the file
+    detector.enable_rotating_persistence(path.clone(), 64).unwrap();
+    detector.record_responses("first", vec![valid_response("node-a", tx_data(1, 0))]);
+    detector.record_responses("second", vec![valid_response("node-a", tx_data(2, 0))]);
+    assert!(rotated.exists());
+    drop(detector);
+
+    let restarted = ByzantineDetector::new(vec![], None, None, None, None, None).unwrap();
+    let subjects: Vec<String> = restarted.load_history(&path).unwrap().into_iter().map(|set| set.subject).collect();
+    assert_eq!(subjects, vec!["first", "second"]);
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&rotated).unwrap();
+}
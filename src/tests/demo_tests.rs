@@ -0,0 +1,64 @@
+use crate::demo::scenarios::{builtin_scenarios, load_scenarios, load_scenarios_or_builtin, scenario_demo_name, scenario_name};
+use crate::demo::weather::{offline_weather_data, weather_transaction, with_api_key};
+use serde_json::json;
+use std::path::Path;
+
+#[test]
+fn test_default_scenarios_file_parses() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("scenarios.json");
+    let scenarios = load_scenarios(&path).expect("shipped scenarios.json should parse");
+
+    let names: Vec<String> = scenarios.iter().map(scenario_name).collect();
+    assert_eq!(names, vec!["js_demo", "python_demo", "weather_demo"]);
+    let demos: Vec<String> = scenarios.iter().map(scenario_demo_name).collect();
+    assert_eq!(demos, vec!["js", "python", "weather"]);
+    assert!(scenarios[2].external_query.as_ref().and_then(|q| q.condition.as_ref()).is_some());
+    // Submitter fields are left for the runner to fill in
+    assert!(scenarios.iter().all(|tx| tx.sender.is_empty() && tx.gas_payment.is_empty()));
+}
+
+#[test]
+fn test_load_scenarios_rejects_non_array() {
+    let path = std::env::temp_dir().join("suimodular_bad_scenarios.json");
+    std::fs::write(&path, r#"{"tx_type": "transfer"}"#).unwrap();
+    assert!(load_scenarios(&path).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_missing_scenario_file_falls_back_to_builtin() {
+    let path = std::env::temp_dir().join(format!("suimodular_missing_scenarios_{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let scenarios = load_scenarios_or_builtin(&path).expect("missing file should fall back");
+    let names: Vec<String> = scenarios.iter().map(scenario_name).collect();
+    let builtin: Vec<String> = builtin_scenarios().unwrap().iter().map(scenario_name).collect();
+    assert_eq!(names, builtin);
+
+    // A file that exists but doesn't parse is not silently replaced
+    std::fs::write(&path, "not json").unwrap();
+    assert!(load_scenarios_or_builtin(&path).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_weather_scenario_is_decided_from_fetched_data() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("scenarios.json");
+    let scenario = load_scenarios(&path).unwrap().into_iter()
+        .find(|tx| scenario_demo_name(tx) == "weather")
+        .expect("shipped scenarios.json should hold the weather demo");
+    // The key is read from the environment, never committed
+    assert!(!scenario.external_query.as_ref().unwrap().url.contains("appid"));
+    assert_eq!(
+        with_api_key("https://example.com/weather?q=London", "key"),
+        "https://example.com/weather?q=London&appid=key"
+    );
+
+    // The offline data is mild, so nothing is submitted
+    assert!(weather_transaction(&scenario, &offline_weather_data()).unwrap().is_none());
+
+    let hot = json!({ "main": { "temp": 31.0 }, "weather": [{ "description": "sunny" }] });
+    let tx = weather_transaction(&scenario, &hot).unwrap().expect("condition should be met");
+    assert_eq!(tx.python_params, Some(json!({ "temperature": 31.0, "description": "sunny" })));
+    assert!(tx.external_query.is_none());
+}
@@ -0,0 +1,159 @@
+use crate::config::generate_test_sui_keypair;
+use crate::execution::manager::ExecutionManager;
+use crate::quorum::simulation::QuorumSimulation;
+use crate::security::audit::SecurityAuditLog;
+use crate::sui::network::{NetworkManager, NetworkType};
+use crate::transaction::handler::TransactionHandler;
+use crate::transaction::http_api::{router, ApiState, IDEMPOTENCY_KEY_HEADER};
+use crate::transaction::types::{ExternalQuery, QueryCondition, Transaction, TransactionType};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use sui_sdk::SuiClientBuilder;
+
+const SENDER: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+const RECEIVER: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+
+/// The API on an ephemeral local port, backed by Sui testnet like the other
+/// handler tests. Returns its base URL and the audit log it writes to.
+async fn serve_api() -> Result<(String, Arc<SecurityAuditLog>)> {
+    let audit_log = Arc::new(SecurityAuditLog::new());
+    let network_manager = Arc::new(NetworkManager::new(NetworkType::Testnet).await?);
+    let sui_client = Arc::new(SuiClientBuilder::default().build(network_manager.get_active_rpc_url()?).await?);
+    let handler = Arc::new(TransactionHandler::new(
+        generate_test_sui_keypair()?,
+        None,
+        Some(audit_log.clone()),
+        None,
+        Arc::new(QuorumSimulation::create_with_random_nodes(3)?),
+        sui_client,
+    ).await?);
+    let execution_manager = Arc::new(ExecutionManager::new(None, Some(network_manager.clone()), Some(audit_log.clone())));
+    let state = ApiState::new(handler, execution_manager, audit_log.clone(), network_manager);
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(axum::Server::from_tcp(listener)?.serve(router(state).into_make_service()));
+    Ok((url, audit_log))
+}
+
+fn transfer() -> Transaction {
+    Transaction::new(TransactionType::Transfer, SENDER.to_string(), RECEIVER.to_string(), 10, "0x5".to_string(), 1000, vec![])
+}
+
+async fn post_json(url: &str, body: String) -> Result<(u16, Value)> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await?;
+    let status = response.status().as_u16();
+    Ok((status, response.json().await?))
+}
+
+#[tokio::test]
+async fn test_malformed_and_invalid_bodies_are_rejected() -> Result<()> {
+    let (url, _) = serve_api().await?;
+    let transactions = format!("{}/transactions", url);
+
+    let (status, _) = post_json(&transactions, "{not json".to_string()).await?;
+    assert_eq!(status, 400);
+    let (status, _) = post_json(&transactions, json!({ "sender": 5 }).to_string()).await?;
+    assert_eq!(status, 422);
+
+    // Well-formed, but failing `Transaction::validate`: every issue is reported
+    let mut tx = transfer();
+    tx.gas_payment = "gas".to_string();
+    tx.gas_budget = 0;
+    let body = serde_json::to_string(&tx)?;
+    for route in ["transactions", "simulate"] {
+        let (status, response) = post_json(&format!("{}/{}", url, route), body.clone()).await?;
+        assert_eq!(status, 422, "{}", route);
+        let fields: Vec<&str> = response["issues"].as_array().unwrap().iter().map(|i| i["field"].as_str().unwrap()).collect();
+        assert_eq!(fields, vec!["gas_budget", "gas_payment"], "{}", route);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_valid_body_is_accepted_and_tracked() -> Result<()> {
+    let (url, _) = serve_api().await?;
+    let tx = transfer();
+
+    let (status, response) = post_json(&format!("{}/transactions", url), serde_json::to_string(&tx)?).await?;
+    assert_eq!(status, 202);
+    assert_eq!(response["id"], json!(tx.payload_hash()?));
+
+    let status = reqwest::get(format!("{}/transactions/{}", url, tx.payload_hash()?)).await?.status();
+    assert_eq!(status.as_u16(), 200);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retried_submission_with_idempotency_key_is_replayed() -> Result<()> {
+    let (url, audit_log) = serve_api().await?;
+    let tx = transfer();
+    let body = serde_json::to_string(&tx)?;
+
+    let mut responses = Vec::new();
+    for _ in 0..2 {
+        let response = reqwest::Client::new()
+            .post(format!("{}/transactions", url))
+            .header("Content-Type", "application/json")
+            .header(IDEMPOTENCY_KEY_HEADER, "retry-key-1")
+            .body(body.clone())
+            .send()
+            .await?;
+        let status = response.status().as_u16();
+        responses.push((status, response.json::<Value>().await?));
+    }
+
+    assert_eq!(responses[0].0, 202);
+    assert_eq!(responses[0].1["id"], json!(tx.payload_hash()?));
+    // The retry gets the original's tracking id instead of a second submission
+    assert_eq!(responses[1].0, 200);
+    assert_eq!(responses[1].1["replayed"], json!(true));
+    assert_eq!(responses[1].1["id"], responses[0].1["id"]);
+    let accepted = audit_log.get_events().into_iter()
+        .filter(|event| event.message == "Transaction accepted over HTTP")
+        .count();
+    assert_eq!(accepted, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_submission_frees_its_idempotency_key() -> Result<()> {
+    let (url, _) = serve_api().await?;
+    // Nothing listens on port 1, so the external query always fails
+    let mut tx = transfer();
+    tx.external_query = Some(ExternalQuery {
+        url: "http://127.0.0.1:1/price".to_string(),
+        path: vec!["price".to_string()],
+        condition: Some(QueryCondition { threshold: 1, operator: "gt".to_string() }),
+    });
+    let id = tx.payload_hash()?;
+    let submit = || reqwest::Client::new()
+        .post(format!("{}/transactions", url))
+        .header("Content-Type", "application/json")
+        .header(IDEMPOTENCY_KEY_HEADER, "retry-after-failure")
+        .body(serde_json::to_string(&tx).unwrap())
+        .send();
+
+    assert_eq!(submit().await?.status().as_u16(), 202);
+    let mut stage = Value::Null;
+    for _ in 0..100 {
+        stage = reqwest::get(format!("{}/transactions/{}", url, id)).await?.json::<Value>().await?["stage"].clone();
+        if stage == json!("failed") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(stage, json!("failed"));
+
+    // The retry is processed again instead of replaying the failure
+    let retry = submit().await?;
+    assert_eq!(retry.status().as_u16(), 202);
+    assert_eq!(retry.json::<Value>().await?["replayed"], Value::Null);
+    Ok(())
+}
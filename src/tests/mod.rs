@@ -1,2 +1,12 @@
 pub mod security_tests;
-pub mod performance_tests;
\ No newline at end of file
+pub mod performance_tests;
+pub mod oracle_tests;
+pub mod transaction_tests;
+pub mod signing_tests;
+pub mod demo_tests;
+pub mod byzantine_tests;
+pub mod network_tests;
+pub mod pipeline_tests;
+#[cfg(feature = "server")]
+pub mod http_api_tests;
+pub mod support;
\ No newline at end of file
@@ -0,0 +1,780 @@
+use super::support::{json_rpc_endpoint, local_network_manager, serve_json_rpc};
+use crate::sui::cross_chain::{
+    with_rpc_timeout, AddressFormat, AddressMapping, ChainAdapter, CrossChainMapper, CrossChainMapperImpl, EthereumAdapter, EvmChainAdapter, InvalidAddress,
+    RpcTimeout, SuiAdapter, UnmappedAddress,
+};
+use crate::sui::events::{event_payload, PendingConfirmations};
+use crate::sui::network::{curated_public_nodes, fullnode_candidates, rpc_url_from_multiaddr, ChainConfig, NetworkType, NodeStatus};
+use crate::transaction::types::Transaction;
+use async_trait::async_trait;
+use crate::tools::reconnect::{is_connection_error, BackoffPolicy, ReconnectingClient};
+use crate::utils::backoff::{retry_async, Backoff, BackoffStrategy};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn test_sui_testnet_explorer_links() {
+    let config = ChainConfig::for_sui(NetworkType::Testnet);
+    assert_eq!(
+        config.tx_explorer_link("ABC123").as_deref(),
+        Some("https://suiexplorer.com/txblock/ABC123?network=testnet")
+    );
+    assert_eq!(
+        config.object_explorer_link("0x2").as_deref(),
+        Some("https://suiexplorer.com/object/0x2?network=testnet")
+    );
+}
+
+#[test]
+fn test_explorer_links_use_default_paths_and_require_explorer_url() {
+    let config = ChainConfig::new(NetworkType::Mainnet).with_param("explorer_url", "https://etherscan.io/");
+    assert_eq!(config.tx_explorer_link("0xabc").as_deref(), Some("https://etherscan.io/tx/0xabc"));
+    assert_eq!(config.object_explorer_link("0xdef").as_deref(), Some("https://etherscan.io/address/0xdef"));
+
+    assert_eq!(ChainConfig::for_sui(NetworkType::Custom("http://node:9000".to_string())).tx_explorer_link("x"), None);
+}
+
+#[test]
+fn test_validator_addresses_become_rpc_candidates() {
+    assert_eq!(rpc_url_from_multiaddr("/dns/node.example.com/tcp/9000/http").as_deref(), Some("http://node.example.com:9000"));
+    assert_eq!(rpc_url_from_multiaddr("/ip4/10.0.0.7/tcp/443/https").as_deref(), Some("https://10.0.0.7:443"));
+    assert_eq!(rpc_url_from_multiaddr("/dns/node.example.com/tcp/8080").as_deref(), Some("https://node.example.com:8080"));
+    assert_eq!(rpc_url_from_multiaddr("/dns/node.example.com/udp/8084"), None);
+    assert_eq!(rpc_url_from_multiaddr("/dns/node.example.com"), None);
+
+    let system_state = json!({
+        "activeValidators": [
+            { "name": "a", "netAddress": "/dns/a.example.com/tcp/8080/http" },
+            { "name": "b", "netAddress": "/dns/b.example.com/udp/8084" },
+            { "name": "c", "netAddress": "/dns/a.example.com/tcp/8080/http" },
+            { "name": "d" },
+        ]
+    });
+    assert_eq!(fullnode_candidates(&system_state), vec!["http://a.example.com:8080".to_string()]);
+    assert!(fullnode_candidates(&json!({})).is_empty());
+
+    assert!(!curated_public_nodes(&NetworkType::Testnet).is_empty());
+    assert!(curated_public_nodes(&NetworkType::Local).is_empty());
+}
+
+#[tokio::test]
+async fn test_slow_adapter_call_times_out() {
+    let result: anyhow::Result<()> = with_rpc_timeout("slow-chain", "execute_transaction", Duration::from_millis(20), async {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Ok(())
+    })
+    .await;
+
+    let err = result.unwrap_err();
+    let timeout = err.downcast_ref::<RpcTimeout>().expect("timeout error");
+    assert_eq!(timeout.chain_id, "slow-chain");
+    assert_eq!(timeout.timeout, Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn test_adapter_rpc_timeout_is_configurable() {
+    let adapter = EthereumAdapter::new(NetworkType::Testnet, None).unwrap()
+        .with_rpc_timeout(Duration::from_secs(2))
+        .with_simulated_rpc(true);
+    assert_eq!(adapter.rpc_timeout(), Duration::from_secs(2));
+
+    let tx_hash = adapter.execute_transaction(&json!({}), Some(Duration::from_secs(1))).await.unwrap();
+    assert!(tx_hash.starts_with("0x"));
+}
+
+#[tokio::test]
+async fn test_ethereum_adapter_submits_raw_transactions_with_failover() {
+    // Bound then released, so connections to it are refused
+    let dead = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let live = json_rpc_endpoint(|method, params| match method {
+        "eth_sendRawTransaction" => json!(format!("0x{}", "ab".repeat(32))),
+        "eth_getTransactionReceipt" if params[0] == json!("0xfeed") => Value::Null,
+        "eth_getTransactionReceipt" => json!({ "status": "0x0", "blockNumber": "0x10", "gasUsed": "0x5208" }),
+        _ => Value::Null,
+    }).await;
+    let adapter = EthereumAdapter::new(NetworkType::Local, None).unwrap().with_rpc_endpoints(&[dead, live]);
+    assert!(!adapter.is_simulated());
+
+    let tx_hash = adapter.execute_transaction(&json!({ "raw": "0x02f86c0180" }), None).await.unwrap();
+    assert_eq!(tx_hash, format!("0x{}", "ab".repeat(32)));
+    assert!(adapter.execute_transaction(&json!({ "to": "0x0" }), None).await.is_err());
+    assert!(adapter.execute_transaction(&json!({ "raw": "0x2f8" }), None).await.is_err());
+
+    let status = adapter.get_transaction_status(&tx_hash, None).await.unwrap();
+    assert_eq!(status["status"], json!("0x0"));
+    assert_eq!(status["blockNumber"], json!("0x10"));
+    assert_eq!(status["confirmed"], json!(true));
+
+    let pending = adapter.get_transaction_status("0xfeed", None).await.unwrap();
+    assert_eq!(pending["status"], json!("pending"));
+    assert_eq!(pending["confirmed"], json!(false));
+}
+
+#[tokio::test]
+async fn test_simulated_ethereum_adapter_needs_no_endpoint() {
+    let adapter = EthereumAdapter::new(NetworkType::Local, None).unwrap()
+        .with_rpc_endpoints(&["http://127.0.0.1:9".to_string()])
+        .with_simulated_rpc(true);
+    assert_eq!(adapter.get_config().params.get("simulated_rpc").map(String::as_str), Some("true"));
+
+    let tx_hash = adapter.execute_transaction(&json!({}), None).await.unwrap();
+    let status = adapter.get_transaction_status(&tx_hash, None).await.unwrap();
+    assert_eq!(status["status"], json!("0x1"));
+}
+
+#[tokio::test]
+async fn test_unreachable_endpoints_are_swept_again_on_the_rpc_backoff() {
+    let addr = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap()
+    };
+    // Down for the first sweep, up by the retry
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        serve_json_rpc(listener, Duration::ZERO, |_, _| json!(format!("0x{}", "cd".repeat(32))));
+    });
+    let endpoints = [format!("http://{}", addr)];
+
+    let adapter = EthereumAdapter::new(NetworkType::Local, None).unwrap()
+        .with_rpc_endpoints(&endpoints)
+        .with_rpc_backoff(Backoff::fixed(Duration::from_millis(500)).with_max_attempts(1));
+    let tx_hash = adapter.execute_transaction(&json!({ "raw": "0x02f86c0180" }), None).await.unwrap();
+    assert_eq!(tx_hash, format!("0x{}", "cd".repeat(32)));
+
+    // With no retries left the same outage is an error
+    let dead = EthereumAdapter::new(NetworkType::Local, None).unwrap()
+        .with_rpc_endpoints(&["http://127.0.0.1:9".to_string()])
+        .with_rpc_backoff(Backoff::fixed(Duration::ZERO).with_max_attempts(0));
+    assert!(dead.execute_transaction(&json!({ "raw": "0x02f86c0180" }), None).await.is_err());
+}
+
+#[test]
+fn test_evm_adapter_is_built_from_caller_supplied_endpoints() {
+    let endpoints = vec!["https://arb-sepolia.example/v2/key".to_string(), "https://sepolia-rollup.arbitrum.io/rpc".to_string()];
+    let adapter = EvmChainAdapter::from_config("arbitrum-sepolia", endpoints.clone(), 250, None)
+        .unwrap()
+        .with_explorer_url("https://sepolia.arbiscan.io");
+    assert_eq!(adapter.chain_id(), "arbitrum-sepolia");
+    assert_eq!(adapter.address_format(), AddressFormat::Ethereum);
+
+    let config = adapter.get_config();
+    assert_eq!(config.get_rpc_endpoints(), endpoints);
+    assert_eq!(config.get_block_time_ms(), 250);
+    assert_eq!(config.tx_explorer_link("0xabc").as_deref(), Some("https://sepolia.arbiscan.io/tx/0xabc"));
+
+    assert!(EvmChainAdapter::from_config("arbitrum-sepolia", vec![], 250, None).is_err());
+    assert!(EvmChainAdapter::from_config("", endpoints, 250, None).is_err());
+
+    // The Ethereum presets no longer carry placeholder API keys
+    for network in [NetworkType::Mainnet, NetworkType::Testnet, NetworkType::Devnet] {
+        let endpoints = EthereumAdapter::new(network, None).unwrap().get_config().get_rpc_endpoints();
+        assert!(!endpoints.is_empty());
+        assert!(endpoints.iter().all(|url| !url.contains("YOUR_")));
+    }
+}
+
+/// Adapter for a chain the mapper has no built-in knowledge of, counting its submissions
+struct CountingAdapter {
+    config: ChainConfig,
+    executed: Arc<AtomicU32>,
+}
+
+#[async_trait]
+impl ChainAdapter for CountingAdapter {
+    fn chain_id(&self) -> &str {
+        "polygon-amoy"
+    }
+
+    async fn format_transaction(&self, tx: &Transaction) -> anyhow::Result<Value> {
+        Ok(json!({ "from": tx.sender, "to": tx.receiver }))
+    }
+
+    async fn execute_transaction(&self, _tx_data: &Value, _timeout: Option<Duration>) -> anyhow::Result<String> {
+        let n = self.executed.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(format!("0x{:064x}", n))
+    }
+
+    async fn get_transaction_status(&self, tx_hash: &str, _timeout: Option<Duration>) -> anyhow::Result<Value> {
+        Ok(json!({ "hash": tx_hash, "status": "0x1" }))
+    }
+
+    fn supports_transaction_type(&self, tx_type: &str) -> bool {
+        tx_type == "Transfer"
+    }
+
+    fn get_config(&self) -> ChainConfig {
+        self.config.clone()
+    }
+
+    fn address_format(&self) -> AddressFormat {
+        AddressFormat::Ethereum
+    }
+
+    fn native_decimals(&self) -> u8 {
+        18
+    }
+
+    fn native_coin_type(&self) -> &str {
+        "POL"
+    }
+}
+
+#[tokio::test]
+async fn test_mapper_reuses_registered_adapters() -> anyhow::Result<()> {
+    use crate::transaction::types::TransactionType;
+
+    let network_manager = Arc::new(local_network_manager(|_, _| Value::Null).await?);
+    let mapper = CrossChainMapperImpl::new(network_manager, None);
+    let executed = Arc::new(AtomicU32::new(0));
+    mapper.add_adapter(Box::new(CountingAdapter { config: ChainConfig::new(NetworkType::Local), executed: executed.clone() }))?;
+
+    let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+    let mut tx = Transaction::new(TransactionType::Transfer, address.to_string(), address.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    // SUI isn't paid out on this chain, so that is caught before mapping
+    assert!(!mapper.can_map(&tx, "polygon-amoy").await?);
+    tx.coin_type = Some("POL".to_string());
+    tx.decimals = Some(18);
+    assert!(mapper.can_map(&tx, "polygon-amoy").await?);
+
+    let mut mapped = mapper.map_transaction(&tx, "polygon-amoy").await?;
+    mapped.target_tx_id = Some(mapper.execute_mapped(&mapped).await?);
+    assert!(mapper.verify_mapped(&mapped).await?);
+    assert_eq!(executed.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_adapters_report_whether_their_chain_is_reachable() -> anyhow::Result<()> {
+    let dead = "http://127.0.0.1:9".to_string();
+    let ethereum = json_rpc_endpoint(|method, _| match method {
+        "eth_blockNumber" => json!("0x10"),
+        _ => Value::Null,
+    }).await;
+    let silent = json_rpc_endpoint(|_, _| Value::Null).await;
+
+    // Any one Ethereum endpoint answering is enough; an empty result doesn't count
+    assert!(EthereumAdapter::new(NetworkType::Local, None)?.with_rpc_endpoints(&[dead.clone(), ethereum]).is_available().await);
+    assert!(!EthereumAdapter::new(NetworkType::Local, None)?.with_rpc_endpoints(&[dead, silent]).is_available().await);
+
+    let sui = local_network_manager(|method, _| match method {
+        "sui_getLatestCheckpointSequenceNumber" => json!("1024"),
+        _ => Value::Null,
+    }).await?;
+    assert!(SuiAdapter::new(Arc::new(sui), None)?.is_available().await);
+    let stalled = local_network_manager(|_, _| Value::Null).await?;
+    assert!(!SuiAdapter::new(Arc::new(stalled), None)?.is_available().await);
+
+    // Adapters without their own check are assumed reachable
+    let counting = CountingAdapter { config: ChainConfig::new(NetworkType::Local), executed: Arc::new(AtomicU32::new(0)) };
+    assert!(counting.is_available().await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_can_map_skips_unreachable_chains() -> anyhow::Result<()> {
+    let live = json_rpc_endpoint(|method, _| match method {
+        "eth_blockNumber" => json!("0x10"),
+        _ => Value::Null,
+    }).await;
+    let tx = ether_transfer("0x742d35Cc6634C0532925a3b844Bc454e4438f44e", 1);
+
+    for (endpoint, reachable) in [(live, true), ("http://127.0.0.1:9".to_string(), false)] {
+        let mapper = CrossChainMapperImpl::new(Arc::new(local_network_manager(|_, _| Value::Null).await?), None);
+        let adapter = EthereumAdapter::new(NetworkType::Local, None)?.with_rpc_endpoints(&[endpoint]);
+        let chain_id = adapter.chain_id().to_string();
+        mapper.add_adapter(Box::new(adapter))?;
+        assert_eq!(mapper.can_map(&tx, &chain_id).await?, reachable);
+    }
+    Ok(())
+}
+
+/// Adapter whose submissions never finish within its RPC deadline
+struct SlowAdapter {
+    config: ChainConfig,
+}
+
+#[async_trait]
+impl ChainAdapter for SlowAdapter {
+    fn chain_id(&self) -> &str {
+        "slow-chain"
+    }
+
+    async fn format_transaction(&self, tx: &Transaction) -> anyhow::Result<Value> {
+        Ok(json!({ "from": tx.sender, "to": tx.receiver }))
+    }
+
+    async fn execute_transaction(&self, _tx_data: &Value, timeout: Option<Duration>) -> anyhow::Result<String> {
+        with_rpc_timeout(self.chain_id(), "execute_transaction", timeout.unwrap_or(self.rpc_timeout()), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok("0x1".to_string())
+        }).await
+    }
+
+    async fn get_transaction_status(&self, tx_hash: &str, _timeout: Option<Duration>) -> anyhow::Result<Value> {
+        Ok(json!({ "hash": tx_hash, "status": "0x1" }))
+    }
+
+    fn rpc_timeout(&self) -> Duration {
+        Duration::from_millis(20)
+    }
+
+    fn supports_transaction_type(&self, tx_type: &str) -> bool {
+        tx_type == "Transfer"
+    }
+
+    fn get_config(&self) -> ChainConfig {
+        self.config.clone()
+    }
+
+    fn address_format(&self) -> AddressFormat {
+        AddressFormat::Ethereum
+    }
+
+    fn native_decimals(&self) -> u8 {
+        18
+    }
+
+    fn native_coin_type(&self) -> &str {
+        "SLOW"
+    }
+
+    fn accepts_coin(&self, _coin_type: &str) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn test_timed_out_execution_marks_the_mapping_failed() -> anyhow::Result<()> {
+    use crate::sui::cross_chain::CrossChainStatus;
+    use crate::transaction::types::TransactionType;
+
+    let network_manager = Arc::new(local_network_manager(|_, _| Value::Null).await?);
+    let mapper = CrossChainMapperImpl::new(network_manager, None);
+    mapper.add_adapter(Box::new(SlowAdapter { config: ChainConfig::new(NetworkType::Local) }))?;
+
+    let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+    let tx = Transaction::new(TransactionType::Transfer, address.to_string(), address.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    let mapped = mapper.map_transaction(&tx, "slow-chain").await?;
+
+    let err = mapper.execute_mapped(&mapped).await.unwrap_err();
+    assert!(err.downcast_ref::<RpcTimeout>().is_some());
+
+    let stored = mapper.get_mapping(&mapped.origin_chain_id, &mapped.origin_tx_id).expect("mapping is kept");
+    let expected = format!("timeout: {}", RpcTimeout {
+        chain_id: "slow-chain".to_string(),
+        operation: "execute_transaction",
+        timeout: Duration::from_millis(20),
+    });
+    assert_eq!(stored.status, CrossChainStatus::Failed(expected.clone()));
+    assert_eq!(stored.error, Some(expected));
+    assert!(stored.target_tx_id.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_network_manager_fails_over_to_a_healthy_endpoint() -> anyhow::Result<()> {
+    let dead = "http://127.0.0.1:9".to_string();
+    let live = json_rpc_endpoint(|method, _| match method {
+        "sui_getChainIdentifier" => json!("4c78adac"),
+        _ => Value::Null,
+    }).await;
+    let manager = local_network_manager(|_, _| Value::Null).await?;
+    manager.set_rpc_endpoints(&[dead.clone(), live.clone()]);
+
+    assert_eq!(manager.get_healthy_rpc_url().await?, live);
+    assert!(matches!(manager.is_node_healthy(&dead).await?, NodeStatus::Down(_)));
+    Ok(())
+}
+
+#[test]
+fn test_address_formats_per_chain() {
+    let sui = "0x4c45f32d0c5e9fd297e52d792c261a85f0582d0bfed0edd54e0cabe12cadd0f6";
+    let eth = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+    let sol = "So11111111111111111111111111111111111111112";
+
+    assert!(AddressFormat::Sui.is_valid(sui));
+    assert!(!AddressFormat::Sui.is_valid(eth));
+    assert!(AddressFormat::Ethereum.is_valid(eth));
+    assert!(!AddressFormat::Ethereum.is_valid(sui));
+    assert!(!AddressFormat::Ethereum.is_valid("742d35Cc6634C0532925a3b844Bc454e4438f44e"));
+    assert!(AddressFormat::Solana.is_valid(sol));
+    assert!(AddressFormat::Solana.is_valid("11111111111111111111111111111111"));
+    // Valid base58, but 25 bytes; and '0' is outside the alphabet
+    assert!(!AddressFormat::Solana.is_valid("3QJmV3qfvL9SuYo34YihAf3sRCW3qSinyC"));
+    assert!(!AddressFormat::Solana.is_valid("0o11111111111111111111111111111111111111112"));
+}
+
+#[tokio::test]
+async fn test_ethereum_adapter_rejects_sui_addresses() {
+    use crate::transaction::types::{Transaction, TransactionType};
+
+    let adapter = EthereumAdapter::new(NetworkType::Testnet, None).unwrap().with_simulated_rpc(true);
+    let sui_sender = "0x4c45f32d0c5e9fd297e52d792c261a85f0582d0bfed0edd54e0cabe12cadd0f6";
+    let eth_receiver = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+    let tx = Transaction::new(TransactionType::Transfer, sui_sender.to_string(), eth_receiver.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    let err = adapter.format_transaction(&tx).await.unwrap_err();
+    let invalid = err.downcast_ref::<InvalidAddress>().expect("address error");
+    assert_eq!(invalid.field, "sender");
+    assert_eq!(invalid.format, AddressFormat::Ethereum);
+
+    let tx = ether_transfer(eth_receiver, 1);
+    let formatted = adapter.format_transaction(&tx).await.unwrap();
+    assert_eq!(formatted["to"], json!(eth_receiver));
+}
+
+/// A transfer of `wei` wei between two copies of `address`
+fn ether_transfer(address: &str, wei: u64) -> Transaction {
+    use crate::transaction::amount::{ETH_COIN_TYPE, ETH_DECIMALS};
+    use crate::transaction::types::TransactionType;
+
+    let mut tx = Transaction::new(TransactionType::Transfer, address.to_string(), address.to_string(), wei, "0x5".to_string(), 1000, vec![]);
+    tx.coin_type = Some(ETH_COIN_TYPE.to_string());
+    tx.decimals = Some(ETH_DECIMALS);
+    tx
+}
+
+#[tokio::test]
+async fn test_ethereum_adapter_converts_amounts_to_wei() {
+    use crate::transaction::amount::AmountError;
+    use crate::transaction::types::TransactionType;
+
+    let weth = "0x5d4b::coin::WETH";
+    let adapter = EthereumAdapter::new(NetworkType::Testnet, None).unwrap().with_simulated_rpc(true).with_mapped_coin(weth);
+    let eth_address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+    let tx = ether_transfer(eth_address, 1_000_000_000_000_000_000);
+    assert_eq!(adapter.native_amount(&tx).unwrap(), 1_000_000_000_000_000_000);
+    assert_eq!(adapter.format_transaction(&tx).await.unwrap()["value"], json!("0xde0b6b3a7640000"));
+
+    // Bridged ether keeps its value across the decimals change
+    let mut bridged = tx.clone();
+    bridged.amount = 150_000_000;
+    bridged.coin_type = Some(weth.to_string());
+    bridged.decimals = Some(8);
+    assert_eq!(adapter.native_amount(&bridged).unwrap(), 1_500_000_000_000_000_000);
+
+    // Bare amounts are MIST, and neither SUI nor an unmapped coin is paid out as ether
+    let sui = Transaction::new(TransactionType::Transfer, eth_address.to_string(), eth_address.to_string(), 1_000_000_000, "0x5".to_string(), 1000, vec![]);
+    assert!(matches!(adapter.native_amount(&sui), Err(AmountError::UnmappedCoin { .. })));
+    assert!(adapter.format_transaction(&sui).await.is_err());
+
+    let mut usdc = tx.clone();
+    usdc.amount = 1_500_000;
+    usdc.coin_type = Some("0x5d4b::coin::USDC".to_string());
+    usdc.decimals = Some(6);
+    assert_eq!(
+        adapter.native_amount(&usdc),
+        Err(AmountError::UnmappedCoin { coin_type: "0x5d4b::coin::USDC".to_string(), chain_id: adapter.chain_id().to_string() })
+    );
+}
+
+#[tokio::test]
+async fn test_ethereum_transactions_use_the_network_nonce_and_gas_price() {
+    let endpoint = json_rpc_endpoint(|method, params| match method {
+        "eth_getTransactionCount" if params[1] == json!("pending") => json!("0x2a"),
+        "eth_gasPrice" => json!("0x4a817c800"),
+        _ => Value::Null,
+    }).await;
+    let adapter = EthereumAdapter::new(NetworkType::Local, None).unwrap().with_rpc_endpoints(&[endpoint]);
+    let tx = ether_transfer("0x742d35Cc6634C0532925a3b844Bc454e4438f44e", 1_000_000_000_000_000_000);
+
+    let formatted = adapter.format_transaction(&tx).await.unwrap();
+    assert_eq!(formatted["nonce"], json!("0x2a"));
+    assert_eq!(formatted["gasPrice"], json!("0x4a817c800"));
+    assert_eq!(formatted["value"], json!("0xde0b6b3a7640000"));
+
+    // A node that can't quote a price leaves the configured recommendation, in gwei
+    let endpoint = json_rpc_endpoint(|method, _| match method {
+        "eth_getTransactionCount" => json!("0x0"),
+        _ => Value::Null,
+    }).await;
+    let adapter = EthereumAdapter::new(NetworkType::Local, None).unwrap().with_rpc_endpoints(&[endpoint]);
+    let formatted = adapter.format_transaction(&tx).await.unwrap();
+    assert_eq!(formatted["gasPrice"], json!(format!("0x{:x}", 10u128 * 1_000_000_000)));
+
+    // Without a nonce there is no safe transaction to build
+    let dead = EthereumAdapter::new(NetworkType::Local, None).unwrap().with_rpc_endpoints(&["http://127.0.0.1:9".to_string()]);
+    assert!(dead.format_transaction(&tx).await.is_err());
+}
+
+#[test]
+fn test_address_mapping_resolves_per_target_chain() {
+    let sui = "0x4c45f32d0c5e9fd297e52d792c261a85f0582d0bfed0edd54e0cabe12cadd0f6";
+    let mapping = AddressMapping::new();
+    assert!(mapping.is_empty());
+
+    mapping.register("sui-testnet", sui, "ethereum-sepolia", "0x742d35Cc6634C0532925a3b844Bc454e4438f44e");
+    mapping.register("sui-testnet", sui, "solana-devnet", "So11111111111111111111111111111111111111112");
+    assert_eq!(mapping.len(), 2);
+    assert_eq!(mapping.resolve("sui-testnet", sui, "ethereum-sepolia").as_deref(), Some("0x742d35Cc6634C0532925a3b844Bc454e4438f44e"));
+    assert!(mapping.resolve("sui-mainnet", sui, "ethereum-sepolia").is_none());
+
+    // Re-registering replaces the earlier target
+    mapping.register("sui-testnet", sui, "ethereum-sepolia", "0x0000000000000000000000000000000000000001");
+    assert_eq!(mapping.len(), 2);
+    assert_eq!(mapping.resolve("sui-testnet", sui, "ethereum-sepolia").as_deref(), Some("0x0000000000000000000000000000000000000001"));
+}
+
+#[tokio::test]
+async fn test_map_transaction_swaps_in_registered_addresses() -> anyhow::Result<()> {
+    let sui = "0x4c45f32d0c5e9fd297e52d792c261a85f0582d0bfed0edd54e0cabe12cadd0f6";
+    let unknown = "0x02a212de6a9dfa3a69e22387acfbafbb1a9e591bd9d636e7895dcfc8de05f331";
+    let eth = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+    let endpoint = json_rpc_endpoint(|method, _| match method {
+        "eth_blockNumber" => json!("0x10"),
+        "eth_getTransactionCount" => json!("0x0"),
+        "eth_gasPrice" => json!("0x4a817c800"),
+        _ => Value::Null,
+    }).await;
+    let adapter = EthereumAdapter::new(NetworkType::Local, None)?.with_rpc_endpoints(&[endpoint]);
+    let target_chain = adapter.chain_id().to_string();
+
+    let network_manager = Arc::new(local_network_manager(|_, _| Value::Null).await?);
+    let config = network_manager.get_active_config();
+    let origin_chain = config.get_chain_id().unwrap_or_else(|| format!("sui-{:?}", config.network_type));
+    let mapper = CrossChainMapperImpl::new(network_manager, None);
+    mapper.add_adapter(Box::new(adapter))?;
+    mapper.address_mapping().register(&origin_chain, sui, &target_chain, eth);
+
+    // The Sui sender is swapped for its registered counterpart; the receiver is already valid
+    let mut tx = ether_transfer(eth, 1);
+    tx.sender = sui.to_string();
+    let mapped = mapper.map_transaction(&tx, &target_chain).await?;
+    assert_eq!(mapped.target_tx_data["from"], json!(eth));
+    assert_eq!(mapped.target_tx_data["to"], json!(eth));
+    assert_eq!(mapped.origin_chain_id, origin_chain);
+
+    tx.receiver = unknown.to_string();
+    let err = mapper.map_transaction(&tx, &target_chain).await.unwrap_err();
+    let unmapped = err.downcast_ref::<UnmappedAddress>().expect("unmapped address error");
+    assert_eq!(unmapped, &UnmappedAddress {
+        origin_chain,
+        target_chain,
+        field: "receiver",
+        address: unknown.to_string(),
+    });
+    Ok(())
+}
+
+#[test]
+fn test_http_client_factory_applies_shared_settings() {
+    use crate::utils::{HttpClientFactory, HttpConfig};
+    use std::path::PathBuf;
+
+    let config = HttpConfig { proxy: Some("http://proxy.internal:3128".to_string()), ..HttpConfig::default() };
+    let factory = HttpClientFactory::new(config);
+    assert!(factory.build().is_ok());
+    assert!(factory.build_with_timeout(Duration::from_millis(500)).is_ok());
+    assert!(factory.config().user_agent.starts_with("sui-modular-middleware/"));
+
+    let bad_proxy = HttpClientFactory::new(HttpConfig { proxy: Some("http://[invalid".to_string()), ..HttpConfig::default() });
+    assert!(bad_proxy.build().unwrap_err().to_string().contains("Invalid HTTP proxy"));
+
+    let missing_ca = HttpClientFactory::new(HttpConfig {
+        root_certificates: vec![PathBuf::from("/nonexistent/corp-ca.pem")],
+        ..HttpConfig::default()
+    });
+    assert!(format!("{:#}", missing_ca.build().unwrap_err()).contains("corp-ca.pem"));
+}
+
+#[test]
+fn test_certificate_pins_parse_and_build_pinned_client() {
+    use crate::utils::pinning::{certificate_fingerprint, normalize_fingerprint};
+    use crate::utils::{HttpClientFactory, HttpConfig};
+
+    let fingerprint = certificate_fingerprint(b"not really a certificate");
+    assert_eq!(fingerprint.len(), 64);
+    let colon_separated = fingerprint.to_uppercase().as_bytes().chunks(2)
+        .map(|pair| std::str::from_utf8(pair).unwrap())
+        .collect::<Vec<_>>()
+        .join(":");
+    assert_eq!(normalize_fingerprint(&colon_separated), fingerprint);
+
+    let config = HttpConfig::default()
+        .with_pins_spec(&format!("Fullnode.Testnet.Sui.io={}; api.example.com={},{}", fingerprint, fingerprint, "ab:cd"))
+        .unwrap();
+    assert_eq!(config.pinned_certificates["fullnode.testnet.sui.io"], vec![fingerprint.clone()]);
+    assert_eq!(config.pinned_certificates["api.example.com"].len(), 2);
+    assert!(HttpConfig::default().with_pins_spec("missing-separator").is_err());
+
+    assert!(HttpClientFactory::new(config).build().is_ok());
+}
+
+#[test]
+fn test_certificate_pin_mismatch_is_rejected_and_audited() {
+    use crate::security::audit::{AuditEventType, SecurityAuditLog};
+    use crate::utils::pinning::{certificate_fingerprint, PinningVerifier};
+    use rustls::client::ServerCertVerifier;
+    use rustls::{Certificate, ServerName};
+    use std::collections::HashMap;
+    use std::time::SystemTime;
+
+    let pinned = Certificate(b"pinned certificate".to_vec());
+    let pins = HashMap::from([("API.example.com".to_string(), vec![certificate_fingerprint(&pinned.0).to_uppercase()])]);
+    let audit_log = Arc::new(SecurityAuditLog::new());
+    // Chain validation is skipped so the made-up certificates reach the pin check
+    let verifier = PinningVerifier::new(&pins, &[], true, Some(audit_log.clone())).unwrap();
+    let verify = |host: &str, certificate: &Certificate| {
+        let server_name = ServerName::try_from(host).unwrap();
+        verifier.verify_server_cert(certificate, &[], &server_name, &mut std::iter::empty::<&[u8]>(), &[], SystemTime::now())
+    };
+
+    let impostor = Certificate(b"impostor certificate".to_vec());
+    assert!(verify("api.example.com", &pinned).is_ok());
+    // Unpinned hosts aren't checked against anything
+    assert!(verify("other.example.com", &impostor).is_ok());
+    assert!(audit_log.get_events().is_empty());
+
+    let err = verify("api.example.com", &impostor).unwrap_err();
+    assert!(err.to_string().contains("Certificate pin mismatch for api.example.com"));
+    let events = audit_log.get_events_by_type(AuditEventType::SecurityError);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].source, "CertificatePinning");
+    assert_eq!(events[0].context["host"], json!("api.example.com"));
+    assert_eq!(events[0].context["presented"], json!(certificate_fingerprint(&impostor.0)));
+}
+
+#[test]
+fn test_pending_confirmations_match_by_digest_then_payload() {
+    assert_eq!(event_payload(&json!({ "payload": [1, 2, 3] })), Some(vec![1, 2, 3]));
+    assert_eq!(event_payload(&json!({ "attestation_payload": "0x0a0b" })), Some(vec![10, 11]));
+    assert_eq!(event_payload(&json!({ "payload": [1, 300] })), None);
+
+    let mut pending = PendingConfirmations::default();
+    pending.track(b"by-digest", Some("DIGEST1"));
+    pending.track(&[7, 8], None);
+    assert_eq!(pending.len(), 2);
+
+    // An unrelated event confirms nothing
+    assert_eq!(pending.resolve("OTHER", &json!({ "payload": [9] })), None);
+    assert_eq!(pending.resolve("DIGEST1", &json!({})), Some(b"by-digest".to_vec()));
+    assert_eq!(pending.resolve("DIGEST2", &json!({ "payload": [7, 8] })), Some(vec![7, 8]));
+    assert!(pending.is_empty());
+}
+
+#[test]
+fn test_pending_confirmations_are_capped_and_expire() {
+    let mut pending = PendingConfirmations::new(2, Duration::from_millis(50));
+    pending.track(b"first", Some("DIGEST1"));
+    pending.track(b"second", Some("DIGEST2"));
+    pending.track(b"third", Some("DIGEST3"));
+    // Past capacity the oldest submission is forgotten
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending.resolve("DIGEST1", &json!({})), None);
+    assert_eq!(pending.resolve("DIGEST2", &json!({})), Some(b"second".to_vec()));
+
+    // One whose event never arrives is dropped after the TTL
+    std::thread::sleep(Duration::from_millis(80));
+    assert_eq!(pending.resolve("DIGEST3", &json!({})), None);
+    assert!(pending.is_empty());
+}
+
+#[test]
+fn test_backoff_doubles_up_to_cap() {
+    let backoff = BackoffPolicy {
+        initial_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(500),
+        max_attempts: 5,
+    };
+    assert_eq!(backoff.delay(0), Duration::from_millis(100));
+    assert_eq!(backoff.delay(1), Duration::from_millis(200));
+    assert_eq!(backoff.delay(2), Duration::from_millis(400));
+    assert_eq!(backoff.delay(3), Duration::from_millis(500));
+    assert_eq!(backoff.delay(40), Duration::from_millis(500));
+
+    assert!(is_connection_error("error trying to connect: tcp connect error: Connection refused"));
+    assert!(!is_connection_error("InsufficientGas"));
+}
+
+#[test]
+fn test_backoff_strategies_stay_within_bounds() {
+    let mut fixed = Backoff::fixed(Duration::from_millis(50)).with_max_attempts(2);
+    assert_eq!(fixed.next_delay(), Some(Duration::from_millis(50)));
+    assert_eq!(fixed.next_delay(), Some(Duration::from_millis(50)));
+    assert_eq!(fixed.next_delay(), None);
+
+    // Converted policies keep their exponential schedule
+    let mut exponential: Backoff = BackoffPolicy::default().into();
+    assert_eq!(exponential.strategy(), BackoffStrategy::Exponential);
+    let delays: Vec<Duration> = std::iter::from_fn(|| exponential.next_delay()).collect();
+    assert_eq!(delays, (0..5).map(|attempt| BackoffPolicy::default().delay(attempt)).collect::<Vec<_>>());
+
+    let base = Duration::from_millis(10);
+    let cap = Duration::from_millis(300);
+    let mut jittered = Backoff::decorrelated_jitter(base, cap);
+    let mut previous = base;
+    let mut distinct = std::collections::HashSet::new();
+    for _ in 0..1000 {
+        let delay = jittered.next_delay().unwrap();
+        assert!(delay >= base && delay <= cap, "{:?} outside [{:?}, {:?}]", delay, base, cap);
+        assert!(delay <= previous * 3, "{:?} more than triples {:?}", delay, previous);
+        distinct.insert(delay);
+        previous = delay;
+    }
+    assert!(distinct.len() > 10, "jitter produced only {} distinct delays", distinct.len());
+}
+
+#[tokio::test]
+async fn test_retry_async_stops_on_success_or_non_retryable_error() {
+    let calls = &AtomicU32::new(0);
+    let backoff = Backoff::fixed(Duration::from_millis(1)).with_max_attempts(3);
+    let result: Result<u32, String> = retry_async(|| async move {
+        match calls.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => Err("connection refused".to_string()),
+            n => Ok(n),
+        }
+    }, backoff.clone(), |e| is_connection_error(e)).await;
+    assert_eq!(result, Ok(2));
+
+    calls.store(0, Ordering::SeqCst);
+    let result: Result<u32, String> = retry_async(|| async move {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err("InsufficientGas".to_string())
+    }, backoff.clone(), |e| is_connection_error(e)).await;
+    assert_eq!(result, Err("InsufficientGas".to_string()));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Retryable errors give up once the attempts are used
+    calls.store(0, Ordering::SeqCst);
+    let result: Result<u32, String> = retry_async(|| async move {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err("timed out".to_string())
+    }, backoff, |e| is_connection_error(e)).await;
+    assert!(result.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn test_reconnecting_client_rebuilds_only_on_connection_errors() {
+    // Each "connection" is the number of times the client has been built
+    let builds = Arc::new(AtomicU32::new(0));
+    let counter = builds.clone();
+    let backoff = BackoffPolicy {
+        initial_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(1),
+        max_attempts: 3,
+    };
+    let client = ReconnectingClient::connect("test", backoff, move || {
+        let counter = counter.clone();
+        async move { Ok(counter.fetch_add(1, Ordering::SeqCst) + 1) }
+    }).await.unwrap();
+    assert_eq!(*client.current(), 1);
+
+    assert!(!client.recover_from(&"Object version mismatch").await);
+    assert_eq!(client.reconnections(), 0);
+    assert_eq!(*client.current(), 1);
+
+    assert!(client.recover_from(&"connection refused").await);
+    assert_eq!(client.reconnections(), 1);
+    assert_eq!(*client.current(), 2);
+    assert_eq!(builds.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,761 @@
+use crate::external::oracle::{
+    create_weather_oracle_async, extract_path_value, AmbiguousConsensus, ConsensusOutcome, ConsensusStrategy, NamedQuorum,
+    NamedQuorumUnmet, NumericConsensusMode, OracleManager, OracleSource,
+    OracleSourceStatus, OutlierStrategy, PathSegment, RestApiOracleSource, SourceFetchError, TieBreakPolicy,
+    weighted_mean, weighted_median, weighted_trimmed_mean,
+};
+use crate::external::consensus_history::ConsensusHistory;
+use crate::external::websocket::WebSocketOracleSource;
+use crate::metrics::prometheus;
+use crate::testkit::MockOracleSource;
+use crate::utils::backoff::Backoff;
+use crate::utils::canonical_json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_add_source_reuses_single_background_task() {
+    let mut manager = OracleManager::new(None, None, Some(1), None, Some(Duration::from_secs(60)), None);
+
+    for (i, name) in ["source_a", "source_b", "source_c"].iter().enumerate() {
+        manager.add_source(Arc::new(MockOracleSource::returning(json!(i)).with_name(name))).unwrap();
+    }
+
+    assert_eq!(manager.polled_source_count(), 3);
+    assert_eq!(manager.background_task_count(), 1, "Adding sources must not spawn extra polling tasks");
+}
+
+fn path(segments: &[&str]) -> Vec<String> {
+    segments.iter().map(|s| s.to_string()).collect()
+}
+
+fn flights_response() -> Value {
+    json!({
+        "data": [
+            { "flight": { "iata": "BA123" }, "flight_status": "landed", "delay": 0 },
+            { "flight": { "iata": "LH987" }, "flight_status": "active", "delay": 45 },
+            { "flight": { "iata": "AF001" } }
+        ]
+    })
+}
+
+#[test]
+fn test_path_segment_parsing() {
+    assert_eq!(PathSegment::parse("data"), PathSegment::Key("data".to_string()));
+    assert_eq!(PathSegment::parse("0"), PathSegment::Key("0".to_string()));
+    assert_eq!(PathSegment::parse("*"), PathSegment::Wildcard);
+    assert_eq!(
+        PathSegment::parse("[flight_status=active]"),
+        PathSegment::Filter { field: "flight_status".to_string(), value: "active".to_string() }
+    );
+    // Brackets without '=' fall back to a plain key
+    assert_eq!(PathSegment::parse("[oops]"), PathSegment::Key("[oops]".to_string()));
+}
+
+#[test]
+fn test_extract_path_index_and_keys() {
+    let data = flights_response();
+    assert_eq!(extract_path_value(&data, &path(&["data", "0", "flight_status"])), Some(json!("landed")));
+    assert_eq!(extract_path_value(&data, &path(&["data", "5"])), None);
+    assert_eq!(extract_path_value(&data, &path(&["data", "first"])), None);
+}
+
+#[test]
+fn test_extract_path_wildcard_skips_missing_fields() {
+    let data = flights_response();
+    assert_eq!(
+        extract_path_value(&data, &path(&["data", "*", "flight_status"])),
+        Some(json!(["landed", "active"]))
+    );
+}
+
+#[test]
+fn test_extract_path_filter_segment() {
+    let data = flights_response();
+    assert_eq!(
+        extract_path_value(&data, &path(&["data", "[flight_status=active]", "flight", "iata"])),
+        Some(json!("LH987"))
+    );
+    // Numeric fields compare against their string form
+    assert_eq!(
+        extract_path_value(&data, &path(&["data", "[delay=45]", "flight_status"])),
+        Some(json!("active"))
+    );
+    assert_eq!(extract_path_value(&data, &path(&["data", "[flight_status=cancelled]"])), None);
+}
+
+#[tokio::test]
+async fn test_fallback_source_used_when_consensus_unreachable() {
+    // Requiring three sources makes consensus impossible with only two registered
+    let mut manager = OracleManager::new(None, None, Some(3), None, Some(Duration::ZERO), None);
+    manager.add_source(Arc::new(MockOracleSource::returning(json!(21.5)).with_name("primary"))).unwrap();
+    manager.add_source(Arc::new(MockOracleSource::returning(json!(22.0)).with_name("backup"))).unwrap();
+
+    assert!(manager.get_consensus_result("temp", &json!({})).await.is_err());
+    assert!(manager.set_fallback_sources(vec!["missing".to_string()]).is_err());
+
+    manager.set_fallback_sources(vec!["backup".to_string(), "primary".to_string()]).unwrap();
+    let result = manager.get_consensus_result("temp", &json!({})).await.unwrap();
+    assert_eq!(result.value, json!(22.0));
+    assert_eq!(result.strategy, ConsensusStrategy::Fallback { source: "backup".to_string() });
+}
+
+#[tokio::test]
+async fn test_object_consensus_ignores_key_order_and_number_format() {
+    let first: Value = serde_json::from_str(r#"{"status":"delayed","delay":{"minutes":45,"ratio":1.0}}"#).unwrap();
+    let second: Value = serde_json::from_str(r#"{"delay":{"ratio":1,"minutes":45.0},"status":"delayed"}"#).unwrap();
+    assert_ne!(first.to_string(), second.to_string());
+    assert_eq!(canonical_json(&first), canonical_json(&second));
+    assert_eq!(canonical_json(&first), r#"{"delay":{"minutes":45,"ratio":1},"status":"delayed"}"#);
+
+    let mut manager = OracleManager::new(None, Some(1.0), Some(2), None, Some(Duration::ZERO), None);
+    manager.add_source(Arc::new(MockOracleSource::returning(first.clone()).with_name("node_a"))).unwrap();
+    manager.add_source(Arc::new(MockOracleSource::returning(second).with_name("node_b"))).unwrap();
+
+    let result = manager.get_consensus_result("flight", &json!({})).await.unwrap();
+    assert_eq!(result.strategy, ConsensusStrategy::Consensus);
+    assert_eq!(canonical_json(&result.value), canonical_json(&first));
+}
+
+#[tokio::test]
+async fn test_require_sources_fails_before_query_time() {
+    let mut manager = OracleManager::new(None, None, Some(1), None, Some(Duration::ZERO), None);
+    let err = manager.require_sources(1).unwrap_err();
+    assert!(err.to_string().contains("0 oracle source(s) registered"), "{}", err);
+
+    manager.add_source(Arc::new(MockOracleSource::returning(json!(1)).with_name("only"))).unwrap();
+    assert_eq!(manager.source_count(), 1);
+    assert!(manager.require_sources(1).is_ok());
+    assert!(manager.require_sources(2).is_err());
+}
+
+fn manager_with(sources: Vec<MockOracleSource>, min_sources: usize) -> OracleManager {
+    manager_with_threshold(sources, min_sources, None)
+}
+
+fn manager_with_threshold(sources: Vec<MockOracleSource>, min_sources: usize, threshold: Option<f64>) -> OracleManager {
+    let mut manager = OracleManager::new(None, threshold, Some(min_sources), None, Some(Duration::ZERO), None);
+    for source in sources {
+        manager.add_source(Arc::new(source)).unwrap();
+    }
+    manager
+}
+
+#[tokio::test]
+async fn test_outlier_strategies_on_single_outlier() {
+    let values = [10.0, 10.2, 9.9, 10.1, 50.0];
+    let weighted: Vec<(f64, u8)> = values.iter().map(|v| (*v, 1)).collect();
+    let kept = |strategy: OutlierStrategy| -> Vec<f64> {
+        strategy.filter(&weighted).into_iter().map(|(v, _)| v).collect()
+    };
+
+    assert_eq!(OutlierStrategy::default(), OutlierStrategy::Iqr { k: 1.5 });
+    assert_eq!(kept(OutlierStrategy::Iqr { k: 1.5 }), vec![10.0, 10.2, 9.9, 10.1]);
+    assert_eq!(kept(OutlierStrategy::MadBased { threshold: 3.0 }), vec![10.0, 10.2, 9.9, 10.1]);
+    assert_eq!(kept(OutlierStrategy::None), values.to_vec());
+    // The outlier drags the mean and standard deviation with it: its z-score is only 2.0,
+    // so a conventional threshold of 3 keeps it in a sample this small
+    assert_eq!(kept(OutlierStrategy::ZScore { threshold: 3.0 }), values.to_vec());
+    assert_eq!(kept(OutlierStrategy::ZScore { threshold: 1.5 }), vec![10.0, 10.2, 9.9, 10.1]);
+
+    for (strategy, expected) in [
+        (OutlierStrategy::Iqr { k: 1.5 }, 10.05),
+        (OutlierStrategy::MadBased { threshold: 3.0 }, 10.05),
+        (OutlierStrategy::ZScore { threshold: 3.0 }, 10.1),
+        (OutlierStrategy::None, 10.1),
+    ] {
+        let sources = values.iter().enumerate()
+            .map(|(i, v)| MockOracleSource::returning(json!(v)).with_name(&format!("node_{}", i)))
+            .collect();
+        let mut manager = manager_with(sources, 3);
+        manager.set_outlier_strategy(strategy);
+        let value = manager.get_consensus_data("temp", &json!({})).await.unwrap().as_f64().unwrap();
+        assert!((value - expected).abs() < 1e-9, "{:?}: expected {}, got {}", strategy, expected, value);
+    }
+}
+
+#[tokio::test]
+async fn test_mad_rejects_gross_outlier_among_three_sources() {
+    let kept = |strategy: OutlierStrategy, values: &[f64]| -> Vec<f64> {
+        let weighted: Vec<(f64, u8)> = values.iter().map(|v| (*v, 1)).collect();
+        strategy.filter(&weighted).into_iter().map(|(v, _)| v).collect()
+    };
+    let mad = OutlierStrategy::MadBased { threshold: 3.0 };
+
+    // With three values the IQR quartiles are the extremes, so nothing is an outlier
+    assert_eq!(kept(OutlierStrategy::Iqr { k: 1.5 }, &[21.0, 21.4, 85.0]), vec![21.0, 21.4, 85.0]);
+    assert_eq!(kept(mad, &[21.0, 21.4, 85.0]), vec![21.0, 21.4]);
+    // Two identical readings make the MAD 0; a reading within tolerance still counts
+    assert_eq!(kept(mad, &[21.0, 21.0, 21.1]), vec![21.0, 21.0, 21.1]);
+    assert_eq!(kept(mad, &[21.0, 21.0, 85.0]), vec![21.0, 21.0]);
+    assert_eq!(kept(mad, &[21.0, 22.0]), vec![21.0, 22.0]);
+
+    let mut manager = OracleManager::new(None, None, Some(2), None, Some(Duration::ZERO), Some(mad));
+    for (i, v) in [21.0, 21.4, 85.0].iter().enumerate() {
+        manager.add_source(Arc::new(MockOracleSource::returning(json!(v)).with_name(&format!("station_{}", i)))).unwrap();
+    }
+    let value = manager.get_consensus_data("temp", &json!({})).await.unwrap().as_f64().unwrap();
+    assert!((21.0..=21.4).contains(&value), "median of the agreeing stations expected, got {}", value);
+    assert_eq!(manager.consensus_metrics().per_source_outlier_counts.get("station_2"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_detailed_consensus_reports_who_counted_and_why_others_did_not() {
+    let sources = vec![
+        MockOracleSource::returning(json!(10.0)).with_name("a"),
+        MockOracleSource::returning(json!(10.1)).with_name("b"),
+        MockOracleSource::returning(json!(9.9)).with_name("c").with_weight(2),
+        MockOracleSource::returning(json!(1000.0)).with_name("bad"),
+        MockOracleSource::failing().with_name("down"),
+        MockOracleSource::returning(json!(10.0)).with_name("rejects").with_validation_failure("range"),
+        MockOracleSource::returning(json!(10.0)).with_name("offline").with_status(OracleSourceStatus::Failed("maintenance".to_string())),
+    ];
+    let mut manager = manager_with(sources, 3);
+    manager.set_outlier_strategy(OutlierStrategy::MadBased { threshold: 3.0 });
+
+    let outcome: ConsensusOutcome = manager.get_consensus_data_detailed("temp", &json!({})).await.unwrap();
+    assert_eq!(outcome.strategy, ConsensusStrategy::Consensus);
+    assert_eq!(outcome.participating_sources, vec!["a", "b", "c"]);
+    assert_eq!(outcome.achieved_weight, 4);
+    // 51% of the six operational sources' weight of 7
+    assert_eq!(outcome.required_weight, 3);
+    let reasons: HashMap<&str, &str> = outcome.rejected_sources.iter().map(|(s, r)| (s.as_str(), r.as_str())).collect();
+    assert_eq!(reasons.len(), 4);
+    assert_eq!(reasons["bad"], "outlier");
+    assert!(reasons["down"].starts_with("fetch failed"), "{}", reasons["down"]);
+    assert!(reasons["rejects"].starts_with("invalid"), "{}", reasons["rejects"]);
+    assert!(reasons["offline"].starts_with("not operational"), "{}", reasons["offline"]);
+
+    // The plain value API returns the same answer
+    let value = manager.get_consensus_data("temp", &json!({})).await.unwrap();
+    assert_eq!(value, outcome.value);
+}
+
+#[tokio::test]
+async fn test_numeric_consensus_rejects_outlier_and_takes_median() {
+    let values = [10.0, 11.0, 12.0, 12.5, 13.0, 1000.0];
+    let sources = values.iter().enumerate()
+        .map(|(i, v)| MockOracleSource::returning(json!(v)).with_name(&format!("node_{}", i)))
+        .collect();
+    let manager = manager_with(sources, 3);
+
+    let value = manager.get_consensus_data("temp", &json!({})).await.unwrap();
+    assert_eq!(value, json!(12.0));
+}
+
+#[test]
+fn test_weighted_mean_and_trimmed_mean() {
+    let values = [(10.0, 1), (20.0, 1), (60.0, 2)];
+    assert_eq!(weighted_mean(&values), Some(37.5));
+    // A quarter of the weight off each end drops 10 and half of 60's weight
+    assert_eq!(weighted_trimmed_mean(&values, 0.25), Some(40.0));
+    assert_eq!(weighted_trimmed_mean(&values, 0.0), weighted_mean(&values));
+    assert_eq!(weighted_trimmed_mean(&values, 0.5), weighted_median(&values));
+    assert_eq!(weighted_mean(&[(1.0, 0)]), None);
+    assert_eq!(weighted_trimmed_mean(&[], 0.1), None);
+}
+
+#[tokio::test]
+async fn test_numeric_consensus_modes_on_five_sources() {
+    let consensus = |mode: Option<NumericConsensusMode>| async move {
+        let sources = [10.0, 10.0, 11.0, 14.0, 15.0].iter().enumerate()
+            .map(|(i, v)| MockOracleSource::returning(json!(v)).with_name(&format!("feed_{}", i)))
+            .collect();
+        let mut manager = manager_with(sources, 3);
+        manager.set_outlier_strategy(OutlierStrategy::None);
+        if let Some(mode) = mode {
+            manager.set_numeric_consensus_mode(mode).unwrap();
+        }
+        manager.get_consensus_data("price", &json!({})).await.unwrap().as_f64().unwrap()
+    };
+
+    // The median is the default and ignores the skew towards the high readings
+    assert_eq!(consensus(None).await, 11.0);
+    assert_eq!(consensus(Some(NumericConsensusMode::WeightedMedian)).await, 11.0);
+    assert_eq!(consensus(Some(NumericConsensusMode::WeightedMean)).await, 12.0);
+    // Dropping one source's weight at each end leaves 10, 11 and 14
+    let trimmed = consensus(Some(NumericConsensusMode::TrimmedMean(0.2))).await;
+    assert!((trimmed - 35.0 / 3.0).abs() < 1e-9, "trimmed mean {}", trimmed);
+
+    let mut manager = manager_with(Vec::new(), 1);
+    assert!(manager.set_numeric_consensus_mode(NumericConsensusMode::TrimmedMean(0.5)).is_err());
+}
+
+#[tokio::test]
+async fn test_consensus_metrics_flag_repeat_outlier_source() {
+    let sources = vec![
+        MockOracleSource::returning(json!(10.0)).with_name("a"),
+        MockOracleSource::returning(json!(10.1)).with_name("b"),
+        MockOracleSource::returning(json!(9.9)).with_name("c"),
+        MockOracleSource::returning(json!(10.2)).with_name("d"),
+        MockOracleSource::returning(json!(1000.0)).with_name("bad"),
+    ];
+    let manager = manager_with(sources, 3);
+    for round in 0..3 {
+        manager.get_consensus_data(&format!("temp_{}", round), &json!({})).await.unwrap();
+    }
+    // Cache hits don't count as rounds
+    manager.get_consensus_data("temp_0", &json!({})).await.unwrap();
+
+    let metrics = manager.consensus_metrics();
+    assert_eq!(metrics.consensus_reached, 3);
+    assert_eq!(metrics.consensus_failed, 0);
+    assert_eq!(metrics.outliers_rejected, 3);
+    assert_eq!(metrics.per_source_outlier_counts.get("bad"), Some(&3));
+    assert_eq!(metrics.per_source_outlier_counts.get("a"), None);
+    assert_eq!(metrics.outlier_rate("bad"), 1.0);
+    assert_eq!(metrics.flagged_sources(0.5, 3), vec!["bad".to_string()]);
+    assert!(metrics.flagged_sources(0.5, 4).is_empty());
+
+    let exposition = prometheus::render_consensus_metrics(&metrics);
+    assert!(exposition.contains("oracle_consensus_reached_total 3\n"));
+    assert!(exposition.contains("oracle_source_outliers_total{source=\"bad\"} 3\n"));
+
+    let starved = manager_with(vec![MockOracleSource::returning(json!(1.0))], 2);
+    assert!(starved.get_consensus_data("temp", &json!({})).await.is_err());
+    assert_eq!(starved.consensus_metrics().consensus_failed, 1);
+    assert_eq!(starved.consensus_metrics().failure_rate(), 1.0);
+}
+
+#[tokio::test]
+async fn test_cost_optimized_consensus_stops_once_heaviest_sources_agree() {
+    let heavy = Arc::new(MockOracleSource::returning(json!(10.0)).with_name("heavy").with_weight(60));
+    let second = Arc::new(MockOracleSource::sequence(vec![json!(10.0), json!(20.0)]).with_name("second").with_weight(30));
+    let third = Arc::new(MockOracleSource::returning(json!(10.05)).with_name("third").with_weight(20));
+    let fourth = Arc::new(MockOracleSource::returning(json!(10.0)).with_name("fourth").with_weight(10));
+    let mut manager = OracleManager::new(None, None, Some(2), None, Some(Duration::ZERO), None);
+    for source in [heavy.clone(), second.clone(), third.clone(), fourth.clone()] {
+        manager.add_source(source).unwrap();
+    }
+    manager.set_cost_optimized(true);
+
+    // 60 + 30 of 120 crosses the 51% threshold, so the lighter sources aren't asked
+    assert_eq!(manager.get_consensus_data("agree", &json!({})).await.unwrap(), json!(10.0));
+    assert_eq!((third.fetch_count(), fourth.fetch_count()), (0, 0));
+
+    // "second" now disagrees, so the next heaviest source is queried as a corroborator
+    assert_eq!(manager.get_consensus_data("disagree", &json!({})).await.unwrap(), json!(10.0));
+    assert_eq!((heavy.fetch_count(), third.fetch_count(), fourth.fetch_count()), (2, 1, 0));
+
+    let metrics = manager.consensus_metrics();
+    assert_eq!(metrics.sources_queried, 5);
+    assert_eq!(metrics.sources_skipped, 3);
+}
+
+/// Median of the values expanded `weight` times each.
+fn reference_weighted_median(values: &[(f64, u8)]) -> Option<f64> {
+    let mut expanded: Vec<f64> = values.iter()
+        .flat_map(|(v, w)| std::iter::repeat(*v).take(*w as usize))
+        .collect();
+    if expanded.is_empty() {
+        return None;
+    }
+    expanded.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = expanded.len() / 2;
+    Some(if expanded.len() % 2 == 0 { (expanded[mid - 1] + expanded[mid]) / 2.0 } else { expanded[mid] })
+}
+
+#[test]
+fn test_weighted_median_matches_reference() {
+    let cases: Vec<Vec<(f64, u8)>> = vec![
+        vec![(1.0, 1), (2.0, 1)],
+        vec![(1.0, 1), (2.0, 1), (3.0, 1), (4.0, 1)],
+        vec![(3.0, 2), (1.0, 1), (2.0, 1)],
+        vec![(10.0, 50), (20.0, 30), (30.0, 20)],
+        vec![(5.0, 10), (7.0, 0), (9.0, 10)],
+        vec![(1.5, 3), (2.5, 5), (4.0, 1), (8.0, 7)],
+        vec![(42.0, 9)],
+        vec![(1.0, 0)],
+    ];
+    for case in cases {
+        assert_eq!(weighted_median(&case), reference_weighted_median(&case), "weights {:?}", case);
+    }
+}
+
+#[tokio::test]
+async fn test_numeric_consensus_averages_middle_values_for_even_weight() {
+    let sources = [10.0, 11.0, 12.0, 13.0].iter().enumerate()
+        .map(|(i, v)| MockOracleSource::returning(json!(v)).with_name(&format!("node_{}", i)))
+        .collect();
+    let manager = manager_with(sources, 4);
+
+    assert_eq!(manager.get_consensus_data("price", &json!({})).await.unwrap(), json!(11.5));
+}
+
+#[tokio::test]
+async fn test_categorical_consensus_picks_plurality() {
+    let manager = manager_with(vec![
+        MockOracleSource::returning(json!("on_time")).with_name("a"),
+        MockOracleSource::returning(json!("on_time")).with_name("b"),
+        MockOracleSource::returning(json!("delayed")).with_name("c"),
+    ], 3);
+
+    assert_eq!(manager.get_consensus_data("status", &json!({})).await.unwrap(), json!("on_time"));
+}
+
+#[tokio::test]
+async fn test_named_quorum_requires_named_sources_to_agree() {
+    let down = || OracleSourceStatus::Failed("down".to_string());
+    let manager_answering = |b: &str| manager_with(vec![
+        MockOracleSource::returning(json!("on_time")).with_name("a"),
+        MockOracleSource::returning(json!(b)).with_name("b"),
+        MockOracleSource::returning(json!("on_time")).with_name("c").with_status(down()),
+        MockOracleSource::returning(json!("on_time")).with_name("d"),
+    ], 2);
+    let named = NamedQuorum { required_sources: vec!["a".to_string(), "b".to_string(), "c".to_string()], min_agreeing: 2 };
+
+    let mut manager = manager_answering("on_time");
+    assert!(manager.set_named_quorum(NamedQuorum { min_agreeing: 4, ..named.clone() }).is_err());
+    manager.set_named_quorum(named.clone()).unwrap();
+    assert_eq!(manager.get_consensus_data("status", &json!({})).await.unwrap(), json!("on_time"));
+
+    // "a" and "d" outweigh "b", but only one named source backs the answer and the fallback can't stand in
+    let mut manager = manager_answering("delayed");
+    manager.set_named_quorum(named).unwrap();
+    manager.set_fallback_sources(vec!["d".to_string()]).unwrap();
+    let error = manager.get_consensus_result("status", &json!({})).await.unwrap_err();
+    let unmet = error.downcast_ref::<NamedQuorumUnmet>().expect("named quorum error");
+    assert_eq!((unmet.agreeing, unmet.unavailable.clone()), (1, vec!["c".to_string()]));
+}
+
+#[tokio::test]
+async fn test_categorical_tie_is_deterministic_or_rejected() {
+    let tied = || vec![
+        MockOracleSource::returning(json!(true)).with_name("yes"),
+        MockOracleSource::returning(json!(false)).with_name("no"),
+    ];
+
+    for _ in 0..5 {
+        let manager = manager_with_threshold(tied(), 2, Some(0.5));
+        assert_eq!(manager.get_consensus_data("delayed", &json!({})).await.unwrap(), json!(false));
+    }
+
+    let mut manager = manager_with_threshold(tied(), 2, Some(0.5));
+    manager.set_tie_break_policy(TieBreakPolicy::Reject);
+    let err = manager.get_consensus_data("delayed", &json!({})).await.unwrap_err();
+    let ambiguous = err.downcast_ref::<AmbiguousConsensus>().expect("tie should be reported as ambiguous");
+    assert_eq!(ambiguous.candidates, vec!["false".to_string(), "true".to_string()]);
+    assert_eq!(ambiguous.weight, 100);
+}
+
+#[tokio::test]
+async fn test_failed_invalid_and_down_sources_are_excluded() {
+    // Half the operational weight answers validly, so accept a 50% threshold
+    let manager = manager_with_threshold(vec![
+        MockOracleSource::returning(json!(5)).with_name("good_a"),
+        MockOracleSource::returning(json!(5)).with_name("good_b"),
+        MockOracleSource::failing().with_name("erroring"),
+        MockOracleSource::returning(json!(99)).with_name("invalid").with_validation_failure("range"),
+        MockOracleSource::returning(json!(99)).with_name("down").with_status(OracleSourceStatus::Failed("offline".to_string())),
+    ], 2, Some(0.5));
+
+    assert_eq!(manager.get_consensus_data("value", &json!({})).await.unwrap(), json!(5.0));
+}
+
+#[tokio::test]
+async fn test_failed_fetches_are_retried_on_the_fetch_backoff() {
+    let flaky = Arc::new(MockOracleSource::returning(json!(5)).with_name("flaky").with_failing_fetches(1));
+    let steady = Arc::new(MockOracleSource::returning(json!(5)).with_name("steady"));
+    let mut manager = OracleManager::new(None, None, Some(2), None, Some(Duration::ZERO), None);
+    manager.add_source(flaky.clone()).unwrap();
+    manager.add_source(steady.clone()).unwrap();
+    manager.set_fetch_backoff(Backoff::fixed(Duration::from_millis(1)).with_max_attempts(1));
+
+    // The retry recovers from the outage, so both sources take part
+    let outcome = manager.get_consensus_data_detailed("value", &json!({})).await.unwrap();
+    assert_eq!(outcome.participating_sources.len(), 2);
+    assert_eq!((flaky.fetch_count(), steady.fetch_count()), (2, 1));
+
+    // Without retries the outage leaves too few sources
+    let flaky = Arc::new(MockOracleSource::returning(json!(5)).with_name("flaky").with_failing_fetches(1));
+    let mut manager = OracleManager::new(None, None, Some(2), None, Some(Duration::ZERO), None);
+    manager.add_source(flaky.clone()).unwrap();
+    manager.add_source(Arc::new(MockOracleSource::returning(json!(5)).with_name("steady"))).unwrap();
+    manager.set_fetch_backoff(Backoff::fixed(Duration::ZERO).with_max_attempts(0));
+    assert!(manager.get_consensus_data("value", &json!({})).await.is_err());
+    assert_eq!(flaky.fetch_count(), 1);
+
+    // Refusals like a bad key or an exhausted quota aren't retried
+    for status in [reqwest::StatusCode::UNAUTHORIZED, reqwest::StatusCode::TOO_MANY_REQUESTS] {
+        let refused = Arc::new(MockOracleSource::returning(json!(5)).with_name("refused").with_failing_fetches(1).with_outage_status(status));
+        let mut manager = OracleManager::new(None, None, Some(1), None, Some(Duration::ZERO), None);
+        manager.add_source(refused.clone()).unwrap();
+        manager.set_fetch_backoff(Backoff::fixed(Duration::ZERO).with_max_attempts(3));
+        assert!(manager.get_consensus_data("value", &json!({})).await.is_err());
+        assert_eq!(refused.fetch_count(), 1);
+    }
+    assert!(SourceFetchError::Transport("connection reset".to_string()).is_retryable());
+    assert!(SourceFetchError::Status(reqwest::StatusCode::BAD_GATEWAY).is_retryable());
+    assert!(!SourceFetchError::is_retryable_error(&anyhow::anyhow!("Failed to parse JSON")));
+}
+
+#[tokio::test]
+async fn test_mock_source_replays_sequence_then_repeats_last() {
+    let source = MockOracleSource::sequence(vec![json!(1), json!(2)]);
+    for expected in [1, 2, 2] {
+        assert_eq!(source.fetch(&json!({})).await.unwrap(), json!(expected));
+    }
+    assert_eq!(source.fetch_count(), 3);
+    assert!(MockOracleSource::failing().fetch(&json!({})).await.is_err());
+}
+
+#[tokio::test]
+async fn test_per_call_timeout_overrides_source_timeout() {
+    // Accepts connections into the backlog but never answers
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut config = MockOracleSource::returning(json!(1)).config().clone();
+    config.name = "slow_api".to_string();
+    config.url = format!("http://{}/data", listener.local_addr().unwrap());
+    config.timeout_ms = 10_000;
+    config.background_timeout_ms = Some(30_000);
+    assert_eq!(config.background_timeout(), Duration::from_secs(30));
+    let source = RestApiOracleSource::new(config, None, Arc::new(Mutex::new(HashMap::new())), Duration::ZERO).unwrap();
+
+    let start = std::time::Instant::now();
+    let result = source.fetch_with_timeout(&json!({}), Duration::from_millis(100)).await;
+    assert!(result.is_err());
+    assert!(start.elapsed() < Duration::from_secs(5), "per-call timeout ignored: {:?}", start.elapsed());
+}
+
+#[test]
+fn test_regex_rules_match_patterns_not_substrings() {
+    use crate::external::oracle::create_regex_rule;
+
+    let mut config = MockOracleSource::returning(json!(1)).config().clone();
+    config.validation_rules = vec![
+        create_regex_rule("status", "^(active|landed)$", "Invalid status"),
+        create_regex_rule("broken", "(unclosed", "unused"),
+    ];
+    let source = RestApiOracleSource::new(config, None, Arc::new(Mutex::new(HashMap::new())), Duration::ZERO).unwrap();
+
+    let results = source.validate(&json!({ "flight_status": "landed" }));
+    let status = results.iter().find(|r| r.rule_name == "status").unwrap();
+    assert!(status.passed);
+    let broken = results.iter().find(|r| r.rule_name == "broken").unwrap();
+    assert!(!broken.passed);
+    assert!(broken.error_message.as_deref().unwrap().contains("(unclosed"));
+
+    // A substring check would have accepted this
+    let results = source.validate(&json!({ "flight_status": "active_but_diverted" }));
+    let status = results.iter().find(|r| r.rule_name == "status").unwrap();
+    assert!(!status.passed);
+    assert_eq!(status.error_message.as_deref(), Some("Invalid status"));
+}
+
+#[test]
+fn test_targeted_rules_check_only_their_field() {
+    use crate::external::oracle::{create_numeric_range_rule, create_string_pattern_rule};
+
+    let mut config = MockOracleSource::returning(json!(1)).config().clone();
+    config.validation_rules = vec![
+        create_numeric_range_rule("delay_range", Some(0.0), Some(600.0), "Delay out of range").with_target_field(&["arrival", "delay"]),
+        create_string_pattern_rule("status", None, Some(vec!["active", "landed"]), "Invalid status").with_target_field(&["flight_status"]),
+    ];
+    let source = RestApiOracleSource::new(config, None, Arc::new(Mutex::new(HashMap::new())), Duration::ZERO).unwrap();
+
+    let flight = json!({ "flight_status": "landed", "airline": "Lufthansa", "arrival": { "delay": 45 } });
+    let results = source.validate(&flight);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.passed));
+    assert_eq!(results[0].data_field, "arrival.delay");
+    assert_eq!(results[0].value, json!(45));
+
+    let results = source.validate(&json!({ "flight_status": "landed" }));
+    assert!(!results[0].passed);
+    assert!(results[0].error_message.as_deref().unwrap().contains("'arrival.delay' not found"));
+
+    // Untargeted rules still apply to every field
+    let broadcast = create_numeric_range_rule("any", Some(0.0), None, "Negative");
+    let mut config = MockOracleSource::returning(json!(1)).config().clone();
+    config.validation_rules = vec![broadcast];
+    let source = RestApiOracleSource::new(config, None, Arc::new(Mutex::new(HashMap::new())), Duration::ZERO).unwrap();
+    assert_eq!(source.validate(&json!({ "a": 1, "b": -1 })).iter().filter(|r| !r.passed).count(), 1);
+}
+
+#[test]
+fn test_request_bodies_for_post_and_graphql_sources() {
+    let mut config = MockOracleSource::returning(json!(1)).config().clone();
+    config.default_params = Some(json!({ "api_key": "k", "units": "metric" }));
+    let merged = config.merged_params(&json!({ "units": "imperial", "city": "Oslo" }));
+    assert_eq!(merged, json!({ "api_key": "k", "units": "imperial", "city": "Oslo" }));
+
+    // Existing GET configs keep sending query parameters
+    assert_eq!(config.request_body(&merged).unwrap(), None);
+
+    config.http_method = "POST".to_string();
+    config.body_template = Some(json!({ "format": "json", "units": "kelvin" }));
+    assert_eq!(
+        config.request_body(&merged).unwrap(),
+        Some(json!({ "format": "json", "api_key": "k", "units": "imperial", "city": "Oslo" }))
+    );
+
+    config.source_type = "GraphQL".to_string();
+    config.body_template = Some(json!({ "query": "query($city: String!) { weather(city: $city) { temp } }", "variables": { "units": "metric" } }));
+    let body = config.request_body(&json!({ "city": "Oslo" })).unwrap().unwrap();
+    assert_eq!(body["query"], json!("query($city: String!) { weather(city: $city) { temp } }"));
+    assert_eq!(body["variables"], json!({ "units": "metric", "city": "Oslo" }));
+
+    config.body_template = None;
+    assert!(RestApiOracleSource::new(config.clone(), None, Arc::new(Mutex::new(HashMap::new())), Duration::ZERO).is_err());
+    config.source_type = "REST".to_string();
+    config.http_method = "PATCH".to_string();
+    assert!(config.request_body(&merged).is_err());
+}
+
+#[tokio::test]
+async fn test_consensus_history_records_sources_and_persists() {
+    let path = std::env::temp_dir().join(format!("suimodular_consensus_history_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    // Persisted timestamps have millisecond precision
+    let start = chrono::Utc::now() - chrono::Duration::seconds(1);
+
+    let sources = vec![
+        MockOracleSource::returning(json!(20.0)).with_name("a"),
+        MockOracleSource::returning(json!(21.0)).with_name("b"),
+    ];
+    let mut manager = manager_with(sources, 2);
+    manager.set_consensus_history(ConsensusHistory::with_file(2, &path).unwrap());
+    for city in ["London", "Paris", "Rome"] {
+        manager.get_consensus_data("temp", &json!({ "city": city })).await.unwrap();
+    }
+    manager.get_consensus_data("humidity", &json!({})).await.unwrap();
+
+    // Capacity 2: only the newest records stay in memory
+    let history = manager.consensus_history("temp", start);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].params, json!({ "city": "Rome" }));
+    assert_eq!(history[0].value, json!(20.5));
+    assert_eq!(history[0].strategy, ConsensusStrategy::Consensus);
+    let mut contributors = history[0].sources.clone();
+    contributors.sort();
+    assert_eq!(contributors, vec!["a", "b"]);
+    assert!(manager.consensus_history("temp", chrono::Utc::now() + chrono::Duration::seconds(60)).is_empty());
+
+    // The file keeps everything; reloading keeps the newest `capacity`
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+    let reloaded = ConsensusHistory::with_file(10, &path).unwrap();
+    assert_eq!(reloaded.query("temp", start).len(), 3);
+
+    // A record torn by a crash mid-write doesn't stop the next start
+    let mut contents = std::fs::read_to_string(&path).unwrap();
+    let valid_line = contents.lines().next().unwrap().to_string();
+    contents.push_str("{\"query_id\":\"te");
+    std::fs::write(&path, &contents).unwrap();
+    assert_eq!(ConsensusHistory::with_file(10, &path).unwrap().query("temp", start).len(), 3);
+    // Corruption anywhere else is still an error
+    contents.push('\n');
+    contents.push_str(&valid_line);
+    std::fs::write(&path, &contents).unwrap();
+    assert!(ConsensusHistory::with_file(10, &path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_consensus_history_leaves_out_rejected_sources() {
+    let sources = [10.0, 10.2, 9.9, 10.1, 50.0].iter().enumerate()
+        .map(|(i, v)| MockOracleSource::returning(json!(v)).with_name(&format!("node_{}", i)))
+        .collect();
+    let manager = manager_with(sources, 3);
+    let start = chrono::Utc::now() - chrono::Duration::seconds(1);
+    let outcome = manager.get_consensus_data_detailed("temp", &json!({})).await.unwrap();
+    assert_eq!(outcome.rejected_sources.len(), 1);
+
+    // The outlier answered, but the recorded value doesn't rest on it
+    let history = manager.consensus_history("temp", start);
+    assert_eq!(history[0].sources, outcome.participating_sources);
+    assert!(!history[0].sources.contains(&"node_4".to_string()));
+}
+
+#[tokio::test]
+async fn test_expired_consensus_outcomes_are_evicted() {
+    let source = Arc::new(MockOracleSource::returning(json!(12.5)).with_name("only"));
+    let mut manager = OracleManager::new(None, None, Some(1), Some(Duration::from_millis(50)), Some(Duration::ZERO), None);
+    manager.add_source(source.clone()).unwrap();
+
+    for city in ["London", "Paris", "Rome"] {
+        manager.get_consensus_data("temp", &json!({ "city": city })).await.unwrap();
+    }
+    let outcome = manager.get_consensus_data_detailed("temp", &json!({ "city": "Rome" })).await.unwrap();
+    assert_eq!(outcome.participating_sources, vec!["only"]);
+    assert_eq!(source.fetch_count(), 3);
+    assert_eq!(manager.cached_outcome_count(), 3);
+
+    // The next round drops every outcome that has expired
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    manager.get_consensus_data("temp", &json!({ "city": "Oslo" })).await.unwrap();
+    assert_eq!(manager.cached_outcome_count(), 1);
+}
+
+#[tokio::test]
+async fn test_async_weather_source_exposes_its_config_to_the_manager() {
+    let mut config = MockOracleSource::returning(json!(null)).with_name("weather_async").config().clone();
+    config.url = "http://127.0.0.1:9/weather".to_string();
+    config.weight = 40;
+    config.path = vec!["main".to_string(), "temp".to_string()];
+
+    let cache = Arc::new(Mutex::new(HashMap::new()));
+    let source = create_weather_oracle_async(&config, reqwest::Client::new(), cache, Duration::ZERO, Duration::from_secs(60))
+        .await
+        .unwrap();
+    let source: Arc<dyn OracleSource> = Arc::from(source);
+    assert_eq!(source.config().weight, 40);
+    assert_eq!(source.config().path, config.path);
+
+    // Used to panic as soon as the manager read the config
+    let mut manager = OracleManager::new(None, None, Some(1), None, None, None);
+    manager.add_source(source.clone()).unwrap();
+    assert_eq!(source.name(), "weather_async");
+}
+
+#[tokio::test]
+async fn test_websocket_source_subscribes_caches_and_reconnects() {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    // Each connection receives the subscription, sends one price, then drops
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let subscriptions = Arc::new(Mutex::new(Vec::new()));
+    let server_subscriptions = subscriptions.clone();
+    tokio::spawn(async move {
+        for price in [42.5, 43.0] {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            if let Some(Ok(Message::Text(text))) = socket.next().await {
+                server_subscriptions.lock().unwrap().push(text);
+            }
+            socket.send(Message::Text(json!({ "type": "ack" }).to_string())).await.unwrap();
+            socket.send(Message::Text(json!({ "data": { "price": price } }).to_string())).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    });
+
+    let mut config = StaticSource::new("price_stream", json!(null)).config;
+    config.source_type = "WebSocket".to_string();
+    config.url = url;
+    config.body_template = Some(json!({ "op": "subscribe", "channel": "SUI-USD" }));
+    config.path = vec!["data".to_string(), "price".to_string()];
+    let cache = Arc::new(Mutex::new(HashMap::new()));
+    let source = WebSocketOracleSource::new(config, None, cache.clone(), Duration::from_secs(60))
+        .unwrap()
+        .with_backoff(Backoff::fixed(Duration::from_millis(10)).with_max_attempts(20));
+
+    assert_eq!(source.fetch(&json!({})).await.unwrap(), json!(42.5));
+    assert_eq!(source.status(), OracleSourceStatus::Operational);
+    assert!(cache.lock().unwrap().contains_key("price_stream:{}"));
+
+    // The server drops each connection; the source reconnects and picks up the next price
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while source.fetch(&json!({})).await.unwrap() != json!(43.0) {
+        assert!(std::time::Instant::now() < deadline, "no reconnection");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let subscriptions: Vec<Value> = subscriptions.lock().unwrap().iter().map(|text| serde_json::from_str(text).unwrap()).collect();
+    assert_eq!(subscriptions, vec![json!({ "op": "subscribe", "channel": "SUI-USD" }); 2]);
+
+    let mut rest_config = StaticSource::new("not_a_stream", json!(null)).config;
+    rest_config.url = "https://example.com".to_string();
+    assert!(WebSocketOracleSource::new(rest_config, None, cache, Duration::from_secs(60)).is_err());
+}
@@ -38,6 +38,11 @@ fn create_js_transaction() -> Transaction {
         websocket_endpoint: None,
         websocket_message: None,
         time_condition: None,
+        critical: false,
+        client_request_id: None,
+        valid_until: None,
+        coin_type: None,
+        decimals: None,
         language: Some("javascript".to_string()),
     }
 }
@@ -68,6 +73,11 @@ result = {
         websocket_endpoint: None,
         websocket_message: None,
         time_condition: None,
+        critical: false,
+        client_request_id: None,
+        valid_until: None,
+        coin_type: None,
+        decimals: None,
         language: Some("python".to_string()),
     }
 }
@@ -157,4 +167,349 @@ async fn test_python_transaction_performance() -> Result<()> {
     metrics_storage.print_metrics_summary();
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_byzantine_resilience_table_sorted_by_percentage() {
+    use crate::metrics::performance::ComponentBenchmark;
+
+    let storage = MetricsStorage::new();
+    for (pct, rate, not_enough) in [(0.4, "0.200", "8"), (0.0, "1.000", "0")] {
+        let mut benchmark = ComponentBenchmark::new("byzantine_resilience_n5", "test", 10);
+        benchmark.add_config("byzantine_percentage", &pct.to_string());
+        benchmark.add_config("verification_success_rate", rate);
+        benchmark.add_config("failure_reason_not_enough_signatures", not_enough);
+        benchmark.record_operation("total_iteration", 120);
+        storage.add_benchmark(benchmark);
+    }
+    storage.add_benchmark(ComponentBenchmark::new("end_to_end_performance_n5", "0_percent_byzantine", 10));
+
+    let table = storage.byzantine_resilience_table();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 4, "header, separator and one row per percentage:\n{}", table);
+    assert!(lines[0].contains("not_enough_signatures"));
+    assert!(lines[2].contains("0.0%") && lines[2].contains("100.0%"));
+    assert!(lines[3].contains("40.0%") && lines[3].contains("20.0%"));
+    assert!(lines.iter().all(|l| l.len() == lines[0].len()), "columns must be aligned:\n{}", table);
+}
+
+#[test]
+fn test_check_resilience_reports_regressions_and_missing_scenarios() {
+    use crate::metrics::performance::ComponentBenchmark;
+    use crate::tools::benchmark_suite::check_resilience;
+
+    let storage = MetricsStorage::new();
+    for (pct, rate) in [(0.0, "1.000"), (0.33, "0.700")] {
+        let mut benchmark = ComponentBenchmark::new("byzantine_resilience_n5", "test", 10);
+        benchmark.add_config("byzantine_percentage", &pct.to_string());
+        benchmark.add_config("verification_success_rate", rate);
+        storage.add_benchmark(benchmark);
+    }
+
+    let violations = check_resilience(&storage, &[(0.0, 0.95), (0.33, 0.9), (0.5, 0.1)]);
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0].byzantine_percentage, 0.33);
+    assert_eq!(violations[0].actual_success_rate, Some(0.7));
+    assert_eq!(violations[1].byzantine_percentage, 0.5);
+    assert_eq!(violations[1].actual_success_rate, None);
+
+    assert!(check_resilience(&storage, &[(0.33, 0.7)]).is_empty());
+}
+
+#[test]
+fn test_gas_usage_accumulates_per_scenario_and_total() {
+    use crate::metrics::performance::ComponentBenchmark;
+    use crate::tools::benchmark_suite::GasUsage;
+
+    let mut e2e = GasUsage::default();
+    for mist in [2_000_000, 3_000_000, 1_000_000] {
+        e2e.record(mist);
+    }
+    assert_eq!(e2e.transactions, 3);
+    assert_eq!(e2e.total_mist, 6_000_000);
+    assert_eq!(e2e.max_mist, 3_000_000);
+    assert_eq!(e2e.average_mist(), 2_000_000.0);
+
+    // Storage rebates can make a transaction net negative
+    let mut byzantine = GasUsage::default();
+    byzantine.record(-500_000);
+    assert_eq!(byzantine.max_mist, -500_000);
+
+    let mut total = GasUsage::default();
+    total.merge(&e2e);
+    total.merge(&byzantine);
+    total.merge(&GasUsage::default());
+    assert_eq!(total.transactions, 4);
+    assert_eq!(total.total_mist, 5_500_000);
+    assert_eq!(total.max_mist, 3_000_000);
+    assert!((total.total_sui() - 0.0055).abs() < 1e-12);
+
+    let mut benchmark = ComponentBenchmark::new("end_to_end_performance_n5", "test", 3);
+    e2e.add_to_config(&mut benchmark);
+    assert_eq!(benchmark.configuration["gas_total_mist"], "6000000");
+    assert_eq!(benchmark.configuration["gas_avg_mist"], "2000000");
+    assert_eq!(benchmark.configuration["gas_max_mist"], "3000000");
+    assert_eq!(e2e.summary_line(), "3 txs, total 0.006000 SUI (avg 2000000 MIST, max 3000000 MIST)");
+}
+
+#[test]
+fn test_shortfall_submissions_record_contract_enforcement() {
+    use crate::metrics::performance::ComponentBenchmark;
+    use crate::tools::benchmark_suite::ShortfallSubmissions;
+
+    let mut shortfall = ShortfallSubmissions::default();
+    shortfall.record_execution(false, 1_000_000);
+    shortfall.record_execution(false, 1_400_000);
+    shortfall.record_inconclusive();
+    assert_eq!(shortfall.submitted, 3);
+    assert_eq!(shortfall.rejected, 2);
+    assert_eq!(shortfall.gas.transactions, 2);
+    assert!(shortfall.enforced());
+
+    shortfall.record_execution(true, 2_000_000);
+    assert!(!shortfall.enforced());
+
+    let mut benchmark = ComponentBenchmark::new("byzantine_resilience_n5_50pct", "test", 4);
+    shortfall.add_to_config(&mut benchmark);
+    assert_eq!(benchmark.configuration["below_threshold_submissions"], "4");
+    assert_eq!(benchmark.configuration["below_threshold_accepted"], "1");
+    assert_eq!(benchmark.configuration["below_threshold_inconclusive"], "1");
+    assert_eq!(benchmark.configuration["below_threshold_gas_max_mist"], "2000000");
+    assert_eq!(shortfall.summary_line(), "4 submitted: 2 rejected, 1 accepted, 1 inconclusive (avg 1466667 MIST)");
+}
+
+#[test]
+fn test_resume_keeps_completed_scenarios_from_checkpoint() {
+    use crate::metrics::performance::ComponentBenchmark;
+    use crate::tools::benchmark_suite::{
+        byzantine_scenario_name, completed_benchmarks, GasUsage, BENCHMARK_ITERATIONS, END_TO_END_SCENARIO,
+    };
+
+    let storage = MetricsStorage::new();
+    let mut e2e = ComponentBenchmark::new(END_TO_END_SCENARIO, "0_percent_byzantine", BENCHMARK_ITERATIONS as u32);
+    e2e.record_operation("total_iteration", 120);
+    let mut gas = GasUsage::default();
+    gas.record(2_000_000);
+    gas.record(3_000_000);
+    gas.add_to_config(&mut e2e);
+    storage.add_benchmark(e2e.end().clone());
+    // Left by a run with a different iteration count
+    let mut short = ComponentBenchmark::new(&byzantine_scenario_name(0.33), "33.0%_byzantine", 10);
+    storage.add_benchmark(short.end().clone());
+
+    let path = std::env::temp_dir().join(format!("suimodular_benchmark_checkpoint_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "[").unwrap();
+    // Replaces the old file whole, through a temporary copy
+    storage.save_benchmarks_to_json_file(path).unwrap();
+    assert!(!std::path::Path::new(&format!("{}.tmp", path)).exists());
+    let restored = MetricsStorage::read_benchmarks_from_json_file(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(restored.len(), 2);
+
+    let completed = completed_benchmarks(restored);
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].component_name, END_TO_END_SCENARIO);
+    assert_eq!(completed[0].get_operation_stats("total_iteration").unwrap().total_duration_ms, 120);
+    assert_eq!(GasUsage::from_config(&completed[0]), gas);
+    assert_eq!(byzantine_scenario_name(0.33), "byzantine_resilience_n5_33pct");
+}
+
+#[test]
+fn test_progress_event_serializes_as_one_json_line() {
+    use crate::tools::benchmark_suite::ProgressEvent;
+    use std::time::Instant;
+
+    let event = ProgressEvent::new("byzantine_resilience_n5_33pct", 50, 100, 40, Instant::now());
+    let line = serde_json::to_string(&event).unwrap();
+    assert!(!line.contains('\n'));
+
+    let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(parsed["scenario"], "byzantine_resilience_n5_33pct");
+    assert_eq!(parsed["iteration"], 50);
+    assert_eq!(parsed["total"], 100);
+    assert_eq!(parsed["success_rate"], 0.8);
+    assert!(parsed["elapsed_ms"].is_u64());
+
+    // Nothing completed yet: no division by zero
+    assert_eq!(ProgressEvent::new("end_to_end_performance_n5", 0, 100, 0, Instant::now()).success_rate, 0.0);
+}
+
+#[test]
+fn test_execution_trace_exports_chrome_events() {
+    use crate::metrics::trace::ExecutionTrace;
+
+    let trace = ExecutionTrace::new("tx-1");
+    {
+        let _outer = trace.span("submission");
+        let _inner = trace.span("signing");
+    }
+    let open = trace.start_span("confirmation");
+
+    let spans = trace.spans();
+    assert_eq!(spans.len(), 3);
+    assert!(spans[0].start_us <= spans[1].start_us);
+    assert!(spans[0].duration_us.unwrap() >= spans[1].duration_us.unwrap());
+    assert!(spans[2].duration_us.is_none());
+
+    let events = trace.to_chrome_trace()["traceEvents"].as_array().unwrap().clone();
+    assert_eq!(events[0]["ph"], "X");
+    assert_eq!(events[0]["cat"], "tx-1");
+    assert!(events[0]["dur"].is_u64());
+    assert_eq!(events[2]["ph"], "B");
+
+    trace.end_span(open);
+    assert_eq!(trace.to_chrome_trace()["traceEvents"][2]["ph"], "X");
+}
+
+#[test]
+fn test_snapshot_returns_only_new_operations() {
+    use crate::metrics::performance::ComponentBenchmark;
+
+    let mut benchmark = ComponentBenchmark::new("server", "0_percent_byzantine", 0);
+    benchmark.record_operation("quorum_signing", 10).record_operation("quorum_signing", 30);
+    let first = benchmark.snapshot();
+    assert_eq!(first["quorum_signing"].count, 2);
+    assert!(benchmark.snapshot().is_empty());
+
+    benchmark.record_operation("quorum_signing", 50).record_operation("l1_submission", 5);
+    let second = benchmark.snapshot();
+    assert_eq!(second["quorum_signing"].count, 1);
+    assert_eq!(second["quorum_signing"].min_duration_ms, 50);
+    assert_eq!(second["l1_submission"].count, 1);
+    // Cumulative stats are untouched
+    assert_eq!(benchmark.get_operation_stats("quorum_signing").unwrap().count, 3);
+}
+
+#[test]
+fn test_middleware_overhead_splits_time_between_middleware_and_l1() {
+    use crate::metrics::performance::ComponentBenchmark;
+
+    let mut benchmark = ComponentBenchmark::new("end_to_end_performance_n5", "0_percent_byzantine", 2);
+    assert!(benchmark.middleware_overhead().is_none());
+    // Operations outside both groups don't count
+    benchmark.record_operation("total_iteration", 1000);
+    assert!(benchmark.middleware_overhead().is_none());
+
+    benchmark
+        .record_operation("middleware_processing_and_prep", 20)
+        .record_operation("quorum_signing", 10)
+        .record_operation("quorum_signing", 30)
+        .record_operation("l1_submission", 100)
+        .record_operation("l1_confirmation", 40);
+    let overhead = benchmark.middleware_overhead().unwrap();
+    assert_eq!(overhead.middleware_ms, 60);
+    assert_eq!(overhead.l1_ms, 140);
+    assert!((overhead.middleware_percent - 30.0).abs() < 1e-9);
+    assert_eq!(benchmark.to_json()["middleware_overhead"]["middleware_ms"], 60);
+
+    // Time spent only in the middleware is all overhead
+    let mut offline = ComponentBenchmark::new("middleware_only", "0_percent_byzantine", 1);
+    offline.record_operation("quorum_signing", 5);
+    assert_eq!(offline.middleware_overhead().unwrap().middleware_percent, 100.0);
+}
+
+#[test]
+fn test_operation_samples_are_capped() {
+    use crate::metrics::performance::{ComponentBenchmark, OperationStats, MAX_SAMPLES};
+
+    let mut benchmark = ComponentBenchmark::new("soak", "0_percent_byzantine", 0);
+    for i in 0..MAX_SAMPLES as u64 + 500 {
+        benchmark.record_operation("quorum_signing", i);
+    }
+    let stats = benchmark.get_operation_stats("quorum_signing").unwrap();
+    assert_eq!(stats.count as usize, MAX_SAMPLES + 500);
+    assert_eq!(stats.samples_ms.len(), MAX_SAMPLES);
+    // Aggregates still cover every execution; percentiles only the retained ones
+    assert_eq!(stats.min_duration_ms, 0);
+    assert_eq!(stats.percentile(0.0), Some(500));
+
+    let mut merged = OperationStats::default();
+    merged.merge(stats);
+    merged.merge(stats);
+    assert_eq!(merged.samples_ms.len(), MAX_SAMPLES);
+
+    // A snapshot after more than the cap only sees what was retained
+    assert_eq!(benchmark.snapshot()["quorum_signing"].count as usize, MAX_SAMPLES);
+    benchmark.record_operation("quorum_signing", 7);
+    assert_eq!(benchmark.snapshot()["quorum_signing"].count, 1);
+}
+
+#[test]
+fn test_windowed_stats_forget_old_buckets() {
+    use crate::metrics::performance::WindowedStats;
+    use crate::metrics::prometheus::render_operation_window;
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let window = WindowedStats::new(Duration::from_secs(1), 60);
+    assert_eq!(window.capacity(), Duration::from_secs(60));
+    for second in 0..10 {
+        window.record_at("l1_submission", 100, start + Duration::from_secs(second));
+    }
+
+    let minute = Duration::from_secs(60);
+    let now = start + Duration::from_secs(9);
+    assert_eq!(window.stats_at(minute, now)["l1_submission"].count, 10);
+    assert_eq!(window.stats_at(Duration::from_secs(5), now)["l1_submission"].count, 5);
+    assert_eq!(window.rate_at("l1_submission", Duration::from_secs(5), now), 1.0);
+
+    // Busy an hour ago says nothing about now
+    let later = start + Duration::from_secs(3600);
+    window.record_at("l1_submission", 900, later);
+    let recent = window.stats_at(minute, later);
+    assert_eq!(recent["l1_submission"].count, 1);
+    assert_eq!(recent["l1_submission"].max_duration_ms, 900);
+    // Too old for the ring once it has moved on
+    window.record_at("l1_submission", 100, start);
+    assert_eq!(window.stats_at(minute, later)["l1_submission"].count, 1);
+
+    let live = WindowedStats::default();
+    live.record("quorum_signing", 40);
+    let exposition = render_operation_window(&live, minute);
+    assert!(exposition.contains("# TYPE middleware_operation_rate gauge\n"));
+    assert!(exposition.contains("middleware_operation_avg_ms{operation=\"quorum_signing\"} 40\n"));
+}
+
+#[test]
+fn test_sla_budgets_checked_against_percentiles() {
+    use crate::metrics::performance::{ComponentBenchmark, OperationStats};
+    use crate::tools::benchmark_suite::{check_sla, parse_sla, sla_table, SlaBudget, END_TO_END_SCENARIO};
+
+    let mut stats = OperationStats::default();
+    for ms in 1..=100 {
+        stats.record(ms);
+    }
+    assert_eq!(stats.p95(), Some(95));
+    assert_eq!(stats.p99(), Some(99));
+    assert_eq!(stats.percentile(100.0), Some(100));
+    assert_eq!(OperationStats::default().p95(), None);
+
+    let budgets = parse_sla("total_iteration_p95=2000, l1_confirmation_p99.9=3000").unwrap();
+    assert_eq!(budgets[0], SlaBudget { operation: "total_iteration".to_string(), percentile: 95.0, budget_ms: 2000 });
+    assert_eq!(budgets[1].operation, "l1_confirmation");
+    assert_eq!(budgets[1].percentile, 99.9);
+    for bad in ["total_iteration=2000", "total_iteration_p95", "total_iteration_p0=5", "total_iteration_p95=fast", ""] {
+        assert!(parse_sla(bad).is_err(), "accepted '{}'", bad);
+    }
+
+    let storage = MetricsStorage::new();
+    let mut e2e = ComponentBenchmark::new(END_TO_END_SCENARIO, "0_percent_byzantine", 20);
+    for i in 0..20 {
+        // One slow iteration in twenty: p95 is fast, p99 catches it
+        e2e.record_operation("total_iteration", if i == 7 { 5000 } else { 1000 });
+    }
+    storage.add_benchmark(e2e);
+
+    let checks = check_sla(&storage, END_TO_END_SCENARIO, &parse_sla("total_iteration_p95=2000,total_iteration_p99=2000,l1_submission_p50=10").unwrap());
+    assert_eq!(checks[0].actual_ms, Some(1000));
+    assert!(checks[0].passed());
+    assert_eq!(checks[1].actual_ms, Some(5000));
+    assert!(!checks[1].passed());
+    assert_eq!(checks[2].actual_ms, None);
+    assert!(!checks[2].passed());
+
+    let table = sla_table(&checks);
+    assert_eq!(table.lines().count(), 4);
+    assert_eq!(table.matches("FAIL").count(), 2);
+}
@@ -0,0 +1,481 @@
+use crate::config::{SUBMITTER_ADDRESS, SUBMITTER_GAS_OBJECT_ID};
+use crate::execution::degradation::DegradationLevel;
+use crate::external::flight_api::{AviationStackApi, FlightStatus};
+use crate::external::oracle::OracleSourceStatus;
+use crate::sui::network::NodeStatus;
+use crate::testkit::MockPipelineBuilder;
+use crate::sui::verification::VerificationStatus;
+use crate::transaction::amount::{SUI_COIN_TYPE, SUI_DECIMALS};
+use crate::transaction::tracker::LifecycleState;
+use crate::transaction::types::{ExternalQuery, MiddlewareAttestation, QueryCondition, Transaction, TransactionType};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const SENDER: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+const RECEIVER: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+const FLIGHT_URL: &str = "https://example.com/flights/BA117";
+const AVIATIONSTACK_URL: &str = "https://example.com/v1/flights?flight_iata=LH987";
+
+fn delay_payout(threshold_minutes: u64) -> Transaction {
+    let tx_type = TransactionType::ConditionalPayout {
+        oracle_query: ExternalQuery {
+            url: FLIGHT_URL.to_string(),
+            path: vec!["flight".to_string(), "delay_minutes".to_string()],
+            condition: None,
+        },
+        condition: QueryCondition { threshold: threshold_minutes, operator: "gt".to_string() },
+        payout_amount: 500,
+    };
+    Transaction::new(tx_type, SENDER.to_string(), RECEIVER.to_string(), 0, "0x5".to_string(), 1000, vec![])
+}
+
+#[tokio::test]
+async fn test_conditional_payout_runs_end_to_end() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+
+    let digest = pipeline.run(&tx).await.unwrap().expect("payout should be submitted");
+
+    assert_eq!(pipeline.oracle.requests(), vec![json!({ "url": FLIGHT_URL })]);
+    let submitted = pipeline.sui_client.submitted();
+    assert_eq!(submitted.len(), 1);
+    assert_eq!(submitted[0].digest, digest);
+    assert_eq!(submitted[0].status, VerificationStatus::Verified);
+    assert_eq!(submitted[0].input.quorum_signatures.len(), pipeline.quorum.get_threshold());
+    assert_eq!(pipeline.state_of(&tx), Some(LifecycleState::Confirmed));
+}
+
+#[tokio::test]
+async fn test_conditional_payout_not_submitted_when_condition_fails() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 15 } })])
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+
+    assert_eq!(pipeline.run(&tx).await.unwrap(), None);
+    assert!(pipeline.sui_client.submitted().is_empty());
+    assert_eq!(pipeline.state_of(&tx), Some(LifecycleState::OracleResolved));
+}
+
+#[tokio::test]
+async fn test_all_byzantine_quorum_fails_pipeline() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .with_byzantine_percentage(1.0)
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+
+    assert!(pipeline.run(&tx).await.is_err());
+    assert!(matches!(pipeline.state_of(&tx), Some(LifecycleState::Failed(_))));
+    assert!(pipeline.sui_client.submitted().iter().all(|l1_tx| l1_tx.status != VerificationStatus::Verified));
+}
+
+#[tokio::test]
+async fn test_pipeline_records_trace_for_each_stage() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+    pipeline.run(&tx).await.unwrap();
+
+    let trace = pipeline.execution_manager.trace(&tx).expect("trace should be recorded");
+    let names: Vec<String> = trace.spans().into_iter().map(|s| s.name).collect();
+    assert_eq!(names, vec!["validation", "oracle_fetch", "quorum_signing", "l1_submission", "l1_confirmation"]);
+    assert!(trace.spans().iter().all(|s| s.duration_us.is_some()));
+
+    let stats = trace.operation_stats();
+    assert_eq!(stats.len(), 5);
+    assert!(stats.values().all(|s| s.count == 1));
+}
+
+#[test]
+fn test_traces_are_bounded_and_saved_when_finished() {
+    use crate::execution::manager::ExecutionManager;
+
+    let dir = std::env::temp_dir().join(format!("suimodular_traces_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let execution_manager = ExecutionManager::new(None, None, None).with_trace_capacity(2).with_trace_dir(&dir);
+    let txs: Vec<Transaction> = [60, 90, 120].into_iter().map(delay_payout).collect();
+    for tx in &txs {
+        drop(execution_manager.trace_for(tx).span("validation"));
+    }
+
+    // Only the two most recent stay in memory
+    assert!(execution_manager.trace(&txs[0]).is_none());
+    let id = txs[2].payload_hash().unwrap();
+    assert_eq!(execution_manager.trace_by_id(&id).unwrap().name(), id);
+
+    let path = dir.join(format!("{}.trace.json", id));
+    execution_manager.track(&txs[2], LifecycleState::Submitted);
+    assert!(!path.exists());
+    execution_manager.track(&txs[2], LifecycleState::Confirmed);
+    let saved: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(saved["traceEvents"][0]["name"], "validation");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_expired_transaction_is_rejected_before_signing() {
+    use crate::transaction::types::Expired;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .build()
+        .unwrap();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+    let mut stale = delay_payout(120);
+    stale.valid_until = Some(now - 60);
+    let err = pipeline.run(&stale).await.unwrap_err();
+    assert!(err.downcast_ref::<Expired>().is_some());
+    assert!(pipeline.oracle.requests().is_empty());
+    assert!(pipeline.sui_client.submitted().is_empty());
+    assert!(matches!(pipeline.state_of(&stale), Some(LifecycleState::Failed(_))));
+
+    let mut fresh = delay_payout(120);
+    fresh.valid_until = Some(now + 3600);
+    assert!(pipeline.run(&fresh).await.unwrap().is_some());
+    assert_eq!(pipeline.sui_client.submitted().len(), 1);
+}
+
+#[tokio::test]
+async fn test_prepare_attestation_returns_attested_outcome() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+
+    let (input, outcome) = pipeline.execution_manager.prepare_attestation(&tx).await.unwrap().unwrap();
+    assert!(!input.attestation_payload.is_empty());
+    assert_eq!(outcome["oracle_value"], json!(180.0));
+    assert_eq!(outcome["payout_amount"], json!(500));
+}
+
+#[tokio::test]
+async fn test_process_and_submit_verification_reports_what_happened() {
+    use crate::config::generate_test_sui_keypair;
+    use crate::testkit::MockPipeline;
+    use crate::transaction::utils::{process_and_submit_verification, VerificationOutcome};
+    use sui_sdk::types::base_types::ObjectID;
+
+    async fn run(pipeline: &MockPipeline, tx: &Transaction) -> VerificationOutcome {
+        process_and_submit_verification(
+            tx,
+            "delay_payout",
+            pipeline,
+            &pipeline.execution_manager,
+            None,
+            &pipeline.audit_log,
+            &generate_test_sui_keypair().unwrap(),
+            &ObjectID::from_hex_literal("0x5").unwrap(),
+        ).await.unwrap()
+    }
+
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![
+            json!({ "flight": { "delay_minutes": 180 } }),
+            json!({ "flight": { "delay_minutes": 15 } }),
+        ])
+        .build()
+        .unwrap();
+
+    let tx = delay_payout(120);
+    let outcome = run(&pipeline, &tx).await;
+    assert!(outcome.decision);
+    let submitted = pipeline.sui_client.submitted();
+    assert_eq!(submitted.len(), 1);
+    assert_eq!(outcome.l1_digest.as_deref(), Some(submitted[0].digest.as_str()));
+    assert_eq!(outcome.verification_status, VerificationStatus::Verified);
+    let attested = outcome.outcome.expect("attested outcome");
+    assert_eq!(attested["oracle_value"], json!(180.0));
+    assert_eq!(attested["payout_amount"], json!(500));
+    assert_eq!(outcome.oracle_values.len(), 1);
+    assert_eq!(outcome.oracle_values[0].url, FLIGHT_URL);
+    assert_eq!(outcome.oracle_values[0].value, 180.0);
+    // One span per stage: validation, oracle fetch, signing, submission, confirmation
+    let stages = pipeline.execution_manager.trace(&tx).unwrap().operation_stats();
+    assert_eq!(outcome.timings.count, 5);
+    assert_eq!(outcome.timings.total_duration_ms, stages.values().map(|s| s.total_duration_ms).sum::<u64>());
+    assert_eq!(pipeline.state_of(&tx), Some(LifecycleState::Confirmed));
+
+    // Condition not met: the reading is still reported, nothing is submitted
+    let tx = delay_payout(60);
+    let outcome = run(&pipeline, &tx).await;
+    assert!(!outcome.decision);
+    assert!(outcome.l1_digest.is_none());
+    assert!(outcome.outcome.is_none());
+    assert_eq!(outcome.oracle_values.len(), 1);
+    assert_eq!(outcome.oracle_values[0].value, 15.0);
+    assert_eq!(outcome.timings.count, 2);
+    assert_eq!(pipeline.sui_client.submitted().len(), 1);
+}
+
+#[tokio::test]
+async fn test_attested_outcome_is_the_script_result() {
+    use crate::languages::javascript::JavaScriptExecutor;
+
+    let pipeline = MockPipelineBuilder::new().build().unwrap();
+    let mut tx = Transaction::new(TransactionType::Transfer, SENDER.to_string(), RECEIVER.to_string(), 100, "0x5".to_string(), 1000, vec![]);
+    tx.language = Some("javascript".to_string());
+    tx.script = Some("6 * 7".to_string());
+
+    let (input, outcome) = pipeline.execution_manager.prepare_attestation(&tx).await.unwrap().unwrap();
+    assert_eq!(outcome, JavaScriptExecutor::execute("6 * 7", None).unwrap().output);
+    assert_eq!(outcome, json!(42));
+    assert!(!input.attestation_payload.is_empty());
+}
+
+#[tokio::test]
+async fn test_simulation_previews_decision_without_side_effects() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![
+            json!({ "flight": { "delay_minutes": 180 } }),
+            json!({ "flight": { "delay_minutes": 15 } }),
+        ])
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+
+    let result = pipeline.execution_manager.simulate(&tx).await.unwrap();
+    assert!(result.would_attest);
+    assert_eq!(result.oracle_values.len(), 1);
+    assert_eq!(result.oracle_values[0].url, FLIGHT_URL);
+    assert_eq!(result.oracle_values[0].value, 180.0);
+    assert_eq!(result.outcome.as_ref().unwrap()["payout_amount"], 500);
+    assert!(result.verification_input.is_some());
+    assert_eq!(result.gas_budget, tx.gas_budget);
+
+    let result = pipeline.execution_manager.simulate(&tx).await.unwrap();
+    assert!(!result.would_attest);
+    assert!(result.skip_reason.unwrap().contains("15"));
+
+    // Nothing was tracked, traced or submitted
+    assert_eq!(pipeline.state_of(&tx), None);
+    assert!(pipeline.execution_manager.trace(&tx).is_none());
+    assert!(pipeline.sui_client.submitted().is_empty());
+}
+
+#[tokio::test]
+async fn test_simulation_and_processing_reach_the_same_outcome() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![
+            json!({ "flight": { "delay_minutes": 180 } }),
+            json!({ "flight": { "delay_minutes": 180 } }),
+        ])
+        .build()
+        .unwrap();
+    let mut script = Transaction::new(TransactionType::Transfer, SENDER.to_string(), RECEIVER.to_string(), 100, "0x5".to_string(), 1000, vec![]);
+    script.language = Some("javascript".to_string());
+    script.script = Some("6 * 7".to_string());
+
+    for tx in [delay_payout(120), script] {
+        let simulated = pipeline.execution_manager.simulate(&tx).await.unwrap();
+        let (_, processed) = pipeline.execution_manager.prepare_attestation(&tx).await.unwrap().unwrap();
+        assert!(simulated.would_attest);
+        assert_eq!(simulated.outcome, Some(processed));
+    }
+}
+
+#[test]
+fn test_degradation_level_from_subsystem_health() {
+    assert_eq!(DegradationLevel::assess(None, None), DegradationLevel::Full);
+    assert_eq!(DegradationLevel::assess(Some(&OracleSourceStatus::Operational), None), DegradationLevel::Full);
+
+    let oracle_down = DegradationLevel::assess(Some(&OracleSourceStatus::Failed("timeout".to_string())), None);
+    assert!(matches!(&oracle_down, DegradationLevel::Degraded { reason } if reason.contains("timeout")));
+    assert!(oracle_down.allows_attestation());
+    assert!(!oracle_down.allows_optional_work());
+
+    let mut rpc = HashMap::new();
+    rpc.insert("primary".to_string(), NodeStatus::Healthy);
+    rpc.insert("backup".to_string(), NodeStatus::Down("timeout".to_string()));
+    let partial = DegradationLevel::assess(None, Some(&rpc));
+    assert!(matches!(&partial, DegradationLevel::Degraded { reason } if reason.contains("1 of 2")));
+
+    rpc.insert("primary".to_string(), NodeStatus::Down("timeout".to_string()));
+    let offline = DegradationLevel::assess(Some(&OracleSourceStatus::Operational), Some(&rpc));
+    assert!(matches!(offline, DegradationLevel::ReadOnly { .. }));
+    assert!(!offline.allows_attestation());
+}
+
+#[tokio::test]
+async fn test_read_only_refuses_attestation_but_simulates() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+    pipeline.execution_manager.set_degradation(DegradationLevel::ReadOnly { reason: "no healthy RPC endpoint".to_string() });
+
+    let err = pipeline.execution_manager.prepare_attestation(&tx).await.unwrap_err();
+    assert!(err.to_string().contains("read-only"));
+
+    let result = pipeline.execution_manager.simulate(&tx).await.unwrap();
+    assert!(!result.would_attest);
+    assert!(result.skip_reason.unwrap().contains("read-only"));
+    assert!(pipeline.sui_client.submitted().is_empty());
+}
+
+#[tokio::test]
+async fn test_degraded_attestation_is_flagged() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .build()
+        .unwrap();
+    let tx = delay_payout(120);
+    pipeline.oracle.set_status(OracleSourceStatus::Degraded("slow responses".to_string()));
+
+    let level = pipeline.execution_manager.assess_degradation().await;
+    assert!(matches!(level, DegradationLevel::Degraded { .. }));
+    assert_eq!(pipeline.execution_manager.current_degradation(), level);
+
+    let (_, outcome) = pipeline.execution_manager.prepare_attestation(&tx).await.unwrap().unwrap();
+    assert_eq!(outcome["payout_amount"], json!(500));
+    assert!(outcome["degraded"].as_str().unwrap().contains("slow responses"));
+}
+
+#[tokio::test]
+async fn test_failing_oracle_lowers_the_level_in_the_background() {
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![json!({ "flight": { "delay_minutes": 180 } })])
+        .build()
+        .unwrap();
+    let monitor = pipeline.execution_manager.spawn_degradation_monitor(std::time::Duration::from_millis(10));
+    let level_once = |wanted: fn(&DegradationLevel) -> bool| {
+        let execution_manager = pipeline.execution_manager.clone();
+        async move {
+            for _ in 0..100 {
+                let level = execution_manager.current_degradation();
+                if wanted(&level) {
+                    return level;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            execution_manager.current_degradation()
+        }
+    };
+
+    pipeline.oracle.set_status(OracleSourceStatus::Failed("connection refused".to_string()));
+    let level = level_once(|level| *level != DegradationLevel::Full).await;
+    assert!(matches!(&level, DegradationLevel::Degraded { reason } if reason.contains("connection refused")));
+
+    // Attestations made from here on carry the reason
+    let (_, outcome) = pipeline.execution_manager.prepare_attestation(&delay_payout(120)).await.unwrap().unwrap();
+    assert!(outcome["degraded"].as_str().unwrap().contains("connection refused"));
+
+    pipeline.oracle.set_status(OracleSourceStatus::Operational);
+    assert_eq!(level_once(|level| *level == DegradationLevel::Full).await, DegradationLevel::Full);
+    monitor.abort();
+}
+
+/// AviationStack `/flights` response for LH987 with the given status and arrival delay.
+fn aviationstack_response(flight_status: &str, delay_minutes: i64) -> Value {
+    json!({
+        "data": [{
+            "flight_status": flight_status,
+            "flight": { "iata": "LH987" },
+            "departure": { "scheduled": "2024-05-01T10:00:00+00:00" },
+            "arrival": { "scheduled": "2024-05-01T12:00:00+00:00", "delay": delay_minutes },
+        }]
+    })
+}
+
+fn parse_flight(response: &Value) -> FlightStatus {
+    AviationStackApi::new(String::new()).parse_flight_data(&response["data"][0], "LH987").unwrap()
+}
+
+/// Conditional payout of the premium compensation for `flight_status`, or `None` when nothing is owed.
+///
+/// The middleware re-reads the arrival delay and only attests if the flight is
+/// still at least 30 minutes late. The query URL carries no access key; a real
+/// AviationStack source adds it from its own params, as `create_flight_api_source` does.
+fn build_claim_transaction(flight_status: &FlightStatus) -> Option<Transaction> {
+    let compensation = flight_status.get_compensation_amount("premium");
+    if compensation == 0 {
+        return None;
+    }
+    let tx_type = TransactionType::ConditionalPayout {
+        oracle_query: ExternalQuery {
+            url: AVIATIONSTACK_URL.to_string(),
+            path: ["data", "0", "arrival", "delay"].iter().map(|s| s.to_string()).collect(),
+            condition: None,
+        },
+        condition: QueryCondition { threshold: 29, operator: "gt".to_string() },
+        payout_amount: compensation,
+    };
+    let claim = Transaction::builder(tx_type)
+        .sender(SUBMITTER_ADDRESS)
+        .receiver(SUBMITTER_ADDRESS)
+        .amount(compensation)
+        .gas_payment(SUBMITTER_GAS_OBJECT_ID)
+        .command("process_claim_enhanced")
+        .params(json!({ "policy_id": "POLICY_1", "flight_number": flight_status.flight_number }))
+        .build()
+        .unwrap();
+    Some(claim)
+}
+
+#[tokio::test]
+async fn test_flight_insurance_delayed_flight_pays_out() {
+    let response = aviationstack_response("active", 240);
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![response.clone()])
+        .build()
+        .unwrap();
+
+    let flight_status = parse_flight(&response);
+    assert_eq!(flight_status.delay_minutes, 240);
+    let claim = build_claim_transaction(&flight_status)
+        .expect("a 4 hour delay is compensated");
+    assert_eq!(claim.amount, 600);
+
+    pipeline.run(&claim).await.unwrap().expect("claim should be submitted");
+    assert_eq!(pipeline.oracle.requests(), vec![json!({ "url": AVIATIONSTACK_URL })]);
+    let submitted = pipeline.sui_client.submitted();
+    assert_eq!(submitted.len(), 1);
+    assert_eq!(submitted[0].status, VerificationStatus::Verified);
+
+    let payload = &submitted[0].input.attestation_payload;
+    let mut expected = MiddlewareAttestation::new(
+        claim.digest().unwrap().to_vec(),
+        json!({
+            "condition_met": true,
+            "oracle_value": 240.0,
+            "payout_amount": 600,
+            "coin_type": SUI_COIN_TYPE,
+            "decimals": SUI_DECIMALS,
+            "recipient": SUBMITTER_ADDRESS,
+        }),
+    );
+    // BCS puts the timestamp just before the two unset options, take it from the payload
+    expected.timestamp = u64::from_le_bytes(payload[payload.len() - 10..payload.len() - 2].try_into().unwrap());
+    assert_eq!(&payload[payload.len() - 2..], &[0, 0]);
+    assert_eq!(payload, &expected.to_bytes_for_signing().unwrap());
+}
+
+#[tokio::test]
+async fn test_flight_insurance_on_time_flight_pays_nothing() {
+    let response = aviationstack_response("landed", 0);
+    let pipeline = MockPipelineBuilder::new()
+        .with_oracle_values(vec![response.clone()])
+        .build()
+        .unwrap();
+
+    assert!(build_claim_transaction(&parse_flight(&response)).is_none());
+
+    // A claim built from a stale delayed status is rejected once the oracle reports on time
+    let stale = parse_flight(&aviationstack_response("active", 240));
+    let claim = build_claim_transaction(&stale).unwrap();
+    assert_eq!(pipeline.run(&claim).await.unwrap(), None);
+    assert!(pipeline.sui_client.submitted().is_empty());
+    assert_eq!(pipeline.state_of(&claim), Some(LifecycleState::OracleResolved));
+}
@@ -17,6 +17,14 @@ mod tests {
     use crate::config;
     use sui_sdk::types::base_types::ObjectID;
     use std::str::FromStr;
+    use crate::security::model::SecurityGuarantee;
+    use crate::security::alerts::{AlertSink, WebhookAlertSink};
+    use crate::security::property_tests::{arb_transaction, fuzz_transactions, INTEGRITY_INVARIANTS};
+    use crate::security::audit::{AuditEvent, AuditEventType, AuditLogConfig};
+    use crate::security::verification::{
+        self as formal, AggregationPolicy, BuiltinProver, FormalProperty, PropertyProver,
+        VerificationFrameworkBuilder, VerificationResult, VerificationTechnique,
+    };
     
     // Helper function to create a test transaction
     fn create_test_transaction() -> Transaction {
@@ -37,6 +45,11 @@ mod tests {
             websocket_endpoint: None,
             websocket_message: None,
             time_condition: None,
+            critical: false,
+            client_request_id: None,
+            valid_until: None,
+            coin_type: None,
+            decimals: None,
             language: None,
         }
     }
@@ -47,7 +60,7 @@ mod tests {
         let network_manager = Arc::new(NetworkManager::new(NetworkType::Testnet).await?);
         let rpc_url = network_manager.get_active_rpc_url().expect("Failed to get RPC URL");
         let verification_manager = Arc::new(VerificationManager::new(&rpc_url));
-        let byzantine_detector = Arc::new(ByzantineDetector::new(vec![], Some(security_audit_log.clone()), None, None));
+        let byzantine_detector = Arc::new(ByzantineDetector::new(vec![], Some(security_audit_log.clone()), None, None, None, None).unwrap());
         let node_keypair = generate_test_sui_keypair()?;
         let quorum_sim = Arc::new(QuorumSimulation::create_with_random_nodes(3)?);
         
@@ -289,4 +302,514 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Prover that verifies everything, standing in for an SMT backend
+    struct AlwaysVerifiedProver;
+
+    impl PropertyProver for AlwaysVerifiedProver {
+        fn name(&self) -> &str {
+            "SmtProver"
+        }
+
+        fn technique(&self) -> VerificationTechnique {
+            VerificationTechnique::Custom("SMT Solving".to_string())
+        }
+
+        fn supports_property(&self, _property: &FormalProperty) -> bool {
+            true
+        }
+
+        fn verify_property(&self, property: &FormalProperty, _context: &serde_json::Value) -> Result<VerificationResult> {
+            Ok(VerificationResult {
+                property: property.clone(),
+                status: formal::VerificationStatus::Verified,
+                evidence: Some(json!({ "technique": self.technique().to_string() })),
+                timestamp: 0,
+                duration_ms: 0,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verification_framework_builder_composes_provers() {
+        let preset = formal::create_verification_framework(None);
+        assert_eq!(preset.prover_names(), vec!["BasicModelChecker", "PropertyTester", "RuntimeVerifier"]);
+
+        let mut framework = VerificationFrameworkBuilder::new(None)
+            .with_builtins()
+            .without_prover(BuiltinProver::PropertyTesting.name())
+            .with_prover(Box::new(AlwaysVerifiedProver))
+            .build();
+        assert_eq!(framework.prover_names(), vec!["BasicModelChecker", "RuntimeVerifier", "SmtProver"]);
+
+        let results = framework.verify_property("integrity_verification", &json!({})).await.unwrap();
+        let smt = results.iter()
+            .find(|r| r.evidence.as_ref().and_then(|e| e.get("technique")) == Some(&json!("SMT Solving")))
+            .expect("custom prover should take part");
+        assert_eq!(smt.status, formal::VerificationStatus::Verified);
+
+        assert!(framework.remove_prover("SmtProver").is_some());
+        assert!(framework.remove_prover("SmtProver").is_none());
+        assert_eq!(framework.prover_names(), vec!["BasicModelChecker", "RuntimeVerifier"]);
+    }
+
+    /// Prover that takes longer than any reasonable bound
+    struct SlowProver;
+
+    impl PropertyProver for SlowProver {
+        fn name(&self) -> &str {
+            "SlowProver"
+        }
+
+        fn technique(&self) -> VerificationTechnique {
+            VerificationTechnique::ModelChecking
+        }
+
+        fn supports_property(&self, _property: &FormalProperty) -> bool {
+            true
+        }
+
+        fn verify_property(&self, property: &FormalProperty, context: &serde_json::Value) -> Result<VerificationResult> {
+            std::thread::sleep(std::time::Duration::from_millis(300));
+            AlwaysVerifiedProver.verify_property(property, context)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_prover_times_out_as_inconclusive() {
+        let framework = VerificationFrameworkBuilder::new(None)
+            .with_prover(Box::new(SlowProver))
+            .with_prover_timeout(std::time::Duration::from_millis(20))
+            .build();
+
+        let results = framework.verify_property("integrity_verification", &json!({})).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, formal::VerificationStatus::Inconclusive("timeout".to_string()));
+        assert_eq!(results[0].evidence.as_ref().unwrap()["prover"], "SlowProver");
+    }
+
+    #[tokio::test]
+    async fn test_aggregation_policy_resolves_disagreeing_provers() {
+        let framework = formal::create_verification_framework(None);
+        let mut results = framework.verify_property("integrity_verification", &json!({})).await.unwrap();
+        assert!(!results.is_empty());
+        let template = results.remove(0);
+        let result = |status: formal::VerificationStatus, technique: VerificationTechnique| VerificationResult {
+            status,
+            evidence: Some(json!({ "technique": technique.to_string() })),
+            ..template.clone()
+        };
+
+        // Property testing passes, model checking finds a counterexample
+        let disagreeing = vec![
+            result(formal::VerificationStatus::Verified, VerificationTechnique::PropertyTesting),
+            result(formal::VerificationStatus::Falsified("counterexample".to_string()), VerificationTechnique::ModelChecking),
+        ];
+        assert_eq!(AggregationPolicy::AnyVerified.aggregate(&disagreeing), formal::VerificationStatus::Verified);
+        assert_eq!(
+            AggregationPolicy::AllVerified.aggregate(&disagreeing),
+            formal::VerificationStatus::Falsified("counterexample".to_string())
+        );
+        assert!(matches!(
+            AggregationPolicy::MajorityVerified.aggregate(&disagreeing),
+            formal::VerificationStatus::Inconclusive(_)
+        ));
+        assert_eq!(
+            AggregationPolicy::default_technique_weights().aggregate(&disagreeing),
+            formal::VerificationStatus::Falsified("counterexample".to_string())
+        );
+        assert!(matches!(AggregationPolicy::AnyVerified.aggregate(&[]), formal::VerificationStatus::Inconclusive(_)));
+
+        let strict = VerificationFrameworkBuilder::new(None)
+            .with_builtins()
+            .with_aggregation_policy(AggregationPolicy::AllVerified)
+            .build();
+        let mut all_results = std::collections::HashMap::new();
+        for property in ["integrity_verification", "byzantine_detection", "external_data_validation"] {
+            all_results.insert(property.to_string(), disagreeing.clone());
+        }
+        assert!(framework.is_security_guarantee_verified(&SecurityGuarantee::VerifiedExecution, &all_results));
+        assert!(!strict.is_security_guarantee_verified(&SecurityGuarantee::VerifiedExecution, &all_results));
+    }
+
+    /// Sink that remembers what it was notified of
+    #[derive(Default)]
+    struct RecordingSink {
+        messages: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl AlertSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn notify(&self, event: &AuditEvent) {
+            self.messages.lock().unwrap().push(event.message.clone());
+        }
+    }
+
+    fn quiet_audit_log() -> SecurityAuditLog {
+        SecurityAuditLog::with_config(AuditLogConfig {
+            console_enabled: false,
+            file_enabled: false,
+            log_file_path: None,
+            min_severity: AuditSeverity::Info,
+        })
+    }
+
+    #[test]
+    fn test_alert_sinks_receive_events_at_or_above_their_severity() {
+        let audit_log = quiet_audit_log();
+        let pager = Arc::new(RecordingSink::default());
+        let everything = Arc::new(RecordingSink::default());
+        audit_log.add_alert_sink(pager.clone(), AuditSeverity::Error);
+        audit_log.add_alert_sink(everything.clone(), AuditSeverity::Info);
+
+        audit_log.log_network("test", "node reachable", None, AuditSeverity::Info).unwrap();
+        audit_log.log_security_error("test", "signature mismatch", None).unwrap();
+        audit_log.log_critical_security_error("test", "Byzantine node detected", None).unwrap();
+
+        let paged = pager.messages.lock().unwrap().clone();
+        assert_eq!(paged.len(), 2);
+        assert!(paged.iter().any(|m| m.contains("Byzantine node detected")));
+        assert_eq!(everything.messages.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_retention_prunes_low_severity_events_first() {
+        use crate::security::audit::AuditRetention;
+
+        let retention = AuditRetention { max_events: 4, hard_max_events: 6, retention: None };
+        let audit_log = quiet_audit_log().with_retention(retention);
+        let log = |severity: AuditSeverity, message: &str| {
+            audit_log.log_event(AuditEvent::new(AuditEventType::SecurityError, severity, "test", message)).unwrap();
+        };
+
+        log(AuditSeverity::Critical, "critical 0");
+        log(AuditSeverity::Info, "info 0");
+        log(AuditSeverity::Warning, "warning 0");
+        log(AuditSeverity::Info, "info 1");
+        log(AuditSeverity::Error, "error 0");
+        log(AuditSeverity::Info, "info 2");
+        assert_eq!(audit_log.event_count(), 4);
+        let messages: Vec<String> = audit_log.get_events().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["critical 0", "warning 0", "error 0", "info 2"]);
+
+        // Error and Critical events outlive the soft cap and are only dropped past the hard cap
+        for i in 1..=4 {
+            log(AuditSeverity::Error, &format!("error {}", i));
+        }
+        assert_eq!(audit_log.event_count(), 6);
+        assert_eq!(audit_log.get_events()[0].message, "critical 0");
+        log(AuditSeverity::Error, "error 5");
+        let messages: Vec<String> = audit_log.get_events().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["error 0", "error 1", "error 2", "error 3", "error 4", "error 5"]);
+    }
+
+    #[test]
+    fn test_retention_period_prunes_old_low_severity_events() {
+        use crate::security::audit::AuditRetention;
+
+        let retention = AuditRetention { retention: Some(std::time::Duration::from_secs(60)), ..AuditRetention::default() };
+        let audit_log = quiet_audit_log().with_retention(retention);
+        let mut stale_info = AuditEvent::new(AuditEventType::NetworkOperation, AuditSeverity::Info, "test", "stale info");
+        stale_info.timestamp = chrono::Utc::now() - chrono::Duration::minutes(5);
+        let mut stale_error = AuditEvent::new(AuditEventType::SecurityError, AuditSeverity::Error, "test", "stale error");
+        stale_error.timestamp = stale_info.timestamp;
+        audit_log.log_event(stale_info).unwrap();
+        audit_log.log_event(stale_error).unwrap();
+        audit_log.log_network("test", "fresh info", None, AuditSeverity::Info).unwrap();
+
+        let messages: Vec<String> = audit_log.get_events().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["stale error", "fresh info"]);
+    }
+
+    #[test]
+    fn test_audit_file_rotates_and_reloads_after_a_restart() {
+        let path = std::env::temp_dir().join(format!("suimodular_audit_{}.jsonl", std::process::id()));
+        let rotated = std::path::PathBuf::from(format!("{}.1", path.display()));
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&rotated);
+
+        let audit_log = quiet_audit_log().with_rotating_file(&path, 600).unwrap();
+        for i in 0..10 {
+            audit_log.log_network("test", &format!("event {}", i), Some("sui-testnet"), AuditSeverity::Warning).unwrap();
+        }
+        assert!(rotated.exists());
+        assert!(std::fs::metadata(&path).unwrap().len() <= 600);
+        assert_eq!(audit_log.event_count(), 10);
+
+        // A crash mid-write leaves a partial last line
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, b"{\"timestamp\":17").unwrap();
+        drop(file);
+
+        let reloaded = SecurityAuditLog::load_from_file(&path).unwrap();
+        let messages: Vec<String> = reloaded.get_events().into_iter().map(|e| e.message).collect();
+        let kept = messages.len();
+        assert!(kept > 0 && kept <= 10);
+        let expected: Vec<String> = (10 - kept..10).map(|i| format!("event {}", i)).collect();
+        assert_eq!(messages, expected);
+        assert_eq!(reloaded.get_events()[0].chain_id.as_deref(), Some("sui-testnet"));
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&rotated);
+    }
+
+    #[test]
+    fn test_hash_chain_flags_edited_and_removed_events() {
+        let path = std::env::temp_dir().join(format!("suimodular_audit_chain_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let audit_log = quiet_audit_log().with_hash_chain().with_file(&path).unwrap();
+        for i in 0..5 {
+            audit_log.log_validation("test", &format!("event {}", i), Some("tx"), AuditSeverity::Info).unwrap();
+        }
+        assert_eq!(audit_log.verify_chain(), Ok(()));
+        assert_eq!(SecurityAuditLog::load_from_file(&path).unwrap().verify_chain(), Ok(()));
+        let original = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = original.lines().collect();
+
+        // Edit the middle event
+        let mut edited: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        edited["message"] = json!("nothing to see here");
+        let mut tampered: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        tampered[2] = edited.to_string();
+        std::fs::write(&path, tampered.join("\n")).unwrap();
+        assert_eq!(SecurityAuditLog::load_from_file(&path).unwrap().verify_chain(), Err(2));
+
+        // Remove it instead: its successor no longer links up
+        let mut removed: Vec<&str> = lines.clone();
+        removed.remove(2);
+        std::fs::write(&path, removed.join("\n")).unwrap();
+        assert_eq!(SecurityAuditLog::load_from_file(&path).unwrap().verify_chain(), Err(2));
+
+        // Without chaining there is nothing to verify against
+        let unchained = quiet_audit_log();
+        unchained.log_validation("test", "unchained", None, AuditSeverity::Info).unwrap();
+        assert_eq!(unchained.verify_chain(), Err(0));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_hash_chain_flags_a_missing_head_and_a_restart() {
+        let path = std::env::temp_dir().join(format!("suimodular_audit_head_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let first = quiet_audit_log().with_hash_chain().with_file(&path).unwrap();
+        for i in 0..3 {
+            first.log_validation("test", &format!("event {}", i), None, AuditSeverity::Info).unwrap();
+        }
+        drop(first);
+        // A restarted process carries on from the file's last event
+        let second = quiet_audit_log().with_file(&path).unwrap().with_hash_chain();
+        for i in 3..5 {
+            second.log_validation("test", &format!("event {}", i), None, AuditSeverity::Info).unwrap();
+        }
+        assert_eq!(second.verify_chain(), Ok(()));
+        let reloaded = SecurityAuditLog::load_from_file(&path).unwrap();
+        assert_eq!(reloaded.verify_chain(), Ok(()));
+        let events = reloaded.get_events();
+        assert_eq!(events[3].prev_hash, events[2].hash);
+        let original = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = original.lines().collect();
+
+        // Deleting the first event leaves a log that no longer starts at genesis
+        std::fs::write(&path, lines[1..].join("\n")).unwrap();
+        assert_eq!(SecurityAuditLog::load_from_file(&path).unwrap().verify_chain(), Err(0));
+
+        // Neither does one truncated to its last few events
+        std::fs::write(&path, lines[3..].join("\n")).unwrap();
+        assert_eq!(SecurityAuditLog::load_from_file(&path).unwrap().verify_chain(), Err(0));
+
+        // A second chain starting over at genesis could have replaced the first
+        let _ = std::fs::remove_file(&path);
+        let fresh = quiet_audit_log().with_hash_chain().with_file(&path).unwrap();
+        fresh.log_validation("test", "replacement", None, AuditSeverity::Info).unwrap();
+        let spliced = format!("{}\n{}", lines[..2].join("\n"), std::fs::read_to_string(&path).unwrap());
+        std::fs::write(&path, spliced).unwrap();
+        assert_eq!(SecurityAuditLog::load_from_file(&path).unwrap().verify_chain(), Err(2));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_hash_chain_verifies_across_discarded_rotations() {
+        let path = std::env::temp_dir().join(format!("suimodular_audit_anchor_{}.jsonl", std::process::id()));
+        let rotated = std::path::PathBuf::from(format!("{}.1", path.display()));
+        let anchor = std::path::PathBuf::from(format!("{}.anchor", path.display()));
+        for file in [&path, &rotated, &anchor] {
+            let _ = std::fs::remove_file(file);
+        }
+
+        let audit_log = quiet_audit_log().with_hash_chain().with_rotating_file(&path, 1200).unwrap();
+        for i in 0..20 {
+            audit_log.log_network("test", &format!("event {}", i), None, AuditSeverity::Warning).unwrap();
+        }
+        // Several generations have been discarded by now
+        assert!(anchor.exists());
+        let reloaded = SecurityAuditLog::load_from_file(&path).unwrap();
+        assert!(reloaded.event_count() < 20);
+        assert_eq!(reloaded.verify_chain(), Ok(()));
+
+        // Without the anchor the oldest kept event links to nothing
+        std::fs::remove_file(&anchor).unwrap();
+        assert_eq!(SecurityAuditLog::load_from_file(&path).unwrap().verify_chain(), Err(0));
+
+        for file in [&path, &rotated, &anchor] {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    #[test]
+    fn test_hash_chain_survives_pruning_and_clearing() {
+        use crate::security::audit::AuditRetention;
+
+        let retention = AuditRetention { max_events: 3, hard_max_events: 5, retention: None };
+        let audit_log = quiet_audit_log().with_hash_chain().with_retention(retention);
+        for (i, severity) in [AuditSeverity::Info, AuditSeverity::Error, AuditSeverity::Info, AuditSeverity::Warning, AuditSeverity::Info, AuditSeverity::Critical]
+            .into_iter()
+            .enumerate()
+        {
+            audit_log.log_event(AuditEvent::new(AuditEventType::SecurityError, severity, "test", &format!("event {}", i))).unwrap();
+        }
+        // Pruning took Info events out of the middle of the chain
+        assert_eq!(audit_log.event_count(), 3);
+        assert_eq!(audit_log.verify_chain(), Ok(()));
+
+        audit_log.clear_events();
+        audit_log.log_network("test", "after clear", None, AuditSeverity::Info).unwrap();
+        assert_eq!(audit_log.verify_chain(), Ok(()));
+    }
+
+    #[test]
+    fn test_query_filters_by_severity_type_component_and_time() {
+        use crate::security::audit::AuditFilter;
+
+        let audit_log = quiet_audit_log();
+        let now = chrono::Utc::now();
+        let mut old_error = AuditEvent::new(AuditEventType::SecurityError, AuditSeverity::Error, "ByzantineDetector", "old error");
+        old_error.timestamp = now - chrono::Duration::hours(2);
+        audit_log.log_event(AuditEvent::new(AuditEventType::SecurityError, AuditSeverity::Critical, "ByzantineDetector", "recent critical")).unwrap();
+        audit_log.log_event(old_error).unwrap();
+        audit_log.log_network("NetworkManager", "recent warning", None, AuditSeverity::Warning).unwrap();
+        audit_log.log_security_error("KeyManager", "recent error", None).unwrap();
+        audit_log.log_validation("TransactionHandler", "recent info", None, AuditSeverity::Info).unwrap();
+
+        let messages = |filter: AuditFilter| -> Vec<String> { audit_log.query(filter).into_iter().map(|e| e.message).collect() };
+
+        // Security errors from the last hour
+        let last_hour = AuditFilter {
+            event_types: Some(vec![AuditEventType::SecurityError]),
+            since: Some((now - chrono::Duration::hours(1)).timestamp()),
+            ..AuditFilter::default()
+        };
+        assert_eq!(messages(last_hour), vec!["recent critical", "recent error"]);
+
+        let at_least_warning = AuditFilter { severity_at_least: Some(AuditSeverity::Warning), ..AuditFilter::default() };
+        assert_eq!(messages(at_least_warning), vec!["old error", "recent critical", "recent warning", "recent error"]);
+
+        let detector = AuditFilter { component: Some("ByzantineDetector".to_string()), until: Some((now - chrono::Duration::hours(1)).timestamp()), ..AuditFilter::default() };
+        assert_eq!(messages(detector), vec!["old error"]);
+        assert_eq!(messages(AuditFilter::default()).len(), 5);
+    }
+
+    #[test]
+    fn test_unresponsive_webhook_does_not_block_logging() {
+        // Accepts connections into the backlog but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        let sink = Arc::new(WebhookAlertSink::new(&url, 1).unwrap());
+        let audit_log = quiet_audit_log();
+        audit_log.add_alert_sink(sink.clone(), AuditSeverity::Error);
+
+        let start = std::time::Instant::now();
+        for i in 0..5 {
+            let event = AuditEvent::new(AuditEventType::SecurityError, AuditSeverity::Critical, "test", &format!("alert {}", i));
+            audit_log.log_event(event).unwrap();
+        }
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+        // One in flight, at most one queued, the rest dropped
+        assert!(sink.dropped() >= 3);
+    }
+
+    #[test]
+    fn test_transaction_invariants_hold_under_fuzzing() {
+        for (name, invariant) in INTEGRITY_INVARIANTS {
+            if let Err(e) = fuzz_transactions(256, invariant) {
+                panic!("{}: {}", name, e);
+            }
+        }
+
+        // The generator reaches boundary values, so a planted bug is found
+        let err = fuzz_transactions(256, |tx| {
+            if tx.gas_budget == u64::MAX { Err("overflowing gas budget".to_string()) } else { Ok(()) }
+        }).unwrap_err();
+        assert!(err.contains("overflowing gas budget"));
+    }
+
+    #[test]
+    fn test_rejected_transactions_are_never_submitted() {
+        use crate::testkit::MockPipelineBuilder;
+        use proptest::prelude::*;
+        use proptest::test_runner::TestRunner;
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let pipeline = MockPipelineBuilder::new().build().unwrap();
+        let mut config = ProptestConfig::with_cases(128);
+        config.failure_persistence = None;
+
+        TestRunner::new(config).run(&arb_transaction(), |tx| {
+            if tx.validate().is_ok() {
+                return Ok(());
+            }
+            let submitted = pipeline.sui_client.submitted().len();
+            prop_assert!(runtime.block_on(pipeline.run(&tx)).is_err());
+            prop_assert_eq!(pipeline.sui_client.submitted().len(), submitted);
+            Ok(())
+        }).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_property_tester_fuzzes_integrity_invariants() {
+        let framework = VerificationFrameworkBuilder::new(None).with_builtin(BuiltinProver::PropertyTesting).build();
+        let context = json!({ "transaction": serde_json::to_value(create_test_transaction()).unwrap() });
+        let results = framework.verify_property("integrity_verification", &context).await.unwrap();
+        assert_eq!(results[0].status, formal::VerificationStatus::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_same_verification_input_is_not_submitted_twice() -> Result<()> {
+        use crate::quorum::binding::ReplayError;
+        use crate::transaction::types::VerificationInput;
+
+        let (_, handler, _, _) = setup_security_test_env().await?;
+        let payload = b"attestation: flight BA117 delayed 140 minutes".to_vec();
+        let quorum_signatures = handler.collect_quorum_signatures(&payload).await?;
+        // The contract verifies over the payload itself, so that is what the validators signed
+        let verifier = crate::testkit::MockVerifier::for_quorum(&handler.quorum_simulation);
+        assert_eq!(verifier.valid_signature_count(&payload, &quorum_signatures), quorum_signatures.len());
+        let input = VerificationInput { attestation_payload: payload.clone(), quorum_signatures };
+        let replay_error = |result: Result<String>| result.unwrap_err().downcast::<ReplayError>().ok();
+
+        // The first submission holds its claim while it waits on L1
+        let (_, second) = tokio::join!(
+            handler.submit_for_onchain_verification(input.clone(), 10_000_000),
+            handler.submit_for_onchain_verification(input.clone(), 10_000_000),
+        );
+        assert!(matches!(replay_error(second), Some(ReplayError::InFlight { .. })));
+
+        // Once a submission of the set has landed, it can't be sent again
+        handler.claim_signature_binding(&input)?;
+        handler.settle_signature_binding(&input, true);
+        let again = handler.submit_for_onchain_verification(input.clone(), 10_000_000).await;
+        assert!(matches!(replay_error(again), Some(ReplayError::AlreadySubmitted { .. })));
+
+        // Signatures the handler never collected are rejected unless explicitly allowed
+        let foreign = VerificationInput { attestation_payload: payload, quorum_signatures: vec![vec![7; 64]] };
+        let rejected = handler.submit_for_onchain_verification(foreign, 10_000_000).await;
+        assert_eq!(replay_error(rejected), Some(ReplayError::Unbound));
+        Ok(())
+    }
+}
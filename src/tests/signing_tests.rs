@@ -0,0 +1,324 @@
+use crate::config::{decode_submitter_keypair, detect_signature_scheme, parse_signature_scheme};
+use crate::security::key_manager::KeyManager;
+use crate::transaction::multisig::MultisigSubmitter;
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::crypto::{Signature, SuiKeyPair, SuiSignature};
+use sui_sdk::types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+use sui_sdk::types::transaction::TransactionData;
+use sui_types::crypto::SignatureScheme;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
+
+// Flag byte followed by a fixed 32-byte private key, Base64 encoded the way Sui keystores do
+fn encoded_test_key(scheme: SignatureScheme) -> String {
+    let mut bytes = vec![scheme.flag()];
+    bytes.extend_from_slice(&[7u8; 32]);
+    base64::encode(bytes)
+}
+
+fn sign_and_verify(keypair: &SuiKeyPair, scheme: SignatureScheme) {
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), "dummy payload".to_string());
+    let signature = Signature::new_secure(&intent_msg, keypair);
+    let address = SuiAddress::from(&keypair.public());
+
+    assert_eq!(signature.scheme(), scheme);
+    assert!(signature.verify_secure(&intent_msg, address, scheme).is_ok(), "{:?} signature failed to verify", scheme);
+}
+
+#[test]
+fn test_load_and_sign_with_each_scheme() {
+    for scheme in [SignatureScheme::ED25519, SignatureScheme::Secp256k1, SignatureScheme::Secp256r1] {
+        let encoded = encoded_test_key(scheme);
+
+        // Auto-detection and an explicit matching scheme both succeed
+        let detected = decode_submitter_keypair(&encoded, None).unwrap();
+        assert_eq!(detect_signature_scheme(&detected), scheme);
+        let explicit = decode_submitter_keypair(&encoded, Some(scheme)).unwrap();
+
+        sign_and_verify(&explicit, scheme);
+    }
+}
+
+#[test]
+fn test_scheme_mismatch_is_rejected() {
+    let encoded = encoded_test_key(SignatureScheme::Secp256k1);
+    assert!(decode_submitter_keypair(&encoded, Some(SignatureScheme::ED25519)).is_err());
+}
+
+#[test]
+fn test_parse_signature_scheme_names() {
+    assert_eq!(parse_signature_scheme("auto").unwrap(), None);
+    assert_eq!(parse_signature_scheme("Secp256R1").unwrap(), Some(SignatureScheme::Secp256r1));
+    assert!(parse_signature_scheme("rsa").is_err());
+}
+
+#[test]
+fn test_key_manager_staging_keeps_current_key() {
+    let current = decode_submitter_keypair(&encoded_test_key(SignatureScheme::ED25519), None).unwrap();
+    let next = decode_submitter_keypair(&encoded_test_key(SignatureScheme::Secp256k1), None).unwrap();
+    let current_address = SuiAddress::from(&current.public());
+    let next_address = SuiAddress::from(&next.public());
+
+    let manager = KeyManager::new(current, None);
+    assert_eq!(manager.current_address(), current_address);
+    assert_eq!(manager.next_address(), None);
+
+    // Staging alone must not change which key signs
+    manager.set_next_key(next);
+    assert_eq!(manager.current_address(), current_address);
+    assert_eq!(manager.next_address(), Some(next_address));
+    assert!(manager.previous_addresses().is_empty());
+}
+
+fn three_test_keys() -> Vec<SuiKeyPair> {
+    [SignatureScheme::ED25519, SignatureScheme::Secp256k1, SignatureScheme::Secp256r1]
+        .into_iter()
+        .map(|scheme| decode_submitter_keypair(&encoded_test_key(scheme), None).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_two_of_three_multisig_signature_verifies() {
+    let submitter = MultisigSubmitter::new(three_test_keys(), 2).unwrap();
+    let sender = submitter.address();
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_all_sui(SuiAddress::random_for_testing_only());
+        builder.finish()
+    };
+    let gas = (ObjectID::random(), SequenceNumber::new(), ObjectDigest::random());
+    let tx_data = TransactionData::new_programmable(sender, vec![gas], pt, 10_000_000, 1000);
+
+    let signature = submitter.sign(&tx_data).unwrap();
+    match &signature {
+        GenericSignature::MultiSig(multisig) => assert_eq!(multisig.get_sigs().len(), 2),
+        other => panic!("expected a multisig signature, got {:?}", other),
+    }
+
+    // The combined signature is accepted for the multisig address and rejected for any other
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data.clone());
+    assert!(signature.verify_authenticator(&intent_msg, sender, None, &VerifyParams::default()).is_ok());
+    let other = SuiAddress::random_for_testing_only();
+    assert!(signature.verify_authenticator(&intent_msg, other, None, &VerifyParams::default()).is_err());
+    assert!(submitter.verify(&tx_data, &signature).is_ok());
+
+    assert!(submitter.sign_transaction(tx_data).is_ok());
+}
+
+#[tokio::test]
+async fn test_critical_dry_run_signs_with_the_multisig() -> anyhow::Result<()> {
+    use crate::config::generate_test_sui_keypair;
+    use crate::quorum::simulation::QuorumSimulation;
+    use crate::sui::network::{NetworkManager, NetworkType};
+    use crate::transaction::handler::TransactionHandler;
+    use crate::transaction::types::{MiddlewareAttestation, Transaction, TransactionType, VerificationInput, DEFAULT_GAS_BUDGET};
+    use std::sync::Arc;
+    use sui_sdk::SuiClientBuilder;
+
+    let network_manager = NetworkManager::new(NetworkType::Testnet).await?;
+    let sui_client = Arc::new(SuiClientBuilder::default().build(network_manager.get_active_rpc_url()?).await?);
+    let quorum = Arc::new(QuorumSimulation::create_with_random_nodes(3)?);
+    let multisig = MultisigSubmitter::new(three_test_keys(), 2)?;
+    let multisig_address = multisig.address();
+    let handler = TransactionHandler::new(generate_test_sui_keypair()?, None, None, None, quorum.clone(), sui_client.clone())
+        .await?
+        .with_multisig_submitter(multisig);
+
+    let address = SuiAddress::random_for_testing_only().to_string();
+    let mut tx = Transaction::new(TransactionType::Transfer, address.clone(), address, 10, "0x5".to_string(), DEFAULT_GAS_BUDGET, vec![]);
+    tx.critical = true;
+    let attestation_payload = MiddlewareAttestation::for_transaction(&tx, serde_json::json!({ "approved": true }))?.to_bytes_for_signing()?;
+    let quorum_signatures = handler.collect_quorum_signatures(&attestation_payload).await?;
+    let input = VerificationInput { attestation_payload, quorum_signatures };
+
+    // The multisig address has no gas on testnet; the dry run still goes through with its signature checked
+    let preview = handler.dry_run_verification(&tx, input.clone()).await?;
+    assert_eq!(preview.sender, multisig_address.to_string());
+    assert!(!preview.status.is_empty());
+
+    let single_key = TransactionHandler::new(generate_test_sui_keypair()?, None, None, None, quorum, sui_client).await?;
+    let err = single_key.dry_run_verification(&tx, input).await.unwrap_err();
+    assert!(err.to_string().contains("requires a multisig submitter"));
+    Ok(())
+}
+
+#[test]
+fn test_multisig_rejects_invalid_threshold() {
+    assert!(MultisigSubmitter::new(three_test_keys(), 0).is_err());
+    assert!(MultisigSubmitter::new(three_test_keys(), 4).is_err());
+}
+
+#[tokio::test]
+async fn test_quorum_signs_bcs_encoded_record() {
+    use crate::quorum::simulation::payload_signing_digest;
+    use crate::testkit::{MockPipelineBuilder, MockVerifier};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct FlightClaim {
+        flight: String,
+        delay_minutes: u64,
+        payout: u64,
+    }
+
+    let pipeline = MockPipelineBuilder::new().with_quorum_size(4).build().unwrap();
+    let claim = FlightClaim { flight: "LH987".to_string(), delay_minutes: 180, payout: 500 };
+
+    let signed = pipeline.quorum.request_signatures_over(&claim).await.unwrap();
+    assert_eq!(signed.payload, bcs::to_bytes(&claim).unwrap());
+    assert_eq!(signed.digest, payload_signing_digest(&signed.payload));
+
+    let signatures: Vec<_> = signed.signatures.into_iter().map(|(bytes, _)| bytes).collect();
+    let verifier = MockVerifier::for_quorum(&pipeline.quorum);
+    assert_eq!(verifier.valid_signature_count(&signed.digest, &signatures), 4);
+    assert_eq!(verifier.valid_signature_count(&signed.payload, &signatures), 0);
+}
+
+#[test]
+fn test_sponsored_transaction_carries_sender_and_sponsor_signatures() {
+    use crate::transaction::sponsored::{sign_sponsored, sponsored_transaction_data};
+    use sui_sdk::types::transaction::TransactionDataAPI;
+
+    let mut keys = three_test_keys();
+    let sponsor = keys.remove(1);
+    let sender = keys.remove(0);
+    let sender_address = SuiAddress::from(&sender.public());
+    let sponsor_address = SuiAddress::from(&sponsor.public());
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_all_sui(SuiAddress::random_for_testing_only());
+        builder.finish()
+    };
+    let sponsor_gas = (ObjectID::random(), SequenceNumber::new(), ObjectDigest::random());
+    let tx_data = sponsored_transaction_data(sender_address, sponsor_address, sponsor_gas, pt, 10_000_000, 1000);
+    assert_eq!(tx_data.sender(), sender_address);
+    assert_eq!(tx_data.gas_owner(), sponsor_address);
+
+    // Signing with the keys swapped is rejected
+    assert!(sign_sponsored(tx_data.clone(), &sponsor, &sender).is_err());
+
+    let signed = sign_sponsored(tx_data.clone(), &sender, &sponsor).unwrap();
+    let signatures = signed.data().tx_signatures();
+    assert_eq!(signatures.len(), 2);
+
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data);
+    assert!(signatures[0].verify_authenticator(&intent_msg, sender_address, None, &VerifyParams::default()).is_ok());
+    assert!(signatures[1].verify_authenticator(&intent_msg, sponsor_address, None, &VerifyParams::default()).is_ok());
+}
+
+#[test]
+fn test_encrypted_keyfile_round_trip() {
+    use crate::config::{decrypt_keypair, encrypt_keypair, is_encrypted_keyfile};
+
+    let encoded = encoded_test_key(SignatureScheme::ED25519);
+    let encrypted = encrypt_keypair(&encoded, "correct horse").unwrap();
+    assert!(is_encrypted_keyfile(&encrypted));
+    assert!(!is_encrypted_keyfile(encoded.as_bytes()));
+    assert!(!String::from_utf8_lossy(&encrypted).contains(&encoded));
+
+    assert!(decrypt_keypair(&encrypted, "wrong horse").is_err());
+    let decrypted = decrypt_keypair(&encrypted, "correct horse").unwrap();
+    let keypair = decode_submitter_keypair(&decrypted, Some(SignatureScheme::ED25519)).unwrap();
+    let original = decode_submitter_keypair(&encoded, None).unwrap();
+    assert_eq!(SuiAddress::from(&keypair.public()), SuiAddress::from(&original.public()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_keyfile_is_written_owner_only() {
+    use crate::config::write_keyfile;
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = std::env::temp_dir().join(format!("suimodular_keyfile_{}.age", std::process::id()));
+    let path_str = path.to_str().unwrap();
+    std::fs::write(&path, "old").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    // An existing world-readable file is tightened, not just truncated
+    write_keyfile(path_str, b"encrypted").unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    assert_eq!(std::fs::read(&path).unwrap(), b"encrypted");
+    std::fs::remove_file(&path).unwrap();
+
+    write_keyfile(path_str, b"fresh").unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_signed_intent_helpers_produce_verifiable_signatures() {
+    use crate::sui::signing::{build_signed_transaction, sign_transaction_data};
+
+    let keys = three_test_keys();
+    let sender = SuiAddress::from(&keys[0].public());
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_all_sui(SuiAddress::random_for_testing_only());
+        builder.finish()
+    };
+    let gas = (ObjectID::random(), SequenceNumber::new(), ObjectDigest::random());
+    let tx_data = TransactionData::new_programmable(sender, vec![gas], pt, 10_000_000, 1000);
+
+    let (intent_msg, signature) = sign_transaction_data(&tx_data, &keys[0]);
+    assert_eq!(intent_msg.value, tx_data);
+    assert!(signature.verify_secure(&intent_msg, sender, SignatureScheme::ED25519).is_ok());
+    let other = SuiAddress::random_for_testing_only();
+    assert!(signature.verify_secure(&intent_msg, other, SignatureScheme::ED25519).is_err());
+
+    // One signature per key, in key order
+    let signed = build_signed_transaction(tx_data, &[&keys[0], &keys[1]]);
+    let signatures = signed.data().tx_signatures();
+    assert_eq!(signatures.len(), 2);
+    let second = SuiAddress::from(&keys[1].public());
+    assert!(signatures[0].verify_authenticator(&intent_msg, sender, None, &VerifyParams::default()).is_ok());
+    assert!(signatures[1].verify_authenticator(&intent_msg, second, None, &VerifyParams::default()).is_ok());
+}
+
+#[tokio::test]
+async fn test_swapped_payload_is_rejected_before_submission() {
+    use crate::quorum::binding::{binding_digest, ReplayError, SignatureBindings};
+    use crate::quorum::simulation::QuorumSimulation;
+
+    let quorum = QuorumSimulation::create_with_random_nodes(4).unwrap();
+    let payload_a = b"attestation: flight LH987 delayed 180 minutes".to_vec();
+    let payload_b = b"attestation: flight LH987 delayed 999 minutes".to_vec();
+    let signatures: Vec<_> = quorum.request_signatures(payload_a.clone()).await.unwrap()
+        .into_iter()
+        .map(|(bytes, _)| bytes)
+        .collect();
+
+    let bindings = SignatureBindings::default();
+    let binding = bindings.bind(&payload_a, &signatures);
+    assert_eq!(binding.digest, binding_digest(&payload_a, binding.nonce));
+    assert_ne!(binding_digest(&payload_a, binding.nonce + 1), binding.digest);
+
+    assert_eq!(bindings.check(&payload_a, &signatures), Ok(binding));
+    assert_eq!(bindings.check(&payload_b, &signatures), Err(ReplayError::PayloadMismatch { nonce: binding.nonce }));
+
+    // A set that was never collected can't be checked
+    let other: Vec<_> = quorum.request_signatures(payload_b.clone()).await.unwrap()
+        .into_iter()
+        .map(|(bytes, _)| bytes)
+        .collect();
+    assert_eq!(bindings.check(&payload_b, &other), Err(ReplayError::Unbound));
+
+    // A claimed set is rejected until released, and for good once completed
+    assert_eq!(bindings.claim(&payload_a, &signatures), Ok(binding));
+    assert_eq!(bindings.claim(&payload_a, &signatures), Err(ReplayError::InFlight { nonce: binding.nonce }));
+    bindings.release(&signatures);
+    assert_eq!(bindings.claim(&payload_a, &signatures), Ok(binding));
+    bindings.complete(&signatures);
+    assert_eq!(bindings.check(&payload_a, &signatures), Err(ReplayError::AlreadySubmitted { nonce: binding.nonce }));
+    assert_eq!(bindings.claim(&payload_b, &signatures), Err(ReplayError::PayloadMismatch { nonce: binding.nonce }));
+
+    // Each collection gets a fresh nonce, and old bindings are evicted past capacity
+    let small = SignatureBindings::new(1);
+    let first = small.bind(&payload_a, &signatures);
+    let second = small.bind(&payload_b, &other);
+    assert_ne!(first.nonce, second.nonce);
+    assert_eq!(small.len(), 1);
+    assert_eq!(small.check(&payload_a, &signatures), Err(ReplayError::Unbound));
+}
@@ -0,0 +1,113 @@
+//! Helpers shared by the test modules.
+
+use crate::sui::network::{NetworkManager, NetworkType};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Whether `request` holds the headers and as much body as they announce
+fn http_request_complete(request: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(request).to_lowercase();
+    let Some(header_end) = text.find("\r\n\r\n") else {
+        return false;
+    };
+    let body_length = text.lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    request.len() >= header_end + 4 + body_length
+}
+
+/// JSON-RPC endpoint answering each request with `answer(method, params)`
+pub async fn json_rpc_endpoint<F>(answer: F) -> String
+where
+    F: Fn(&str, &Value) -> Value + Send + Sync + 'static,
+{
+    delayed_json_rpc_endpoint(Duration::ZERO, answer).await
+}
+
+/// Like `json_rpc_endpoint`, but each answer is sent only after `delay`
+pub async fn delayed_json_rpc_endpoint<F>(delay: Duration, answer: F) -> String
+where
+    F: Fn(&str, &Value) -> Value + Send + Sync + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    serve_json_rpc(listener, delay, answer);
+    url
+}
+
+/// Answer JSON-RPC requests arriving on `listener` from a background task, as `delayed_json_rpc_endpoint` does
+pub fn serve_json_rpc<F>(listener: tokio::net::TcpListener, delay: Duration, answer: F)
+where
+    F: Fn(&str, &Value) -> Value + Send + Sync + 'static,
+{
+    serve_http(listener, delay, move |call| {
+        let result = answer(call["method"].as_str().unwrap_or(""), &call["params"]);
+        ("200 OK", json!({ "jsonrpc": "2.0", "id": call["id"], "result": result }).to_string())
+    });
+}
+
+/// `NetworkManager` for a local Sui fullnode mock answering `answer(method, params)`,
+/// so tests don't depend on a public network
+pub async fn local_network_manager<F>(answer: F) -> anyhow::Result<NetworkManager>
+where
+    F: Fn(&str, &Value) -> Value + Send + Sync + 'static,
+{
+    let url = json_rpc_endpoint(move |method, params| match method {
+        // What the SDK client reads on construction to learn the server's API
+        "rpc.discover" => json!({ "info": { "version": "1.0.0" }, "methods": [] }),
+        _ => answer(method, params),
+    }).await;
+    NetworkManager::new(NetworkType::Custom(url)).await
+}
+
+/// Endpoint answering every request with the HTTP `status` line and raw `body`,
+/// for JSON-RPC error objects and failed responses
+pub async fn raw_http_endpoint(status: &'static str, body: impl Into<String>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let body = body.into();
+    serve_http(listener, Duration::ZERO, move |_| (status, body.clone()));
+    url
+}
+
+/// Serve each request on `listener` with `respond(json_body)`, which returns the status line and body
+fn serve_http<F>(listener: tokio::net::TcpListener, delay: Duration, respond: F)
+where
+    F: Fn(&Value) -> (&'static str, String) + Send + Sync + 'static,
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let respond = Arc::new(respond);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                // Read the headers and the Content-Length bytes of body after them
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                loop {
+                    let n = stream.read(&mut buf).await.unwrap_or(0);
+                    request.extend_from_slice(&buf[..n]);
+                    if n == 0 || http_request_complete(&request) {
+                        break;
+                    }
+                }
+                let text = String::from_utf8_lossy(&request);
+                let call: Value = text.split_once("\r\n\r\n")
+                    .and_then(|(_, body)| serde_json::from_str(body).ok())
+                    .unwrap_or(Value::Null);
+                let (status, body) = respond(&call);
+                tokio::time::sleep(delay).await;
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+}
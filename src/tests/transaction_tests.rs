@@ -0,0 +1,469 @@
+use crate::transaction::idempotency::{IdempotencyClaim, IdempotencyStore};
+use crate::transaction::rate_limit::{RateLimitConfig, SenderRateLimiter};
+use crate::transaction::tracker::{LifecycleState, TransactionTracker};
+use crate::transaction::types::{ExternalQuery, QueryCondition, Transaction, TransactionType, DEFAULT_GAS_BUDGET};
+use serde_json::json;
+use std::time::Duration;
+
+const SENDER_A: &str = "0x4c45f32d0c5e9fd297e52d792c261a85f0582d0bfed0edd54e0cabe12cadd0f6";
+const SENDER_B: &str = "0x02a212de6a9dfa3a69e22387acfbafbb1a9e591bd9d636e7895dcfc8de05f331";
+
+#[test]
+fn test_rate_limiter_rejects_burst_over_capacity() {
+    // No refill, so only the initial burst is allowed
+    let limiter = SenderRateLimiter::new(RateLimitConfig::new(2, 0.0));
+
+    assert!(limiter.check(SENDER_A).is_ok());
+    assert!(limiter.check(SENDER_A).is_ok());
+    let err = limiter.check(SENDER_A).unwrap_err();
+    assert_eq!(err.scope, "sender");
+    assert_eq!(err.sender, SENDER_A);
+
+    // Other senders have their own bucket
+    assert!(limiter.check(SENDER_B).is_ok());
+}
+
+#[test]
+fn test_rate_limiter_sender_override_and_global_cap() {
+    let limiter = SenderRateLimiter::new(RateLimitConfig::new(1, 0.0))
+        .with_sender_override(SENDER_A, RateLimitConfig::new(5, 0.0))
+        .with_global_limit(RateLimitConfig::new(3, 0.0));
+
+    assert!(limiter.check(SENDER_A).is_ok());
+    assert!(limiter.check(SENDER_A).is_ok());
+    assert!(limiter.check(SENDER_B).is_ok());
+
+    // Sender A still has quota, but the global cap is exhausted
+    let err = limiter.check(SENDER_A).unwrap_err();
+    assert_eq!(err.scope, "global");
+}
+
+#[test]
+fn test_rate_limiter_evicts_idle_sender_buckets() {
+    // A's bucket refills within milliseconds; B's never does
+    let limiter = SenderRateLimiter::new(RateLimitConfig::new(1, 1000.0))
+        .with_sender_override(SENDER_B, RateLimitConfig::new(1, 0.0))
+        .with_idle_sweep_interval(Duration::ZERO);
+
+    assert!(limiter.check(SENDER_A).is_ok());
+    assert!(limiter.check(SENDER_B).is_ok());
+    assert_eq!(limiter.tracked_senders(), 2);
+
+    std::thread::sleep(Duration::from_millis(20));
+    let third = "0x3333333333333333333333333333333333333333333333333333333333333333";
+    assert!(limiter.check(third).is_ok());
+    // A was full again and dropped; B still owes its quota, so it has to be kept
+    assert_eq!(limiter.tracked_senders(), 2);
+    assert!(limiter.check(SENDER_B).is_err());
+    assert!(limiter.check(SENDER_A).is_ok());
+}
+
+#[test]
+fn test_query_condition_operators() {
+    let gt = QueryCondition { threshold: 120, operator: "gt".to_string() };
+    assert!(gt.is_met(180.0));
+    assert!(!gt.is_met(120.0));
+
+    let unknown = QueryCondition { threshold: 0, operator: "gte".to_string() };
+    assert!(!unknown.is_met(1.0));
+}
+
+#[test]
+fn test_conditional_payout_json_round_trip() {
+    let tx_type = TransactionType::ConditionalPayout {
+        oracle_query: ExternalQuery {
+            url: "https://example.com/flights/BA117".to_string(),
+            path: vec!["delay_minutes".to_string()],
+            condition: None,
+        },
+        condition: QueryCondition { threshold: 120, operator: "gt".to_string() },
+        payout_amount: 500,
+    };
+
+    let value = serde_json::to_value(&tx_type).unwrap();
+    assert_eq!(value["conditional_payout"]["payout_amount"], json!(500));
+    let parsed: TransactionType = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed, tx_type);
+    assert_eq!(parsed.to_string(), "conditional_payout");
+}
+
+#[test]
+fn test_validate_reports_all_issues() {
+    let mut tx = Transaction::new(TransactionType::Transfer, String::new(), SENDER_B.to_string(), 1, "not-an-id".to_string(), 0, vec![]);
+    tx.language = Some("javascript".to_string());
+
+    let fields: Vec<&str> = tx.validate().unwrap_err().iter().map(|issue| issue.field).collect();
+    assert_eq!(fields, vec!["sender", "gas_budget", "gas_payment", "script"]);
+}
+
+#[test]
+fn test_validate_accepts_well_formed_transaction() {
+    let mut tx = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    tx.language = Some("python".to_string());
+    tx.python_code = Some("result = {}".to_string());
+    assert!(tx.validate().is_ok());
+
+    tx.external_query = Some(ExternalQuery { url: "https://example.com".to_string(), path: vec![], condition: None });
+    assert_eq!(tx.validate().unwrap_err()[0].field, "external_query");
+}
+
+#[test]
+fn test_builder_fills_defaults_and_validates() {
+    let tx = Transaction::builder(TransactionType::Transfer)
+        .sender(SENDER_A)
+        .receiver(SENDER_B)
+        .amount(5)
+        .gas_payment("0x5")
+        .python("result = {}")
+        .build()
+        .unwrap();
+    assert_eq!(tx.gas_budget, DEFAULT_GAS_BUDGET);
+    assert_eq!(tx.language.as_deref(), Some("python"));
+    assert!(tx.script.is_none() && tx.external_query.is_none() && !tx.critical);
+
+    let fields: Vec<&str> = Transaction::builder(TransactionType::Transfer)
+        .sender(SENDER_A)
+        .gas_payment("0x5")
+        .javascript("({})")
+        .python("result = {}")
+        .external_query(ExternalQuery { url: "https://example.com".to_string(), path: vec![], condition: None })
+        .build()
+        .unwrap_err()
+        .iter()
+        .map(|issue| issue.field)
+        .collect();
+    assert_eq!(fields, vec!["receiver", "external_query", "language"]);
+}
+
+#[test]
+fn test_digest_ignores_volatile_fields() {
+    let mut tx = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    let digest = tx.digest().unwrap();
+    assert_eq!(digest.len(), 32);
+
+    tx.signatures = Some(vec!["sig".to_string()]);
+    tx.python_params = Some(json!({ "x": 1 }));
+    tx.critical = true;
+    tx.client_request_id = Some("retry-1".to_string());
+    assert_eq!(tx.digest().unwrap(), digest);
+
+    tx.amount = 2;
+    assert_ne!(tx.digest().unwrap(), digest);
+}
+
+#[test]
+fn test_deadline_is_inclusive_and_attested() {
+    use crate::transaction::types::Expired;
+
+    let mut tx = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    let undated = tx.digest().unwrap();
+    assert!(tx.check_deadline(u64::MAX).is_ok());
+
+    tx.valid_until = Some(1_700_000_000);
+    assert!(tx.check_deadline(1_700_000_000).is_ok());
+    assert_eq!(tx.check_deadline(1_700_000_001), Err(Expired { valid_until: 1_700_000_000, now: 1_700_000_001 }));
+    assert_ne!(tx.digest().unwrap(), undated);
+
+    let decoded = Transaction::from_bcs(&tx.to_bcs().unwrap()).unwrap();
+    assert_eq!(decoded.valid_until, Some(1_700_000_000));
+    assert_eq!(decoded.digest().unwrap(), tx.digest().unwrap());
+}
+
+#[test]
+fn test_amounts_rescale_without_rounding() {
+    use crate::transaction::amount::{Amount, AmountError, ETH_DECIMALS, SUI_COIN_TYPE, SUI_DECIMALS};
+
+    let amount = Amount::parse("1.5", SUI_COIN_TYPE, SUI_DECIMALS).unwrap();
+    assert_eq!(amount, Amount::sui(1_500_000_000));
+    assert_eq!(amount.to_string(), "1.5 SUI");
+    assert_eq!(Amount::sui(7).to_string(), "0.000000007 SUI");
+    assert_eq!(amount.to_base_units(ETH_DECIMALS).unwrap(), 1_500_000_000_000_000_000);
+    assert_eq!(amount.rescale(6).unwrap().value, 1_500_000);
+
+    assert_eq!(Amount::sui(7).rescale(6), Err(AmountError::PrecisionLoss { value: 7, from: 9, to: 6 }));
+    assert!(matches!(Amount::sui(u64::MAX).rescale(ETH_DECIMALS), Err(AmountError::Overflow { .. })));
+    assert!(Amount::parse("1.0000000001", SUI_COIN_TYPE, SUI_DECIMALS).is_err());
+    assert!(Amount::parse("1,5", SUI_COIN_TYPE, SUI_DECIMALS).is_err());
+
+    // Transactions without a coin keep their digest and read as MIST
+    let mut tx = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 1_500_000_000, "0x5".to_string(), 1000, vec![]);
+    let undenominated = tx.digest().unwrap();
+    assert_eq!(tx.denominated_amount(), amount);
+    tx.coin_type = Some(SUI_COIN_TYPE.to_string());
+    tx.decimals = Some(SUI_DECIMALS);
+    assert_eq!(tx.denominated_amount(), amount);
+    assert_ne!(tx.digest().unwrap(), undenominated);
+}
+
+#[test]
+fn test_expiry_and_coin_fields_cannot_collide_in_the_digest() {
+    let base = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    // These 8 bytes are what BCS makes of `(Some("abcde"), None)`
+    let mut expiring = base.clone();
+    expiring.valid_until = Some(u64::from_le_bytes([0x01, 0x05, b'a', b'b', b'c', b'd', b'e', 0x00]));
+    let mut denominated = base.clone();
+    denominated.coin_type = Some("abcde".to_string());
+    assert_ne!(expiring.payload_digest().unwrap(), denominated.payload_digest().unwrap());
+
+    // Each field moves the digest, and an unset extension leaves it alone
+    let mut decimals_only = base.clone();
+    decimals_only.decimals = Some(9);
+    let digests = [base.digest().unwrap(), expiring.digest().unwrap(), denominated.digest().unwrap(), decimals_only.digest().unwrap()];
+    for (i, a) in digests.iter().enumerate() {
+        assert!(digests[i + 1..].iter().all(|b| a != b));
+    }
+}
+
+#[test]
+fn test_bcs_round_trip_is_smaller_than_json() {
+    let tx_type = TransactionType::ConditionalPayout {
+        oracle_query: ExternalQuery {
+            url: "https://example.com/flights/BA117".to_string(),
+            path: vec!["delay_minutes".to_string()],
+            condition: None,
+        },
+        condition: QueryCondition { threshold: 120, operator: "gt".to_string() },
+        payout_amount: 500,
+    };
+    let mut tx = Transaction::new(tx_type, SENDER_A.to_string(), SENDER_B.to_string(), 500, "0x5".to_string(), DEFAULT_GAS_BUDGET, vec![]);
+    tx.python_params = Some(json!({ "flight": "BA117" }));
+    tx.client_request_id = Some("retry-1".to_string());
+
+    let bcs = tx.to_bcs().unwrap();
+    let decoded = Transaction::from_bcs(&bcs).unwrap();
+    assert_eq!(decoded.payload_hash().unwrap(), tx.payload_hash().unwrap());
+    assert_eq!(decoded.python_params, tx.python_params);
+    assert_eq!(decoded.client_request_id.as_deref(), Some("retry-1"));
+
+    // JSON drops python_params, so this understates the saving
+    let json = serde_json::to_vec(&tx).unwrap();
+    // Field names, nulls and quoting make up well over a quarter of the JSON
+    assert!(bcs.len() * 4 < json.len() * 3, "BCS is {} bytes against {} bytes of JSON", bcs.len(), json.len());
+
+    assert!(Transaction::from_bcs(&bcs[..bcs.len() - 1]).is_err());
+}
+
+#[test]
+fn test_repeated_idempotency_key_replays_original_submission() {
+    let mut tx = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 1, "0x5".to_string(), 1000, vec![]);
+    tx.client_request_id = Some("order-42".to_string());
+    let id = tx.payload_hash().unwrap();
+    let keys = IdempotencyStore::default();
+
+    assert_eq!(keys.claim("order-42", &id), IdempotencyClaim::New);
+    assert_eq!(keys.claim("order-42", &id), IdempotencyClaim::Replay { tracking_id: id.clone() });
+    assert_eq!(keys.claim("order-42", "other"), IdempotencyClaim::Conflict { tracking_id: id.clone() });
+
+    // Released keys (rejected before acceptance) and expired keys can be claimed afresh
+    keys.release("order-42");
+    assert_eq!(keys.claim("order-42", "other"), IdempotencyClaim::New);
+    let expiring = IdempotencyStore::new(Duration::ZERO);
+    assert_eq!(expiring.claim("order-42", &id), IdempotencyClaim::New);
+    assert_eq!(expiring.claim("order-42", &id), IdempotencyClaim::New);
+}
+
+#[test]
+fn test_max_in_flight_must_be_positive() {
+    use crate::execution::gateway::parse_max_in_flight;
+
+    assert_eq!(parse_max_in_flight("8"), Ok(8));
+    assert!(parse_max_in_flight("0").unwrap_err().contains("at least 1"));
+    assert!(parse_max_in_flight("-1").is_err());
+    assert!(parse_max_in_flight("many").is_err());
+}
+
+#[tokio::test]
+async fn test_submission_gateway_rejects_over_capacity() {
+    use crate::execution::gateway::{OverloadPolicy, SubmissionGateway};
+
+    let gateway = SubmissionGateway::new(2, OverloadPolicy::Reject);
+    let first = gateway.acquire().await.unwrap();
+    let _second = gateway.acquire().await.unwrap();
+    assert_eq!(gateway.in_flight(), 2);
+
+    let busy = gateway.acquire().await.unwrap_err();
+    assert_eq!((busy.in_flight, busy.max_in_flight), (2, 2));
+
+    drop(first);
+    assert_eq!(gateway.in_flight(), 1);
+    assert!(gateway.acquire().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_submission_gateway_queues_when_waiting() {
+    use crate::execution::gateway::{OverloadPolicy, SubmissionGateway};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let gateway = Arc::new(SubmissionGateway::new(1, OverloadPolicy::Wait));
+    let held = gateway.acquire().await.unwrap();
+
+    let waiter = {
+        let gateway = gateway.clone();
+        tokio::spawn(async move { gateway.acquire().await.map(|_| ()) })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(gateway.queue_depth(), 1);
+    assert!(!waiter.is_finished());
+
+    drop(held);
+    tokio::time::timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap().unwrap();
+    assert_eq!(gateway.queue_depth(), 0);
+    assert_eq!(gateway.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_failed_transaction_is_dead_lettered_once_and_replayed() {
+    use crate::execution::manager::ExecutionManager;
+    use crate::transaction::dead_letter::{DeadLetter, DeadLetterQueue};
+    use std::sync::Arc;
+
+    let path = std::env::temp_dir().join(format!("suimodular_dead_letters_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let queue = Arc::new(DeadLetterQueue::new(&path));
+    let manager = ExecutionManager::new(None, None, None).with_dead_letter_queue(queue.clone());
+
+    let tx = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 7, "0x5".to_string(), 1000, vec![]);
+    manager.track(&tx, LifecycleState::Received);
+    manager.track(&tx, LifecycleState::Failed("insufficient signatures".to_string()));
+    // The same failure reported again by an outer layer
+    manager.track(&tx, LifecycleState::Failed("L1 submission failed".to_string()));
+
+    let entries = queue.entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].error, "insufficient signatures");
+    assert_eq!(entries[0].payload_hash, tx.payload_hash().unwrap());
+    assert_eq!(entries[0].history.len(), 2);
+    assert_eq!(entries[0].transaction.amount, 7);
+
+    let report = queue.replay(|replayed| async move {
+        assert_eq!(replayed.amount, 7);
+        Ok(())
+    }).await.unwrap();
+    assert_eq!(report.resubmitted, 1);
+    assert!(report.failed.is_empty());
+    assert!(queue.entries().unwrap().is_empty());
+    // The rewrite goes through a temporary copy that is renamed into place
+    assert!(!path.with_extension("jsonl.tmp").exists());
+
+    // A stale entry is dropped instead of resubmitted
+    let mut stale = tx.clone();
+    stale.valid_until = Some(1);
+    manager.track(&stale, LifecycleState::Failed("insufficient signatures".to_string()));
+    let report = queue.replay(|_| async move { Err(anyhow::anyhow!("expired transaction was resubmitted")) }).await.unwrap();
+    assert_eq!(report.resubmitted, 0);
+    assert!(report.failed.is_empty());
+    assert_eq!(report.expired, vec![stale.payload_hash().unwrap()]);
+    assert!(queue.entries().unwrap().is_empty());
+
+    // Failures stay in the log, and entries recorded mid-replay aren't lost
+    let late = Transaction::new(TransactionType::Transfer, SENDER_A.to_string(), SENDER_B.to_string(), 9, "0x5".to_string(), 1000, vec![]);
+    queue.record(&DeadLetter::new(tx.clone(), &tx.payload_hash().unwrap(), "insufficient signatures", Vec::new())).unwrap();
+    let report = queue.replay(|_| {
+        let queue = queue.clone();
+        let late = late.clone();
+        async move {
+            queue.record(&DeadLetter::new(late.clone(), &late.payload_hash().unwrap(), "gas exhausted", Vec::new()))?;
+            Err(anyhow::anyhow!("still failing"))
+        }
+    }).await.unwrap();
+    assert_eq!(report.failed.len(), 1);
+    let remaining: Vec<u64> = queue.entries().unwrap().iter().map(|entry| entry.transaction.amount).collect();
+    assert_eq!(remaining, vec![7, 9]);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_version_conflicts_are_told_apart_from_other_failures() {
+    use crate::transaction::handler::is_version_conflict;
+
+    assert!(is_version_conflict(
+        "Failed to execute L1 verification transaction: Error checking transaction input objects: \
+         ObjectVersionUnavailableForConsumption { provided_obj_ref: (0x5, SequenceNumber(12), o#abc), current_version: SequenceNumber(13) }"
+    ));
+    assert!(is_version_conflict("Object (0x5, 12) already locked by a different transaction"));
+    assert!(!is_version_conflict("L1 verification transaction failed: MoveAbort(verify_signatures, 1)"));
+    assert!(!is_version_conflict("connection refused"));
+}
+
+#[test]
+fn test_tracker_records_each_transition_in_order() {
+    let tracker = TransactionTracker::new();
+    assert!(tracker.is_empty());
+    assert!(tracker.get("missing").is_none());
+    assert!(tracker.current_state("missing").is_none());
+
+    tracker.update("tx1", LifecycleState::Received);
+    tracker.update("tx1", LifecycleState::ValidationPassed);
+    tracker.update("tx1", LifecycleState::Signed);
+    tracker.update("tx2", LifecycleState::Received);
+
+    assert_eq!(tracker.len(), 2);
+    assert_eq!(tracker.current_state("tx1"), Some(LifecycleState::Signed));
+    assert_eq!(tracker.current_state("tx2"), Some(LifecycleState::Received));
+
+    let record = tracker.get("tx1").unwrap();
+    assert_eq!(record.payload_hash, "tx1");
+    let states: Vec<LifecycleState> = record.history.iter().map(|e| e.state.clone()).collect();
+    assert_eq!(states, vec![LifecycleState::Received, LifecycleState::ValidationPassed, LifecycleState::Signed]);
+    assert!(record.history.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    assert_eq!(record.last_updated(), record.history.last().map(|e| e.timestamp));
+}
+
+#[test]
+fn test_tracker_l1_digest_only_attaches_to_known_records() {
+    let tracker = TransactionTracker::new();
+    tracker.set_l1_digest("unknown", "digest");
+    assert!(tracker.is_empty());
+
+    tracker.update("tx1", LifecycleState::Submitted);
+    assert_eq!(tracker.get("tx1").unwrap().l1_digest, None);
+    tracker.set_l1_digest("tx1", "digest");
+    tracker.update("tx1", LifecycleState::Confirmed);
+
+    let record = tracker.get("tx1").unwrap();
+    assert_eq!(record.l1_digest.as_deref(), Some("digest"));
+    assert!(record.state.is_terminal());
+}
+
+#[test]
+fn test_tracker_reports_only_stale_unfinished_transactions() {
+    let tracker = TransactionTracker::new();
+    tracker.update("stuck", LifecycleState::Signed);
+    tracker.update("confirmed", LifecycleState::Confirmed);
+    tracker.update("failed", LifecycleState::Failed("no quorum".to_string()));
+    std::thread::sleep(Duration::from_millis(20));
+    tracker.update("fresh", LifecycleState::Received);
+
+    let stuck: Vec<String> = tracker.stuck_transactions(chrono::Duration::milliseconds(10))
+        .into_iter()
+        .map(|r| r.payload_hash)
+        .collect();
+    assert_eq!(stuck, vec!["stuck"]);
+    assert!(tracker.stuck_transactions(chrono::Duration::hours(1)).is_empty());
+    assert_eq!(LifecycleState::Failed("no quorum".to_string()).to_string(), "failed: no quorum");
+}
+
+#[test]
+fn test_tracker_evicts_finished_records_first() {
+    let tracker = TransactionTracker::with_limits(2, chrono::Duration::hours(1));
+    tracker.update("done", LifecycleState::Confirmed);
+    tracker.update("in_flight", LifecycleState::Signed);
+    tracker.update("new", LifecycleState::Received);
+    assert_eq!(tracker.len(), 2);
+    assert!(tracker.get("done").is_none());
+    assert!(tracker.get("in_flight").is_some());
+
+    // Updating a known record never evicts
+    tracker.update("new", LifecycleState::ValidationPassed);
+    assert_eq!(tracker.len(), 2);
+
+    let expiring = TransactionTracker::with_limits(10, chrono::Duration::milliseconds(10));
+    expiring.update("done", LifecycleState::Failed("no quorum".to_string()));
+    expiring.update("stuck", LifecycleState::Signed);
+    std::thread::sleep(Duration::from_millis(20));
+    expiring.update("fresh", LifecycleState::Received);
+    assert!(expiring.get("done").is_none());
+    assert!(expiring.get("stuck").is_some());
+}
@@ -20,16 +20,39 @@
 //! ```bash
 //! # Run benchmarks with output directory:
 //! cargo run --release -- --benchmark --output-dir benchmark_results_100_iter
+//!
+//! # Fail if resilience drops below the floors in expectations.json:
+//! cargo run --release -- --benchmark --assert-resilience expectations.json
+//!
+//! # Also stream one JSON progress event per iteration to stderr (human output stays on stdout):
+//! cargo run --release -- --benchmark --progress-json 2> progress.jsonl
+//!
+//! # Re-run every scenario, ignoring results already in the output directory:
+//! cargo run --release -- --benchmark --force
+//!
+//! # Fail if end-to-end tail latency exceeds a budget (milliseconds):
+//! cargo run --release -- --benchmark --sla total_iteration_p95=2000,l1_confirmation_p99=3000
+//!
+//! # Submit even when the quorum signs below threshold, to observe on-chain rejection:
+//! cargo run --release -- --benchmark --submit-below-threshold
 //! ```
+//!
+//! Results are checkpointed to `refactored_benchmarks.json` after each scenario,
+//! and a later run against the same output directory skips the scenarios found
+//! there, so a run that dies partway only repeats the unfinished work.
 
 // Standard library imports
 use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::fs;
+use std::io::Write;
 use chrono;
-use rand::Rng;
+use tracing::{debug, error, info, warn};
 
 // Sui SDK imports
 use sui_sdk::{
@@ -40,10 +63,9 @@ use sui_sdk::{
         SuiTransactionBlockEffectsAPI,
     },
     SuiClient,
-    SuiClientBuilder,
     types::{
         base_types::{ObjectID, SuiAddress},
-        crypto::{SuiKeyPair, Signature as SdkSignature},
+        crypto::SuiKeyPair,
         object::Owner,
         Identifier,
     },
@@ -53,13 +75,10 @@ use sui_sdk::{
 use sui_types::{
     quorum_driver_types::ExecuteTransactionRequestType,
     programmable_transaction_builder::ProgrammableTransactionBuilder,
-    transaction::{TransactionData, Transaction, CallArg, ObjectArg},
+    transaction::{TransactionData, CallArg, ObjectArg},
     crypto::EncodeDecodeBase64,
 };
 
-// Shared Crypto imports
-use shared_crypto::intent::{Intent, IntentMessage};
-
 // Crate specific imports
 use crate::{
     config::{self, SUI_TESTNET_RPC},
@@ -70,9 +89,11 @@ use crate::{
         storage::MetricsStorage,
     },
     quorum::simulation::QuorumSimulation,
-    security::audit::SecurityAuditLog,
-    sui::{byzantine::ByzantineDetector, network::{NetworkManager, NetworkType}, verification::VerificationManager},
-    transaction::types::Transaction as MiddlewareTransaction,
+    security::audit::{AuditRetention, SecurityAuditLog},
+    sui::{byzantine::ByzantineDetector, network::{NetworkManager, NetworkType}, signing::build_signed_transaction, verification::VerificationManager},
+    transaction::types::{Transaction as MiddlewareTransaction, TransactionType},
+    tools::reconnect::{connect_sui, ReconnectingClient},
+    transaction::handler::{is_version_conflict, MAX_VERSION_CONFLICT_RETRIES},
 };
 
 /// Number of iterations to run per benchmark scenario.
@@ -81,6 +102,396 @@ pub const BENCHMARK_ITERATIONS: usize = 100;
 /// Byzantine percentages to test (as decimals).
 pub const BYZANTINE_PERCENTAGES: [f64; 6] = [0.0, 0.1, 0.2, 0.33, 0.5, 0.75];
 
+/// Percentages closer than this are treated as the same scenario.
+const PERCENTAGE_TOLERANCE: f64 = 1e-6;
+
+/// MIST per SUI.
+const MIST_PER_SUI: f64 = 1_000_000_000.0;
+
+/// Config name of the end-to-end scenario.
+pub const END_TO_END_SCENARIO: &str = "end_to_end_performance_n5";
+
+/// Results file in the output directory, rewritten after every scenario.
+pub const RESULTS_FILE: &str = "refactored_benchmarks.json";
+
+/// Config name of the resilience scenario at `percentage` (0.0-1.0).
+pub fn byzantine_scenario_name(percentage: f64) -> String {
+    format!("byzantine_resilience_n5_{:.0}pct", percentage * 100.0)
+}
+
+/// Gas charged across the transactions of one benchmark scenario.
+///
+/// Amounts are net MIST (computation + storage - storage rebate), taken from each
+/// transaction's effects. Failed executions are charged gas too, so they count.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GasUsage {
+    pub transactions: u64,
+    pub total_mist: i64,
+    pub max_mist: i64,
+}
+
+impl GasUsage {
+    pub fn record(&mut self, net_gas_mist: i64) {
+        if self.transactions == 0 || net_gas_mist > self.max_mist {
+            self.max_mist = net_gas_mist;
+        }
+        self.transactions += 1;
+        self.total_mist += net_gas_mist;
+    }
+
+    pub fn average_mist(&self) -> f64 {
+        if self.transactions == 0 {
+            0.0
+        } else {
+            self.total_mist as f64 / self.transactions as f64
+        }
+    }
+
+    pub fn total_sui(&self) -> f64 {
+        self.total_mist as f64 / MIST_PER_SUI
+    }
+
+    /// Combine with another scenario's usage.
+    pub fn merge(&mut self, other: &GasUsage) {
+        if other.transactions == 0 {
+            return;
+        }
+        if self.transactions == 0 || other.max_mist > self.max_mist {
+            self.max_mist = other.max_mist;
+        }
+        self.transactions += other.transactions;
+        self.total_mist += other.total_mist;
+    }
+
+    /// Store the usage in `benchmark`'s configuration.
+    pub fn add_to_config(&self, benchmark: &mut ComponentBenchmark) {
+        benchmark.add_config("gas_transactions", &self.transactions.to_string());
+        benchmark.add_config("gas_total_mist", &self.total_mist.to_string());
+        benchmark.add_config("gas_avg_mist", &format!("{:.0}", self.average_mist()));
+        benchmark.add_config("gas_max_mist", &self.max_mist.to_string());
+    }
+
+    /// Usage recorded in `benchmark`'s configuration by `add_to_config`, zero if absent.
+    pub fn from_config(benchmark: &ComponentBenchmark) -> Self {
+        let read = |key: &str| benchmark.configuration.get(key).and_then(|v| v.parse::<i64>().ok()).unwrap_or(0);
+        Self {
+            transactions: read("gas_transactions") as u64,
+            total_mist: read("gas_total_mist"),
+            max_mist: read("gas_max_mist"),
+        }
+    }
+
+    /// One summary line, e.g. `100 txs, total 0.215000 SUI (avg 2150000 MIST, max 2300000 MIST)`.
+    pub fn summary_line(&self) -> String {
+        format!(
+            "{} txs, total {:.6} SUI (avg {:.0} MIST, max {} MIST)",
+            self.transactions, self.total_sui(), self.average_mist(), self.max_mist
+        )
+    }
+}
+
+/// L1 submissions made with fewer signatures than the quorum threshold (`--submit-below-threshold`).
+///
+/// The verification contract should reject every one of them. An accepted
+/// submission means the contract doesn't enforce the threshold.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShortfallSubmissions {
+    pub submitted: u64,
+    /// Executed and failed on-chain, as expected
+    pub rejected: u64,
+    /// Executed successfully despite the missing signatures
+    pub accepted: u64,
+    /// No on-chain outcome: the RPC call failed or returned no effects
+    pub inconclusive: u64,
+    /// Gas charged for the executed submissions
+    pub gas: GasUsage,
+}
+
+impl ShortfallSubmissions {
+    /// Record an executed submission and whether the contract accepted it.
+    pub fn record_execution(&mut self, accepted: bool, net_gas_mist: i64) {
+        self.submitted += 1;
+        if accepted {
+            self.accepted += 1;
+        } else {
+            self.rejected += 1;
+        }
+        self.gas.record(net_gas_mist);
+    }
+
+    pub fn record_inconclusive(&mut self) {
+        self.submitted += 1;
+        self.inconclusive += 1;
+    }
+
+    /// Whether every submission with an on-chain outcome was rejected.
+    pub fn enforced(&self) -> bool {
+        self.accepted == 0
+    }
+
+    /// Store the counts in `benchmark`'s configuration.
+    pub fn add_to_config(&self, benchmark: &mut ComponentBenchmark) {
+        benchmark.add_config("below_threshold_submissions", &self.submitted.to_string());
+        benchmark.add_config("below_threshold_rejected", &self.rejected.to_string());
+        benchmark.add_config("below_threshold_accepted", &self.accepted.to_string());
+        benchmark.add_config("below_threshold_inconclusive", &self.inconclusive.to_string());
+        benchmark.add_config("below_threshold_gas_avg_mist", &format!("{:.0}", self.gas.average_mist()));
+        benchmark.add_config("below_threshold_gas_max_mist", &self.gas.max_mist.to_string());
+    }
+
+    /// One summary line, e.g. `20 submitted: 19 rejected, 0 accepted, 1 inconclusive (avg 1200000 MIST)`.
+    pub fn summary_line(&self) -> String {
+        format!(
+            "{} submitted: {} rejected, {} accepted, {} inconclusive (avg {:.0} MIST)",
+            self.submitted, self.rejected, self.accepted, self.inconclusive, self.gas.average_mist()
+        )
+    }
+}
+
+/// Progress of one benchmark scenario, emitted as a JSON line with `--progress-json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProgressEvent {
+    /// Benchmark config name, e.g. `byzantine_resilience_n5_33pct`
+    pub scenario: String,
+    /// Iterations completed so far; equals `total` on the scenario's last event
+    pub iteration: usize,
+    pub total: usize,
+    /// Confirmed fraction (0.0-1.0) of the completed iterations
+    pub success_rate: f64,
+    /// Time since the scenario started
+    pub elapsed_ms: u64,
+}
+
+impl ProgressEvent {
+    pub fn new(scenario: &str, completed: usize, total: usize, successes: usize, started: Instant) -> Self {
+        Self {
+            scenario: scenario.to_string(),
+            iteration: completed,
+            total,
+            success_rate: if completed > 0 { successes as f64 / completed as f64 } else { 0.0 },
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// Writes `ProgressEvent`s to stderr when enabled, leaving stdout to the human-readable output.
+///
+/// While enabled, stderr carries nothing but events, so the benchmark's own
+/// error lines go through `human_error` to stdout instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProgressReporter {
+    enabled: bool,
+}
+
+impl ProgressReporter {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    pub fn report(&self, event: ProgressEvent) {
+        if !self.enabled {
+            return;
+        }
+        match serde_json::to_string(&event) {
+            // One write per event, so concurrent output can't split a line
+            Ok(line) => {
+                let _ = writeln!(std::io::stderr().lock(), "{}", line);
+            }
+            Err(e) => warn!("Could not serialize progress event: {}", e),
+        }
+    }
+
+    /// Print a human-readable error line: to stderr normally, to stdout while
+    /// stderr is reserved for events.
+    pub fn human_error(&self, line: &str) {
+        if self.enabled {
+            println!("{}", line);
+        } else {
+            eprintln!("{}", line);
+        }
+    }
+
+}
+
+/// Benchmarks from an earlier run's results that finished all `BENCHMARK_ITERATIONS`.
+///
+/// Scenarios are only written to the results file once complete, but a file
+/// from a run with a different iteration count doesn't count as done.
+pub fn completed_benchmarks(benchmarks: Vec<ComponentBenchmark>) -> Vec<ComponentBenchmark> {
+    benchmarks
+        .into_iter()
+        .filter(|b| b.iterations as usize == BENCHMARK_ITERATIONS)
+        .collect()
+}
+
+/// Write everything recorded so far, so an interrupted run can resume.
+fn checkpoint(metrics_storage: &MetricsStorage, output_dir: &str) {
+    let results_file = format!("{}/{}", output_dir, RESULTS_FILE);
+    if let Err(e) = metrics_storage.save_benchmarks_to_json_file(&results_file) {
+        warn!("Failed to checkpoint benchmark results to {}: {}", results_file, e);
+    }
+}
+
+/// Minimum acceptable success rate (0.0-1.0) per Byzantine percentage (0.0-1.0).
+pub type ResilienceExpectations = Vec<(f64, f64)>;
+
+/// Read resilience floors from a JSON object such as `{"0.0": 1.0, "0.33": 0.9}`.
+///
+/// Keys and values are fractions, matching `BYZANTINE_PERCENTAGES` and the
+/// `verification_success_rate` recorded by the resilience benchmark.
+pub fn load_resilience_expectations(path: &str) -> Result<ResilienceExpectations> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read resilience expectations {}", path))?;
+    let raw: HashMap<String, f64> = serde_json::from_str(&contents)
+        .with_context(|| format!("Resilience expectations {} must map percentages to success rates", path))?;
+
+    let mut expectations = Vec::with_capacity(raw.len());
+    for (key, min_success_rate) in raw {
+        let percentage = key.trim().parse::<f64>()
+            .map_err(|_| anyhow!("Invalid Byzantine percentage '{}' in {}", key, path))?;
+        if !(0.0..=1.0).contains(&percentage) || !(0.0..=1.0).contains(&min_success_rate) {
+            return Err(anyhow!("Expectation {} -> {} in {} must use fractions between 0 and 1", key, min_success_rate, path));
+        }
+        expectations.push((percentage, min_success_rate));
+    }
+    expectations.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(expectations)
+}
+
+/// A resilience scenario that missed its expected success rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResilienceViolation {
+    pub byzantine_percentage: f64,
+    pub min_success_rate: f64,
+    /// `None` if no benchmark was recorded for this percentage
+    pub actual_success_rate: Option<f64>,
+}
+
+impl fmt::Display for ResilienceViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.actual_success_rate {
+            Some(actual) => write!(
+                f,
+                "{:.1}% Byzantine: success rate {:.1}% is below the expected minimum {:.1}%",
+                self.byzantine_percentage * 100.0, actual * 100.0, self.min_success_rate * 100.0
+            ),
+            None => write!(
+                f,
+                "{:.1}% Byzantine: no benchmark result recorded (expected at least {:.1}% success)",
+                self.byzantine_percentage * 100.0, self.min_success_rate * 100.0
+            ),
+        }
+    }
+}
+
+/// Compare recorded resilience benchmarks against the expected floors.
+pub fn check_resilience(metrics_storage: &MetricsStorage, expectations: &[(f64, f64)]) -> Vec<ResilienceViolation> {
+    let recorded: Vec<(f64, f64)> = metrics_storage.get_all_benchmarks()
+        .iter()
+        .filter_map(|b| {
+            let percentage = b.configuration.get("byzantine_percentage")?.parse::<f64>().ok()?;
+            let rate = b.configuration.get("verification_success_rate")?.parse::<f64>().ok()?;
+            Some((percentage, rate))
+        })
+        .collect();
+
+    expectations.iter()
+        .filter_map(|&(percentage, min_success_rate)| {
+            let actual = recorded.iter()
+                .find(|(p, _)| (p - percentage).abs() < PERCENTAGE_TOLERANCE)
+                .map(|(_, rate)| *rate);
+            match actual {
+                Some(rate) if rate >= min_success_rate => None,
+                _ => Some(ResilienceViolation {
+                    byzantine_percentage: percentage,
+                    min_success_rate,
+                    actual_success_rate: actual,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// A latency budget for one operation of the end-to-end benchmark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlaBudget {
+    /// Operation name as recorded by the benchmark, e.g. `total_iteration`
+    pub operation: String,
+    /// Percentile in (0, 100]
+    pub percentile: f64,
+    pub budget_ms: u64,
+}
+
+/// Parse `--sla` budgets written `<operation>_p<percentile>=<ms>`, comma separated.
+///
+/// The percentile may be fractional (`l1_submission_p99.9=5000`).
+pub fn parse_sla(spec: &str) -> Result<Vec<SlaBudget>> {
+    let mut budgets = Vec::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (key, budget) = entry.split_once('=')
+            .ok_or_else(|| anyhow!("SLA entry '{}' must be written <operation>_p<percentile>=<ms>", entry))?;
+        let (operation, percentile) = key.trim().rsplit_once("_p")
+            .ok_or_else(|| anyhow!("SLA entry '{}' does not name a percentile (e.g. total_iteration_p95)", entry))?;
+        let percentile: f64 = percentile.parse()
+            .map_err(|_| anyhow!("Invalid percentile '{}' in SLA entry '{}'", percentile, entry))?;
+        if operation.is_empty() || !(percentile > 0.0 && percentile <= 100.0) {
+            return Err(anyhow!("SLA entry '{}' needs an operation and a percentile between 0 and 100", entry));
+        }
+        let budget_ms: u64 = budget.trim().parse()
+            .map_err(|_| anyhow!("Invalid budget '{}' in SLA entry '{}', expected milliseconds", budget, entry))?;
+        budgets.push(SlaBudget { operation: operation.to_string(), percentile, budget_ms });
+    }
+    if budgets.is_empty() {
+        return Err(anyhow!("--sla needs at least one budget"));
+    }
+    Ok(budgets)
+}
+
+/// The measured percentile for one `SlaBudget`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlaCheck {
+    pub budget: SlaBudget,
+    /// `None` if the operation has no recorded samples
+    pub actual_ms: Option<u64>,
+}
+
+impl SlaCheck {
+    pub fn passed(&self) -> bool {
+        matches!(self.actual_ms, Some(actual) if actual <= self.budget.budget_ms)
+    }
+}
+
+/// Measure every budget against the `scenario` benchmark in `metrics_storage`.
+pub fn check_sla(metrics_storage: &MetricsStorage, scenario: &str, budgets: &[SlaBudget]) -> Vec<SlaCheck> {
+    let benchmark = metrics_storage.get_all_benchmarks().into_iter().find(|b| b.component_name == scenario);
+    budgets.iter()
+        .map(|budget| SlaCheck {
+            budget: budget.clone(),
+            actual_ms: benchmark.as_ref()
+                .and_then(|b| b.get_operation_stats(&budget.operation))
+                .and_then(|stats| stats.percentile(budget.percentile)),
+        })
+        .collect()
+}
+
+/// Pass/fail table of SLA checks.
+pub fn sla_table(checks: &[SlaCheck]) -> String {
+    let mut table = format!("{:<32} {:>8} {:>10} {:>10}  {}\n", "Operation", "Pctl", "Budget ms", "Actual ms", "Result");
+    for check in checks {
+        let percentile = format!("p{}", check.budget.percentile);
+        let actual = check.actual_ms.map_or_else(|| "-".to_string(), |ms| ms.to_string());
+        table.push_str(&format!(
+            "{:<32} {:>8} {:>10} {:>10}  {}\n",
+            check.budget.operation,
+            percentile,
+            check.budget.budget_ms,
+            actual,
+            if check.passed() { "PASS" } else { "FAIL" },
+        ));
+    }
+    table
+}
+
 /// Main entry point for running comprehensive benchmarks.
 /// 
 /// Runs all benchmark scenarios (End-to-End, Byzantine Resilience)
@@ -88,32 +499,78 @@ pub const BYZANTINE_PERCENTAGES: [f64; 6] = [0.0, 0.1, 0.2, 0.33, 0.5, 0.75];
 ///
 /// # Parameters
 /// * `output_dir` - Directory to save benchmark results (e.g., "benchmark_results_100_iter").
+/// * `expectations_path` - Optional resilience floors (see `load_resilience_expectations`).
+///   When set, the run returns an error if any scenario falls below its floor.
+/// * `progress_json` - Emit a `ProgressEvent` JSON line to stderr at the
+///   start of each scenario and after every iteration.
+/// * `force` - Re-run every scenario instead of skipping those already
+///   completed in `output_dir`.
+/// * `sla_spec` - Optional latency budgets for the end-to-end scenario (see
+///   `parse_sla`). When set, the run returns an error if any is exceeded.
+/// * `submit_below_threshold` - Submit to L1 with whatever signatures were
+///   collected, instead of skipping iterations short of the threshold. The
+///   outcomes are recorded as `ShortfallSubmissions`.
 ///
 /// # Returns
 /// Result indicating success or error.
-pub async fn run_comprehensive_benchmarks(output_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn run_comprehensive_benchmarks(
+    output_dir: &str,
+    expectations_path: Option<&str>,
+    progress_json: bool,
+    force: bool,
+    sla_spec: Option<&str>,
+    submit_below_threshold: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let progress = ProgressReporter::new(progress_json);
     let start_time = Instant::now();
-    println!("Running comprehensive middleware benchmarks...");
-    println!("Output Directory: {}", output_dir);
-    println!("Iterations per scenario: {}", BENCHMARK_ITERATIONS);
+    // Load expectations and budgets before the run so bad input fails fast
+    let expectations = expectations_path.map(load_resilience_expectations).transpose()?;
+    let sla_budgets = sla_spec.map(parse_sla).transpose()?;
+    info!("Running comprehensive middleware benchmarks");
+    info!("Output Directory: {}", output_dir);
+    info!("Iterations per scenario: {}", BENCHMARK_ITERATIONS);
+    if submit_below_threshold {
+        info!("Submitting below-threshold signature sets to L1 to observe rejection");
+    }
     
     // Create output directory if it doesn't exist.
     fs::create_dir_all(output_dir)?;
     let metrics_storage = Arc::new(MetricsStorage::new());
 
+    // Resume: keep the scenarios an earlier run already finished
+    let results_file = format!("{}/{}", output_dir, RESULTS_FILE);
+    let mut gas_by_scenario: Vec<(String, GasUsage)> = Vec::new();
+    let mut completed = HashSet::new();
+    if !force && std::path::Path::new(&results_file).exists() {
+        let previous = MetricsStorage::read_benchmarks_from_json_file(&results_file)
+            .with_context(|| format!("Failed to read earlier results {} (use --force to start over)", results_file))?;
+        for benchmark in completed_benchmarks(previous) {
+            completed.insert(benchmark.component_name.clone());
+            gas_by_scenario.push((benchmark.component_name.clone(), GasUsage::from_config(&benchmark)));
+            metrics_storage.add_benchmark(benchmark);
+        }
+        if !completed.is_empty() {
+            let mut names: Vec<&String> = completed.iter().collect();
+            names.sort();
+            info!("Resuming: skipping {} completed scenario(s): {:?}", completed.len(), names);
+        }
+    }
+
     // Initialize shared components.
-    let security_audit_log = Arc::new(SecurityAuditLog::new());
+    let security_audit_log = Arc::new(SecurityAuditLog::new().with_retention(AuditRetention::from_env()?));
     let network_manager = Arc::new(crate::sui::network::NetworkManager::new(NetworkType::Testnet).await?);
     
     let rpc_url = network_manager.get_active_rpc_url()?;
     // Create VerificationManager directly, no Arc needed for ExecutionManager::new
     let verification_manager = VerificationManager::new(&rpc_url);
     let byzantine_detector = Arc::new(ByzantineDetector::new(
-        network_manager.get_active_config().get_rpc_endpoints().clone(),
+        network_manager.cross_verification_endpoints().await,
         Some(security_audit_log.clone()),
         None,
         None,
-    ));
+        None,
+        None,
+    )?);
     let execution_manager = Arc::new(ExecutionManager::new(
         Some(verification_manager.clone()), // Pass clone of VM
         Some(network_manager.clone()),
@@ -127,28 +584,35 @@ pub async fn run_comprehensive_benchmarks(output_dir: &str) -> Result<(), Box<dy
     let submitter_keypair = load_keypair()?;
     
     // --- Connect to Sui RPC ---
-    let sui_client: Arc<SuiClient> = Arc::new(SuiClientBuilder::default().build(SUI_TESTNET_RPC).await?);
+    let sui_client = Arc::new(connect_sui(SUI_TESTNET_RPC).await?);
         
     // Parse gas object ID from config.
     let gas_object_id = ObjectID::from_str(config::SUBMITTER_GAS_OBJECT_ID)?;
 
     // --- Run Benchmark Scenarios (Fixed n=5) --- 
 
-    println!("\nRunning End-to-End Performance Benchmark (n=5)...");
-    run_end_to_end_performance(
-        output_dir,
-        metrics_storage.clone(),
-        execution_manager.clone(),
-        security_audit_log.clone(),
-        sui_client.clone(),
-        quorum_simulation.clone(),
-        &submitter_keypair, // Pass reference to keypair
-        &gas_object_id,
-    ).await?;
+    if completed.contains(END_TO_END_SCENARIO) {
+        info!("Skipping End-to-End Performance Benchmark (n=5): already completed");
+    } else {
+        info!("Running End-to-End Performance Benchmark (n=5)");
+        let e2e_gas = run_end_to_end_performance(
+            output_dir,
+            metrics_storage.clone(),
+            execution_manager.clone(),
+            security_audit_log.clone(),
+            sui_client.clone(),
+            quorum_simulation.clone(),
+            &submitter_keypair, // Pass reference to keypair
+            &gas_object_id,
+            progress,
+            submit_below_threshold,
+        ).await?;
+        gas_by_scenario.push((END_TO_END_SCENARIO.to_string(), e2e_gas));
+    }
     
     // Run Byzantine resilience testing.
-    println!("\nRunning Byzantine Resilience Benchmarks (n=5)...");
-        run_byzantine_resilience(
+    info!("Running Byzantine Resilience Benchmarks (n=5)");
+        let byzantine_gas = run_byzantine_resilience(
             output_dir,
             metrics_storage.clone(),
             execution_manager.clone(),
@@ -156,12 +620,22 @@ pub async fn run_comprehensive_benchmarks(output_dir: &str) -> Result<(), Box<dy
             quorum_simulation.clone(),
         &submitter_keypair, // Pass reference to keypair
         &gas_object_id,
+        progress,
+        &completed,
+        submit_below_threshold,
         ).await?;
+    gas_by_scenario.extend(byzantine_gas);
+
+    let mut total_gas = GasUsage::default();
+    for (_, usage) in &gas_by_scenario {
+        total_gas.merge(usage);
+    }
     
     // --- Save Benchmark Results ---
-    let results_file = format!("{}/refactored_benchmarks.json", output_dir);
     metrics_storage.save_benchmarks_to_json_file(&results_file)?;
     metrics_storage.print_benchmark_summary();
+    let resilience_table = metrics_storage.byzantine_resilience_table();
+    println!("\n=== BYZANTINE RESILIENCE (n=5) ===\n{}", resilience_table);
     
     // --- Generate Summary File --- 
     let summary_path = format!("{}/benchmark_summary.txt", output_dir);
@@ -174,38 +648,106 @@ pub async fn run_comprehensive_benchmarks(output_dir: &str) -> Result<(), Box<dy
     summary.push_str("Quorum Size: n=5, Threshold t=4 (2f+1 for f=1)\n");
     summary.push_str(&format!("Iterations per scenario: {}\n", BENCHMARK_ITERATIONS));
     summary.push_str(&format!("Byzantine percentages tested: {:?}\n", BYZANTINE_PERCENTAGES.iter().map(|p| format!("{:.1}%", p * 100.0)).collect::<Vec<_>>() ));
+    summary.push_str("\nByzantine Resilience:\n");
+    summary.push_str(&resilience_table);
+    summary.push_str("\nGas Consumed:\n");
+    for (scenario, usage) in &gas_by_scenario {
+        summary.push_str(&format!("  {}: {}\n", scenario, usage.summary_line()));
+    }
+    summary.push_str(&format!("  Total: {}\n", total_gas.summary_line()));
     fs::write(&summary_path, summary)?;
     println!("Benchmark summary written to {}", summary_path);
     
     println!("\nBenchmarks completed successfully!");
     println!("Results JSON written to {}", results_file);
     println!("Summary text written to {}", summary_path);
+    println!("Estimated gas cost of this run: {:.6} SUI over {} transactions", total_gas.total_sui(), total_gas.transactions);
+
+    if let Some(expectations) = expectations {
+        let violations = check_resilience(&metrics_storage, &expectations);
+        if !violations.is_empty() {
+            progress.human_error("\n❌ Resilience assertions failed:");
+            for violation in &violations {
+                progress.human_error(&format!("  - {}", violation));
+            }
+            return Err(anyhow!("{} of {} resilience expectations not met", violations.len(), expectations.len()).into());
+        }
+        println!("✅ All {} resilience expectations met.", expectations.len());
+    }
+
+    if let Some(budgets) = sla_budgets {
+        let checks = check_sla(&metrics_storage, END_TO_END_SCENARIO, &budgets);
+        println!("\n=== LATENCY SLA ({}) ===\n{}", END_TO_END_SCENARIO, sla_table(&checks));
+        let failed = checks.iter().filter(|check| !check.passed()).count();
+        if failed > 0 {
+            return Err(anyhow!("{} of {} latency budgets exceeded", failed, checks.len()).into());
+        }
+        println!("✅ All {} latency budgets met.", checks.len());
+    }
     
     Ok(())
 }
 
+/// Canonical digest of a unique per-iteration transaction, used as the payload the quorum signs.
+fn benchmark_payload(iteration: usize, sender: &SuiAddress, gas_object_id: &ObjectID) -> Result<Vec<u8>> {
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_millis();
+    let tx = MiddlewareTransaction::new(
+        TransactionType::Custom("benchmark".to_string()),
+        sender.to_string(),
+        sender.to_string(),
+        iteration as u64,
+        gas_object_id.to_string(),
+        100_000_000,
+        vec![format!("benchmark_iteration:{}:{}", iteration, now_ms)], // Unique across runs
+    );
+    Ok(tx.digest()?.to_vec())
+}
+
+/// Print the outcome of a scenario's below-threshold submissions.
+fn report_shortfall(scenario: &str, shortfall: &ShortfallSubmissions) {
+    if shortfall.submitted == 0 {
+        return;
+    }
+    if shortfall.enforced() {
+        println!("    Below-threshold submissions ({}): {}", scenario, shortfall.summary_line());
+    } else {
+        warn!("Contract accepted below-threshold submissions ({}): {}", scenario, shortfall.summary_line());
+    }
+}
+
 /// Runs the end-to-end performance benchmark.
 ///
 /// Measures baseline performance with no Byzantine faults (0%).
 /// Simulates payload generation, quorum signing, and L1 verification submission.
+#[tracing::instrument(
+    name = "benchmark",
+    skip_all,
+    fields(scenario = END_TO_END_SCENARIO, network = "testnet", byzantine_pct = 0.0)
+)]
 async fn run_end_to_end_performance(
-    _output_dir: &str, // Parameter kept for consistency, but not used directly here
+    output_dir: &str, // Results are checkpointed here once the scenario completes
     metrics_storage: Arc<MetricsStorage>,
-    _execution_manager: Arc<ExecutionManager>, // Not directly used for submission logic here
+    execution_manager: Arc<ExecutionManager>, // Only used for explorer links; submission is done directly here
     _security_audit_log: Arc<SecurityAuditLog>, // Not directly used for submission logic here
-    sui_client: Arc<SuiClient>,
+    sui_client: Arc<ReconnectingClient<SuiClient>>,
     quorum_simulation: Arc<QuorumSimulation>,
     submitter_sui_keypair: &SuiKeyPair, // Take reference
     gas_object_id: &ObjectID,
-) -> Result<(), anyhow::Error> {
+    progress: ProgressReporter,
+    submit_below_threshold: bool,
+) -> Result<GasUsage> {
     // Implementation largely unchanged, comments refined...
-    println!("  Running End-to-End Performance Benchmark (0% Byzantine)...");
-    let config_name = "end_to_end_performance_n5";
+    info!("Running End-to-End Performance Benchmark (0% Byzantine)");
+    let config_name = END_TO_END_SCENARIO;
     let security_level = "0_percent_byzantine";
     let mut benchmark = ComponentBenchmark::new(config_name, security_level, BENCHMARK_ITERATIONS as u32);
     benchmark.add_config("num_transactions", &BENCHMARK_ITERATIONS.to_string());
     benchmark.add_config("quorum_size", &quorum_simulation.keypairs.len().to_string());
     benchmark.add_config("byzantine_percentage", "0.0");
+    let mut gas_usage = GasUsage::default();
+    let mut shortfall = ShortfallSubmissions::default();
 
     let mut successful_submissions = 0;
     let mut successful_confirmations = 0;
@@ -220,22 +762,20 @@ async fn run_end_to_end_performance(
     let function_name = Identifier::from_str(config::VERIFICATION_CONTRACT_FUNCTION)?;
     let config_object_id = ObjectID::from_str(config::VERIFICATION_CONTRACT_CONFIG_OBJECT_ID)?;
 
-    println!("    Using Submitter Address: {}", l1_submission_address);
-    println!("    Using Gas Object ID: {}", gas_object_id);
-    println!("    Using Config Object ID: {}", config_object_id);
+    debug!(submitter = %l1_submission_address, gas_object = %gas_object_id, config_object = %config_object_id, "Benchmark accounts resolved");
 
-    for i in 0..BENCHMARK_ITERATIONS {
+    let scenario_start = Instant::now();
+    let mut version_conflicts_retried = 0;
+    let mut failure_version_conflict = 0;
+    'iterations: for i in 0..BENCHMARK_ITERATIONS {
+        // Reported here rather than at the end so iterations that bail out early still count
+        progress.report(ProgressEvent::new(config_name, i, BENCHMARK_ITERATIONS, successful_confirmations, scenario_start));
         let iteration_start = Instant::now();
+        let client = sui_client.current();
 
         // 1. Generate unique payload for this iteration
         let processing_start = Instant::now();
-        let mut unique_payload = vec![0u8; 32]; // Simulate a unique payload hash
-        let now_ms = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)?
-                        .as_millis();
-        unique_payload[0..8].copy_from_slice(&i.to_le_bytes());
-        unique_payload[8..24].copy_from_slice(&now_ms.to_le_bytes());
-        rand::thread_rng().fill(&mut unique_payload[24..]);
+        let unique_payload = benchmark_payload(i, &l1_submission_address, gas_object_id)?;
 
         // 2. Request signatures from the quorum simulation (0% Byzantine here)
         let quorum_size = quorum_simulation.keypairs.len();
@@ -244,7 +784,7 @@ async fn run_end_to_end_performance(
         let all_signatures = match quorum_simulation.request_signatures(unique_payload.clone()).await {
              Ok(sigs) => sigs,
              Err(e) => { 
-                 eprintln!("ERROR: Failed to get signatures in iteration {}: {}", i, e);
+                 error!("Failed to get signatures in iteration {}: {}", i, e);
                  // Decide how to handle: skip iteration? record failure?
                  // Skipping for now, but might want failure counter.
                  continue;
@@ -252,10 +792,14 @@ async fn run_end_to_end_performance(
          };
         
         // Ensure enough signatures were obtained (should always pass with 0% Byzantine)
-        if all_signatures.len() < quorum_threshold {
-             eprintln!("ERROR: Not enough signatures ({}/{}) obtained for threshold ({}) in iteration {} (0% Byzantine)", 
+        let below_threshold = all_signatures.len() < quorum_threshold;
+        if below_threshold {
+             error!("Not enough signatures ({}/{}) obtained for threshold ({}) in iteration {} (0% Byzantine)", 
                       all_signatures.len(), quorum_size, quorum_threshold, i);
-             continue;
+             if !submit_below_threshold {
+                 continue;
+             }
+             info!("Submitting {} signature(s) anyway; expecting on-chain rejection", all_signatures.len());
          }
         
         // Extract signatures (bytes) needed for the Move contract call
@@ -275,76 +819,112 @@ async fn run_end_to_end_performance(
         let signatures_bcs = match bcs::to_bytes(&signatures_for_move) {
              Ok(bytes) => bytes,
              Err(e) => { 
-                 eprintln!("ERROR: Failed to serialize signatures in iteration {}: {}", i, e);
+                 error!("Failed to serialize signatures in iteration {}: {}", i, e);
                  continue;
              }
          };
 
-        // Fetch the latest gas object reference
-        let gas_object_response = sui_client.read_api().get_object_with_options(
-            *gas_object_id,
-            SuiObjectDataOptions::new().with_owner().with_previous_transaction()
-        ).await.context(format!("Failed to fetch gas object {}", gas_object_id))?;
-
-        let gas_object_ref = gas_object_response.object_ref_if_exists()
-            .ok_or_else(|| anyhow!("Gas object {} not found or deleted", gas_object_id))?;
-
-        // Fetch the latest Config Object version (it's a shared object)
-        let config_object_response = sui_client.read_api().get_object_with_options(
-            config_object_id,
-            SuiObjectDataOptions::new().with_owner()
-        ).await.context(format!("Failed to fetch config object {}", config_object_id))?;
-
-        let config_object_version = config_object_response.owner()
-             .and_then(|owner_enum| match owner_enum {
-                 Owner::Shared { initial_shared_version } => Some(initial_shared_version),
-                 _ => None,
-             })
-            .ok_or_else(|| anyhow!("Could not get initial shared version for config object {}", config_object_id))?;
-
-        // Fetch the current reference gas price
-        let reference_gas_price = sui_client.read_api().get_reference_gas_price().await?;
-
-        // Serialize the unique payload for Move contract argument
-        let payload_bcs = bcs::to_bytes(&unique_payload)
-            .context("Failed to serialize payload")?;
-
-        // Build the Programmable Transaction Block (PTB)
-        let pt = {
-             let mut builder = ProgrammableTransactionBuilder::new();
-             // Define arguments for the Move call
-             let config_call_arg = CallArg::Object(ObjectArg::SharedObject { id: config_object_id, initial_shared_version: config_object_version, mutable: true });
-             let payload_call_arg = CallArg::Pure(payload_bcs);
-             let sigs_call_arg = CallArg::Pure(signatures_bcs);
-             // Create the Move call to the verification function
-             builder.move_call( package_id, module_name.clone(), function_name.clone(), vec![], vec![config_call_arg, payload_call_arg, sigs_call_arg] )?;
-             builder.finish()
-         };
+        // Owned-object version conflicts (e.g. a gas coin changed by another
+        // submission) are retried with freshly fetched object refs
+        let mut version_conflict_retries = 0;
+        let transaction_response_result = loop {
+            // Fetch the latest gas object reference
+            let gas_object_response = match client.read_api().get_object_with_options(
+                *gas_object_id,
+                SuiObjectDataOptions::new().with_owner().with_previous_transaction()
+            ).await {
+                Ok(response) => response,
+                Err(e) => {
+                    if sui_client.recover_from(&e).await {
+                        error!("RPC connection lost fetching gas object in iteration {}; reconnected", i);
+                        continue 'iterations;
+                    }
+                    return Err(anyhow::Error::new(e).context(format!("Failed to fetch gas object {}", gas_object_id)));
+                }
+            };
+
+            let gas_object_ref = gas_object_response.object_ref_if_exists()
+                .ok_or_else(|| anyhow!("Gas object {} not found or deleted", gas_object_id))?;
+
+            // Fetch the latest Config Object version (it's a shared object)
+            let config_object_response = match client.read_api().get_object_with_options(
+                config_object_id,
+                SuiObjectDataOptions::new().with_owner()
+            ).await {
+                Ok(response) => response,
+                Err(e) => {
+                    if sui_client.recover_from(&e).await {
+                        error!("RPC connection lost fetching config object in iteration {}; reconnected", i);
+                        continue 'iterations;
+                    }
+                    return Err(anyhow::Error::new(e).context(format!("Failed to fetch config object {}", config_object_id)));
+                }
+            };
 
-        // Create the transaction data
-        let tx_data = TransactionData::new_programmable(
-             l1_submission_address,
-             vec![gas_object_ref],
-             pt,
-             100_000_000, // Increased gas budget for safety
-             reference_gas_price
-         );
-
-        // Sign the transaction data
-        let intent = Intent::sui_transaction();
-        let intent_message = IntentMessage::new(intent, tx_data.clone());
-        // Pass the keypair reference directly
-        let sdk_signature = SdkSignature::new_secure(&intent_message, submitter_sui_keypair);
-
-        // Create the final transaction object
-        let transaction = Transaction::from_data(tx_data, vec![sdk_signature.into()]);
-
-        // Execute the transaction on the Sui network
-        let transaction_response_result = sui_client.quorum_driver_api().execute_transaction_block(
-            transaction,
-            SuiTransactionBlockResponseOptions::new().with_effects(), // Request effects to check status
-            Some(ExecuteTransactionRequestType::WaitForLocalExecution) // Wait for node execution
-                ).await;
+            let config_object_version = config_object_response.owner()
+                 .and_then(|owner_enum| match owner_enum {
+                     Owner::Shared { initial_shared_version } => Some(initial_shared_version),
+                     _ => None,
+                 })
+                .ok_or_else(|| anyhow!("Could not get initial shared version for config object {}", config_object_id))?;
+
+            // Fetch the current reference gas price
+            let reference_gas_price = match client.read_api().get_reference_gas_price().await {
+                Ok(price) => price,
+                Err(e) => {
+                    if sui_client.recover_from(&e).await {
+                        error!("RPC connection lost fetching gas price in iteration {}; reconnected", i);
+                        continue 'iterations;
+                    }
+                    return Err(e.into());
+                }
+            };
+
+            // Serialize the unique payload for Move contract argument
+            let payload_bcs = bcs::to_bytes(&unique_payload)
+                .context("Failed to serialize payload")?;
+
+            // Build the Programmable Transaction Block (PTB)
+            let pt = {
+                 let mut builder = ProgrammableTransactionBuilder::new();
+                 // Define arguments for the Move call
+                 let config_call_arg = CallArg::Object(ObjectArg::SharedObject { id: config_object_id, initial_shared_version: config_object_version, mutable: true });
+                 let payload_call_arg = CallArg::Pure(payload_bcs);
+                 let sigs_call_arg = CallArg::Pure(signatures_bcs.clone());
+                 // Create the Move call to the verification function
+                 builder.move_call( package_id, module_name.clone(), function_name.clone(), vec![], vec![config_call_arg, payload_call_arg, sigs_call_arg] )?;
+                 builder.finish()
+             };
+
+            // Create the transaction data
+            let tx_data = TransactionData::new_programmable(
+                 l1_submission_address,
+                 vec![gas_object_ref],
+                 pt,
+                 100_000_000, // Increased gas budget for safety
+                 reference_gas_price
+             );
+
+            // Sign the transaction data
+            let transaction = build_signed_transaction(tx_data, &[submitter_sui_keypair]);
+
+            // Execute the transaction on the Sui network
+            let result = client.quorum_driver_api().execute_transaction_block(
+                transaction,
+                SuiTransactionBlockResponseOptions::new().with_effects(), // Request effects to check status
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution) // Wait for node execution
+            ).await;
+
+            match result {
+                Err(e) if version_conflict_retries < MAX_VERSION_CONFLICT_RETRIES && is_version_conflict(&e.to_string()) => {
+                    version_conflict_retries += 1;
+                    version_conflicts_retried += 1;
+                    warn!("Object version conflict in iteration {}, rebuilding (retry {}/{})",
+                          i, version_conflict_retries, MAX_VERSION_CONFLICT_RETRIES);
+                }
+                result => break result,
+            }
+        };
 
                 let l1_submission_duration = l1_submit_start.elapsed();
                 benchmark.record_operation("l1_submission", l1_submission_duration.as_millis() as u64);
@@ -356,7 +936,15 @@ async fn run_end_to_end_performance(
                  successful_submissions += 1;
 
                  if let Some(effects) = response.effects {
+                      let net_gas = effects.gas_cost_summary().net_gas_usage();
+                      gas_usage.record(net_gas);
                       match effects.status() {
+                           SuiExecutionStatus::Success if below_threshold => {
+                                error!("L1 transaction {} accepted {} signature(s), below threshold {}",
+                                          l1_digest, signatures_for_move.len(), quorum_threshold);
+                                shortfall.record_execution(true, net_gas);
+                                benchmark.record_operation("l1_confirmation", 0);
+                           },
                            SuiExecutionStatus::Success => {
                                 // Simulate confirmation time (e.g., small delay)
                     let l1_confirm_start = Instant::now();
@@ -364,20 +952,44 @@ async fn run_end_to_end_performance(
                                 let l1_confirmation_duration = l1_confirm_start.elapsed();
                                 benchmark.record_operation("l1_confirmation", l1_confirmation_duration.as_millis() as u64);
                                 successful_confirmations += 1;
+                                if let Some(link) = execution_manager.tx_explorer_link(&l1_digest.to_string()) {
+                                    info!("L1 transaction confirmed: {}", link);
+                                }
+                           },
+                           SuiExecutionStatus::Failure { error } if below_threshold => {
+                                info!("Below-threshold L1 transaction {} rejected as expected: {:?}", l1_digest, error);
+                                shortfall.record_execution(false, net_gas);
+                                benchmark.record_operation("l1_confirmation", 0);
                            },
                            SuiExecutionStatus::Failure { error } => {
-                                eprintln!("ERROR: L1 transaction {} failed: {:?}", l1_digest, error);
+                                error!("L1 transaction {} failed: {:?}", l1_digest, error);
                                 benchmark.record_operation("l1_confirmation", 0); // Record 0 for failure
                            }
                     }
                 } else {
-                      eprintln!("WARNING: L1 transaction {} succeeded but had no effects reported.", l1_digest);
+                      warn!("L1 transaction {} succeeded but had no effects reported", l1_digest);
+                      if below_threshold {
+                          shortfall.record_inconclusive();
+                      }
                       benchmark.record_operation("l1_confirmation", 0); // Treat as failure if effects missing
                  }
             },
+            Err(e) if is_version_conflict(&e.to_string()) => {
+                 error!("Object version conflict persisted after {} retries: {:?}", MAX_VERSION_CONFLICT_RETRIES, e);
+                 if below_threshold {
+                     shortfall.record_inconclusive();
+                 } else {
+                     failure_version_conflict += 1;
+                 }
+                 benchmark.record_operation("l1_confirmation", 0);
+            }
             Err(e) => {
-                 eprintln!("ERROR: L1 submission failed: {:?}", e);
+                 error!("L1 submission failed: {:?}", e);
+                 if below_threshold {
+                     shortfall.record_inconclusive();
+                 }
                  benchmark.record_operation("l1_confirmation", 0); // Record 0 for submission error
+                 sui_client.recover_from(&e).await;
             }
         }
 
@@ -386,40 +998,58 @@ async fn run_end_to_end_performance(
         
         // Progress indicator
         if (i + 1) % (BENCHMARK_ITERATIONS / 10).max(1) == 0 || i == BENCHMARK_ITERATIONS - 1 {
-            println!("    Iteration {}/{} complete ({:?}), Current Success Rate: {:.1}%", 
+            info!("Iteration {}/{} complete ({:?}), Current Success Rate: {:.1}%", 
                     i + 1, BENCHMARK_ITERATIONS, total_iteration_time, 
                     (successful_confirmations as f64 / (i + 1) as f64) * 100.0);
         }
     }
+    progress.report(ProgressEvent::new(config_name, BENCHMARK_ITERATIONS, BENCHMARK_ITERATIONS, successful_confirmations, scenario_start));
 
     // Finalize and record benchmark results
     let success_rate = if BENCHMARK_ITERATIONS > 0 { successful_confirmations as f64 / BENCHMARK_ITERATIONS as f64 } else { 0.0 };
     benchmark.add_config("verification_success_rate", &format!("{:.3}", success_rate));
+    benchmark.add_config("rpc_reconnections", &sui_client.reconnections().to_string());
+    benchmark.add_config("version_conflicts_retried", &version_conflicts_retried.to_string());
+    benchmark.add_config("version_conflict_failures", &failure_version_conflict.to_string());
+    gas_usage.add_to_config(&mut benchmark);
+    if submit_below_threshold {
+        shortfall.add_to_config(&mut benchmark);
+        report_shortfall(config_name, &shortfall);
+    }
     benchmark.end();
     benchmark.print_summary();
     metrics_storage.add_benchmark(benchmark);
+    checkpoint(&metrics_storage, output_dir);
 
-    println!("  End-to-End Performance Benchmark completed.");
-    Ok(())
+    info!("End-to-End Performance Benchmark completed. Gas: {}", gas_usage.summary_line());
+    Ok(gas_usage)
 }
 
 /// Runs the Byzantine resilience benchmark.
 ///
 /// Tests middleware resilience by injecting Byzantine behavior (invalid signatures, non-responses)
 /// into the simulated quorum at varying percentages.
+#[tracing::instrument(
+    name = "benchmark",
+    skip_all,
+    fields(scenario = tracing::field::Empty, network = "testnet", byzantine_pct = tracing::field::Empty)
+)]
 async fn run_byzantine_resilience(
-    _output_dir: &str, // Parameter kept for consistency
+    output_dir: &str, // Results are checkpointed here after each percentage
     metrics_storage: Arc<MetricsStorage>,
     _execution_manager: Arc<ExecutionManager>, // Not directly used
     _security_audit_log: Arc<SecurityAuditLog>, // Not directly used
     base_quorum_simulation: Arc<QuorumSimulation>,
     submitter_sui_keypair: &SuiKeyPair,
     gas_object_id: &ObjectID,
-) -> Result<(), anyhow::Error> {
-    println!("  Starting Byzantine Resilience Benchmarks (n=5) with percentages: {:?}", 
+    progress: ProgressReporter,
+    completed: &HashSet<String>,
+    submit_below_threshold: bool,
+) -> Result<Vec<(String, GasUsage)>> {
+    info!("Starting Byzantine Resilience Benchmarks (n=5) with percentages: {:?}", 
              BYZANTINE_PERCENTAGES.iter().map(|p| format!("{:.1}%", p * 100.0)).collect::<Vec<_>>());
 
-    let sui_client: Arc<SuiClient> = Arc::new(SuiClientBuilder::default().build(SUI_TESTNET_RPC).await?);
+    let sui_client = connect_sui(SUI_TESTNET_RPC).await?;
     let l1_submission_address = SuiAddress::from(&submitter_sui_keypair.public());
     let submitter_keypair_arc = Arc::new(submitter_sui_keypair.clone());
 
@@ -429,9 +1059,19 @@ async fn run_byzantine_resilience(
     let function_name = Identifier::from_str(config::VERIFICATION_CONTRACT_FUNCTION)?;
     let config_object_id = ObjectID::from_str(config::VERIFICATION_CONTRACT_CONFIG_OBJECT_ID)?;
 
+    let mut gas_by_scenario = Vec::new();
+
     // Test each Byzantine percentage
     for &percentage in BYZANTINE_PERCENTAGES.iter() {
-        println!("    Running Benchmark with {:.1}% Byzantine Nodes...", percentage * 100.0);
+        let config_name = byzantine_scenario_name(percentage);
+        let span = tracing::Span::current();
+        span.record("scenario", config_name.as_str());
+        span.record("byzantine_pct", percentage * 100.0);
+        if completed.contains(&config_name) {
+            info!("Skipping {:.1}% Byzantine Nodes: already completed", percentage * 100.0);
+            continue;
+        }
+        info!("Running Benchmark with {:.1}% Byzantine Nodes", percentage * 100.0);
 
         // Create a new quorum simulation instance for this percentage, cloning base keys
         let keypairs_clone = base_quorum_simulation.keypairs.iter().map(|kp| {
@@ -443,12 +1083,13 @@ async fn run_byzantine_resilience(
         let current_sim_arc = Arc::new(current_sim);
 
         // Create benchmark component for this scenario
-        let config_name = format!("byzantine_resilience_n5_{:.0}pct", percentage * 100.0);
         let security_level = format!("{:.1}%_byzantine", percentage * 100.0);
         let mut benchmark = ComponentBenchmark::new(&config_name, &security_level, BENCHMARK_ITERATIONS as u32);
         benchmark.add_config("num_transactions", &BENCHMARK_ITERATIONS.to_string());
         benchmark.add_config("quorum_size", &current_sim_arc.keypairs.len().to_string());
         benchmark.add_config("byzantine_percentage", &percentage.to_string());
+        let mut gas_usage = GasUsage::default();
+        let mut shortfall = ShortfallSubmissions::default();
 
         // Initialize counters for success and failure reasons
         let mut successful_confirmations = 0;
@@ -457,19 +1098,21 @@ async fn run_byzantine_resilience(
         let mut failure_l1_rpc = 0;
         let mut failure_signing_error = 0;
 
+        // Version conflicts come from concurrent object use, not from Byzantine signers
+        let mut version_conflicts_retried = 0;
+        let mut failure_version_conflict = 0;
+        let reconnections_before = sui_client.reconnections();
+
         // Run iterations for this percentage
-        for i in 0..BENCHMARK_ITERATIONS {
+        let scenario_start = Instant::now();
+        'iterations: for i in 0..BENCHMARK_ITERATIONS {
+            progress.report(ProgressEvent::new(&config_name, i, BENCHMARK_ITERATIONS, successful_confirmations, scenario_start));
             let iteration_start = Instant::now();
+            let client = sui_client.current();
             
             // 1. Generate unique payload
             let processing_start = Instant::now();
-            let mut unique_payload = vec![0u8; 32];
-            let now_ms = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)?
-                            .as_millis();
-            unique_payload[0..8].copy_from_slice(&i.to_le_bytes());
-            unique_payload[8..24].copy_from_slice(&now_ms.to_le_bytes());
-            rand::thread_rng().fill(&mut unique_payload[24..]);
+            let unique_payload = benchmark_payload(i, &l1_submission_address, gas_object_id)?;
 
             // 2. Request signatures from quorum (with simulated Byzantine behavior)
             let quorum_size = current_sim_arc.keypairs.len();
@@ -480,7 +1123,7 @@ async fn run_byzantine_resilience(
             let all_signatures_with_validity = match all_signatures_with_validity_result {
                 Ok(sigs) => sigs,
                 Err(e) => {
-                    eprintln!("ERROR: Signing payload failed in iteration {} ({}% Byzantine): {}", 
+                    error!("Signing payload failed in iteration {} ({}% Byzantine): {}", 
                              i, percentage * 100.0, e);
                     failure_signing_error += 1;
                     // Record zero timings for failed signing attempts
@@ -496,11 +1139,15 @@ async fn run_byzantine_resilience(
             let num_signatures_obtained = all_signatures_with_validity.len();
 
             // Check if enough signatures were gathered (even if some are invalid)
-            if num_signatures_obtained < quorum_threshold {
+            let below_threshold = num_signatures_obtained < quorum_threshold;
+            if below_threshold {
                  // This is expected when Byzantine % is high enough to prevent reaching threshold
-                 println!("INFO: Not enough signatures ({}/{}) for threshold ({}) in iteration {} ({}% Byzantine). Recording failure.", 
+                 info!("Not enough signatures ({}/{}) for threshold ({}) in iteration {} ({}% Byzantine). Recording failure", 
                           num_signatures_obtained, quorum_size, quorum_threshold, i, percentage * 100.0);
+                 // Submitted or not, the iteration fails for lack of signatures
                  failure_not_enough_signatures += 1;
+             }
+             if below_threshold && !submit_below_threshold {
                  // Record appropriate timings
                  benchmark.record_operation("middleware_processing_and_prep", processing_start.elapsed().as_millis() as u64);
                  benchmark.record_operation("quorum_signing", processing_start.elapsed().as_millis() as u64);
@@ -529,7 +1176,7 @@ async fn run_byzantine_resilience(
              let signatures_bcs = match bcs::to_bytes(&signatures_for_move) {
                   Ok(bytes) => bytes,
                   Err(e) => { 
-                      eprintln!("ERROR: Failed to serialize signatures in iteration {}: {}", i, e);
+                      error!("Failed to serialize signatures in iteration {}: {}", i, e);
                       // Consider this a form of signing error for counting purposes?
                       failure_signing_error += 1; 
                       benchmark.record_operation("l1_submission", 0);
@@ -539,96 +1186,107 @@ async fn run_byzantine_resilience(
                   }
               };
 
-             // Fetch gas object reference
-             let gas_object_response_res = sui_client.read_api().get_object_with_options(
-                 *gas_object_id,
-                 SuiObjectDataOptions::new().with_owner().with_previous_transaction()
-             ).await;
-              let gas_object_ref = match gas_object_response_res {
-                 Ok(resp) => resp.object_ref_if_exists().ok_or_else(|| anyhow!("Gas object {} not found or deleted", gas_object_id))?,
-                 Err(e) => {
-                     eprintln!("ERROR: Failed to fetch gas object {} ({}% Byzantine): {}", gas_object_id, percentage * 100.0, e);
-                     failure_l1_rpc += 1;
-                     benchmark.record_operation("l1_submission", l1_submit_start.elapsed().as_millis() as u64);
-                     benchmark.record_operation("l1_confirmation", 0);
-                     benchmark.record_operation("total_iteration", iteration_start.elapsed().as_millis() as u64);
-                     continue;
-                 }
-             };
+             // Shared-object version conflicts are retried with fresh object refs
+             let mut version_conflict_retries = 0;
+             let transaction_response_result = loop {
+                 // Fetch gas object reference
+                 let gas_object_response_res = client.read_api().get_object_with_options(
+                     *gas_object_id,
+                     SuiObjectDataOptions::new().with_owner().with_previous_transaction()
+                 ).await;
+                  let gas_object_ref = match gas_object_response_res {
+                     Ok(resp) => resp.object_ref_if_exists().ok_or_else(|| anyhow!("Gas object {} not found or deleted", gas_object_id))?,
+                     Err(e) => {
+                         error!("Failed to fetch gas object {} ({}% Byzantine): {}", gas_object_id, percentage * 100.0, e);
+                         failure_l1_rpc += 1;
+                         sui_client.recover_from(&e).await;
+                         benchmark.record_operation("l1_submission", l1_submit_start.elapsed().as_millis() as u64);
+                         benchmark.record_operation("l1_confirmation", 0);
+                         benchmark.record_operation("total_iteration", iteration_start.elapsed().as_millis() as u64);
+                         continue 'iterations;
+                     }
+                 };
              
 
-             // Fetch config object version
-             let config_object_response_res = sui_client.read_api().get_object_with_options(
-                 config_object_id,
-                 SuiObjectDataOptions::new().with_owner()
-             ).await;
-              let config_object_version = match config_object_response_res {
-                 Ok(resp) => resp.owner()
-                     .and_then(|owner_enum| match owner_enum { Owner::Shared { initial_shared_version } => Some(initial_shared_version), _ => None, })
-                     .ok_or_else(|| anyhow!("Could not get initial shared version for config object {}", config_object_id))?,
-                 Err(e) => {
-                     eprintln!("ERROR: Failed to fetch config object {} ({}% Byzantine): {}", config_object_id, percentage * 100.0, e);
-                     failure_l1_rpc += 1;
-                     benchmark.record_operation("l1_submission", l1_submit_start.elapsed().as_millis() as u64);
-                     benchmark.record_operation("l1_confirmation", 0);
-                     benchmark.record_operation("total_iteration", iteration_start.elapsed().as_millis() as u64);
-                     continue;
-                 }
-             };
+                 // Fetch config object version
+                 let config_object_response_res = client.read_api().get_object_with_options(
+                     config_object_id,
+                     SuiObjectDataOptions::new().with_owner()
+                 ).await;
+                  let config_object_version = match config_object_response_res {
+                     Ok(resp) => resp.owner()
+                         .and_then(|owner_enum| match owner_enum { Owner::Shared { initial_shared_version } => Some(initial_shared_version), _ => None, })
+                         .ok_or_else(|| anyhow!("Could not get initial shared version for config object {}", config_object_id))?,
+                     Err(e) => {
+                         error!("Failed to fetch config object {} ({}% Byzantine): {}", config_object_id, percentage * 100.0, e);
+                         failure_l1_rpc += 1;
+                         sui_client.recover_from(&e).await;
+                         benchmark.record_operation("l1_submission", l1_submit_start.elapsed().as_millis() as u64);
+                         benchmark.record_operation("l1_confirmation", 0);
+                         benchmark.record_operation("total_iteration", iteration_start.elapsed().as_millis() as u64);
+                         continue 'iterations;
+                     }
+                 };
              
-             // Get reference gas price
-             let reference_gas_price_res = sui_client.read_api().get_reference_gas_price().await;
-             let reference_gas_price = match reference_gas_price_res {
-                 Ok(price) => price,
-                 Err(e) => {
-                     eprintln!("ERROR: Failed to get reference gas price ({}% Byzantine): {}", percentage * 100.0, e);
-                     failure_l1_rpc += 1;
-                     benchmark.record_operation("l1_submission", l1_submit_start.elapsed().as_millis() as u64);
-                     benchmark.record_operation("l1_confirmation", 0);
-                     benchmark.record_operation("total_iteration", iteration_start.elapsed().as_millis() as u64);
-                     continue;
-                 }
-             };
+                 // Get reference gas price
+                 let reference_gas_price_res = client.read_api().get_reference_gas_price().await;
+                 let reference_gas_price = match reference_gas_price_res {
+                     Ok(price) => price,
+                     Err(e) => {
+                         error!("Failed to get reference gas price ({}% Byzantine): {}", percentage * 100.0, e);
+                         failure_l1_rpc += 1;
+                         sui_client.recover_from(&e).await;
+                         benchmark.record_operation("l1_submission", l1_submit_start.elapsed().as_millis() as u64);
+                         benchmark.record_operation("l1_confirmation", 0);
+                         benchmark.record_operation("total_iteration", iteration_start.elapsed().as_millis() as u64);
+                         continue 'iterations;
+                     }
+                 };
              
 
-             // Serialize payload for Move contract
-             let payload_bcs = bcs::to_bytes(&unique_payload)
-                 .context("Failed to serialize payload")?; // Should not fail
-
-             // Build Programmable Transaction Block (PTB)
-             let pt = {
-                  let mut builder = ProgrammableTransactionBuilder::new();
-                  let config_call_arg = CallArg::Object(ObjectArg::SharedObject { id: config_object_id, initial_shared_version: config_object_version, mutable: true });
-                  let payload_call_arg = CallArg::Pure(payload_bcs);
-                  let sigs_call_arg = CallArg::Pure(signatures_bcs);
-                  builder.move_call( package_id, module_name.clone(), function_name.clone(), vec![], vec![config_call_arg, payload_call_arg, sigs_call_arg] )?;
-                  builder.finish()
-              };
-
-             // Create transaction data
-             let tx_data = TransactionData::new_programmable(
-                  l1_submission_address,
-                  vec![gas_object_ref],
-                  pt,
-                  100_000_000, // Increased gas budget
-                  reference_gas_price
-              );
-
-             // Sign transaction
-             let intent = Intent::sui_transaction();
-             let intent_message = IntentMessage::new(intent, tx_data.clone());
-             // Pass the keypair reference directly without Arc deref
-             let sdk_signature = SdkSignature::new_secure(&intent_message, submitter_sui_keypair);
-
-             // Create transaction
-             let transaction = Transaction::from_data(tx_data, vec![sdk_signature.into()]);
-
-             // Execute transaction
-             let transaction_response_result = sui_client.quorum_driver_api().execute_transaction_block(
-                 transaction,
-                 SuiTransactionBlockResponseOptions::new().with_effects(),
-                 Some(ExecuteTransactionRequestType::WaitForLocalExecution)
-             ).await;
+                 // Serialize payload for Move contract
+                 let payload_bcs = bcs::to_bytes(&unique_payload)
+                     .context("Failed to serialize payload")?; // Should not fail
+
+                 // Build Programmable Transaction Block (PTB)
+                 let pt = {
+                      let mut builder = ProgrammableTransactionBuilder::new();
+                      let config_call_arg = CallArg::Object(ObjectArg::SharedObject { id: config_object_id, initial_shared_version: config_object_version, mutable: true });
+                      let payload_call_arg = CallArg::Pure(payload_bcs);
+                      let sigs_call_arg = CallArg::Pure(signatures_bcs.clone());
+                      builder.move_call( package_id, module_name.clone(), function_name.clone(), vec![], vec![config_call_arg, payload_call_arg, sigs_call_arg] )?;
+                      builder.finish()
+                  };
+
+                 // Create transaction data
+                 let tx_data = TransactionData::new_programmable(
+                      l1_submission_address,
+                      vec![gas_object_ref],
+                      pt,
+                      100_000_000, // Increased gas budget
+                      reference_gas_price
+                  );
+
+                 // Sign transaction
+                 let transaction = build_signed_transaction(tx_data, &[submitter_sui_keypair]);
+
+                 // Execute transaction
+                 let result = client.quorum_driver_api().execute_transaction_block(
+                     transaction,
+                     SuiTransactionBlockResponseOptions::new().with_effects(),
+                     Some(ExecuteTransactionRequestType::WaitForLocalExecution)
+                 ).await;
+
+                 match result {
+                     Err(e) if version_conflict_retries < MAX_VERSION_CONFLICT_RETRIES && is_version_conflict(&e.to_string()) => {
+                         version_conflict_retries += 1;
+                         version_conflicts_retried += 1;
+                         warn!("Object version conflict in iteration {} ({}% Byzantine), rebuilding (retry {}/{})",
+                                   i, percentage * 100.0, version_conflict_retries, MAX_VERSION_CONFLICT_RETRIES);
+                     }
+                     result => break result,
+                 }
+             };
 
              // Record L1 submission timing
              let l1_submission_duration = l1_submit_start.elapsed();
@@ -638,7 +1296,15 @@ async fn run_byzantine_resilience(
              match transaction_response_result {
                  Ok(response) => {
                       if let Some(effects) = response.effects {
+                           let net_gas = effects.gas_cost_summary().net_gas_usage();
+                           gas_usage.record(net_gas);
                            match effects.status() {
+                                SuiExecutionStatus::Success if below_threshold => {
+                                     error!("L1 transaction {} accepted {} signature(s), below threshold {} ({}% Byzantine)",
+                                               response.digest, signatures_for_move.len(), quorum_threshold, percentage * 100.0);
+                                     shortfall.record_execution(true, net_gas);
+                                     benchmark.record_operation("l1_confirmation", 0);
+                                },
                                 SuiExecutionStatus::Success => {
                                      // Record L1 confirmation timing (simulated)
                                      let l1_confirm_start = Instant::now();
@@ -647,25 +1313,50 @@ async fn run_byzantine_resilience(
                                      benchmark.record_operation("l1_confirmation", l1_confirmation_duration.as_millis() as u64);
                                      successful_confirmations += 1;
                                 },
+                                SuiExecutionStatus::Failure { error } if below_threshold => {
+                                     info!("Below-threshold L1 transaction {} rejected as expected ({}% Byzantine): {:?}",
+                                              response.digest, percentage * 100.0, error);
+                                     shortfall.record_execution(false, net_gas);
+                                     benchmark.record_operation("l1_confirmation", 0);
+                                },
                                 SuiExecutionStatus::Failure { error } => {
                                      // This is expected when enough invalid signatures are included
-                                     println!("INFO: L1 transaction {} failed as expected ({}% Byzantine): {:?}", 
+                                     info!("L1 transaction {} failed as expected ({}% Byzantine): {:?}", 
                                               response.digest, percentage * 100.0, error);
                                      failure_l1_execution += 1;
                                      benchmark.record_operation("l1_confirmation", 0);
                                 }
                            }
                       } else {
-                           eprintln!("WARNING: L1 transaction {} succeeded but had no effects ({}% Byzantine)", 
+                           warn!("L1 transaction {} succeeded but had no effects ({}% Byzantine)", 
                                     response.digest, percentage * 100.0);
                            // Count as execution failure if effects are missing
-                           failure_l1_execution += 1; 
+                           if below_threshold {
+                               shortfall.record_inconclusive();
+                           } else {
+                               failure_l1_execution += 1;
+                           }
                            benchmark.record_operation("l1_confirmation", 0);
                       }
                  },
+                 Err(e) if is_version_conflict(&e.to_string()) => {
+                      error!("Object version conflict persisted after {} retries ({}% Byzantine): {:?}",
+                                MAX_VERSION_CONFLICT_RETRIES, percentage * 100.0, e);
+                      if below_threshold {
+                          shortfall.record_inconclusive();
+                      } else {
+                          failure_version_conflict += 1;
+                      }
+                      benchmark.record_operation("l1_confirmation", 0);
+                 }
                  Err(e) => {
-                      eprintln!("ERROR: L1 submission RPC error ({}% Byzantine): {:?}", percentage * 100.0, e);
-                      failure_l1_rpc += 1;
+                      error!("L1 submission RPC error ({}% Byzantine): {:?}", percentage * 100.0, e);
+                      if below_threshold {
+                          shortfall.record_inconclusive();
+                      } else {
+                          failure_l1_rpc += 1;
+                      }
+                      sui_client.recover_from(&e).await;
                       benchmark.record_operation("l1_confirmation", 0);
                  }
              }
@@ -676,11 +1367,12 @@ async fn run_byzantine_resilience(
              
              // Progress indicator
              if (i + 1) % (BENCHMARK_ITERATIONS / 10).max(1) == 0 || i == BENCHMARK_ITERATIONS - 1 {
-                 println!("      Iteration {}/{} ({:.1}% Byzantine) complete ({:?}), Current Success Rate: {:.1}%",
+                 info!("Iteration {}/{} ({:.1}% Byzantine) complete ({:?}), Current Success Rate: {:.1}%",
                      i + 1, BENCHMARK_ITERATIONS, percentage * 100.0, total_iteration_time, 
                      (successful_confirmations as f64 / (i + 1) as f64) * 100.0);
              }
         } // End iterations loop
+        progress.report(ProgressEvent::new(&config_name, BENCHMARK_ITERATIONS, BENCHMARK_ITERATIONS, successful_confirmations, scenario_start));
 
         // Record success rate and failure reasons for this percentage
         let success_rate = if BENCHMARK_ITERATIONS > 0 { successful_confirmations as f64 / BENCHMARK_ITERATIONS as f64 } else { 0.0 };
@@ -689,14 +1381,25 @@ async fn run_byzantine_resilience(
         benchmark.add_config("failure_reason_not_enough_signatures", &failure_not_enough_signatures.to_string());
         benchmark.add_config("failure_reason_l1_execution", &failure_l1_execution.to_string());
         benchmark.add_config("failure_reason_l1_rpc", &failure_l1_rpc.to_string());
+        // Infrastructure recoveries, kept apart from the Byzantine failure reasons
+        benchmark.add_config("rpc_reconnections", &(sui_client.reconnections() - reconnections_before).to_string());
+        benchmark.add_config("version_conflicts_retried", &version_conflicts_retried.to_string());
+        benchmark.add_config("version_conflict_failures", &failure_version_conflict.to_string());
+        gas_usage.add_to_config(&mut benchmark);
+        if submit_below_threshold {
+            shortfall.add_to_config(&mut benchmark);
+            report_shortfall(&config_name, &shortfall);
+        }
 
         // Finalize and store benchmark results
         benchmark.end();
         benchmark.print_summary();
         metrics_storage.add_benchmark(benchmark);
+        checkpoint(&metrics_storage, output_dir);
+        gas_by_scenario.push((config_name, gas_usage));
 
     } // End percentages loop
     
-    println!("  Byzantine Resilience Benchmarks completed.");
-    Ok(())
+    info!("Byzantine Resilience Benchmarks completed");
+    Ok(gas_by_scenario)
 }
\ No newline at end of file
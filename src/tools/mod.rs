@@ -1 +1,2 @@
-pub mod benchmark_suite; 
\ No newline at end of file
+pub mod benchmark_suite; 
+pub mod reconnect;
\ No newline at end of file
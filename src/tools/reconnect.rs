@@ -0,0 +1,167 @@
+//! Automatic reconnection for long-running RPC clients.
+//!
+//! The benchmarks hold one `SuiClient` for hundreds of iterations. If the node
+//! restarts or the connection drops, `ReconnectingClient` rebuilds it with
+//! backoff instead of letting every later iteration fail.
+
+use crate::utils::backoff::Backoff;
+use anyhow::{anyhow, Result};
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+
+/// Exponential backoff between reconnection attempts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackoffPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub max_attempts: u32,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            max_attempts: 5,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before attempt `attempt` (0-based): doubles each time, capped at `max_delay`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// The same bounds with decorrelated jitter, so clients that lost the same node don't reconnect in lockstep.
+    pub fn jittered(&self) -> Backoff {
+        Backoff::decorrelated_jitter(self.initial_delay, self.max_delay).with_max_attempts(self.max_attempts)
+    }
+}
+
+impl From<BackoffPolicy> for Backoff {
+    fn from(policy: BackoffPolicy) -> Self {
+        Backoff::exponential(policy.initial_delay, policy.max_delay).with_max_attempts(policy.max_attempts)
+    }
+}
+
+/// Error messages that mean the connection itself failed, not the request.
+const CONNECTION_ERROR_MARKERS: [&str; 10] = [
+    "connection refused",
+    "connection reset",
+    "connection closed",
+    "broken pipe",
+    "transport error",
+    "error trying to connect",
+    "restart required",
+    "networking or low-level protocol error",
+    "dns error",
+    "timed out",
+];
+
+/// Whether `message` describes a dropped or unreachable connection.
+pub fn is_connection_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    CONNECTION_ERROR_MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+type ConnectFn<C> = Box<dyn Fn() -> Pin<Box<dyn Future<Output = Result<C>> + Send>> + Send + Sync>;
+
+/// A client that can be rebuilt in place after connection-level errors.
+pub struct ReconnectingClient<C> {
+    label: String,
+    connect: ConnectFn<C>,
+    client: Mutex<Arc<C>>,
+    backoff: Backoff,
+    reconnections: AtomicUsize,
+}
+
+impl<C> ReconnectingClient<C> {
+    /// Connect once with `connect`, which is reused for every reconnection.
+    pub async fn connect<F, Fut>(label: &str, backoff: impl Into<Backoff>, connect: F) -> Result<Self>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<C>> + Send + 'static,
+    {
+        let connect: ConnectFn<C> = Box::new(move || Box::pin(connect()));
+        let client = connect().await?;
+        Ok(Self {
+            label: label.to_string(),
+            connect,
+            client: Mutex::new(Arc::new(client)),
+            backoff: backoff.into(),
+            reconnections: AtomicUsize::new(0),
+        })
+    }
+
+    /// The client to use for the next call.
+    pub fn current(&self) -> Arc<C> {
+        self.client.lock().unwrap().clone()
+    }
+
+    /// Successful reconnections so far.
+    pub fn reconnections(&self) -> usize {
+        self.reconnections.load(Ordering::SeqCst)
+    }
+
+    /// Rebuild the client, retrying until the backoff's attempts run out.
+    pub async fn reconnect(&self) -> Result<()> {
+        let mut backoff = self.backoff.clone();
+        let mut last_error = None;
+        while let Some(delay) = backoff.next_delay() {
+            tokio::time::sleep(delay).await;
+            match (self.connect)().await {
+                Ok(client) => {
+                    *self.client.lock().unwrap() = Arc::new(client);
+                    self.reconnections.fetch_add(1, Ordering::SeqCst);
+                    println!("Reconnected {} after {} attempt(s)", self.label, backoff.attempts());
+                    return Ok(());
+                }
+                Err(e) => {
+                    eprintln!("Reconnect attempt {} for {} failed: {}", backoff.attempts(), self.label, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(anyhow!(
+            "Could not reconnect {} after {} attempts: {}",
+            self.label,
+            backoff.attempts(),
+            last_error.map_or_else(|| "no attempts made".to_string(), |e| e.to_string())
+        ))
+    }
+
+    /// Reconnect if `error` is connection-level. Returns whether the client was rebuilt.
+    pub async fn recover_from(&self, error: &dyn Display) -> bool {
+        if !is_connection_error(&error.to_string()) {
+            return false;
+        }
+        match self.reconnect().await {
+            Ok(()) => true,
+            Err(e) => {
+                // The next failing call will try again
+                eprintln!("{}", e);
+                false
+            }
+        }
+    }
+}
+
+/// `SuiClient` for `rpc_url` that reconnects with the default backoff, jittered.
+pub async fn connect_sui(rpc_url: &str) -> Result<ReconnectingClient<SuiClient>> {
+    let rpc_url = rpc_url.to_string();
+    let label = format!("Sui RPC {}", rpc_url);
+    ReconnectingClient::connect(&label, BackoffPolicy::default().jittered(), move || {
+        let rpc_url = rpc_url.clone();
+        async move {
+            SuiClientBuilder::default().build(&rpc_url).await
+                .map_err(|e| anyhow!("Failed to connect to {}: {}", rpc_url, e))
+        }
+    }).await
+}
@@ -0,0 +1,127 @@
+//! Amounts tagged with their coin type and decimal precision.
+//!
+//! `Transaction.amount` is a count of base units of `Transaction.coin_type`.
+//! Transactions that don't name a coin are in MIST, SUI's 9-decimal base unit.
+//! Adapters use `Amount::to_base_units` to get the target chain's native units
+//! (wei for Ethereum), so the same transfer has the same value on every chain.
+//! They only convert their own native coin and coins explicitly mapped onto it;
+//! 1 SUI is not 1 ETH.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+/// Move type of the native SUI coin
+pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+/// Decimals of SUI: 1 SUI = 10^9 MIST
+pub const SUI_DECIMALS: u8 = 9;
+
+/// Coin type of ether, the native coin of EVM chains
+pub const ETH_COIN_TYPE: &str = "ETH";
+
+/// Decimals of ETH: 1 ETH = 10^18 wei
+pub const ETH_DECIMALS: u8 = 18;
+
+/// Returned when an amount can't be represented in the requested units
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AmountError {
+    /// Rescaling to fewer decimals would drop a non-zero remainder
+    #[error("{value} at {from} decimals is not a whole number of units at {to} decimals")]
+    PrecisionLoss { value: u64, from: u8, to: u8 },
+    /// The rescaled value doesn't fit the target integer type
+    #[error("{value} at {from} decimals overflows at {to} decimals")]
+    Overflow { value: u64, from: u8, to: u8 },
+    /// Not a decimal number, or more fractional digits than the coin has
+    #[error("Invalid amount '{text}' for a coin with {decimals} decimals")]
+    Invalid { text: String, decimals: u8 },
+    /// The coin is neither the target chain's native coin nor mapped onto it
+    #[error("{coin_type} is not the native coin of {chain_id} and has no mapping to it")]
+    UnmappedCoin { coin_type: String, chain_id: String },
+}
+
+/// A value in base units of `coin_type`, which has `decimals` decimal places.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Amount {
+    pub value: u64,
+    pub coin_type: String,
+    pub decimals: u8,
+}
+
+impl Amount {
+    pub fn new(value: u64, coin_type: impl Into<String>, decimals: u8) -> Self {
+        Self { value, coin_type: coin_type.into(), decimals }
+    }
+
+    /// `mist` MIST of native SUI
+    pub fn sui(mist: u64) -> Self {
+        Self::new(mist, SUI_COIN_TYPE, SUI_DECIMALS)
+    }
+
+    /// Parse a human-readable amount such as "1.5" into base units.
+    pub fn parse(text: &str, coin_type: impl Into<String>, decimals: u8) -> Result<Self, AmountError> {
+        let invalid = || AmountError::Invalid { text: text.to_string(), decimals };
+        let (whole, fraction) = text.trim().split_once('.').unwrap_or((text.trim(), ""));
+        let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !all_digits(whole) || !all_digits(fraction) {
+            return Err(invalid());
+        }
+        if fraction.len() > decimals as usize {
+            return Err(invalid());
+        }
+
+        let mut digits = format!("{}{}", whole, fraction);
+        digits.push_str(&"0".repeat(decimals as usize - fraction.len()));
+        let value = digits.parse::<u64>().map_err(|_| invalid())?;
+        Ok(Self::new(value, coin_type, decimals))
+    }
+
+    /// The same quantity counted in units with `decimals` decimal places.
+    ///
+    /// Fails rather than rounding when fewer decimals can't hold the value exactly.
+    pub fn to_base_units(&self, decimals: u8) -> Result<u128, AmountError> {
+        let overflow = AmountError::Overflow { value: self.value, from: self.decimals, to: decimals };
+        let value = u128::from(self.value);
+        if decimals >= self.decimals {
+            return 10u128.checked_pow(u32::from(decimals - self.decimals))
+                .and_then(|scale| value.checked_mul(scale))
+                .ok_or(overflow);
+        }
+        let scale = match 10u128.checked_pow(u32::from(self.decimals - decimals)) {
+            Some(scale) => scale,
+            // More than u128 can count, so only zero converts
+            None if value == 0 => return Ok(0),
+            None => return Err(AmountError::PrecisionLoss { value: self.value, from: self.decimals, to: decimals }),
+        };
+        if value % scale != 0 {
+            return Err(AmountError::PrecisionLoss { value: self.value, from: self.decimals, to: decimals });
+        }
+        Ok(value / scale)
+    }
+
+    /// This amount re-expressed with `decimals` decimal places, still fitting in a `u64`.
+    pub fn rescale(&self, decimals: u8) -> Result<Amount, AmountError> {
+        let value = u64::try_from(self.to_base_units(decimals)?)
+            .map_err(|_| AmountError::Overflow { value: self.value, from: self.decimals, to: decimals })?;
+        Ok(Amount::new(value, self.coin_type.clone(), decimals))
+    }
+
+    /// Last segment of the coin type, e.g. "SUI" for `0x2::sui::SUI`
+    pub fn symbol(&self) -> &str {
+        self.coin_type.rsplit("::").next().unwrap_or(&self.coin_type)
+    }
+}
+
+impl fmt::Display for Amount {
+    /// Whole units with trailing zeros trimmed, e.g. "1.5 SUI"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:0>width$}", self.value, width = self.decimals as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{} {}", whole, self.symbol())
+        } else {
+            write!(f, "{}.{} {}", whole, fraction, self.symbol())
+        }
+    }
+}
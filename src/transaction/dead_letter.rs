@@ -0,0 +1,179 @@
+//! Dead-letter log for transactions that failed permanently.
+//!
+//! Each failure is appended to a JSONL file with the transaction, the final
+//! error and its lifecycle history, so it can be inspected and resubmitted
+//! later instead of being lost with the process output.
+
+use super::tracker::LifecycleEvent;
+use super::types::Transaction;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One permanently failed transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub transaction: Transaction,
+    /// Tracking id (`Transaction::payload_hash`)
+    pub payload_hash: String,
+    /// The error that ended processing
+    pub error: String,
+    /// States the transaction passed through before failing
+    pub history: Vec<LifecycleEvent>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    pub fn new(transaction: Transaction, payload_hash: &str, error: &str, history: Vec<LifecycleEvent>) -> Self {
+        Self {
+            transaction,
+            payload_hash: payload_hash.to_string(),
+            error: error.to_string(),
+            history,
+            failed_at: Utc::now(),
+        }
+    }
+}
+
+/// Outcome of `DeadLetterQueue::replay`.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    pub resubmitted: usize,
+    /// Payload hash and error of each resubmission that failed again
+    pub failed: Vec<(String, String)>,
+    /// Payload hashes of entries dropped because their deadline had passed
+    pub expired: Vec<String>,
+}
+
+/// Append-only JSONL file of `DeadLetter`s.
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    path: PathBuf,
+    /// Serializes appends and replays so lines never interleave
+    file_lock: Mutex<()>,
+}
+
+impl DeadLetterQueue {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), file_lock: Mutex::new(()) }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `letter` as one JSON line.
+    pub fn record(&self, letter: &DeadLetter) -> Result<()> {
+        let line = serde_json::to_string(letter)?;
+        let _guard = self.file_lock.lock().unwrap();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open dead-letter log {}", self.path.display()))?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// Every recorded entry, oldest first. A missing file means no entries.
+    pub fn entries(&self) -> Result<Vec<DeadLetter>> {
+        let _guard = self.file_lock.lock().unwrap();
+        self.read_entries()
+    }
+
+    fn read_entries(&self) -> Result<Vec<DeadLetter>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read dead-letter log {}", self.path.display()))?;
+        contents.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| serde_json::from_str(line)
+                .with_context(|| format!("Invalid entry on line {} of {}", i + 1, self.path.display())))
+            .collect()
+    }
+
+    /// Remove and return every entry.
+    pub fn drain(&self) -> Result<Vec<DeadLetter>> {
+        let _guard = self.file_lock.lock().unwrap();
+        let entries = self.read_entries()?;
+        if self.path.exists() {
+            fs::write(&self.path, "")?;
+        }
+        Ok(entries)
+    }
+
+    /// Resubmit every entry through `submit`.
+    ///
+    /// Works from a snapshot of the log, removing each entry only once it has
+    /// been resubmitted, so a crash mid-replay loses nothing. Entries past their
+    /// `valid_until` deadline are dropped rather than resubmitted. An entry that
+    /// fails again stays in the log; if the pipeline it went through re-recorded
+    /// it, as `ExecutionManager` does when it has a dead-letter queue, only that
+    /// newer record is kept.
+    pub async fn replay<F, Fut>(&self, mut submit: F) -> Result<ReplayReport>
+    where
+        F: FnMut(Transaction) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let mut report = ReplayReport::default();
+        for letter in self.entries()? {
+            if letter.transaction.check_not_expired().is_err() {
+                self.remove(&letter)?;
+                report.expired.push(letter.payload_hash);
+                continue;
+            }
+            match submit(letter.transaction.clone()).await {
+                Ok(()) => {
+                    self.remove(&letter)?;
+                    report.resubmitted += 1;
+                }
+                Err(e) => {
+                    if self.rerecorded(&letter)? {
+                        self.remove(&letter)?;
+                    }
+                    report.failed.push((letter.payload_hash, format!("{:#}", e)));
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Whether a newer entry for the same transaction was recorded after `letter`.
+    fn rerecorded(&self, letter: &DeadLetter) -> Result<bool> {
+        let _guard = self.file_lock.lock().unwrap();
+        Ok(self.read_entries()?.iter().any(|entry| entry.payload_hash == letter.payload_hash && entry.failed_at > letter.failed_at))
+    }
+
+    /// Rewrite the log without `letter`, leaving entries recorded since untouched.
+    fn remove(&self, letter: &DeadLetter) -> Result<()> {
+        let _guard = self.file_lock.lock().unwrap();
+        let mut entries = self.read_entries()?;
+        let position = entries.iter().position(|entry| entry.payload_hash == letter.payload_hash && entry.failed_at == letter.failed_at);
+        let Some(position) = position else { return Ok(()) };
+        entries.remove(position);
+
+        let mut contents = String::new();
+        for entry in &entries {
+            contents.push_str(&serde_json::to_string(entry)?);
+            contents.push('\n');
+        }
+        // Swap in a complete copy so a crash mid-write can't lose entries
+        let mut temp_path = self.path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+        fs::write(&temp_path, contents)
+            .with_context(|| format!("Failed to write dead-letter log copy {}", temp_path.display()))?;
+        fs::rename(&temp_path, &self.path)
+            .with_context(|| format!("Failed to rewrite dead-letter log {}", self.path.display()))?;
+        Ok(())
+    }
+}
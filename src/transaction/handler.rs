@@ -2,21 +2,32 @@
 
 // Local Crate Imports
 use super::types::{Transaction as MiddlewareTransaction, QuorumError, SignatureBytes, VerificationInput};
+use super::rate_limit::SenderRateLimiter;
+use super::idempotency::{IdempotencyClaim, IdempotencyStore};
+use super::multisig::MultisigSubmitter;
+use super::sponsored;
 use crate::config; // Import top-level config module
+use crate::execution::simulation::L1Preview;
 use crate::metrics::performance::PerformanceMetrics;
-use crate::quorum::simulation::QuorumSimulation;
+use crate::quorum::binding::{ReplayError, SignatureBindings};
+use crate::quorum::simulation::{QuorumSimulation, SimulatedSignature};
 use crate::security::audit::{AuditEvent, AuditEventType, AuditSeverity, SecurityAuditLog};
+use crate::security::key_manager::KeyManager;
+use crate::sui::events::VerificationEventListener;
+use crate::sui::signing::build_signed_transaction;
+use crate::utils::backoff::Backoff;
 use crate::sui::verification::VerificationManager;
 
 // External Crate Imports
 use anyhow::{anyhow, Context, Result};
 use bcs;
-use shared_crypto::intent::{Intent, IntentMessage};
+use serde::Serialize;
 use std::{
     str::FromStr,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
+use tracing::{debug, error, info, warn};
 use sui_sdk::{
     rpc_types::{ 
         SuiExecutionStatus,
@@ -29,10 +40,10 @@ use sui_sdk::{
         SuiTransactionBlockResponseOptions,
     },
     types::{
-        base_types::{ObjectID, SuiAddress},
-        crypto::{Signature, SuiKeyPair},
+        base_types::{ObjectID, ObjectRef, SuiAddress},
+        crypto::SuiKeyPair,
         object::Owner,
-        transaction::{CallArg, ObjectArg, Transaction, TransactionData},
+        transaction::{CallArg, ObjectArg, ProgrammableTransaction, Transaction, TransactionData},
         Identifier,
     },
     SuiClient,
@@ -42,6 +53,41 @@ use sui_types::{
     quorum_driver_types::ExecuteTransactionRequestType,
 };
 
+/// Times a submission is rebuilt after a shared-object version conflict.
+pub const MAX_VERSION_CONFLICT_RETRIES: usize = 3;
+
+/// Shortest and longest wait before rebuilding after a version conflict. Jittered,
+/// since conflicting submitters would otherwise collide again on the retry.
+const VERSION_CONFLICT_BASE_DELAY: Duration = Duration::from_millis(100);
+const VERSION_CONFLICT_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Error messages Sui returns when an input object version is stale or locked by another transaction.
+const VERSION_CONFLICT_MARKERS: [&str; 6] = [
+    "objectversionunavailableforconsumption",
+    "object version unavailable",
+    "objectlockedbyothertransaction",
+    "already locked by a different transaction",
+    "objects on which the transaction depends have been modified",
+    "is not available for consumption",
+];
+
+/// Whether `message` is a conflict over an input object version rather than a real failure.
+///
+/// These are caused by concurrent submissions touching the same shared or gas
+/// object, and succeed once the transaction is rebuilt with fresh object refs.
+pub fn is_version_conflict(message: &str) -> bool {
+    let message = message.to_lowercase();
+    VERSION_CONFLICT_MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+/// The first `threshold` of `signatures`, or `InsufficientSignatures` if there are fewer.
+fn take_quorum(signatures: Vec<SimulatedSignature>, threshold: usize) -> Result<Vec<SignatureBytes>, QuorumError> {
+    if signatures.len() < threshold {
+        return Err(QuorumError::InsufficientSignatures { got: signatures.len(), needed: threshold });
+    }
+    Ok(signatures.into_iter().take(threshold).map(|(bytes, _is_valid)| bytes).collect())
+}
+
 /// Handles the lifecycle of middleware transactions.
 #[derive(Clone)]
 pub struct TransactionHandler {
@@ -50,6 +96,20 @@ pub struct TransactionHandler {
     pub verification_manager: Option<Arc<VerificationManager>>,
     pub security_audit_log: Option<Arc<SecurityAuditLog>>,
     pub quorum_simulation: Arc<QuorumSimulation>,
+    /// Optional per-sender submission limits, checked first in `validate_transaction`.
+    pub rate_limiter: Option<Arc<SenderRateLimiter>>,
+    /// When set, L1 submissions are signed with the manager's current key instead of `node_keypair`.
+    pub key_manager: Option<Arc<KeyManager>>,
+    /// Signs submissions for transactions flagged `critical`.
+    pub multisig_submitter: Option<Arc<MultisigSubmitter>>,
+    /// Payloads the collected signature sets belong to, checked before every submission.
+    pub signature_bindings: Arc<SignatureBindings>,
+    /// Submit signature sets this handler didn't collect instead of rejecting them.
+    pub allow_unbound_signatures: bool,
+    /// Told about each successful submission so its `VerificationSuccess` event can be matched to it.
+    pub event_listener: Option<Arc<VerificationEventListener>>,
+    /// Idempotency keys of accepted submissions, so client retries aren't processed twice.
+    pub idempotency_keys: Arc<IdempotencyStore>,
 }
 
 // Implement Clone manually IF needed, otherwise remove if Arc makes it unnecessary
@@ -78,11 +138,7 @@ impl TransactionHandler {
         let node_count = quorum_simulation.get_public_key_bytes().len();
         let quorum_threshold = quorum_simulation.get_threshold();
 
-        println!(
-            "Initializing TransactionHandler with {} nodes and quorum threshold of {}",
-            node_count,
-            quorum_threshold
-        );
+        info!(node_count, quorum_threshold, "Initializing TransactionHandler");
 
         Ok(Self {
             sui_client,
@@ -90,10 +146,85 @@ impl TransactionHandler {
             verification_manager: verification_manager.map(Arc::new),
             security_audit_log,
             quorum_simulation,
+            rate_limiter: None,
+            key_manager: None,
+            multisig_submitter: None,
+            signature_bindings: Arc::new(SignatureBindings::default()),
+            allow_unbound_signatures: false,
+            event_listener: None,
+            idempotency_keys: Arc::new(IdempotencyStore::default()),
         })
     }
 
+    /// Enable per-sender rate limiting of incoming transactions.
+    pub fn with_rate_limiter(mut self, rate_limiter: SenderRateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+        self
+    }
+
+    /// Sign L1 submissions with a rotatable key instead of the fixed `node_keypair`.
+    pub fn with_key_manager(mut self, key_manager: Arc<KeyManager>) -> Self {
+        self.key_manager = Some(key_manager);
+        self
+    }
+
+    /// Route critical transactions through an M-of-N multisig.
+    pub fn with_multisig_submitter(mut self, multisig_submitter: MultisigSubmitter) -> Self {
+        self.multisig_submitter = Some(Arc::new(multisig_submitter));
+        self
+    }
+
+    /// Track submitted verifications for confirmation by `listener`'s event stream.
+    pub fn with_event_listener(mut self, listener: Arc<VerificationEventListener>) -> Self {
+        self.event_listener = Some(listener);
+        self
+    }
+
+    /// Let through signature sets this handler didn't collect, leaving them to the contract's own checks.
+    pub fn with_unbound_signatures_allowed(mut self) -> Self {
+        self.allow_unbound_signatures = true;
+        self
+    }
+
+    /// Remember idempotency keys for `ttl` instead of `DEFAULT_IDEMPOTENCY_TTL`.
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency_keys = Arc::new(IdempotencyStore::new(ttl));
+        self
+    }
+
+    /// Claim idempotency `key` for the submission tracked as `tracking_id`.
+    ///
+    /// Only `IdempotencyClaim::New` should be processed; a replay is answered
+    /// with the original tracking id.
+    pub fn claim_idempotency_key(&self, key: &str, tracking_id: &str) -> IdempotencyClaim {
+        let claim = self.idempotency_keys.claim(key, tracking_id);
+        let _ = match &claim {
+            IdempotencyClaim::New => Ok(()),
+            IdempotencyClaim::Replay { tracking_id } => self.log_audit(
+                AuditSeverity::Info,
+                &format!("Repeated idempotency key '{}' answered without resubmitting", key),
+                Some(tracking_id),
+            ),
+            IdempotencyClaim::Conflict { tracking_id } => self.log_audit(
+                AuditSeverity::Warning,
+                &format!("Idempotency key '{}' reused for a different transaction", key),
+                Some(tracking_id),
+            ),
+        };
+        claim
+    }
+
+    /// Keypair used for the next L1 submission.
+    pub fn submitter_keypair(&self) -> Arc<SuiKeyPair> {
+        match &self.key_manager {
+            Some(manager) => manager.current_keypair(),
+            None => self.node_keypair.clone(),
+        }
+    }
+
     /// Validates the basic structure and addresses of a transaction.
+    ///
+    /// Returns an error wrapping `RateLimited` if the sender is over its limit.
     pub async fn validate_transaction(
         &self,
         tx: &MiddlewareTransaction,
@@ -101,6 +232,13 @@ impl TransactionHandler {
     ) -> Result<bool> { // Correct Result usage
         let start = Instant::now();
         
+        if let Some(limiter) = &self.rate_limiter {
+            if let Err(limited) = limiter.check(&tx.sender) {
+                self.log_audit(AuditSeverity::Warning, &format!("Rejected transaction: {}", limited), None)?;
+                return Err(anyhow::Error::new(limited));
+            }
+        }
+        
         if !Self::is_valid_sui_address(&tx.sender) {
             self.log_audit(AuditSeverity::Warning, &format!("Invalid sender address: {}", tx.sender), None)?;
             return Ok(false);
@@ -147,24 +285,24 @@ impl TransactionHandler {
                     match data.owner {
                         Some(Owner::AddressOwner(addr)) if addr == owner_address => Ok(true),
                         Some(owner) => {
-                            println!(
-                                "WARN: Gas object {} owner ({:?}) does not match expected owner {}",
+                            warn!(
+                                "Gas object {} owner ({:?}) does not match expected owner {}",
                                 gas_object_id, owner, owner_address
                             );
                             Ok(false)
                         }
                         None => {
-                             println!("WARN: Gas object {} has no owner info.", gas_object_id);
+                             warn!("Gas object {} has no owner info", gas_object_id);
                              Ok(false)
                         }
                     }
                 } else {
-                    println!("WARN: Gas object {} not found.", gas_object_id);
+                    warn!("Gas object {} not found", gas_object_id);
                     Ok(false)
                 }
             }
             Err(e) => {
-                eprintln!("ERROR: Failed to fetch gas object {}: {}", gas_object_id, e);
+                error!("Failed to fetch gas object {}: {}", gas_object_id, e);
                 Ok(false)
             }
         }
@@ -185,23 +323,97 @@ impl TransactionHandler {
             });
         }
 
+        // Validators sign the payload itself, since that is what the contract's `ed25519_verify` checks.
+        // The nonce binding is enforced here in the middleware only.
+        let nonce = self.signature_bindings.reserve_nonce();
         let signatures_with_validity = self.quorum_simulation.request_signatures(attestation_payload.to_vec()).await
             .map_err(|e| QuorumError::SigningError(format!("Simulation signing failed: {}", e)))?;
+        let quorum_signatures = take_quorum(signatures_with_validity, quorum_threshold)?;
 
-        if signatures_with_validity.len() < quorum_threshold {
-            return Err(QuorumError::InsufficientSignatures {
-                got: signatures_with_validity.len(),
-                needed: quorum_threshold,
-            });
+        self.signature_bindings.bind_reserved(attestation_payload, nonce, &quorum_signatures);
+        Ok(quorum_signatures)
+    }
+
+    /// Collects quorum signatures over a structured value, see `QuorumSimulation::request_signatures_over`.
+    ///
+    /// Returns the value's BCS encoding and the verification input to submit, whose
+    /// attestation payload is the digest the nodes signed.
+    pub async fn collect_quorum_signatures_over<T: Serialize>(
+        &self,
+        value: &T,
+    ) -> Result<(Vec<u8>, VerificationInput), QuorumError> {
+        let quorum_threshold = self.quorum_simulation.get_threshold();
+        if quorum_threshold == 0 {
+            return Err(QuorumError::InsufficientSignatures { got: 0, needed: quorum_threshold });
         }
 
-        let quorum_signatures: Vec<Vec<u8>> = signatures_with_validity
-            .into_iter()
-            .take(quorum_threshold)
-            .map(|(bytes, _is_valid)| bytes)
-            .collect();
+        let nonce = self.signature_bindings.reserve_nonce();
+        let signed = self.quorum_simulation.request_signatures_over(value).await
+            .map_err(|e| QuorumError::SigningError(format!("Simulation signing failed: {}", e)))?;
+        let quorum_signatures = take_quorum(signed.signatures, quorum_threshold)?;
 
-        Ok(quorum_signatures)
+        self.signature_bindings.bind_reserved(&signed.digest, nonce, &quorum_signatures);
+        let verification_input = VerificationInput {
+            attestation_payload: signed.digest.to_vec(),
+            quorum_signatures,
+        };
+        Ok((signed.payload, verification_input))
+    }
+
+    /// Claim the signatures of `verification_input` for one submission.
+    ///
+    /// Rejects sets collected for a different payload, sets already submitted or
+    /// in flight, and sets this handler didn't collect unless
+    /// `with_unbound_signatures_allowed` was used. Settle the claim with
+    /// `settle_signature_binding` once the submission is done.
+    pub fn claim_signature_binding(&self, verification_input: &VerificationInput) -> Result<()> {
+        match self.signature_bindings.claim(&verification_input.attestation_payload, &verification_input.quorum_signatures) {
+            Ok(_) => Ok(()),
+            Err(ReplayError::Unbound) if self.allow_unbound_signatures => {
+                self.log_audit(AuditSeverity::Warning, "Submitting quorum signatures not collected by this handler", None)
+            }
+            Err(e) => {
+                self.log_audit(AuditSeverity::Critical, &format!("Rejected L1 submission: {}", e), None)?;
+                Err(anyhow::Error::new(e))
+            }
+        }
+    }
+
+    /// Consume the claimed signatures after a successful submission, or free them for a retry.
+    pub fn settle_signature_binding(&self, verification_input: &VerificationInput, submitted: bool) {
+        if submitted {
+            self.signature_bindings.complete(&verification_input.quorum_signatures);
+        } else {
+            self.signature_bindings.release(&verification_input.quorum_signatures);
+        }
+    }
+
+    /// Submits the verification for `tx`, using the multisig path when it is flagged `critical`.
+    #[tracing::instrument(
+        name = "l1_submission",
+        skip_all,
+        fields(payload_hash = %tx.payload_hash().unwrap_or_default(), critical = tx.critical)
+    )]
+    pub async fn submit_verification_for(
+        &self,
+        tx: &MiddlewareTransaction,
+        verification_input: VerificationInput,
+    ) -> Result<String> {
+        self.ensure_not_expired(tx)?;
+        if tx.critical {
+            self.submit_critical_for_onchain_verification(verification_input, tx.gas_budget).await
+        } else {
+            self.submit_for_onchain_verification(verification_input, tx.gas_budget).await
+        }
+    }
+
+    /// Fails with `Expired` once `tx` is past its deadline, so a late retry never reaches L1.
+    fn ensure_not_expired(&self, tx: &MiddlewareTransaction) -> Result<()> {
+        if let Err(expired) = tx.check_not_expired() {
+            self.log_audit(AuditSeverity::Warning, &format!("Rejected submission: {}", expired), tx.payload_hash().ok().as_deref())?;
+            return Err(expired.into());
+        }
+        Ok(())
     }
 
     /// Submits the attestation and signatures to the on-chain verification contract.
@@ -210,11 +422,180 @@ impl TransactionHandler {
         verification_input: VerificationInput,
         l1_gas_budget: u64,
     ) -> Result<String> { // Correct Result
-        println!("Submitting transaction for on-chain verification...");
+        self.submit_verification(verification_input, l1_gas_budget, None).await
+    }
+
+    /// Like `submit_for_onchain_verification`, but signed by the configured multisig.
+    /// Fails if no `MultisigSubmitter` is configured rather than falling back to a single key.
+    pub async fn submit_critical_for_onchain_verification(
+        &self,
+        verification_input: VerificationInput,
+        l1_gas_budget: u64,
+    ) -> Result<String> {
+        let multisig = match &self.multisig_submitter {
+            Some(multisig) => multisig.clone(),
+            None => {
+                self.log_audit(AuditSeverity::Error, "Critical transaction rejected: no multisig submitter configured", None)?;
+                return Err(anyhow!("Critical transaction requires a multisig submitter, but none is configured"));
+            }
+        };
+        self.submit_verification(verification_input, l1_gas_budget, Some(multisig.as_ref())).await
+    }
+
+    /// Submits the verification for `tx` with gas paid by `sponsor_keypair` from `sponsor_gas`.
+    ///
+    /// The submitter (or the multisig, for `critical` transactions) still signs as
+    /// sender, so it needs no SUI of its own; the sponsor co-signs as gas owner.
+    #[tracing::instrument(
+        name = "sponsored_submission",
+        skip_all,
+        fields(payload_hash = %tx.payload_hash().unwrap_or_default(), critical = tx.critical)
+    )]
+    pub async fn submit_sponsored(
+        &self,
+        tx: &MiddlewareTransaction,
+        verification_input: VerificationInput,
+        sponsor_keypair: &SuiKeyPair,
+        sponsor_gas: ObjectRef,
+    ) -> Result<String> {
+        self.ensure_not_expired(tx)?;
+        self.claim_signature_binding(&verification_input)?;
+        let claimed = verification_input.clone();
+        let result = self.submit_sponsored_claimed(tx, verification_input, sponsor_keypair, sponsor_gas).await;
+        self.settle_signature_binding(&claimed, result.is_ok());
+        result
+    }
+
+    async fn submit_sponsored_claimed(
+        &self,
+        tx: &MiddlewareTransaction,
+        verification_input: VerificationInput,
+        sponsor_keypair: &SuiKeyPair,
+        sponsor_gas: ObjectRef,
+    ) -> Result<String> {
+        let sponsor_address = SuiAddress::from(&sponsor_keypair.public());
+        info!(sponsor = %sponsor_address, gas_object = %sponsor_gas.0, "Submitting sponsored transaction for on-chain verification");
+
+        let multisig = if tx.critical {
+            match &self.multisig_submitter {
+                Some(multisig) => Some(multisig.clone()),
+                None => {
+                    self.log_audit(AuditSeverity::Error, "Critical transaction rejected: no multisig submitter configured", None)?;
+                    return Err(anyhow!("Critical transaction requires a multisig submitter, but none is configured"));
+                }
+            }
+        } else {
+            None
+        };
+        let submitter_keypair = self.submitter_keypair();
+        let sender_address = match &multisig {
+            Some(multisig) => multisig.address(),
+            None => SuiAddress::from(&submitter_keypair.public()),
+        };
+        debug!(sender = %sender_address, "Sponsored sender resolved");
+
+        let reference_gas_price = self.sui_client.read_api().get_reference_gas_price().await
+            .context("Failed to get reference gas price")?;
+        let payload = verification_input.attestation_payload.clone();
+        let pt = self.build_verification_call(verification_input).await?;
+        let tx_data = sponsored::sponsored_transaction_data(
+            sender_address,
+            sponsor_address,
+            sponsor_gas,
+            pt,
+            tx.gas_budget,
+            reference_gas_price,
+        );
+
+        let signed_tx = match &multisig {
+            Some(multisig) => {
+                let sender_signature = multisig.sign(&tx_data)?;
+                let sponsor_signature = sponsored::sponsor_signature(&tx_data, sponsor_keypair)?;
+                Transaction::from_generic_sig_data(tx_data, vec![sender_signature, sponsor_signature])
+            }
+            None => sponsored::sign_sponsored(tx_data, submitter_keypair.as_ref(), sponsor_keypair)?,
+        };
+
+        self.log_audit(
+            AuditSeverity::Info,
+            &format!("Submitting transaction from {} sponsored by {}", sender_address, sponsor_address),
+            None,
+        )?;
+        let digest = self.execute_verification(signed_tx).await?;
+        self.track_confirmation(&payload, &digest);
+        Ok(digest)
+    }
+
+    /// Submits once per collected signature set; the set is consumed if this succeeds.
+    async fn submit_verification(
+        &self,
+        verification_input: VerificationInput,
+        l1_gas_budget: u64,
+        multisig: Option<&MultisigSubmitter>,
+    ) -> Result<String> {
+        self.claim_signature_binding(&verification_input)?;
+        let result = self.submit_with_conflict_retries(&verification_input, l1_gas_budget, multisig).await;
+        self.settle_signature_binding(&verification_input, result.is_ok());
+        if let Ok(digest) = &result {
+            self.track_confirmation(&verification_input.attestation_payload, digest);
+        }
+        result
+    }
+
+    /// Submits, rebuilding with freshly fetched object refs after a version conflict,
+    /// up to `MAX_VERSION_CONFLICT_RETRIES` times with a jittered pause before each.
+    async fn submit_with_conflict_retries(
+        &self,
+        verification_input: &VerificationInput,
+        l1_gas_budget: u64,
+        multisig: Option<&MultisigSubmitter>,
+    ) -> Result<String> {
+        let mut backoff = Backoff::decorrelated_jitter(VERSION_CONFLICT_BASE_DELAY, VERSION_CONFLICT_MAX_DELAY)
+            .with_max_attempts(MAX_VERSION_CONFLICT_RETRIES as u32);
+        loop {
+            match self.submit_verification_once(verification_input.clone(), l1_gas_budget, multisig).await {
+                Err(e) if is_version_conflict(&format!("{:#}", e)) => {
+                    let delay = match backoff.next_delay() {
+                        Some(delay) => delay,
+                        None => return Err(e),
+                    };
+                    let retries = backoff.attempts();
+                    warn!("Object version conflict, rebuilding transaction (retry {}/{}): {:#}", retries, MAX_VERSION_CONFLICT_RETRIES, e);
+                    self.log_audit(
+                        AuditSeverity::Warning,
+                        &format!("Retrying L1 submission after object version conflict ({}/{})", retries, MAX_VERSION_CONFLICT_RETRIES),
+                        None,
+                    )?;
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn track_confirmation(&self, payload: &[u8], l1_digest: &str) {
+        if let Some(listener) = &self.event_listener {
+            listener.track(payload, Some(l1_digest));
+        }
+    }
+
+    async fn submit_verification_once(
+        &self,
+        verification_input: VerificationInput,
+        l1_gas_budget: u64,
+        multisig: Option<&MultisigSubmitter>,
+    ) -> Result<String> {
+        info!(gas_budget = l1_gas_budget, "Submitting transaction for on-chain verification");
         
-        let submitter_keypair = &self.node_keypair;
-        let submitter_address = SuiAddress::from(&submitter_keypair.public());
-        println!("  Submitter Address: {}", submitter_address);
+        let submitter_keypair = self.submitter_keypair();
+        let submitter_address = match multisig {
+            Some(multisig) => {
+                debug!("Using {}-of-{} multisig submitter", multisig.threshold(), multisig.key_count());
+                multisig.address()
+            }
+            None => SuiAddress::from(&submitter_keypair.public()),
+        };
+        debug!(submitter = %submitter_address, "Submitter resolved");
         
         let gas_object_ref = match self.select_best_gas_object_ref(submitter_address).await {
              Ok(obj_ref) => obj_ref,
@@ -223,10 +604,88 @@ impl TransactionHandler {
                  return Err(e.context("Failed to select gas object for L1 submission"));
              }
          };
-        println!("  Using Gas Object: {} (Version: {})", gas_object_ref.0, gas_object_ref.1);
+        debug!(gas_object = %gas_object_ref.0, version = %gas_object_ref.1, "Using gas object");
 
         let reference_gas_price = self.sui_client.read_api().get_reference_gas_price().await
             .context("Failed to get reference gas price")?;
+        let pt = self.build_verification_call(verification_input).await?;
+
+        let tx_data = TransactionData::new_programmable(
+            submitter_address,
+            vec![gas_object_ref],
+            pt,
+            l1_gas_budget,
+            reference_gas_price,
+        );
+    
+        let signed_tx = match multisig {
+            Some(multisig) => multisig.sign_transaction(tx_data)?,
+            None => build_signed_transaction(tx_data, &[submitter_keypair.as_ref()]),
+        };
+
+        self.execute_verification(signed_tx).await
+    }
+
+    /// Dry-runs the L1 call `submit_verification_for` would make for `tx`, without signing or submitting.
+    ///
+    /// The contract checks the quorum signatures, so `verification_input` should
+    /// already carry them for the preview to reflect a real submission. The
+    /// dry-run RPC doesn't check the sender's signature, so a critical
+    /// transaction's multisig signature is built and verified here instead.
+    /// A sender without a gas coin is dry-run with an empty gas payment, which
+    /// the node fills with a mock coin.
+    pub async fn dry_run_verification(
+        &self,
+        tx: &MiddlewareTransaction,
+        verification_input: VerificationInput,
+    ) -> Result<L1Preview> {
+        let multisig = match (&self.multisig_submitter, tx.critical) {
+            (Some(multisig), true) => Some(multisig.clone()),
+            (None, true) => return Err(anyhow!("Critical transaction requires a multisig submitter, but none is configured")),
+            (_, false) => None,
+        };
+        let sender = match &multisig {
+            Some(multisig) => multisig.address(),
+            None => SuiAddress::from(&self.submitter_keypair().public()),
+        };
+        let gas_payment = match self.select_best_gas_object_ref(sender).await {
+            Ok(gas_object_ref) => vec![gas_object_ref],
+            Err(e) => {
+                debug!(%sender, "Dry-running without a gas object: {:#}", e);
+                Vec::new()
+            }
+        };
+        let reference_gas_price = self.sui_client.read_api().get_reference_gas_price().await
+            .context("Failed to get reference gas price")?;
+        let pt = self.build_verification_call(verification_input).await?;
+        let tx_data = TransactionData::new_programmable(sender, gas_payment, pt, tx.gas_budget, reference_gas_price);
+        if let Some(multisig) = &multisig {
+            let signature = multisig.sign(&tx_data)?;
+            multisig.verify(&tx_data, &signature)?;
+        }
+
+        let response = self.sui_client.read_api().dry_run_transaction_block(tx_data).await
+            .context("Failed to dry-run L1 verification transaction")?;
+        let gas = response.effects.gas_cost_summary();
+        let status = match response.effects.status() {
+            SuiExecutionStatus::Success => "success".to_string(),
+            SuiExecutionStatus::Failure { error } => error.clone(),
+        };
+        Ok(L1Preview {
+            status,
+            sender: sender.to_string(),
+            computation_cost: gas.computation_cost,
+            storage_cost: gas.storage_cost,
+            storage_rebate: gas.storage_rebate,
+            net_gas_mist: gas.net_gas_usage(),
+            object_changes: serde_json::to_value(&response.object_changes)?,
+            balance_changes: serde_json::to_value(&response.balance_changes)?,
+            events: serde_json::to_value(&response.events)?,
+        })
+    }
+
+    /// Move call to the verification contract carrying the attestation and its signatures.
+    async fn build_verification_call(&self, verification_input: VerificationInput) -> Result<ProgrammableTransaction> {
         let package_id = ObjectID::from_str(config::VERIFICATION_CONTRACT_PACKAGE_ID)
             .context("Invalid package ID in config")?;
         let module_name = Identifier::from_str(config::VERIFICATION_CONTRACT_MODULE)
@@ -248,7 +707,7 @@ impl TransactionHandler {
             },
             None => return Err(anyhow!("Config object {} not found", config_obj_id)),
         };
-        println!("  Using Config Object: {} (InitialSharedVersion: {})", config_obj_id, initial_shared_version);
+        debug!(config_object = %config_obj_id, %initial_shared_version, "Using config object");
     
         let config_obj_arg = CallArg::Object(ObjectArg::SharedObject {
                 id: config_obj_id,
@@ -271,38 +730,27 @@ impl TransactionHandler {
             )?;
             builder.finish()
         };
+        Ok(pt)
+    }
 
-        let tx_data = TransactionData::new_programmable(
-            submitter_address,
-            vec![gas_object_ref],
-            pt,
-            l1_gas_budget,
-            reference_gas_price,
-        );
-    
-        let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data.clone());
-        // Use as_ref() to pass &SuiKeyPair which implements Signer
-        let signature = Signature::new_secure(&intent_msg, self.node_keypair.as_ref());
-    
-        println!("Submitting verification transaction to Sui network...");
+    /// Executes a signed verification transaction and checks its status.
+    async fn execute_verification(&self, signed_tx: Transaction) -> Result<String> {
+        debug!("Submitting verification transaction to Sui network");
         let options = SuiTransactionBlockResponseOptions::new().with_effects().with_object_changes();
 
         let response = self.sui_client
             .quorum_driver_api()
             .execute_transaction_block(
-                Transaction::from_data(tx_data, vec![signature.into()]),
+                signed_tx,
                 options,
                 Some(ExecuteTransactionRequestType::WaitForLocalExecution),
             )
             .await
             .context("Failed to execute L1 verification transaction")?;
     
-        println!("L1 Transaction executed.");
         let digest_str = response.digest.to_string();
-        println!("  Digest: {}", digest_str);
-        
         let effects = response.effects.context("Missing effects in L1 response")?;
-        println!("  Status: {:?}", effects.status());
+        info!(digest = %digest_str, status = ?effects.status(), "L1 transaction executed");
     
         match effects.status() {
             SuiExecutionStatus::Success => {
@@ -311,12 +759,12 @@ impl TransactionHandler {
                     "L1 verification transaction executed successfully.",
                     Some(&digest_str),
                 )?;
-                println!("L1 verification successful based on execution status.");
+                info!(digest = %digest_str, "L1 verification successful based on execution status");
         Ok(digest_str)
     }
                 SuiExecutionStatus::Failure { error } => {
                 let error_msg = format!("L1 verification transaction failed: {}", error);
-                eprintln!("ERROR: {}", error_msg);
+                error!(digest = %digest_str, "{}", error_msg);
                 self.log_audit(AuditSeverity::Error, &error_msg, Some(&digest_str))?;
                 Err(anyhow!(error_msg))
             }
@@ -349,13 +797,13 @@ impl TransactionHandler {
                          if let SuiMoveStruct::WithFields(fields) = &move_obj.fields {
                             if let Some(SuiMoveValue::Number(bal)) = fields.get("balance") {
                                 if (*bal as u64) > 0 {
-                                    println!("Selected SUI gas coin: {} (Balance: {})", data.object_id, bal);
+                                    debug!("Selected SUI gas coin: {} (Balance: {})", data.object_id, bal);
                             return Ok(data.object_ref());
                         }
                     }
                 }
             }
-                     println!("Selected potential SUI gas coin (balance check failed/skipped): {}", data.object_id);
+                     debug!("Selected potential SUI gas coin (balance check failed/skipped): {}", data.object_id);
                      return Ok(data.object_ref());
                 }
             }
@@ -363,10 +811,7 @@ impl TransactionHandler {
 
         if let Some(first_obj_resp) = gas_objects.first() {
             if let Some(data) = &first_obj_resp.data {
-                println!(
-                    "WARN: No SUI Coin found for gas. Falling back to first owned object: {}",
-                    data.object_id
-                );
+                warn!("No SUI Coin found for gas. Falling back to first owned object: {}", data.object_id);
                 return Ok(data.object_ref());
             }
         }
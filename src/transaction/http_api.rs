@@ -0,0 +1,344 @@
+//! REST API for submitting and querying middleware transactions.
+//!
+//! Routes:
+//! - `POST /transactions` accepts a `Transaction` and returns its tracking id;
+//!   an `Idempotency-Key` header (or `client_request_id`) makes retries safe
+//! - `GET /transactions/{id}` returns the latest pipeline stage and L1 digest
+//! - `POST /simulate` previews a `Transaction` without submitting it
+//! - `GET /health` reports RPC endpoint health for the active network and
+//!   re-assesses the pipeline's degradation level
+//! - `GET /metrics` exposes oracle consensus counters and last-minute
+//!   per-stage throughput and latency in Prometheus format
+//!
+//! Transaction bodies are JSON (`application/json`) or, for high-throughput
+//! clients, BCS (`application/x-bcs`, see `Transaction::to_bcs`), chosen by the
+//! request's `Content-Type`. Responses are always JSON.
+//!
+//! Processing reuses the same staged pipeline as the WebSocket endpoint
+//! (`ingest::ingest_transaction`). Enabled with the `server` feature.
+
+use super::handler::TransactionHandler;
+use super::idempotency::IdempotencyClaim;
+use super::ingest::{ingest_transaction, IngestStage, IngestUpdate};
+use super::types::{Transaction, ValidationIssue};
+use super::utils::simulate_transaction;
+use crate::execution::gateway::OverloadPolicy;
+use crate::execution::manager::ExecutionManager;
+use crate::external::oracle::OracleManager;
+use crate::metrics::prometheus;
+use crate::security::audit::{AuditSeverity, SecurityAuditLog};
+use crate::sui::network::{NetworkManager, NodeStatus};
+use anyhow::{anyhow, Result};
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Header carrying a client-chosen key that identifies retries of one submission
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Content type of a BCS-encoded `Transaction` body
+pub const BCS_CONTENT_TYPE: &str = "application/x-bcs";
+
+/// Largest accepted request body. Transactions carrying scripts are still well below this.
+pub const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Span the `/metrics` operation gauges cover
+const METRICS_WINDOW: Duration = Duration::from_secs(60);
+
+/// Shared state for the HTTP handlers.
+#[derive(Clone)]
+pub struct ApiState {
+    transaction_handler: Arc<TransactionHandler>,
+    execution_manager: Arc<ExecutionManager>,
+    security_audit_log: Arc<SecurityAuditLog>,
+    network_manager: Arc<NetworkManager>,
+    /// Source of the consensus counters served at `/metrics`
+    oracle_manager: Option<Arc<OracleManager>>,
+    /// Latest update per tracking id
+    statuses: Arc<Mutex<HashMap<String, IngestUpdate>>>,
+}
+
+impl ApiState {
+    pub fn new(
+        transaction_handler: Arc<TransactionHandler>,
+        execution_manager: Arc<ExecutionManager>,
+        security_audit_log: Arc<SecurityAuditLog>,
+        network_manager: Arc<NetworkManager>,
+    ) -> Self {
+        Self {
+            transaction_handler,
+            execution_manager,
+            security_audit_log,
+            network_manager,
+            oracle_manager: None,
+            statuses: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_oracle_manager(mut self, oracle_manager: Arc<OracleManager>) -> Self {
+        self.oracle_manager = Some(oracle_manager);
+        self
+    }
+}
+
+/// Build the API router with body size limits applied.
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/transactions", post(submit_transaction))
+        .route("/transactions/:id", get(get_transaction))
+        .route("/simulate", post(simulate))
+        .route("/health", get(health))
+        .route("/metrics", get(metrics))
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+        .with_state(state)
+}
+
+/// Serve the API on `bind_addr` until the server fails.
+pub async fn serve(bind_addr: &str, state: ApiState) -> Result<()> {
+    let addr: SocketAddr = bind_addr.parse()
+        .map_err(|e| anyhow!("Invalid HTTP bind address '{}': {}", bind_addr, e))?;
+    println!("Transaction HTTP API listening on http://{}", addr);
+    axum::Server::bind(&addr)
+        .serve(router(state).into_make_service())
+        .await
+        .map_err(|e| anyhow!("HTTP API server failed: {}", e))
+}
+
+/// `Transaction::validate`, answered with a 422 listing every issue. Checks
+/// that need the chain (addresses, gas ownership) happen in the pipeline via
+/// `TransactionHandler::validate_transaction`.
+fn check_schema(tx: &Transaction) -> Result<(), (StatusCode, Json<Value>)> {
+    tx.validate().map_err(|issues| {
+        (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({
+            "error": ValidationIssue::summarize(&issues),
+            "issues": issues,
+        })))
+    })
+}
+
+fn error_body(status: StatusCode, message: &str) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({ "error": message })))
+}
+
+/// Decode `body` according to the request's `Content-Type`.
+///
+/// Malformed bodies are 400, well-formed JSON of the wrong shape is 422, and
+/// anything other than JSON or BCS is 415.
+fn decode_transaction(headers: &HeaderMap, body: &[u8]) -> Result<Transaction, (StatusCode, Json<Value>)> {
+    let content_type = headers.get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or_default().trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if content_type == BCS_CONTENT_TYPE {
+        Transaction::from_bcs(body).map_err(|e| error_body(StatusCode::BAD_REQUEST, &format!("{:#}", e)))
+    } else if content_type == "application/json" || content_type.ends_with("+json") {
+        serde_json::from_slice(body).map_err(|e| {
+            let status = if e.is_data() { StatusCode::UNPROCESSABLE_ENTITY } else { StatusCode::BAD_REQUEST };
+            error_body(status, &format!("Invalid transaction JSON: {}", e))
+        })
+    } else {
+        Err(error_body(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            &format!("Expected Content-Type application/json or {}", BCS_CONTENT_TYPE),
+        ))
+    }
+}
+
+async fn submit_transaction(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<Value>) {
+    let tx = match decode_transaction(&headers, &body) {
+        Ok(tx) => tx,
+        Err(response) => return response,
+    };
+    if let Err(response) = check_schema(&tx) {
+        return response;
+    }
+    let id = match tx.payload_hash() {
+        Ok(id) => id,
+        Err(e) => return error_body(StatusCode::UNPROCESSABLE_ENTITY, &format!("Failed to hash transaction: {}", e)),
+    };
+
+    let idempotency_key = headers.get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| tx.client_request_id.clone());
+    if let Some(key) = &idempotency_key {
+        match state.transaction_handler.claim_idempotency_key(key, &id) {
+            IdempotencyClaim::New => {}
+            IdempotencyClaim::Replay { tracking_id } => return replayed_submission(&state, &tracking_id),
+            IdempotencyClaim::Conflict { tracking_id } => {
+                return (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({
+                    "id": tracking_id,
+                    "error": "idempotency key already used for a different transaction",
+                })));
+            }
+        }
+    }
+
+    // Rejections have to reach the client, so only `Wait` submissions queue in the background
+    let permit = match state.execution_manager.submission_gateway() {
+        Some(gateway) if gateway.policy() == OverloadPolicy::Reject => match gateway.acquire().await {
+            Ok(permit) => Some(permit),
+            Err(busy) => {
+                // Nothing was accepted, so the client's retry must not be treated as a replay
+                if let Some(key) = &idempotency_key {
+                    state.transaction_handler.idempotency_keys.release(key);
+                }
+                return error_body(StatusCode::SERVICE_UNAVAILABLE, &busy.to_string());
+            }
+        },
+        _ => None,
+    };
+
+    {
+        let mut statuses = state.statuses.lock().unwrap();
+        // A transaction whose earlier submission failed may be submitted again
+        if statuses.get(&id).is_some_and(|update| update.stage != IngestStage::Failed) {
+            if let Some(key) = &idempotency_key {
+                state.transaction_handler.idempotency_keys.release(key);
+            }
+            return (StatusCode::CONFLICT, Json(json!({ "id": id, "error": "transaction already submitted" })));
+        }
+        statuses.insert(id.clone(), IngestUpdate {
+            payload_hash: id.clone(),
+            stage: IngestStage::Received,
+            detail: None,
+            l1_digest: None,
+        });
+    }
+
+    let _ = state.security_audit_log.log_execution(
+        "TransactionHttpApi",
+        "Transaction accepted over HTTP",
+        Some(&id),
+        AuditSeverity::Info,
+    );
+
+    // Run the pipeline in the background; clients poll GET /transactions/{id}
+    let task_state = state.clone();
+    tokio::spawn(async move {
+        let _permit = match permit {
+            Some(permit) => Some(permit),
+            None => task_state.execution_manager.acquire_submission_slot().await.ok().flatten(),
+        };
+        let statuses = task_state.statuses.clone();
+        let idempotency_keys = task_state.transaction_handler.idempotency_keys.clone();
+        let _ = ingest_transaction(
+            &tx,
+            task_state.transaction_handler.as_ref(),
+            &task_state.execution_manager,
+            &task_state.security_audit_log,
+            move |update| {
+                // Free the key before the failure is visible, so the client's retry isn't answered as a replay
+                if let (IngestStage::Failed, Some(key)) = (&update.stage, &idempotency_key) {
+                    idempotency_keys.release(key);
+                }
+                let mut statuses = statuses.lock().unwrap();
+                // Keep the last known digest when later updates don't carry one
+                let previous_digest = statuses.get(&update.payload_hash).and_then(|u| u.l1_digest.clone());
+                let mut update = update;
+                if update.l1_digest.is_none() {
+                    update.l1_digest = previous_digest;
+                }
+                statuses.insert(update.payload_hash.clone(), update);
+            },
+        ).await;
+    });
+
+    (StatusCode::ACCEPTED, Json(json!({ "id": id })))
+}
+
+/// Answer a retried submission with the original's tracking id and current stage.
+fn replayed_submission(state: &ApiState, tracking_id: &str) -> (StatusCode, Json<Value>) {
+    let latest = state.statuses.lock().unwrap().get(tracking_id).cloned();
+    (StatusCode::OK, Json(json!({
+        "id": tracking_id,
+        "replayed": true,
+        "stage": latest.as_ref().map(|update| update.stage.clone()),
+        "l1_digest": latest.and_then(|update| update.l1_digest),
+    })))
+}
+
+async fn simulate(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<Value>) {
+    let tx = match decode_transaction(&headers, &body) {
+        Ok(tx) => tx,
+        Err(response) => return response,
+    };
+    if let Err(response) = check_schema(&tx) {
+        return response;
+    }
+    match simulate_transaction(&tx, &state.transaction_handler, &state.execution_manager).await {
+        Ok(result) => match serde_json::to_value(&result) {
+            Ok(body) => (StatusCode::OK, Json(body)),
+            Err(e) => error_body(StatusCode::INTERNAL_SERVER_ERROR, &format!("Failed to encode simulation: {}", e)),
+        },
+        Err(e) => error_body(StatusCode::UNPROCESSABLE_ENTITY, &format!("Simulation failed: {:#}", e)),
+    }
+}
+
+async fn get_transaction(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    let latest = state.statuses.lock().unwrap().get(&id).cloned();
+    let lifecycle = state.execution_manager.tracker().get(&id);
+    match latest {
+        Some(update) => (StatusCode::OK, Json(json!({
+            "id": id,
+            "stage": update.stage,
+            "detail": update.detail,
+            "l1_digest": update.l1_digest,
+            "lifecycle": lifecycle,
+        }))),
+        None => error_body(StatusCode::NOT_FOUND, "unknown transaction id"),
+    }
+}
+
+async fn health(State(state): State<ApiState>) -> (StatusCode, Json<Value>) {
+    let node_statuses = state.network_manager.health_check_all().await;
+    let degradation = state.execution_manager.assess_degradation().await;
+    let healthy = node_statuses.values().any(|s| *s == NodeStatus::Healthy);
+    let nodes: HashMap<String, String> = node_statuses.into_iter()
+        .map(|(url, status)| (url, format!("{:?}", status)))
+        .collect();
+
+    let submissions = state.execution_manager.submission_gateway().map(|gateway| json!({
+        "in_flight": gateway.in_flight(),
+        "queued": gateway.queue_depth(),
+        "max_in_flight": gateway.max_in_flight(),
+    }));
+
+    let code = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (code, Json(json!({
+        "status": if healthy { "ok" } else { "unavailable" },
+        "network": format!("{}", state.network_manager.get_active_config().network_type),
+        "nodes": nodes,
+        "degradation": degradation,
+        "submissions": submissions,
+    })))
+}
+
+async fn metrics(State(state): State<ApiState>) -> ([(header::HeaderName, &'static str); 1], String) {
+    let mut body = state.oracle_manager.as_ref()
+        .map(|manager| prometheus::render_consensus_metrics(&manager.consensus_metrics()))
+        .unwrap_or_default();
+    body.push_str(&prometheus::render_operation_window(&state.execution_manager.operation_window(), METRICS_WINDOW));
+    ([(header::CONTENT_TYPE, prometheus::CONTENT_TYPE)], body)
+}
@@ -0,0 +1,86 @@
+//! Deduplication of client retries by idempotency key.
+//!
+//! A client that loses the response to `POST /transactions` can't tell whether
+//! the transaction was accepted, so it retries with the same key. The first
+//! request claims the key for its tracking id; repeats within the TTL are
+//! answered with that id instead of being processed again.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a key is remembered when no TTL is configured
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Outcome of claiming an idempotency key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// First use of the key; the request should be processed
+    New,
+    /// The key was used before for the same transaction, tracked as `tracking_id`
+    Replay { tracking_id: String },
+    /// The key was used before for a different transaction
+    Conflict { tracking_id: String },
+}
+
+#[derive(Debug)]
+struct ClaimedKey {
+    tracking_id: String,
+    claimed_at: Instant,
+}
+
+/// Idempotency keys and the tracking ids they were first used for.
+#[derive(Debug)]
+pub struct IdempotencyStore {
+    ttl: Duration,
+    keys: Mutex<HashMap<String, ClaimedKey>>,
+}
+
+impl Default for IdempotencyStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+impl IdempotencyStore {
+    /// Store forgetting keys `ttl` after they were claimed.
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, keys: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Claim `key` for the transaction tracked as `tracking_id`, unless it is already claimed.
+    pub fn claim(&self, key: &str, tracking_id: &str) -> IdempotencyClaim {
+        let now = Instant::now();
+        let mut keys = self.keys.lock().unwrap();
+        keys.retain(|_, claimed| now.duration_since(claimed.claimed_at) < self.ttl);
+
+        match keys.get(key) {
+            Some(claimed) if claimed.tracking_id == tracking_id => {
+                IdempotencyClaim::Replay { tracking_id: claimed.tracking_id.clone() }
+            }
+            Some(claimed) => IdempotencyClaim::Conflict { tracking_id: claimed.tracking_id.clone() },
+            None => {
+                keys.insert(key.to_string(), ClaimedKey { tracking_id: tracking_id.to_string(), claimed_at: now });
+                IdempotencyClaim::New
+            }
+        }
+    }
+
+    /// Forget `key`, so a retry is processed. For requests rejected before they were accepted.
+    pub fn release(&self, key: &str) {
+        self.keys.lock().unwrap().remove(key);
+    }
+
+    /// Keys currently remembered, including any expired since the last claim.
+    pub fn len(&self) -> usize {
+        self.keys.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
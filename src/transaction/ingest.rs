@@ -0,0 +1,386 @@
+//! WebSocket ingestion endpoint for middleware transactions.
+//!
+//! Clients connect, send a `Transaction` as a JSON text frame or a BCS binary
+//! frame (`Transaction::to_bcs`), and receive a stream of `IngestUpdate` frames
+//! as the transaction moves through the pipeline: received → validated →
+//! signed → submitted → confirmed.
+//!
+//! The stages themselves run against an `IngestBackend`, which
+//! `TransactionHandler` implements for the real network and
+//! `testkit::MockPipeline` with mocks. The server is enabled with the `server`
+//! feature and started via `--serve`.
+
+use super::handler::TransactionHandler;
+use super::tracker::LifecycleState;
+use super::types::{SignatureBytes, Transaction, VerificationInput};
+use crate::execution::manager::ExecutionManager;
+use crate::security::audit::{AuditSeverity, SecurityAuditLog};
+use crate::sui::verification::VerificationStatus;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "server")]
+use websocket::sync::{Server, Writer};
+#[cfg(feature = "server")]
+use websocket::OwnedMessage;
+
+/// Pipeline stage reported back to an ingestion client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestStage {
+    Received,
+    Validated,
+    Signed,
+    Submitted,
+    Confirmed,
+    /// Middleware processing decided no L1 verification was needed (e.g. condition not met)
+    Skipped,
+    Failed,
+}
+
+/// Status update streamed to the client for one transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestUpdate {
+    /// Tracking id (`Transaction::payload_hash`), empty if the payload couldn't be parsed
+    pub payload_hash: String,
+    pub stage: IngestStage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l1_digest: Option<String>,
+}
+
+impl IngestUpdate {
+    fn new(payload_hash: &str, stage: IngestStage) -> Self {
+        Self {
+            payload_hash: payload_hash.to_string(),
+            stage,
+            detail: None,
+            l1_digest: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    fn with_l1_digest(mut self, digest: &str) -> Self {
+        self.l1_digest = Some(digest.to_string());
+        self
+    }
+}
+
+/// The network-facing steps of the pipeline, around off-chain processing.
+#[async_trait]
+pub trait IngestBackend: Send + Sync {
+    /// Checks that need the network or per-sender state; `false` rejects `tx`.
+    async fn validate(&self, tx: &Transaction) -> Result<bool>;
+
+    /// Quorum signatures over `attestation_payload`, enough to meet the threshold.
+    async fn collect_signatures(&self, attestation_payload: &[u8]) -> Result<Vec<SignatureBytes>>;
+
+    /// Submit the verification for `tx` to L1, returning its digest.
+    async fn submit(&self, tx: &Transaction, input: VerificationInput) -> Result<String>;
+
+    /// Status of a submitted verification, or `None` if the backend can't check.
+    async fn confirm(&self, l1_digest: &str) -> Result<Option<VerificationStatus>>;
+
+    /// How long `process_and_submit_verification` waits for a submission to
+    /// land before calling `confirm`.
+    fn confirmation_delay(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+}
+
+#[async_trait]
+impl IngestBackend for TransactionHandler {
+    async fn validate(&self, tx: &Transaction) -> Result<bool> {
+        self.validate_transaction(tx, None).await
+    }
+
+    async fn collect_signatures(&self, attestation_payload: &[u8]) -> Result<Vec<SignatureBytes>> {
+        self.collect_quorum_signatures(attestation_payload).await
+    }
+
+    async fn submit(&self, tx: &Transaction, input: VerificationInput) -> Result<String> {
+        self.submit_verification_for(tx, input).await
+    }
+
+    async fn confirm(&self, l1_digest: &str) -> Result<Option<VerificationStatus>> {
+        match &self.verification_manager {
+            Some(vm) => Ok(Some(vm.verify_transaction(l1_digest, None).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl<B: IngestBackend + ?Sized> IngestBackend for Arc<B> {
+    async fn validate(&self, tx: &Transaction) -> Result<bool> {
+        (**self).validate(tx).await
+    }
+
+    async fn collect_signatures(&self, attestation_payload: &[u8]) -> Result<Vec<SignatureBytes>> {
+        (**self).collect_signatures(attestation_payload).await
+    }
+
+    async fn submit(&self, tx: &Transaction, input: VerificationInput) -> Result<String> {
+        (**self).submit(tx, input).await
+    }
+
+    async fn confirm(&self, l1_digest: &str) -> Result<Option<VerificationStatus>> {
+        (**self).confirm(l1_digest).await
+    }
+}
+
+/// Runs a transaction through the middleware pipeline, reporting each stage to `on_update`.
+///
+/// Mirrors `process_and_submit_verification`, but surfaces progress instead of printing it.
+/// Returns the L1 digest, or `None` if processing decided no submission was needed.
+pub async fn ingest_transaction<B, F>(
+    tx: &Transaction,
+    backend: &B,
+    execution_manager: &ExecutionManager,
+    security_audit_log: &SecurityAuditLog,
+    mut on_update: F,
+) -> Result<Option<String>>
+where
+    B: IngestBackend + ?Sized,
+    F: FnMut(IngestUpdate),
+{
+    let payload_hash = tx.payload_hash()?;
+    on_update(IngestUpdate::new(&payload_hash, IngestStage::Received));
+    execution_manager.track(tx, LifecycleState::Received);
+
+    match run_stages(tx, backend, execution_manager, &payload_hash, &mut on_update).await {
+        Ok(l1_digest) => {
+            if let Some(digest) = &l1_digest {
+                let _ = security_audit_log.log_execution(
+                    "TransactionIngest",
+                    &format!("Ingested transaction submitted. L1 Digest: {}", digest),
+                    Some(&payload_hash),
+                    AuditSeverity::Info,
+                );
+            }
+            Ok(l1_digest)
+        }
+        Err(e) => {
+            on_update(IngestUpdate::new(&payload_hash, IngestStage::Failed).with_detail(format!("{:#}", e)));
+            execution_manager.track(tx, LifecycleState::Failed(format!("{:#}", e)));
+            let _ = security_audit_log.log_execution(
+                "TransactionIngest",
+                &format!("Ingested transaction failed: {:#}", e),
+                Some(&payload_hash),
+                AuditSeverity::Warning,
+            );
+            Err(e)
+        }
+    }
+}
+
+async fn run_stages<B, F>(
+    tx: &Transaction,
+    backend: &B,
+    execution_manager: &ExecutionManager,
+    payload_hash: &str,
+    on_update: &mut F,
+) -> Result<Option<String>>
+where
+    B: IngestBackend + ?Sized,
+    F: FnMut(IngestUpdate),
+{
+    if !backend.validate(tx).await? {
+        return Err(anyhow!("Initial validation failed"));
+    }
+    on_update(IngestUpdate::new(payload_hash, IngestStage::Validated));
+    execution_manager.track(tx, LifecycleState::ValidationPassed);
+
+    let verification_input = match execution_manager.prepare_verification_input(tx).await
+        .context("Off-chain processing failed")?
+    {
+        Some(input) => input,
+        None => {
+            on_update(IngestUpdate::new(payload_hash, IngestStage::Skipped)
+                .with_detail("No L1 verification required"));
+            return Ok(None);
+        }
+    };
+
+    let trace = execution_manager.trace_for(tx);
+    let quorum_signatures = {
+        let _span = trace.span("quorum_signing");
+        backend
+            .collect_signatures(&verification_input.attestation_payload)
+            .await
+            .context("Quorum signature collection failed")?
+    };
+    on_update(IngestUpdate::new(payload_hash, IngestStage::Signed)
+        .with_detail(format!("{} signatures", quorum_signatures.len())));
+    execution_manager.track(tx, LifecycleState::Signed);
+
+    let final_input = VerificationInput {
+        attestation_payload: verification_input.attestation_payload,
+        quorum_signatures,
+    };
+    let l1_digest = {
+        let _span = trace.span("l1_submission");
+        backend
+            .submit(tx, final_input)
+            .await
+            .context("L1 submission failed")?
+    };
+    on_update(IngestUpdate::new(payload_hash, IngestStage::Submitted).with_l1_digest(&l1_digest));
+    execution_manager.track(tx, LifecycleState::Submitted);
+    execution_manager.track_l1_digest(tx, &l1_digest);
+
+    let status = {
+        let _span = trace.span("l1_confirmation");
+        backend.confirm(&l1_digest).await
+            .context("L1 confirmation check failed")?
+    };
+    if let Some(status) = status {
+        if status != VerificationStatus::Verified {
+            return Err(anyhow!("L1 transaction {} not verified: {:?}", l1_digest, status));
+        }
+        on_update(IngestUpdate::new(payload_hash, IngestStage::Confirmed).with_l1_digest(&l1_digest));
+        execution_manager.track(tx, LifecycleState::Confirmed);
+    }
+
+    Ok(Some(l1_digest))
+}
+
+/// Blocking WebSocket server that feeds incoming transactions into the pipeline.
+#[cfg(feature = "server")]
+pub struct IngestServer {
+    bind_addr: String,
+    transaction_handler: Arc<TransactionHandler>,
+    execution_manager: Arc<ExecutionManager>,
+    security_audit_log: Arc<SecurityAuditLog>,
+}
+
+#[cfg(feature = "server")]
+impl IngestServer {
+    pub fn new(
+        bind_addr: &str,
+        transaction_handler: Arc<TransactionHandler>,
+        execution_manager: Arc<ExecutionManager>,
+        security_audit_log: Arc<SecurityAuditLog>,
+    ) -> Self {
+        Self {
+            bind_addr: bind_addr.to_string(),
+            transaction_handler,
+            execution_manager,
+            security_audit_log,
+        }
+    }
+
+    /// Accept connections until the listener fails, one thread per client.
+    ///
+    /// Must be called from within a Tokio runtime (e.g. `spawn_blocking`), since
+    /// each connection drives the async pipeline on the runtime's handle.
+    pub fn run(&self) -> Result<()> {
+        let runtime = tokio::runtime::Handle::current();
+        let server = Server::bind(&self.bind_addr)
+            .map_err(|e| anyhow!("Failed to bind ingestion server on {}: {}", self.bind_addr, e))?;
+        println!("Transaction ingestion server listening on ws://{}", self.bind_addr);
+
+        for request in server.filter_map(Result::ok) {
+            let runtime = runtime.clone();
+            let handler = self.transaction_handler.clone();
+            let execution_manager = self.execution_manager.clone();
+            let audit_log = self.security_audit_log.clone();
+
+            std::thread::spawn(move || {
+                let client = match request.accept() {
+                    Ok(client) => client,
+                    Err((_, e)) => {
+                        eprintln!("Failed to accept WebSocket connection: {}", e);
+                        return;
+                    }
+                };
+                let peer = client.peer_addr().map(|a| a.to_string()).unwrap_or_else(|_| "unknown".to_string());
+                println!("Ingestion client connected: {}", peer);
+
+                let (mut receiver, mut sender) = match client.split() {
+                    Ok(halves) => halves,
+                    Err(e) => {
+                        eprintln!("Failed to split WebSocket connection for {}: {}", peer, e);
+                        return;
+                    }
+                };
+
+                for message in receiver.incoming_messages() {
+                    // JSON in text frames, BCS (`Transaction::to_bcs`) in binary frames
+                    let decoded = match message {
+                        Ok(OwnedMessage::Text(text)) => serde_json::from_str::<Transaction>(&text)
+                            .map_err(|e| format!("Invalid transaction JSON: {}", e)),
+                        Ok(OwnedMessage::Binary(bytes)) => Transaction::from_bcs(&bytes)
+                            .map_err(|e| format!("{:#}", e)),
+                        Ok(OwnedMessage::Ping(data)) => {
+                            let _ = sender.send_message(&OwnedMessage::Pong(data));
+                            continue;
+                        }
+                        Ok(OwnedMessage::Close(_)) => {
+                            let _ = sender.send_message(&OwnedMessage::Close(None));
+                            break;
+                        }
+                        Err(e) => {
+                            println!("WebSocket error from {}: {}", peer, e);
+                            break;
+                        }
+                        _ => continue,
+                    };
+                    let tx = match decoded {
+                        Ok(tx) => tx,
+                        Err(detail) => {
+                            let update = IngestUpdate::new("", IngestStage::Failed).with_detail(detail);
+                            let _ = send_update(&mut sender, &update);
+                            continue;
+                        }
+                    };
+
+                    let result = runtime.block_on(async {
+                        let _permit = match execution_manager.acquire_submission_slot().await {
+                            Ok(permit) => permit,
+                            Err(busy) => {
+                                let update = IngestUpdate::new(&tx.payload_hash().unwrap_or_default(), IngestStage::Failed)
+                                    .with_detail(busy.to_string());
+                                let _ = send_update(&mut sender, &update);
+                                return Err(anyhow::Error::from(busy));
+                            }
+                        };
+                        ingest_transaction(
+                            &tx,
+                            handler.as_ref(),
+                            &execution_manager,
+                            &audit_log,
+                            |update| {
+                                if let Err(e) = send_update(&mut sender, &update) {
+                                    eprintln!("Failed to send status update to {}: {}", peer, e);
+                                }
+                            },
+                        ).await
+                    });
+                    if let Err(e) = result {
+                        println!("Ingested transaction from {} failed: {:#}", peer, e);
+                    }
+                }
+                println!("Ingestion client disconnected: {}", peer);
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+fn send_update(sender: &mut Writer<TcpStream>, update: &IngestUpdate) -> Result<()> {
+    let text = serde_json::to_string(update)?;
+    sender.send_message(&OwnedMessage::Text(text))?;
+    Ok(())
+}
@@ -1,3 +1,13 @@
 pub mod handler;
 pub mod types;
-pub mod utils;
\ No newline at end of file
+pub mod amount;
+pub mod utils;
+pub mod tracker;
+pub mod rate_limit;
+pub mod multisig;
+pub mod sponsored;
+pub mod dead_letter;
+pub mod idempotency;
+pub mod ingest;
+#[cfg(feature = "server")]
+pub mod http_api;
\ No newline at end of file
@@ -0,0 +1,95 @@
+//! M-of-N multisig signing for critical L1 submissions.
+//!
+//! Transactions flagged `critical` are submitted from a Sui multisig address
+//! instead of the single submitter key, so no one compromised key can push
+//! a critical attestation on-chain.
+
+use crate::sui::signing::transaction_intent;
+use anyhow::{anyhow, Result};
+use sui_sdk::types::{
+    base_types::SuiAddress,
+    crypto::{PublicKey, Signature, SuiKeyPair},
+    transaction::{Transaction, TransactionData, TransactionDataAPI},
+};
+use sui_types::multisig::{MultiSig, MultiSigPublicKey};
+use sui_types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
+
+/// Signs transactions with a threshold subset of configured keypairs.
+///
+/// Every key has weight 1, so the threshold is simply the number of signers
+/// required. The first `threshold` keypairs are used when signing.
+pub struct MultisigSubmitter {
+    keypairs: Vec<SuiKeyPair>,
+    threshold: u16,
+    multisig_pk: MultiSigPublicKey,
+}
+
+impl MultisigSubmitter {
+    pub fn new(keypairs: Vec<SuiKeyPair>, threshold: u16) -> Result<Self> {
+        if threshold == 0 || threshold as usize > keypairs.len() {
+            return Err(anyhow!(
+                "Multisig threshold {} must be between 1 and the number of keys ({})",
+                threshold,
+                keypairs.len()
+            ));
+        }
+        let public_keys: Vec<PublicKey> = keypairs.iter().map(|kp| kp.public()).collect();
+        let weights = vec![1u8; public_keys.len()];
+        let multisig_pk = MultiSigPublicKey::new(public_keys, weights, threshold)
+            .map_err(|e| anyhow!("Invalid multisig configuration: {}", e))?;
+
+        Ok(Self { keypairs, threshold, multisig_pk })
+    }
+
+    /// Address controlled by the multisig; it must own the gas used for submission.
+    pub fn address(&self) -> SuiAddress {
+        SuiAddress::from(&self.multisig_pk)
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn key_count(&self) -> usize {
+        self.keypairs.len()
+    }
+
+    pub fn multisig_public_key(&self) -> &MultiSigPublicKey {
+        &self.multisig_pk
+    }
+
+    /// Sign the transaction intent with the threshold set and combine the
+    /// partial signatures into a single multisig.
+    pub fn sign(&self, tx_data: &TransactionData) -> Result<GenericSignature> {
+        if tx_data.sender() != self.address() {
+            return Err(anyhow!(
+                "Transaction sender {} is not the multisig address {}",
+                tx_data.sender(),
+                self.address()
+            ));
+        }
+        let intent_msg = transaction_intent(tx_data);
+        let partial_signatures: Vec<Signature> = self.keypairs
+            .iter()
+            .take(self.threshold as usize)
+            .map(|kp| Signature::new_secure(&intent_msg, kp))
+            .collect();
+
+        let multisig = MultiSig::combine(partial_signatures, self.multisig_pk.clone())
+            .map_err(|e| anyhow!("Failed to combine multisig signatures: {}", e))?;
+        Ok(GenericSignature::MultiSig(multisig))
+    }
+
+    /// Check `signature` the way validators would for `tx_data` sent from the multisig address.
+    pub fn verify(&self, tx_data: &TransactionData, signature: &GenericSignature) -> Result<()> {
+        signature
+            .verify_authenticator(&transaction_intent(tx_data), self.address(), None, &VerifyParams::default())
+            .map_err(|e| anyhow!("Multisig signature rejected: {}", e))
+    }
+
+    /// Build a signed transaction ready for `execute_transaction_block`.
+    pub fn sign_transaction(&self, tx_data: TransactionData) -> Result<Transaction> {
+        let signature = self.sign(&tx_data)?;
+        Ok(Transaction::from_generic_sig_data(tx_data, vec![signature]))
+    }
+}
@@ -0,0 +1,173 @@
+//! Token-bucket rate limiting of transaction submissions, keyed by sender.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// How often `check` drops the buckets of senders that have gone idle.
+pub const DEFAULT_IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Bucket size and refill rate for one rate limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitConfig {
+    /// Maximum burst of submissions.
+    pub capacity: u32,
+    /// Tokens restored per second.
+    pub refill_per_sec: f64,
+}
+
+impl RateLimitConfig {
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self { capacity, refill_per_sec }
+    }
+}
+
+/// Returned when a submission exceeds the sender's or the global limit.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Rate limited ({scope} limit) for sender {sender}; retry after {retry_after_ms} ms")]
+pub struct RateLimited {
+    pub sender: String,
+    /// `"sender"` or `"global"`
+    pub scope: &'static str,
+    pub retry_after_ms: u64,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn full(config: &RateLimitConfig) -> Self {
+        Self { tokens: config.capacity as f64, last_refill: Instant::now() }
+    }
+
+    fn refill(&mut self, config: &RateLimitConfig, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * config.refill_per_sec).min(config.capacity as f64);
+        self.last_refill = now;
+    }
+
+    /// Milliseconds until one token is available (0 if one already is).
+    fn wait_ms(&self, config: &RateLimitConfig) -> u64 {
+        if self.tokens >= 1.0 {
+            0
+        } else if config.refill_per_sec <= 0.0 {
+            u64::MAX
+        } else {
+            ((1.0 - self.tokens) / config.refill_per_sec * 1000.0).ceil() as u64
+        }
+    }
+}
+
+/// Per-sender token buckets with optional per-sender overrides and a global cap.
+///
+/// A sender whose bucket has refilled completely is indistinguishable from one
+/// never seen, so such buckets are dropped every `idle_sweep_interval` to keep
+/// memory bounded by the senders that are actually active.
+#[derive(Debug)]
+pub struct SenderRateLimiter {
+    default_limit: RateLimitConfig,
+    overrides: HashMap<String, RateLimitConfig>,
+    global_limit: Option<RateLimitConfig>,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    global_bucket: Mutex<Option<TokenBucket>>,
+    idle_sweep_interval: Duration,
+    last_sweep: Mutex<Instant>,
+}
+
+impl SenderRateLimiter {
+    /// Create a limiter applying `default_limit` to every sender.
+    pub fn new(default_limit: RateLimitConfig) -> Self {
+        Self {
+            default_limit,
+            overrides: HashMap::new(),
+            global_limit: None,
+            buckets: Mutex::new(HashMap::new()),
+            global_bucket: Mutex::new(None),
+            idle_sweep_interval: DEFAULT_IDLE_SWEEP_INTERVAL,
+            last_sweep: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Use a different limit for one sender (e.g. a trusted integration).
+    pub fn with_sender_override(mut self, sender: &str, limit: RateLimitConfig) -> Self {
+        self.overrides.insert(sender.to_string(), limit);
+        self
+    }
+
+    /// Cap the combined submission rate across all senders.
+    pub fn with_global_limit(mut self, limit: RateLimitConfig) -> Self {
+        self.global_bucket = Mutex::new(Some(TokenBucket::full(&limit)));
+        self.global_limit = Some(limit);
+        self
+    }
+
+    /// Sweep idle sender buckets this often instead of every `DEFAULT_IDLE_SWEEP_INTERVAL`.
+    pub fn with_idle_sweep_interval(mut self, interval: Duration) -> Self {
+        self.idle_sweep_interval = interval;
+        self
+    }
+
+    /// Number of senders with a bucket currently held in memory.
+    pub fn tracked_senders(&self) -> usize {
+        self.buckets.lock().unwrap().len()
+    }
+
+    fn limit_for(&self, sender: &str) -> RateLimitConfig {
+        self.overrides.get(sender).copied().unwrap_or(self.default_limit)
+    }
+
+    /// Drop the buckets that have refilled to capacity, at most once per `idle_sweep_interval`.
+    fn evict_idle(&self, buckets: &mut HashMap<String, TokenBucket>, now: Instant) {
+        let mut last_sweep = self.last_sweep.lock().unwrap();
+        if now.duration_since(*last_sweep) < self.idle_sweep_interval {
+            return;
+        }
+        *last_sweep = now;
+        buckets.retain(|sender, bucket| {
+            let limit = self.limit_for(sender);
+            bucket.refill(&limit, now);
+            bucket.tokens < limit.capacity as f64
+        });
+    }
+
+    /// Take one token for `sender`, or report which limit was hit.
+    ///
+    /// Tokens are only consumed when both the sender and global buckets allow
+    /// the submission, so a globally rejected request doesn't eat the sender's quota.
+    pub fn check(&self, sender: &str) -> Result<(), RateLimited> {
+        let now = Instant::now();
+        let sender_limit = self.limit_for(sender);
+
+        let mut buckets = self.buckets.lock().unwrap();
+        self.evict_idle(&mut buckets, now);
+        let bucket = buckets.entry(sender.to_string()).or_insert_with(|| TokenBucket::full(&sender_limit));
+        bucket.refill(&sender_limit, now);
+        if bucket.tokens < 1.0 {
+            return Err(RateLimited {
+                sender: sender.to_string(),
+                scope: "sender",
+                retry_after_ms: bucket.wait_ms(&sender_limit),
+            });
+        }
+
+        let mut global_bucket = self.global_bucket.lock().unwrap();
+        if let (Some(global), Some(global_limit)) = (global_bucket.as_mut(), self.global_limit.as_ref()) {
+            global.refill(global_limit, now);
+            if global.tokens < 1.0 {
+                return Err(RateLimited {
+                    sender: sender.to_string(),
+                    scope: "global",
+                    retry_after_ms: global.wait_ms(global_limit),
+                });
+            }
+            global.tokens -= 1.0;
+        }
+
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+}
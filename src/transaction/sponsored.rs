@@ -0,0 +1,50 @@
+//! Sponsored (gasless) L1 submissions.
+//!
+//! The sender signs the transaction as usual, but gas is paid from a coin owned
+//! by a separate sponsor, who co-signs. Sui requires both signatures.
+
+use crate::sui::signing::sign_transaction_data;
+use anyhow::{anyhow, Result};
+use sui_sdk::types::{
+    base_types::{ObjectRef, SuiAddress},
+    crypto::SuiKeyPair,
+    transaction::{ProgrammableTransaction, Transaction, TransactionData, TransactionDataAPI},
+};
+use sui_types::signature::GenericSignature;
+
+/// Transaction data sent by `sender` with gas paid from `sponsor_gas`, owned by `sponsor`.
+pub fn sponsored_transaction_data(
+    sender: SuiAddress,
+    sponsor: SuiAddress,
+    sponsor_gas: ObjectRef,
+    pt: ProgrammableTransaction,
+    gas_budget: u64,
+    gas_price: u64,
+) -> TransactionData {
+    TransactionData::new_programmable_allow_sponsor(sender, vec![sponsor_gas], pt, gas_budget, gas_price, sponsor)
+}
+
+/// The sponsor's signature over `tx_data`. Fails if `sponsor` doesn't own the gas.
+pub fn sponsor_signature(tx_data: &TransactionData, sponsor: &SuiKeyPair) -> Result<GenericSignature> {
+    let sponsor_address = SuiAddress::from(&sponsor.public());
+    if tx_data.gas_owner() != sponsor_address {
+        return Err(anyhow!(
+            "Sponsor {} is not the gas owner {} of the transaction",
+            sponsor_address,
+            tx_data.gas_owner()
+        ));
+    }
+    let (_, signature) = sign_transaction_data(tx_data, sponsor);
+    Ok(signature.into())
+}
+
+/// Sign `tx_data` as both sender and sponsor, sender first.
+pub fn sign_sponsored(tx_data: TransactionData, sender: &SuiKeyPair, sponsor: &SuiKeyPair) -> Result<Transaction> {
+    let sender_address = SuiAddress::from(&sender.public());
+    if tx_data.sender() != sender_address {
+        return Err(anyhow!("Transaction sender {} does not match signing key {}", tx_data.sender(), sender_address));
+    }
+    let (_, sender_signature) = sign_transaction_data(&tx_data, sender);
+    let sponsor_signature = sponsor_signature(&tx_data, sponsor)?;
+    Ok(Transaction::from_generic_sig_data(tx_data, vec![sender_signature.into(), sponsor_signature]))
+}
@@ -0,0 +1,171 @@
+//! Lifecycle tracking for transactions moving through the middleware.
+//!
+//! Each transaction is keyed by its payload hash (`Transaction::payload_hash`)
+//! and keeps the full history of states it passed through, so operators can
+//! see where a stuck transaction stopped.
+//!
+//! The store is bounded: finished transactions are forgotten once their last
+//! update is older than the TTL, and past capacity the oldest records go first.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Records kept before the oldest are evicted
+pub const DEFAULT_TRACKER_CAPACITY: usize = 10_000;
+
+/// How long a confirmed or failed transaction stays queryable
+pub const DEFAULT_TRACKER_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Stage of the middleware pipeline a transaction has reached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleState {
+    Received,
+    ValidationPassed,
+    OracleResolved,
+    Signed,
+    Submitted,
+    Confirmed,
+    Failed(String),
+}
+
+impl LifecycleState {
+    /// Whether the transaction can no longer change state.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, LifecycleState::Confirmed | LifecycleState::Failed(_))
+    }
+}
+
+impl fmt::Display for LifecycleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleState::Received => write!(f, "received"),
+            LifecycleState::ValidationPassed => write!(f, "validation_passed"),
+            LifecycleState::OracleResolved => write!(f, "oracle_resolved"),
+            LifecycleState::Signed => write!(f, "signed"),
+            LifecycleState::Submitted => write!(f, "submitted"),
+            LifecycleState::Confirmed => write!(f, "confirmed"),
+            LifecycleState::Failed(reason) => write!(f, "failed: {}", reason),
+        }
+    }
+}
+
+/// A single state transition with the time it happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleEvent {
+    pub state: LifecycleState,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Everything known about one tracked transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedTransaction {
+    pub payload_hash: String,
+    pub state: LifecycleState,
+    pub history: Vec<LifecycleEvent>,
+    /// Digest of the L1 verification transaction, once submitted
+    pub l1_digest: Option<String>,
+}
+
+impl TrackedTransaction {
+    /// Time of the most recent state change.
+    pub fn last_updated(&self) -> Option<DateTime<Utc>> {
+        self.history.last().map(|e| e.timestamp)
+    }
+}
+
+/// Thread-safe store of transaction lifecycle records.
+#[derive(Debug, Clone)]
+pub struct TransactionTracker {
+    records: Arc<Mutex<HashMap<String, TrackedTransaction>>>,
+    capacity: usize,
+    ttl: chrono::Duration,
+}
+
+impl Default for TransactionTracker {
+    fn default() -> Self {
+        Self::with_limits(DEFAULT_TRACKER_CAPACITY, chrono::Duration::seconds(DEFAULT_TRACKER_TTL_SECS))
+    }
+}
+
+impl TransactionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most `capacity` records, forgetting finished ones after `ttl`.
+    pub fn with_limits(capacity: usize, ttl: chrono::Duration) -> Self {
+        Self { records: Arc::new(Mutex::new(HashMap::new())), capacity: capacity.max(1), ttl }
+    }
+
+    /// Record that a transaction entered `state`, creating the record if needed.
+    pub fn update(&self, payload_hash: &str, state: LifecycleState) {
+        let mut records = self.records.lock().unwrap();
+        if !records.contains_key(payload_hash) {
+            self.evict(&mut records);
+        }
+        let record = records.entry(payload_hash.to_string()).or_insert_with(|| TrackedTransaction {
+            payload_hash: payload_hash.to_string(),
+            state: state.clone(),
+            history: Vec::new(),
+            l1_digest: None,
+        });
+        record.state = state.clone();
+        record.history.push(LifecycleEvent { state, timestamp: Utc::now() });
+    }
+
+    /// Make room for one more record.
+    ///
+    /// Expired terminal records go first, then the least recently updated,
+    /// preferring terminal ones so in-flight transactions stay visible.
+    fn evict(&self, records: &mut HashMap<String, TrackedTransaction>) {
+        let cutoff = Utc::now() - self.ttl;
+        records.retain(|_, r| !(r.state.is_terminal() && r.last_updated().is_some_and(|t| t < cutoff)));
+        while records.len() >= self.capacity {
+            let oldest = records.values()
+                .min_by_key(|r| (!r.state.is_terminal(), r.last_updated()))
+                .map(|r| r.payload_hash.clone());
+            match oldest {
+                Some(hash) => { records.remove(&hash); }
+                None => break,
+            }
+        }
+    }
+
+    /// Attach the L1 digest to an existing record.
+    pub fn set_l1_digest(&self, payload_hash: &str, l1_digest: &str) {
+        if let Some(record) = self.records.lock().unwrap().get_mut(payload_hash) {
+            record.l1_digest = Some(l1_digest.to_string());
+        }
+    }
+
+    pub fn get(&self, payload_hash: &str) -> Option<TrackedTransaction> {
+        self.records.lock().unwrap().get(payload_hash).cloned()
+    }
+
+    pub fn current_state(&self, payload_hash: &str) -> Option<LifecycleState> {
+        self.records.lock().unwrap().get(payload_hash).map(|r| r.state.clone())
+    }
+
+    /// Non-terminal transactions whose last update is older than `max_age`.
+    pub fn stuck_transactions(&self, max_age: chrono::Duration) -> Vec<TrackedTransaction> {
+        let cutoff = Utc::now() - max_age;
+        self.records.lock().unwrap().values()
+            .filter(|r| !r.state.is_terminal())
+            .filter(|r| r.last_updated().map_or(false, |t| t < cutoff))
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
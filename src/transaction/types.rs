@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use crate::conditions::time::TimeCondition;
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::transaction::amount::{Amount, SUI_COIN_TYPE, SUI_DECIMALS};
 use anyhow::Result;
 // use tokio::sync::oneshot; // Unused
 use std::fmt;
@@ -16,6 +17,13 @@ pub enum TransactionType {
     Transfer, // Simple value transfer
     Invoke,   // Generic contract invocation
     Custom(String), // Custom types for specific middleware logic
+    /// Pays `payout_amount` only if the oracle value satisfies `condition`
+    /// (e.g. flight delay or weather insurance).
+    ConditionalPayout {
+        oracle_query: ExternalQuery,
+        condition: QueryCondition,
+        payout_amount: u64,
+    },
 }
 
 impl fmt::Display for TransactionType {
@@ -24,25 +32,72 @@ impl fmt::Display for TransactionType {
             TransactionType::Transfer => write!(f, "transfer"),
             TransactionType::Invoke => write!(f, "invoke"),
             TransactionType::Custom(s) => write!(f, "custom_{}", s),
+            TransactionType::ConditionalPayout { .. } => write!(f, "conditional_payout"),
         }
     }
 }
 
 /// Condition based on external query results.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct QueryCondition {
     pub threshold: u64,
     pub operator: String, // e.g., "gt", "lt", "eq"
 }
 
+impl QueryCondition {
+    /// Whether `value` satisfies the condition. Unknown operators never match.
+    pub fn is_met(&self, value: f64) -> bool {
+        let threshold = self.threshold as f64;
+        match self.operator.as_str() {
+            "gt" => value > threshold,
+            "lt" => value < threshold,
+            "eq" => (value - threshold).abs() < f64::EPSILON,
+            _ => false,
+        }
+    }
+}
+
 /// Represents a query to an external data source.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ExternalQuery {
     pub url: String,      // Endpoint URL
     pub path: Vec<String>, // JSON path to extract data from the response
     pub condition: Option<QueryCondition>, // Optional condition to evaluate against the result
 }
 
+/// A single problem found by `Transaction::validate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// Name of the offending field
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self { field, message: message.into() }
+    }
+
+    /// All `issues` on one line, for error messages
+    pub fn summarize(issues: &[ValidationIssue]) -> String {
+        issues.iter().map(|issue| issue.to_string()).collect::<Vec<_>>().join("; ")
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Whether `s` looks like a Sui object ID (`0x` followed by 1-64 hex digits).
+fn is_object_id_format(s: &str) -> bool {
+    match s.strip_prefix("0x") {
+        Some(hex) => !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
 /// The primary structure representing a middleware transaction request.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Transaction {
@@ -72,6 +127,37 @@ pub struct Transaction {
     pub websocket_message: Option<String>, // Message to send over WebSocket
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub time_condition: Option<TimeCondition>, // Time-based execution condition
+    /// Critical transactions are submitted through the M-of-N multisig path.
+    /// Not part of `payload_digest`: it changes how a result is submitted, not what is attested.
+    #[serde(default)]
+    pub critical: bool,
+    /// Idempotency key for retried submissions, used when the request has no
+    /// `Idempotency-Key` header. Not part of `payload_digest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_request_id: Option<String>,
+    /// Last moment (Unix epoch seconds) the transaction may be submitted.
+    /// Part of `payload_digest` when set, so the quorum's signatures cover it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<u64>,
+    /// Move type of the coin `amount` is counted in; SUI when unset.
+    /// Part of `payload_digest` when set, like `decimals`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coin_type: Option<String>,
+    /// Decimal places of `coin_type`; 9 (MIST per SUI) when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+}
+
+/// Returned when a transaction is submitted after its `valid_until` deadline.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Transaction expired at {valid_until} (now {now})")]
+pub struct Expired {
+    pub valid_until: u64,
+    pub now: u64,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
 }
 
 impl Transaction {
@@ -106,9 +192,107 @@ impl Transaction {
             websocket_endpoint: None,
             websocket_message: None,
             time_condition: None,
+            critical: false,
+            client_request_id: None,
+            valid_until: None,
+            coin_type: None,
+            decimals: None,
         }
     }
 
+    /// `amount` with its coin type and decimals, defaulting to MIST of SUI.
+    pub fn denominated_amount(&self) -> Amount {
+        Amount::new(
+            self.amount,
+            self.coin_type.as_deref().unwrap_or(SUI_COIN_TYPE),
+            self.decimals.unwrap_or(SUI_DECIMALS),
+        )
+    }
+
+    /// Start building a transaction of `tx_type`, see `TransactionBuilder`.
+    pub fn builder(tx_type: TransactionType) -> TransactionBuilder {
+        TransactionBuilder::new(tx_type)
+    }
+
+    /// Checks that the fields are consistent with each other, before any processing.
+    ///
+    /// Returns every issue found rather than stopping at the first one.
+    pub fn validate(&self) -> Result<(), Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+
+        if self.sender.trim().is_empty() {
+            issues.push(ValidationIssue::new("sender", "must not be empty"));
+        }
+        if self.receiver.trim().is_empty() {
+            issues.push(ValidationIssue::new("receiver", "must not be empty"));
+        }
+        if self.gas_budget == 0 {
+            issues.push(ValidationIssue::new("gas_budget", "must be greater than zero"));
+        }
+        if !is_object_id_format(&self.gas_payment) {
+            issues.push(ValidationIssue::new(
+                "gas_payment",
+                format!("'{}' is not an object ID (expected 0x followed by up to 64 hex digits)", self.gas_payment),
+            ));
+        }
+
+        match self.language.as_deref() {
+            Some("javascript") if self.script.is_none() => {
+                issues.push(ValidationIssue::new("script", "required when language is 'javascript'"));
+            }
+            Some("python") if self.python_code.is_none() => {
+                issues.push(ValidationIssue::new("python_code", "required when language is 'python'"));
+            }
+            None | Some("native") | Some("javascript") | Some("python") => {}
+            Some(other) => {
+                issues.push(ValidationIssue::new("language", format!("unsupported language '{}'", other)));
+            }
+        }
+
+        if self.external_query.is_some() && (self.script.is_some() || self.python_code.is_some()) {
+            issues.push(ValidationIssue::new(
+                "external_query",
+                "cannot be combined with a script; fetch external data from the script instead",
+            ));
+        }
+
+        if issues.is_empty() { Ok(()) } else { Err(issues) }
+    }
+
+    /// Fails with `Expired` if `now` (Unix seconds) is past `valid_until`.
+    /// The deadline itself is still valid.
+    pub fn check_deadline(&self, now: u64) -> Result<(), Expired> {
+        match self.valid_until {
+            Some(valid_until) if now > valid_until => Err(Expired { valid_until, now }),
+            _ => Ok(()),
+        }
+    }
+
+    /// `check_deadline` against the current time.
+    pub fn check_not_expired(&self) -> Result<(), Expired> {
+        self.check_deadline(unix_now())
+    }
+
+    /// Canonical 32-byte digest of the transaction: Blake2b-256 over `payload_digest()`.
+    ///
+    /// This is what the quorum attests to and what the on-chain verifier checks, so
+    /// the included fields must stay in sync with the Move contract. In BCS order:
+    /// `tx_type`, `sender`, `receiver`, `amount`, `gas_payment`, `gas_budget`,
+    /// `commands`, `timestamp`, `script`, `language`, `python_code`,
+    /// `external_query`, `websocket_endpoint`, `websocket_message`, `time_condition`,
+    /// then, only if any of `valid_until`, `coin_type` and `decimals` is set, one
+    /// extension record carrying `DIGEST_EXTENSION_VERSION` and all three as options.
+    /// Transactions using none of them keep their old digests.
+    ///
+    /// Excluded: `signatures` (added after signing), `python_params` (arbitrary JSON
+    /// with no canonical encoding) and `critical` (affects submission, not content).
+    pub fn digest(&self) -> Result<[u8; 32], bcs::Error> {
+        use blake2::digest::consts::U32;
+        use blake2::{Blake2b, Digest};
+        let payload = self.payload_digest()?;
+        Ok(Blake2b::<U32>::digest(&payload).into())
+    }
+
     /// Calculates a hashable digest of the transaction's core payload.
     ///
     /// This digest excludes volatile fields like signatures to ensure that the
@@ -156,7 +340,273 @@ impl Transaction {
             time_condition: &self.time_condition,
         };
 
-        bcs::to_bytes(&digest_payload)
+        // Always all three fields, so no combination of them encodes like another
+        #[derive(Serialize)]
+        struct DigestExtension<'a> {
+            version: u8,
+            valid_until: Option<u64>,
+            coin_type: &'a Option<String>,
+            decimals: Option<u8>,
+        }
+
+        let mut bytes = bcs::to_bytes(&digest_payload)?;
+        if self.valid_until.is_some() || self.coin_type.is_some() || self.decimals.is_some() {
+            bytes.extend(bcs::to_bytes(&DigestExtension {
+                version: DIGEST_EXTENSION_VERSION,
+                valid_until: self.valid_until,
+                coin_type: &self.coin_type,
+                decimals: self.decimals,
+            })?);
+        }
+        Ok(bytes)
+    }
+
+    /// Hex-encoded SHA-256 of `payload_digest`, used to track a transaction by id.
+    pub fn payload_hash(&self) -> Result<String, bcs::Error> {
+        use sha2::{Digest, Sha256};
+        let digest = self.payload_digest()?;
+        Ok(hex::encode(Sha256::digest(&digest)))
+    }
+
+    /// Compact binary encoding for clients submitting at high rates, see `BcsTransaction`.
+    pub fn to_bcs(&self) -> Result<Vec<u8>> {
+        let wire = BcsTransaction::try_from(self)?;
+        Ok(bcs::to_bytes(&wire)?)
+    }
+
+    /// Decode a transaction produced by `to_bcs`.
+    pub fn from_bcs(bytes: &[u8]) -> Result<Self> {
+        let wire: BcsTransaction = bcs::from_bytes(bytes)
+            .map_err(|e| anyhow::anyhow!("Invalid BCS transaction: {}", e))?;
+        wire.try_into()
+    }
+}
+
+/// Wire form of `Transaction` for BCS.
+///
+/// BCS is not self-describing, so every field is always present in declaration
+/// order; the JSON-only `skip_serializing` attributes on `Transaction` would
+/// make it undecodable. `python_params` is carried as JSON text because BCS
+/// can't encode an arbitrary `Value`.
+#[derive(Serialize, Deserialize)]
+struct BcsTransaction {
+    tx_type: TransactionType,
+    sender: String,
+    receiver: String,
+    amount: u64,
+    gas_payment: String,
+    gas_budget: u64,
+    commands: Vec<String>,
+    signatures: Option<Vec<String>>,
+    timestamp: u64,
+    script: Option<String>,
+    language: Option<String>,
+    python_code: Option<String>,
+    python_params: Option<String>,
+    external_query: Option<ExternalQuery>,
+    websocket_endpoint: Option<String>,
+    websocket_message: Option<String>,
+    time_condition: Option<TimeCondition>,
+    critical: bool,
+    client_request_id: Option<String>,
+    valid_until: Option<u64>,
+    coin_type: Option<String>,
+    decimals: Option<u8>,
+}
+
+impl TryFrom<&Transaction> for BcsTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(tx: &Transaction) -> Result<Self> {
+        let python_params = tx.python_params.as_ref().map(serde_json::to_string).transpose()?;
+        Ok(Self {
+            tx_type: tx.tx_type.clone(),
+            sender: tx.sender.clone(),
+            receiver: tx.receiver.clone(),
+            amount: tx.amount,
+            gas_payment: tx.gas_payment.clone(),
+            gas_budget: tx.gas_budget,
+            commands: tx.commands.clone(),
+            signatures: tx.signatures.clone(),
+            timestamp: tx.timestamp,
+            script: tx.script.clone(),
+            language: tx.language.clone(),
+            python_code: tx.python_code.clone(),
+            python_params,
+            external_query: tx.external_query.clone(),
+            websocket_endpoint: tx.websocket_endpoint.clone(),
+            websocket_message: tx.websocket_message.clone(),
+            time_condition: tx.time_condition.clone(),
+            critical: tx.critical,
+            client_request_id: tx.client_request_id.clone(),
+            valid_until: tx.valid_until,
+            coin_type: tx.coin_type.clone(),
+            decimals: tx.decimals,
+        })
+    }
+}
+
+impl TryFrom<BcsTransaction> for Transaction {
+    type Error = anyhow::Error;
+
+    fn try_from(wire: BcsTransaction) -> Result<Self> {
+        let python_params = wire.python_params.as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid python_params in BCS transaction: {}", e))?;
+        Ok(Self {
+            tx_type: wire.tx_type,
+            sender: wire.sender,
+            receiver: wire.receiver,
+            amount: wire.amount,
+            gas_payment: wire.gas_payment,
+            gas_budget: wire.gas_budget,
+            commands: wire.commands,
+            signatures: wire.signatures,
+            timestamp: wire.timestamp,
+            script: wire.script,
+            language: wire.language,
+            python_code: wire.python_code,
+            python_params,
+            external_query: wire.external_query,
+            websocket_endpoint: wire.websocket_endpoint,
+            websocket_message: wire.websocket_message,
+            time_condition: wire.time_condition,
+            critical: wire.critical,
+            client_request_id: wire.client_request_id,
+            valid_until: wire.valid_until,
+            coin_type: wire.coin_type,
+            decimals: wire.decimals,
+        })
+    }
+}
+
+/// Layout version of the extension record `payload_digest` appends for expiry and coin fields.
+pub const DIGEST_EXTENSION_VERSION: u8 = 1;
+
+/// Gas budget used by `TransactionBuilder` unless one is set, enough for the verification call.
+pub const DEFAULT_GAS_BUDGET: u64 = 2_000_000;
+
+/// Fluent construction of a `Transaction`.
+///
+/// Unset fields take the same defaults as `Transaction::new`, with a gas budget
+/// of `DEFAULT_GAS_BUDGET`. `build` runs `Transaction::validate`, so a missing
+/// sender or a script combined with an external query is caught before the
+/// transaction reaches the pipeline.
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    tx: Transaction,
+}
+
+impl TransactionBuilder {
+    pub fn new(tx_type: TransactionType) -> Self {
+        Self {
+            tx: Transaction::new(tx_type, String::new(), String::new(), 0, String::new(), DEFAULT_GAS_BUDGET, Vec::new()),
+        }
+    }
+
+    pub fn sender(mut self, sender: impl Into<String>) -> Self {
+        self.tx.sender = sender.into();
+        self
+    }
+
+    pub fn receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.tx.receiver = receiver.into();
+        self
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.tx.amount = amount;
+        self
+    }
+
+    /// Gas object ID on the L1 chain
+    pub fn gas_payment(mut self, gas_payment: impl Into<String>) -> Self {
+        self.tx.gas_payment = gas_payment.into();
+        self
+    }
+
+    pub fn gas_budget(mut self, gas_budget: u64) -> Self {
+        self.tx.gas_budget = gas_budget;
+        self
+    }
+
+    /// Append a middleware command
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.tx.commands.push(command.into());
+        self
+    }
+
+    /// Override the creation time (Unix epoch seconds), e.g. for reproducible digests
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.tx.timestamp = timestamp;
+        self
+    }
+
+    /// Run `script` in the JavaScript sandbox
+    pub fn javascript(mut self, script: impl Into<String>) -> Self {
+        self.tx.script = Some(script.into());
+        self.tx.language = Some("javascript".to_string());
+        self
+    }
+
+    /// Run `code` in the Python sandbox
+    pub fn python(mut self, code: impl Into<String>) -> Self {
+        self.tx.python_code = Some(code.into());
+        self.tx.language = Some("python".to_string());
+        self
+    }
+
+    /// Parameters passed to the Python script or native handler
+    pub fn params(mut self, params: Value) -> Self {
+        self.tx.python_params = Some(params);
+        self
+    }
+
+    pub fn external_query(mut self, query: ExternalQuery) -> Self {
+        self.tx.external_query = Some(query);
+        self
+    }
+
+    /// Stream from `endpoint`, sending `message` once connected
+    pub fn websocket(mut self, endpoint: impl Into<String>, message: impl Into<String>) -> Self {
+        self.tx.websocket_endpoint = Some(endpoint.into());
+        self.tx.websocket_message = Some(message.into());
+        self
+    }
+
+    pub fn time_condition(mut self, condition: TimeCondition) -> Self {
+        self.tx.time_condition = Some(condition);
+        self
+    }
+
+    /// Submit through the M-of-N multisig path
+    pub fn critical(mut self) -> Self {
+        self.tx.critical = true;
+        self
+    }
+
+    /// Refuse submission after `valid_until` (Unix epoch seconds)
+    pub fn valid_until(mut self, valid_until: u64) -> Self {
+        self.tx.valid_until = Some(valid_until);
+        self
+    }
+
+    /// Count `amount` in base units of `coin_type`, which has `decimals` decimal places
+    pub fn coin(mut self, coin_type: impl Into<String>, decimals: u8) -> Self {
+        self.tx.coin_type = Some(coin_type.into());
+        self.tx.decimals = Some(decimals);
+        self
+    }
+
+    /// The transaction, or every validation issue found.
+    pub fn build(self) -> Result<Transaction, Vec<ValidationIssue>> {
+        let mut issues = self.tx.validate().err().unwrap_or_default();
+        // `language` only records the last script set, so validate alone can't see both
+        if self.tx.script.is_some() && self.tx.python_code.is_some() {
+            issues.push(ValidationIssue::new("language", "javascript and python scripts are mutually exclusive"));
+        }
+        if issues.is_empty() { Ok(self.tx) } else { Err(issues) }
     }
 }
 
@@ -164,7 +614,7 @@ impl Transaction {
 /// Contains the outcome and links back to the original transaction.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MiddlewareAttestation {
-    /// Hash of the original Transaction payload (`payload_digest`).
+    /// Canonical digest of the original Transaction (`Transaction::digest`).
     pub original_payload_hash: Vec<u8>,
     /// Outcome determined by the middleware (e.g., calculated value, decision).
     pub middleware_outcome: Value, // Flexible JSON value for outcome
@@ -172,6 +622,11 @@ pub struct MiddlewareAttestation {
     pub timestamp: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub middleware_node_id: Option<String>, // Optional identifier of the attesting node
+    /// The transaction's deadline, in the clear and covered by the quorum's signatures.
+    /// The deployed verifier doesn't read it: expiry is enforced off-chain only,
+    /// by the middleware refusing to submit late (`Transaction::check_not_expired`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<u64>,
 }
 
 impl MiddlewareAttestation {
@@ -185,12 +640,36 @@ impl MiddlewareAttestation {
                  .unwrap_or_default()
                  .as_secs(),
             middleware_node_id: None,
+            valid_until: None,
          }
      }
 
+     /// Attestation of `outcome` for `tx`, carrying its deadline.
+     pub fn for_transaction(tx: &Transaction, middleware_outcome: Value) -> Result<Self, bcs::Error> {
+         Ok(Self { valid_until: tx.valid_until, ..Self::new(tx.digest()?.to_vec(), middleware_outcome) })
+     }
+
      /// Serializes the attestation into bytes suitable for signing by quorum nodes.
+     ///
+     /// Every field is encoded in declaration order, unset options included, so the
+     /// signed layout doesn't depend on which of them happen to be set.
      pub fn to_bytes_for_signing(&self) -> Result<Vec<u8>, bcs::Error> {
-         bcs::to_bytes(self)
+         #[derive(Serialize)]
+         struct SigningLayout<'a> {
+             original_payload_hash: &'a Vec<u8>,
+             middleware_outcome: &'a Value,
+             timestamp: u64,
+             middleware_node_id: &'a Option<String>,
+             valid_until: Option<u64>,
+         }
+
+         bcs::to_bytes(&SigningLayout {
+             original_payload_hash: &self.original_payload_hash,
+             middleware_outcome: &self.middleware_outcome,
+             timestamp: self.timestamp,
+             middleware_node_id: &self.middleware_node_id,
+             valid_until: self.valid_until,
+         })
      }
  }
 
@@ -1,34 +1,56 @@
 //! Utility functions for transaction processing orchestration.
 
 use super::handler::TransactionHandler;
+use super::ingest::IngestBackend;
 use super::types::{Transaction, VerificationInput}; // Removed VerifiableTransactionData
 use crate::execution::manager::ExecutionManager;
+use crate::execution::simulation::{OracleReading, SimulationResult};
 use crate::metrics::storage::MetricsStorage;
-use crate::metrics::performance::PerformanceMetrics; // Keep if used in metrics.as_mut()
+use crate::metrics::performance::{OperationStats, PerformanceMetrics};
 use crate::security::audit::{AuditSeverity, SecurityAuditLog};
 use crate::sui::verification::VerificationStatus;
+use super::tracker::LifecycleState;
+use super::dead_letter::ReplayReport;
 use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
 use std::sync::Arc;
-use std::time::Duration;
 use sui_sdk::types::{
     base_types::{ObjectID},
     crypto::SuiKeyPair,
 };
 use tokio; // Added import for sleep
 
+/// What `process_and_submit_verification` did with a transaction.
+#[derive(Debug, Clone)]
+pub struct VerificationOutcome {
+    /// Whether the middleware attested the transaction and submitted it to L1
+    pub decision: bool,
+    pub l1_digest: Option<String>,
+    /// L1 confirmation status. `Pending` when confirmation wasn't checked,
+    /// `Unverifiable` when nothing was submitted or the check failed.
+    pub verification_status: VerificationStatus,
+    /// The attested outcome; `None` when nothing was attested
+    pub outcome: Option<Value>,
+    /// Every oracle value read on the way to the decision
+    pub oracle_values: Vec<OracleReading>,
+    /// Time spent across the pipeline stages in the transaction's trace;
+    /// `ExecutionManager::trace` has the per-stage breakdown
+    pub timings: OperationStats,
+}
+
 /// Processes a transaction through the middleware and submits it for L1 verification.
 ///
 /// Orchestrates the flow:
-/// 1. Initial validation (via `TransactionHandler`).
+/// 1. Initial validation (via the backend).
 /// 2. Off-chain processing and attestation preparation (via `ExecutionManager`).
-/// 3. Quorum signature collection (via `TransactionHandler`).
-/// 4. L1 submission for verification (via `TransactionHandler`).
+/// 3. Quorum signature collection (via the backend).
+/// 4. L1 submission for verification (via the backend).
 /// 5. (Optional) L1 confirmation check.
 ///
 /// # Arguments
 /// * `tx`: The middleware transaction request.
 /// * `tx_name`: A descriptive name for logging.
-/// * `transaction_handler`: Shared reference to the transaction handler.
+/// * `backend`: Validates, signs, submits and confirms; the `TransactionHandler` for the real network.
 /// * `execution_manager`: Shared reference to the execution manager.
 /// * `metrics_storage`: Optional shared storage for performance metrics (used for deprecated metrics).
 /// * `security_audit_log`: Shared security audit logger.
@@ -36,46 +58,59 @@ use tokio; // Added import for sleep
 /// * `gas_object_id`: ObjectID of the gas coin for the L1 transaction.
 ///
 /// # Returns
-/// `Ok(())` on successful processing and submission, `Err` otherwise.
-pub async fn process_and_submit_verification(
+/// The `VerificationOutcome`, including when processing decided not to submit
+/// anything; `Err` if any stage failed.
+pub async fn process_and_submit_verification<B: IngestBackend + ?Sized>(
     tx: &Transaction,
     tx_name: &str,
-    transaction_handler: &Arc<TransactionHandler>,
+    backend: &B,
     execution_manager: &Arc<ExecutionManager>,
     metrics_storage: Option<&Arc<MetricsStorage>>,
     security_audit_log: &Arc<SecurityAuditLog>,
     submitter_keypair: &SuiKeyPair, // Now passed directly
     gas_object_id: &ObjectID, // Now passed directly
-) -> Result<()> {
+) -> Result<VerificationOutcome> {
     println!(
         "\n--- Running: {} ---",
         tx_name.to_uppercase()
     );
 
     // Reference to deprecated metrics struct
-    let mut metrics = metrics_storage.map(|_storage| PerformanceMetrics::new(tx_name));
+    let metrics = metrics_storage.map(|_storage| PerformanceMetrics::new(tx_name));
+
+    execution_manager.track(tx, LifecycleState::Received);
 
     // 1. Initial Validation
-    if !transaction_handler.validate_transaction(tx, metrics.as_mut()).await? {
+    if !backend.validate(tx).await? {
         // Validation failure already logged by handler
         println!("❌ Initial validation failed for {}", tx_name);
+        execution_manager.track(tx, LifecycleState::Failed("Initial validation failed".to_string()));
         return Err(anyhow!("Initial validation failed"));
     }
     println!("✅ Initial validation passed.");
+    execution_manager.track(tx, LifecycleState::ValidationPassed);
 
     // 2. Process transaction off-chain & prepare attestation
     println!("Processing transaction off-chain...");
     // Assuming ExecutionManager now handles preparing the input needed for signing/verification
-    let verification_input_opt = execution_manager.prepare_verification_input(tx).await; 
+    let prepared = execution_manager.prepare_attestation_with_readings(tx).await;
+    let trace = execution_manager.trace_for(tx);
 
-    let verification_input = match verification_input_opt {
-        Ok(Some(input)) => {
+    let (verification_input, attested_outcome, oracle_values) = match prepared {
+        Ok((Some((input, outcome)), oracle_values)) => {
             println!("✅ Middleware processing complete, verification input prepared.");
-            input // Assuming this returns the VerificationInput struct directly
+            (input, outcome, oracle_values)
         }
-        Ok(None) => {
+        Ok((None, oracle_values)) => {
             println!("✅ Middleware processing skipped (e.g., condition not met). No L1 verification needed.");
-            return Ok(());
+            return Ok(VerificationOutcome {
+                decision: false,
+                l1_digest: None,
+                verification_status: VerificationStatus::Unverifiable("No L1 submission needed".to_string()),
+                outcome: None,
+                oracle_values,
+                timings: trace.total_stats(),
+            });
         }
         Err(e) => {
              println!("❌ Error during off-chain processing: {}", e);
@@ -85,6 +120,7 @@ pub async fn process_and_submit_verification(
                  None,
                  AuditSeverity::Error,
              )?;
+             execution_manager.track(tx, LifecycleState::Failed(format!("Off-chain processing error: {}", e)));
              // Use context for better error reporting
              return Err(e.context("Off-chain processing failed"));
         }
@@ -92,16 +128,19 @@ pub async fn process_and_submit_verification(
 
     // 3. Collect Quorum Signatures
     println!(
-        "Collecting {} signatures for attestation payload ({} bytes)...",
-        transaction_handler.quorum_simulation.get_threshold(),
+        "Collecting quorum signatures for attestation payload ({} bytes)...",
         verification_input.attestation_payload.len()
     );
-    let quorum_signatures = match transaction_handler
-        .collect_quorum_signatures(&verification_input.attestation_payload)
-        .await
-    {
+    let signing_result = {
+        let _span = trace.span("quorum_signing");
+        backend
+            .collect_signatures(&verification_input.attestation_payload)
+            .await
+    };
+    let quorum_signatures = match signing_result {
         Ok(signatures) => {
             println!("✅ Successfully collected {} signatures.", signatures.len());
+            execution_manager.track(tx, LifecycleState::Signed);
             signatures
         }
         Err(e) => {
@@ -112,6 +151,7 @@ pub async fn process_and_submit_verification(
                 None,
                 AuditSeverity::Error,
             )?;
+            execution_manager.track(tx, LifecycleState::Failed(format!("Quorum signature collection failed: {}", e)));
             // Use context for better error reporting
             return Err(anyhow!(e).context("Quorum signature collection failed"));
         }
@@ -125,16 +165,25 @@ pub async fn process_and_submit_verification(
 
     // 4. Submit for On-Chain Verification
     println!("Submitting for L1 verification...");
-    let submission_result: Result<String> = transaction_handler // Explicit type for result
-        .submit_for_onchain_verification(final_verification_input, tx.gas_budget)
-        .await;
+    let submission_result: Result<String> = { // Explicit type for result
+        let _span = trace.span("l1_submission");
+        backend
+            .submit(tx, final_verification_input)
+            .await
+    };
 
-    match submission_result {
+    let mut verification_status = VerificationStatus::Pending;
+    let l1_digest = match submission_result {
         Ok(l1_digest) => {
             println!(
                 "✅ L1 verification transaction submitted successfully. Digest: {}",
                 l1_digest
             );
+            if let Some(link) = execution_manager.tx_explorer_link(&l1_digest) {
+                println!("   Explorer: {}", link);
+            }
+            execution_manager.track(tx, LifecycleState::Submitted);
+            execution_manager.track_l1_digest(tx, &l1_digest);
             security_audit_log.log_network(
                 tx_name,
                 &format!("Submitted verification tx to SUI. L1 Digest: {}", l1_digest),
@@ -144,38 +193,44 @@ pub async fn process_and_submit_verification(
 
             // 5. Optional: Check L1 Confirmation
             println!("Waiting briefly before checking L1 status for digest: {}", l1_digest);
-            tokio::time::sleep(Duration::from_secs(5)).await;
-
-            if let Some(vm) = &transaction_handler.verification_manager {
-                 // Pass metrics.as_mut() which correctly gives Option<&mut PerformanceMetrics>
-                 match vm.verify_transaction(&l1_digest, metrics.as_mut()).await {
-                     Ok(status) => {
-                         println!("✅ L1 confirmation status for {}: {:?}", l1_digest, status);
-                         security_audit_log.log_verification(
-                             tx_name,
-                             &format!("L1 confirmation status: {:?}", status),
-                             Some(&l1_digest),
-                             AuditSeverity::Info,
-                         )?;
-                         if status != VerificationStatus::Verified {
-                             println!("WARN: L1 transaction {} not fully verified yet (status: {:?})", l1_digest, status);
-                         }
-                     }
-                     Err(e) => {
-                         println!(
-                             "❌ Error checking L1 confirmation for {}: {}",
-                             l1_digest, e
-                         );
-                         security_audit_log.log_verification(
-                             tx_name,
-                             &format!("Error checking L1 confirmation: {}", e),
-                             Some(&l1_digest),
-                             AuditSeverity::Error,
-                         )?;
-                     }
-                 }
-            } else {
-                println!("Skipping L1 confirmation check (Verification Manager not available).");
+            tokio::time::sleep(backend.confirmation_delay()).await;
+
+            let confirmation = {
+                let _span = trace.span("l1_confirmation");
+                backend.confirm(&l1_digest).await
+            };
+            match confirmation {
+                Ok(Some(status)) => {
+                    println!("✅ L1 confirmation status for {}: {:?}", l1_digest, status);
+                    security_audit_log.log_verification(
+                        tx_name,
+                        &format!("L1 confirmation status: {:?}", status),
+                        Some(&l1_digest),
+                        AuditSeverity::Info,
+                    )?;
+                    if status != VerificationStatus::Verified {
+                        println!("WARN: L1 transaction {} not fully verified yet (status: {:?})", l1_digest, status);
+                    } else {
+                        execution_manager.track(tx, LifecycleState::Confirmed);
+                    }
+                    verification_status = status;
+                }
+                Ok(None) => {
+                    println!("Skipping L1 confirmation check (Verification Manager not available).");
+                }
+                Err(e) => {
+                    println!(
+                        "❌ Error checking L1 confirmation for {}: {}",
+                        l1_digest, e
+                    );
+                    security_audit_log.log_verification(
+                        tx_name,
+                        &format!("Error checking L1 confirmation: {}", e),
+                        Some(&l1_digest),
+                        AuditSeverity::Error,
+                    )?;
+                    verification_status = VerificationStatus::Unverifiable(format!("Confirmation check failed: {}", e));
+                }
             }
 
             // Handling deprecated metrics
@@ -186,7 +241,7 @@ pub async fn process_and_submit_verification(
                 //     storage.add_metrics(m); // Deprecated call
                 // }
             }
-
+            l1_digest
         }
         Err(e) => {
             println!("❌ L1 verification transaction submission failed: {:#}", e);
@@ -196,10 +251,89 @@ pub async fn process_and_submit_verification(
                 None,
                 AuditSeverity::Error,
             )?;
+            execution_manager.track(tx, LifecycleState::Failed(format!("L1 submission failed: {}", e)));
             return Err(e.context("L1 submission failed"));
         }
-    }
+    };
 
     println!("\n--- {} Demo Flow Complete ---", tx_name.to_uppercase());
-    Ok(())
-}
\ No newline at end of file
+    Ok(VerificationOutcome {
+        decision: true,
+        l1_digest: Some(l1_digest),
+        verification_status,
+        outcome: Some(attested_outcome),
+        oracle_values,
+        timings: trace.total_stats(),
+    })
+}
+/// Resubmit every transaction in the execution manager's dead-letter log.
+///
+/// Each entry goes through `process_and_submit_verification` again; entries that
+/// fail again are re-recorded by the execution manager.
+pub async fn replay_dead_letters(
+    transaction_handler: &Arc<TransactionHandler>,
+    execution_manager: &Arc<ExecutionManager>,
+    metrics_storage: Option<&Arc<MetricsStorage>>,
+    security_audit_log: &Arc<SecurityAuditLog>,
+    submitter_keypair: &SuiKeyPair,
+    gas_object_id: &ObjectID,
+) -> Result<ReplayReport> {
+    let queue = execution_manager.dead_letter_queue()
+        .ok_or_else(|| anyhow!("No dead-letter log configured"))?;
+    println!("Replaying dead-letter log {}...", queue.path().display());
+
+    let report = queue.replay(|tx| async move {
+        process_and_submit_verification(
+            &tx,
+            "dead_letter_replay",
+            transaction_handler,
+            execution_manager,
+            metrics_storage,
+            security_audit_log,
+            submitter_keypair,
+            gas_object_id,
+        ).await.map(|_| ())
+    }).await?;
+
+    println!(
+        "Resubmitted {} dead-lettered transaction(s), {} failed again, {} dropped as expired.",
+        report.resubmitted,
+        report.failed.len(),
+        report.expired.len()
+    );
+    for (payload_hash, error) in &report.failed {
+        eprintln!("  {}: {}", payload_hash, error);
+    }
+    Ok(report)
+}
+
+/// Preview `tx` end to end: off-chain decision plus a dry run of the L1 call.
+///
+/// Quorum signatures are collected so the contract's signature check is part
+/// of the preview, but nothing is signed for L1, submitted or tracked. An L1
+/// dry-run failure is reported in `l1_preview_error` rather than failing the
+/// simulation, since the off-chain result is still useful on its own.
+pub async fn simulate_transaction(
+    tx: &Transaction,
+    transaction_handler: &TransactionHandler,
+    execution_manager: &ExecutionManager,
+) -> Result<SimulationResult> {
+    let mut result = execution_manager.simulate(tx).await?;
+    let mut verification_input = match result.verification_input.clone() {
+        Some(input) if result.would_attest => input,
+        _ => return Ok(result),
+    };
+
+    match transaction_handler.collect_quorum_signatures(&verification_input.attestation_payload).await {
+        Ok(signatures) => verification_input.quorum_signatures = signatures,
+        Err(e) => {
+            result.l1_preview_error = Some(format!("Quorum signing failed: {}", e));
+            return Ok(result);
+        }
+    }
+    match transaction_handler.dry_run_verification(tx, verification_input).await {
+        Ok(preview) => result.l1_preview = Some(preview),
+        Err(e) => result.l1_preview_error = Some(format!("{:#}", e)),
+    }
+    Ok(result)
+}
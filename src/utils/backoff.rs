@@ -0,0 +1,116 @@
+//! Delay schedules for retrying failed operations.
+//!
+//! Every retry site (RPC reconnection, L1 submission after a version conflict,
+//! confirmation polling) draws its delays from a `Backoff`. Decorrelated jitter
+//! spreads out clients that failed at the same moment, so a node coming back
+//! up isn't hit by all of their retries at once.
+
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+
+/// How successive delays grow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackoffStrategy {
+    /// Always `base`
+    Fixed,
+    /// `base` doubled after every attempt
+    #[default]
+    Exponential,
+    /// Uniformly random between `base` and three times the previous delay.
+    /// See <https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/>.
+    DecorrelatedJitter,
+}
+
+/// A delay schedule and the attempts made against it so far.
+///
+/// Clone a configured `Backoff` to start a fresh schedule for each operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+    strategy: BackoffStrategy,
+    base: Duration,
+    max_delay: Duration,
+    max_attempts: Option<u32>,
+    attempts: u32,
+    previous: Duration,
+}
+
+impl Backoff {
+    pub fn new(strategy: BackoffStrategy, base: Duration, max_delay: Duration) -> Self {
+        Self { strategy, base, max_delay, max_attempts: None, attempts: 0, previous: base }
+    }
+
+    pub fn fixed(delay: Duration) -> Self {
+        Self::new(BackoffStrategy::Fixed, delay, delay)
+    }
+
+    pub fn exponential(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self::new(BackoffStrategy::Exponential, initial_delay, max_delay)
+    }
+
+    pub fn decorrelated_jitter(base: Duration, max_delay: Duration) -> Self {
+        Self::new(BackoffStrategy::DecorrelatedJitter, base, max_delay)
+    }
+
+    /// Give up after `max_attempts` delays. Unlimited by default.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    pub fn strategy(&self) -> BackoffStrategy {
+        self.strategy
+    }
+
+    /// Delays handed out so far.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Delay before the next retry, capped at `max_delay`, or `None` once `max_attempts` are used up.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        if matches!(self.max_attempts, Some(max) if self.attempts >= max) {
+            return None;
+        }
+        let delay = match self.strategy {
+            BackoffStrategy::Fixed => self.base,
+            BackoffStrategy::Exponential => self.base.saturating_mul(2u32.saturating_pow(self.attempts)),
+            BackoffStrategy::DecorrelatedJitter => {
+                let low = self.base.as_millis() as u64;
+                let high = (self.previous.as_millis() as u64).saturating_mul(3).max(low);
+                Duration::from_millis(rand::thread_rng().gen_range(low, high.saturating_add(1)))
+            }
+        }
+        .min(self.max_delay);
+        self.attempts += 1;
+        self.previous = delay;
+        Some(delay)
+    }
+
+    /// Start the schedule over, e.g. after a success.
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+        self.previous = self.base;
+    }
+}
+
+/// Run `op` until it succeeds, fails with an error `is_retryable` rejects, or `backoff` runs out.
+///
+/// Sleeps for the next delay of `backoff` between attempts and returns the
+/// last error when giving up.
+pub async fn retry_async<T, E, F, Fut>(mut op: F, mut backoff: Backoff, is_retryable: impl Fn(&E) -> bool) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if is_retryable(&e) => match backoff.next_delay() {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(e),
+            },
+            Err(e) => return Err(e),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Shared construction of `reqwest` clients.
+//!
+//! Subsystems that talk HTTP build their clients through `HttpClientFactory`
+//! so pool sizing, user agent, proxy and TLS settings are configured once.
+//! Each subsystem still chooses its own request timeout.
+
+use super::pinning::pinned_tls_config;
+use crate::security::audit::SecurityAuditLog;
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Settings applied to every client built by an `HttpClientFactory`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpConfig {
+    /// Request timeout used when the caller doesn't pick one
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    /// Idle connections kept open per host
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout: Duration,
+    pub user_agent: String,
+    /// Proxy URL for all requests (e.g. `http://proxy.corp:3128`)
+    pub proxy: Option<String>,
+    /// Extra PEM root certificates to trust, e.g. a corporate TLS-inspecting proxy's CA
+    pub root_certificates: Vec<PathBuf>,
+    /// Skip certificate validation. Only for local development nodes.
+    pub accept_invalid_certs: bool,
+    /// Host -> accepted SHA-256 leaf certificate fingerprints. Hosts not listed aren't pinned.
+    pub pinned_certificates: HashMap<String, Vec<String>>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            pool_max_idle_per_host: 16,
+            pool_idle_timeout: Duration::from_secs(90),
+            user_agent: format!("sui-modular-middleware/{}", env!("CARGO_PKG_VERSION")),
+            proxy: None,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            pinned_certificates: HashMap::new(),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Accept `fingerprint` (hex SHA-256, colons optional) for `host`, pinning it.
+    pub fn with_pin(mut self, host: &str, fingerprint: &str) -> Self {
+        self.pinned_certificates.entry(host.to_lowercase()).or_default().push(fingerprint.to_string());
+        self
+    }
+
+    /// Defaults overridden by `MIDDLEWARE_HTTP_PROXY`, `MIDDLEWARE_HTTP_USER_AGENT`,
+    /// `MIDDLEWARE_HTTP_POOL_SIZE`, `MIDDLEWARE_HTTP_CA_CERT` and `MIDDLEWARE_HTTP_PINS`
+    /// when set. Pins are written `host=fp1,fp2;other.host=fp3`.
+    pub fn from_env() -> Result<Self> {
+        let mut config = Self::default();
+        if let Ok(proxy) = std::env::var("MIDDLEWARE_HTTP_PROXY") {
+            config.proxy = Some(proxy);
+        }
+        if let Ok(user_agent) = std::env::var("MIDDLEWARE_HTTP_USER_AGENT") {
+            config.user_agent = user_agent;
+        }
+        if let Ok(pool_size) = std::env::var("MIDDLEWARE_HTTP_POOL_SIZE") {
+            config.pool_max_idle_per_host = pool_size.parse()
+                .map_err(|_| anyhow!("MIDDLEWARE_HTTP_POOL_SIZE must be a non-negative integer, got '{}'", pool_size))?;
+        }
+        if let Ok(ca_cert) = std::env::var("MIDDLEWARE_HTTP_CA_CERT") {
+            config.root_certificates.push(PathBuf::from(ca_cert));
+        }
+        if let Ok(pins) = std::env::var("MIDDLEWARE_HTTP_PINS") {
+            config = config.with_pins_spec(&pins)?;
+        }
+        Ok(config)
+    }
+
+    /// Add pins in the `MIDDLEWARE_HTTP_PINS` format.
+    pub fn with_pins_spec(mut self, spec: &str) -> Result<Self> {
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (host, fingerprints) = entry.split_once('=')
+                .ok_or_else(|| anyhow!("Certificate pin '{}' must be written host=fingerprint[,fingerprint]", entry))?;
+            for fingerprint in fingerprints.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                self = self.with_pin(host.trim(), fingerprint);
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// Builds `reqwest::Client`s from an `HttpConfig`.
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientFactory {
+    config: HttpConfig,
+    /// Receives certificate pin failures
+    audit_log: Option<Arc<SecurityAuditLog>>,
+}
+
+impl HttpClientFactory {
+    pub fn new(config: HttpConfig) -> Self {
+        Self { config, audit_log: None }
+    }
+
+    pub fn with_audit_log(mut self, audit_log: Option<Arc<SecurityAuditLog>>) -> Self {
+        self.audit_log = audit_log;
+        self
+    }
+
+    /// Factory configured from the environment (see `HttpConfig::from_env`).
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(HttpConfig::from_env()?))
+    }
+
+    pub fn config(&self) -> &HttpConfig {
+        &self.config
+    }
+
+    /// Client using the configured default timeout.
+    pub fn build(&self) -> Result<reqwest::Client> {
+        self.build_with_timeout(self.config.timeout)
+    }
+
+    /// Client with a caller-specific request timeout and the shared settings otherwise.
+    pub fn build_with_timeout(&self, timeout: Duration) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(self.config.connect_timeout.min(timeout))
+            .pool_max_idle_per_host(self.config.pool_max_idle_per_host)
+            .pool_idle_timeout(self.config.pool_idle_timeout)
+            .user_agent(self.config.user_agent.clone());
+
+        if let Some(proxy) = &self.config.proxy {
+            let proxy = reqwest::Proxy::all(proxy.as_str())
+                .map_err(|e| anyhow!("Invalid HTTP proxy '{}': {}", proxy, e))?;
+            builder = builder.proxy(proxy);
+        }
+
+        // Pinning needs a custom rustls verifier, which then owns roots and validation too
+        if !self.config.pinned_certificates.is_empty() {
+            let tls = pinned_tls_config(
+                &self.config.pinned_certificates,
+                &self.config.root_certificates,
+                self.config.accept_invalid_certs,
+                self.audit_log.clone(),
+            )?;
+            return builder.use_preconfigured_tls(tls)
+                .build()
+                .map_err(|e| anyhow!("Failed to build HTTP client: {}", e));
+        }
+
+        builder = builder.danger_accept_invalid_certs(self.config.accept_invalid_certs);
+        for path in &self.config.root_certificates {
+            let pem = fs::read(path)
+                .with_context(|| format!("Failed to read root certificate {}", path.display()))?;
+            let certificate = reqwest::Certificate::from_pem(&pem)
+                .map_err(|e| anyhow!("Invalid root certificate {}: {}", path.display(), e))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        builder.build().map_err(|e| anyhow!("Failed to build HTTP client: {}", e))
+    }
+}
@@ -0,0 +1,66 @@
+//! Deterministic JSON encoding for comparing and hashing values.
+//!
+//! `serde_json::to_string` writes object keys in map order and keeps the
+//! source number formatting, so two nodes returning the same data can still
+//! produce different strings. `canonical_json` removes both differences.
+
+use serde_json::{Number, Value};
+
+/// Largest integer an `f64` represents exactly (2^53).
+const MAX_EXACT_F64_INT: f64 = 9_007_199_254_740_992.0;
+
+/// Serialize `value` with object keys sorted and numbers normalized.
+///
+/// Integral floats within the exactly-representable range are written as
+/// integers, so `1`, `1.0` and `1e0` all encode as `1`. No whitespace is emitted.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&canonical_number(n)),
+        Value::String(s) => out.push_str(&Value::String(s.clone()).to_string()),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn canonical_number(n: &Number) -> String {
+    if n.is_i64() || n.is_u64() {
+        return n.to_string();
+    }
+    match n.as_f64() {
+        Some(f) if f.fract() == 0.0 && f.abs() < MAX_EXACT_F64_INT => {
+            // Avoid "-0"
+            if f == 0.0 { "0".to_string() } else { format!("{}", f as i64) }
+        }
+        _ => n.to_string(),
+    }
+}
@@ -0,0 +1,23 @@
+//! Console output for `tracing` events.
+//!
+//! The execution, submission, oracle and benchmark paths log through `tracing`
+//! inside spans carrying the payload hash, scenario or Byzantine percentage, so
+//! one transaction's lines can be picked out of interleaved async work.
+
+use tracing_subscriber::EnvFilter;
+
+/// Filter used when `RUST_LOG` is unset: this crate at info, dependencies at warn
+pub const DEFAULT_LOG_FILTER: &str = "warn,suimodular=info";
+
+/// Install the `fmt` subscriber, filtered by `RUST_LOG`.
+///
+/// `LOG_FORMAT=json` switches to one JSON object per event, for log
+/// aggregators. Does nothing if a subscriber is already installed.
+pub fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    let _ = match std::env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => builder.json().try_init(),
+        _ => builder.try_init(),
+    };
+}
@@ -0,0 +1,10 @@
+//! Small helpers shared across subsystems.
+
+pub mod backoff;
+pub mod http;
+pub mod json;
+pub mod logging;
+pub mod pinning;
+
+pub use http::{HttpClientFactory, HttpConfig};
+pub use json::canonical_json;
@@ -0,0 +1,131 @@
+//! TLS certificate pinning for `HttpClientFactory` clients.
+//!
+//! A pin is the SHA-256 fingerprint of a host's leaf certificate (DER). Hosts
+//! without pins are verified normally; for pinned hosts the handshake also
+//! fails unless the presented certificate matches one of the pins. Mismatches
+//! are recorded in the security audit log.
+
+use crate::security::audit::SecurityAuditLog;
+use anyhow::{anyhow, Context, Result};
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Lowercase hex without separators, so `AB:CD:..` and `abcd..` compare equal.
+pub fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint.chars().filter(|c| *c != ':').collect::<String>().to_lowercase()
+}
+
+/// SHA-256 fingerprint of a DER-encoded certificate, in the form pins are compared in.
+pub fn certificate_fingerprint(der: &[u8]) -> String {
+    hex::encode(Sha256::digest(der))
+}
+
+/// Chain verification followed by a fingerprint check for pinned hosts.
+pub struct PinningVerifier {
+    inner: WebPkiVerifier,
+    /// Host -> normalized fingerprints
+    pins: HashMap<String, Vec<String>>,
+    /// Skip chain validation (pins are still enforced)
+    accept_invalid_certs: bool,
+    audit_log: Arc<SecurityAuditLog>,
+}
+
+impl PinningVerifier {
+    /// Verifier trusting the web PKI roots plus `root_certificates`, enforcing `pins` on top.
+    ///
+    /// Without an `audit_log`, mismatches go to a console-only log of their own.
+    pub fn new(
+        pins: &HashMap<String, Vec<String>>,
+        root_certificates: &[PathBuf],
+        accept_invalid_certs: bool,
+        audit_log: Option<Arc<SecurityAuditLog>>,
+    ) -> Result<Self> {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(anchor.subject, anchor.spki, anchor.name_constraints)
+        }));
+        for path in root_certificates {
+            let file = File::open(path)
+                .with_context(|| format!("Failed to read root certificate {}", path.display()))?;
+            let certificates = rustls_pemfile::certs(&mut BufReader::new(file))
+                .with_context(|| format!("Invalid root certificate {}", path.display()))?;
+            for der in certificates {
+                roots.add(&Certificate(der))
+                    .map_err(|e| anyhow!("Invalid root certificate {}: {}", path.display(), e))?;
+            }
+        }
+
+        Ok(Self {
+            inner: WebPkiVerifier::new(roots, None),
+            pins: pins.iter()
+                .map(|(host, fingerprints)| {
+                    (host.to_lowercase(), fingerprints.iter().map(|f| normalize_fingerprint(f)).collect())
+                })
+                .collect(),
+            accept_invalid_certs,
+            audit_log: audit_log.unwrap_or_else(|| Arc::new(SecurityAuditLog::new())),
+        })
+    }
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if !self.accept_invalid_certs {
+            self.inner.verify_server_cert(end_entity, intermediates, server_name, scts, ocsp_response, now)?;
+        }
+
+        let host = match server_name {
+            ServerName::DnsName(name) => name.as_ref().to_lowercase(),
+            ServerName::IpAddress(ip) => ip.to_string(),
+            _ => return Ok(ServerCertVerified::assertion()),
+        };
+        let expected = match self.pins.get(&host) {
+            Some(expected) => expected,
+            None => return Ok(ServerCertVerified::assertion()),
+        };
+
+        let presented = certificate_fingerprint(&end_entity.0);
+        if expected.contains(&presented) {
+            return Ok(ServerCertVerified::assertion());
+        }
+
+        let message = format!("Certificate pin mismatch for {}: presented {}", host, presented);
+        let _ = self.audit_log.log_security_error(
+            "CertificatePinning",
+            &message,
+            Some(json!({ "host": host, "presented": presented, "expected": expected })),
+        );
+        Err(rustls::Error::General(message))
+    }
+}
+
+/// rustls configuration that enforces `pins` on top of the web PKI roots plus `root_certificates`.
+pub fn pinned_tls_config(
+    pins: &HashMap<String, Vec<String>>,
+    root_certificates: &[PathBuf],
+    accept_invalid_certs: bool,
+    audit_log: Option<Arc<SecurityAuditLog>>,
+) -> Result<ClientConfig> {
+    let verifier = PinningVerifier::new(pins, root_certificates, accept_invalid_certs, audit_log)?;
+
+    Ok(ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth())
+}